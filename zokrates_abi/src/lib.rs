@@ -14,6 +14,10 @@ impl<T: From<usize>> Encode<T> for Inputs<T> {
     }
 }
 
+mod stream;
+
+pub use stream::{decode_binary, decode_json, encode_binary};
+
 use std::fmt;
 use zokrates_core::typed_absy::types::{ConcreteType, UBitwidth};
 
@@ -24,6 +28,7 @@ pub enum Error {
     Json(String),
     Conversion(String),
     Type(String),
+    Io(String),
 }
 
 impl fmt::Display for Error {
@@ -32,6 +37,7 @@ impl fmt::Display for Error {
             Error::Json(e) => write!(f, "Invalid JSON: {}", e),
             Error::Conversion(e) => write!(f, "Invalid ZoKrates values: {}", e),
             Error::Type(e) => write!(f, "Type error: {}", e),
+            Error::Io(e) => write!(f, "Could not read inputs: {}", e),
         }
     }
 }
@@ -207,41 +213,47 @@ impl<T: Field> Values<T> {
     }
 }
 
-fn parse_value<T: Field>(
-    value: serde_json::Value,
-    expected_type: ConcreteType,
-) -> Result<Value<T>, Error> {
-    match (&expected_type, value) {
-        (ConcreteType::FieldElement, serde_json::Value::String(s)) => {
-            T::try_from_dec_str(s.as_str())
-                .or_else(|_| T::try_from_str(s.as_str().trim_start_matches("0x"), 16))
-                .map(Value::Field)
-                .map_err(|_| Error::Type(format!("Could not parse `{}` to field type", s)))
-        }
-        (ConcreteType::Uint(UBitwidth::B8), serde_json::Value::String(s)) => s
-            .as_str()
+// parses a field element or uint value from its decimal or hexadecimal representation
+fn parse_str<T: Field>(s: &str, expected_type: &ConcreteType) -> Result<Value<T>, Error> {
+    match expected_type {
+        ConcreteType::FieldElement => T::try_from_dec_str(s)
+            .or_else(|_| T::try_from_str(s.trim_start_matches("0x"), 16))
+            .map(Value::Field)
+            .map_err(|_| Error::Type(format!("Could not parse `{}` to field type", s))),
+        ConcreteType::Uint(UBitwidth::B8) => s
             .parse::<u8>()
-            .or_else(|_| u8::from_str_radix(s.as_str().trim_start_matches("0x"), 16))
+            .or_else(|_| u8::from_str_radix(s.trim_start_matches("0x"), 16))
             .map(Value::U8)
             .map_err(|_| Error::Type(format!("Could not parse `{}` to u8 type", s))),
-        (ConcreteType::Uint(UBitwidth::B16), serde_json::Value::String(s)) => s
-            .as_str()
+        ConcreteType::Uint(UBitwidth::B16) => s
             .parse::<u16>()
-            .or_else(|_| u16::from_str_radix(s.as_str().trim_start_matches("0x"), 16))
+            .or_else(|_| u16::from_str_radix(s.trim_start_matches("0x"), 16))
             .map(Value::U16)
             .map_err(|_| Error::Type(format!("Could not parse `{}` to u16 type", s))),
-        (ConcreteType::Uint(UBitwidth::B32), serde_json::Value::String(s)) => s
-            .as_str()
+        ConcreteType::Uint(UBitwidth::B32) => s
             .parse::<u32>()
-            .or_else(|_| u32::from_str_radix(s.as_str().trim_start_matches("0x"), 16))
+            .or_else(|_| u32::from_str_radix(s.trim_start_matches("0x"), 16))
             .map(Value::U32)
             .map_err(|_| Error::Type(format!("Could not parse `{}` to u32 type", s))),
-        (ConcreteType::Uint(UBitwidth::B64), serde_json::Value::String(s)) => s
-            .as_str()
+        ConcreteType::Uint(UBitwidth::B64) => s
             .parse::<u64>()
-            .or_else(|_| u64::from_str_radix(s.as_str().trim_start_matches("0x"), 16))
+            .or_else(|_| u64::from_str_radix(s.trim_start_matches("0x"), 16))
             .map(Value::U64)
             .map_err(|_| Error::Type(format!("Could not parse `{}` to u64 type", s))),
+        _ => Err(Error::Type(format!(
+            "Value `{}` doesn't match expected type `{}`",
+            serde_json::Value::String(s.to_string()),
+            expected_type
+        ))),
+    }
+}
+
+fn parse_value<T: Field>(
+    value: serde_json::Value,
+    expected_type: ConcreteType,
+) -> Result<Value<T>, Error> {
+    match (&expected_type, value) {
+        (_, serde_json::Value::String(s)) => parse_str(&s, &expected_type),
         (ConcreteType::Boolean, serde_json::Value::Bool(b)) => Ok(Value::Boolean(b)),
         (ConcreteType::Array(array_type), serde_json::Value::Array(a)) => {
             let size = array_type.size;
@@ -0,0 +1,441 @@
+//! Module containing the streaming decoders of program inputs, which encode values as they are read
+//!
+//! `decode_json` reads the same format as `parse_strict`, but instead of materializing the whole document as a
+//! `serde_json::Value`, each value is encoded as soon as it is parsed and appended to the arguments of the program,
+//! whose size is known from the ABI. Array sizes are checked as elements arrive, so that a mismatch is reported without
+//! reading the rest of the input, along with the path of the failing element.
+//!
+//! `decode_binary` reads a raw format for the largest inputs: the number of field elements as a little-endian `u64`,
+//! followed by each element as little-endian bytes, using the smallest width fitting any element of the field.
+
+use crate::{parse_str, Encode, Error};
+use num_bigint::BigUint;
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, Read, Write};
+use zokrates_core::typed_absy::types::ConcreteType;
+use zokrates_field::Field;
+
+// the location of a value in the inputs, built without allocating
+#[derive(Clone, Copy)]
+enum Path<'a> {
+    Input(usize),
+    Index(&'a Path<'a>, usize),
+    Member(&'a Path<'a>, &'a str),
+}
+
+impl fmt::Display for Path<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Path::Input(index) => write!(f, "inputs[{}]", index),
+            Path::Index(path, index) => write!(f, "{}[{}]", path, index),
+            Path::Member(path, id) => write!(f, "{}.{}", path, id),
+        }
+    }
+}
+
+fn at(e: Error, path: &Path) -> Error {
+    match e {
+        Error::Type(e) => Error::Type(format!("{} at `{}`", e, path)),
+        Error::Conversion(e) => Error::Conversion(format!("{} at `{}`", e, path)),
+        e => e,
+    }
+}
+
+// serde errors can only carry a message, so the error found is kept aside and returned once decoding stops
+type ErrorSlot = RefCell<Option<Error>>;
+
+fn fail<E: de::Error>(slot: &ErrorSlot, e: Error) -> E {
+    *slot.borrow_mut() = Some(e);
+    E::custom("invalid input")
+}
+
+// the inputs of a program, as an array of values
+struct InputsSeed<'a, T> {
+    types: &'a [ConcreteType],
+    out: &'a mut Vec<T>,
+    error: &'a ErrorSlot,
+}
+
+impl<'de, T: Field> DeserializeSeed<'de> for InputsSeed<'_, T> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, T: Field> Visitor<'de> for InputsSeed<'_, T> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an array of values")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        for (index, ty) in self.types.iter().enumerate() {
+            let seed = ValueSeed {
+                ty,
+                path: Path::Input(index),
+                out: &mut *self.out,
+                error: self.error,
+            };
+
+            if seq.next_element_seed(seed)?.is_none() {
+                return Err(fail(
+                    self.error,
+                    Error::Type(format!(
+                        "Expected {} inputs, found {}",
+                        self.types.len(),
+                        index
+                    )),
+                ));
+            }
+        }
+
+        match seq.next_element::<IgnoredAny>()? {
+            Some(_) => Err(fail(
+                self.error,
+                Error::Type(format!("Expected {} inputs, found more", self.types.len())),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+// a single value of type `ty`, encoded into `out`
+struct ValueSeed<'a, T> {
+    ty: &'a ConcreteType,
+    path: Path<'a>,
+    out: &'a mut Vec<T>,
+    error: &'a ErrorSlot,
+}
+
+impl<T> ValueSeed<'_, T> {
+    fn fail<E: de::Error>(&self, e: Error) -> E {
+        fail(self.error, at(e, &self.path))
+    }
+
+    fn mismatch<E: de::Error>(&self, value: &str) -> E {
+        self.fail(Error::Type(format!(
+            "Value `{}` doesn't match expected type `{}`",
+            value, self.ty
+        )))
+    }
+}
+
+impl<'de, T: Field> DeserializeSeed<'de> for ValueSeed<'_, T> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, T: Field> Visitor<'de> for ValueSeed<'_, T> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a value of type `{}`", self.ty)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<(), E> {
+        let value = parse_str::<T>(s, self.ty).map_err(|e| self.fail(e))?;
+        self.out.extend(value.encode());
+        Ok(())
+    }
+
+    fn visit_bool<E: de::Error>(self, b: bool) -> Result<(), E> {
+        match self.ty {
+            ConcreteType::Boolean => {
+                self.out.push(if b { T::one() } else { T::zero() });
+                Ok(())
+            }
+            _ => Err(self.mismatch(&b.to_string())),
+        }
+    }
+
+    fn visit_u64<E: de::Error>(self, n: u64) -> Result<(), E> {
+        Err(self.fail(Error::Conversion(format!(
+            "Value `{}` isn't allowed, did you mean `\"{}\"`?",
+            n, n
+        ))))
+    }
+
+    fn visit_i64<E: de::Error>(self, n: i64) -> Result<(), E> {
+        Err(self.fail(Error::Conversion(format!(
+            "Value `{}` isn't allowed, did you mean `\"{}\"`?",
+            n, n
+        ))))
+    }
+
+    fn visit_f64<E: de::Error>(self, n: f64) -> Result<(), E> {
+        Err(self.fail(Error::Conversion(format!(
+            "Value `{}` isn't allowed, did you mean `\"{}\"`?",
+            n, n
+        ))))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        Err(self.mismatch("null"))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let array_type = match self.ty {
+            ConcreteType::Array(array_type) => array_type,
+            _ => return Err(self.mismatch("[..]")),
+        };
+
+        for index in 0..array_type.size {
+            let path = Path::Index(&self.path, index);
+            let seed = ValueSeed {
+                ty: &array_type.ty,
+                path,
+                out: &mut *self.out,
+                error: self.error,
+            };
+
+            if seq.next_element_seed(seed)?.is_none() {
+                return Err(self.fail(Error::Type(format!(
+                    "Expected array of size {}, found array of size {}",
+                    array_type.size, index
+                ))));
+            }
+        }
+
+        match seq.next_element::<IgnoredAny>()? {
+            Some(_) => Err(self.fail(Error::Type(format!(
+                "Expected array of size {}, found a larger array",
+                array_type.size
+            )))),
+            None => Ok(()),
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let struct_type = match self.ty {
+            ConcreteType::Struct(struct_type) => struct_type,
+            _ => return Err(self.mismatch("{..}")),
+        };
+
+        // members can appear in any order, but are encoded in the order of the declaration
+        let mut members: Vec<Option<Vec<T>>> = vec![None; struct_type.members_count()];
+
+        while let Some(id) = map.next_key::<String>()? {
+            let index = struct_type
+                .members
+                .iter()
+                .position(|m| m.id == id)
+                .ok_or_else(|| self.fail(Error::Type(format!("Unexpected member `{}`", id))))?;
+
+            if members[index].is_some() {
+                return Err(self.fail(Error::Type(format!("Duplicate member `{}`", id))));
+            }
+
+            let member = &struct_type.members[index];
+            let mut out = Vec::with_capacity(member.ty.get_primitive_count());
+
+            map.next_value_seed(ValueSeed {
+                ty: &member.ty,
+                path: Path::Member(&self.path, &member.id),
+                out: &mut out,
+                error: self.error,
+            })?;
+
+            members[index] = Some(out);
+        }
+
+        for (member, value) in struct_type.members.iter().zip(members) {
+            match value {
+                Some(value) => self.out.extend(value),
+                None => {
+                    return Err(self.fail(Error::Type(format!(
+                        "Member with id `{}` not found",
+                        member.id
+                    ))))
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Decodes the JSON inputs of a program of signature `types` from `reader`, returning the encoded arguments
+pub fn decode_json<T: Field, R: Read>(reader: R, types: &[ConcreteType]) -> Result<Vec<T>, Error> {
+    let error = RefCell::new(None);
+    let mut arguments = Vec::with_capacity(types.iter().map(|t| t.get_primitive_count()).sum());
+
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+
+    InputsSeed {
+        types,
+        out: &mut arguments,
+        error: &error,
+    }
+    .deserialize(&mut deserializer)
+    .and_then(|_| deserializer.end())
+    .map_err(|e| {
+        error
+            .borrow_mut()
+            .take()
+            .unwrap_or_else(|| Error::Json(e.to_string()))
+    })?;
+
+    Ok(arguments)
+}
+
+// the number of bytes of an element in the binary format
+fn element_width<T: Field>() -> usize {
+    (T::get_required_bits() + 7) / 8
+}
+
+/// Decodes `count` arguments in the binary format from `reader`
+pub fn decode_binary<T: Field, R: Read>(mut reader: R, count: usize) -> Result<Vec<T>, Error> {
+    let io_error = |e: io::Error| Error::Io(e.to_string());
+
+    let mut length = [0u8; 8];
+    reader.read_exact(&mut length).map_err(io_error)?;
+    let length = u64::from_le_bytes(length);
+
+    if length != count as u64 {
+        return Err(Error::Type(format!(
+            "Expected {} field elements, found {}",
+            count, length
+        )));
+    }
+
+    let mut buffer = vec![0u8; element_width::<T>()];
+    // collecting results does not reserve the length up front, so we push into an exactly sized vector
+    let mut arguments = Vec::with_capacity(count);
+
+    for index in 0..count {
+        reader.read_exact(&mut buffer).map_err(io_error)?;
+        arguments.push(T::try_from(BigUint::from_bytes_le(&buffer)).map_err(|_| {
+            Error::Conversion(format!(
+                "Element {} is not lower than the field modulus",
+                index
+            ))
+        })?);
+    }
+
+    Ok(arguments)
+}
+
+/// Encodes `arguments` in the binary format into `writer`
+pub fn encode_binary<T: Field, W: Write>(arguments: &[T], mut writer: W) -> io::Result<()> {
+    writer.write_all(&(arguments.len() as u64).to_le_bytes())?;
+
+    for a in arguments {
+        let mut bytes = a.to_byte_vector();
+        bytes.resize(element_width::<T>(), 0);
+        writer.write_all(&bytes)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_strict;
+    use zokrates_core::typed_absy::types::{ConcreteStructMember, ConcreteStructType};
+    use zokrates_field::Bn128Field;
+
+    fn types() -> Vec<ConcreteType> {
+        vec![
+            ConcreteType::array((ConcreteType::FieldElement, 3usize)),
+            ConcreteType::Struct(ConcreteStructType::new(
+                "".into(),
+                "Foo".into(),
+                vec![],
+                vec![
+                    ConcreteStructMember::new("a".into(), ConcreteType::Boolean),
+                    ConcreteStructMember::new("b".into(), ConcreteType::Uint(32.into())),
+                ],
+            )),
+        ]
+    }
+
+    #[test]
+    fn matches_parse_strict() {
+        let input = r#"[["1", "0x2", "3"], {"b": "42", "a": true}]"#;
+
+        assert_eq!(
+            decode_json::<Bn128Field, _>(input.as_bytes(), &types()).unwrap(),
+            parse_strict::<Bn128Field>(input, types()).unwrap().encode()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let decode = |input: &str| decode_json::<Bn128Field, _>(input.as_bytes(), &types());
+
+        assert_eq!(
+            decode(r#"[["1", "2", "3", "4"], {"a": true, "b": "42"}]"#),
+            Err(Error::Type(
+                "Expected array of size 3, found a larger array at `inputs[0]`".into()
+            ))
+        );
+        assert_eq!(
+            decode(r#"[["1", "2", "x"], {"a": true, "b": "42"}]"#),
+            Err(Error::Type(
+                "Could not parse `x` to field type at `inputs[0][2]`".into()
+            ))
+        );
+        assert_eq!(
+            decode(r#"[["1", "2", "3"], {"a": 1, "b": "42"}]"#),
+            Err(Error::Conversion(
+                "Value `1` isn't allowed, did you mean `\"1\"`? at `inputs[1].a`".into()
+            ))
+        );
+        assert_eq!(
+            decode(r#"[["1", "2", "3"], {"a": true}]"#),
+            Err(Error::Type(
+                "Member with id `b` not found at `inputs[1]`".into()
+            ))
+        );
+        assert_eq!(
+            decode(r#"[["1", "2", "3"]]"#),
+            Err(Error::Type("Expected 2 inputs, found 1".into()))
+        );
+        assert!(matches!(
+            decode(r#"[["1", "2", "3"], "#),
+            Err(Error::Json(_))
+        ));
+    }
+
+    #[test]
+    fn binary() {
+        let arguments = vec![
+            Bn128Field::from(0),
+            Bn128Field::from(42),
+            Bn128Field::max_value(),
+        ];
+
+        let mut buffer = vec![];
+        encode_binary(&arguments, &mut buffer).unwrap();
+
+        assert_eq!(buffer.len(), 8 + 3 * 32);
+        assert_eq!(
+            decode_binary::<Bn128Field, _>(buffer.as_slice(), 3),
+            Ok(arguments)
+        );
+
+        assert_eq!(
+            decode_binary::<Bn128Field, _>(buffer.as_slice(), 2),
+            Err(Error::Type("Expected 2 field elements, found 3".into()))
+        );
+
+        // an element larger than the modulus is rejected rather than reduced
+        let mut buffer = vec![];
+        buffer.extend(&1u64.to_le_bytes());
+        buffer.extend(vec![0xff; 32]);
+        assert!(matches!(
+            decode_binary::<Bn128Field, _>(buffer.as_slice(), 1),
+            Err(Error::Conversion(_))
+        ));
+    }
+}
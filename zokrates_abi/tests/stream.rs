@@ -0,0 +1,123 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::Read;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use zokrates_abi::{decode_binary, decode_json, encode_binary, parse_strict, Encode, Error};
+use zokrates_core::typed_absy::types::ConcreteType;
+use zokrates_field::Bn128Field;
+
+// counts the bytes currently allocated, and the peak since the last reset
+struct Counter;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+        PEAK.fetch_max(current, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counter = Counter;
+
+// returns the result of `f`, and the memory allocated by `f` at its peak in addition to what it returns
+fn transient<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    PEAK.store(CURRENT.load(Ordering::SeqCst), Ordering::SeqCst);
+    let res = f();
+    let additional = PEAK.load(Ordering::SeqCst) - CURRENT.load(Ordering::SeqCst);
+    (res, additional)
+}
+
+// a reader keeping track of the bytes read
+struct Tracked<'a> {
+    inner: &'a [u8],
+    read: usize,
+}
+
+impl Read for Tracked<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read += n;
+        Ok(n)
+    }
+}
+
+const SIZE: usize = 65536;
+
+#[test]
+fn large_array() {
+    let types = vec![ConcreteType::array((ConcreteType::FieldElement, SIZE))];
+
+    let input = format!(
+        "[[{}]]",
+        (0..SIZE)
+            .map(|i| format!("\"{}\"", i * 1_000_000_007))
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+
+    let (streamed, streamed_additional) =
+        transient(|| decode_json::<Bn128Field, _>(input.as_bytes(), &types).unwrap());
+
+    let (strict, strict_additional) = transient(|| {
+        parse_strict::<Bn128Field>(&input, types.clone())
+            .unwrap()
+            .encode()
+    });
+
+    assert_eq!(streamed, strict);
+
+    // the streaming decoder only buffers the value being read, while the strict one materializes the whole document
+    assert!(
+        streamed_additional < 64 * 1024,
+        "decoding used {} additional bytes",
+        streamed_additional
+    );
+    assert!(streamed_additional * 100 < strict_additional);
+
+    // a length mismatch is reported as soon as the extra element is found, without reading the rest of the input
+    let small = vec![ConcreteType::array((ConcreteType::FieldElement, 4usize))];
+    let mut reader = Tracked {
+        inner: input.as_bytes(),
+        read: 0,
+    };
+
+    assert_eq!(
+        decode_json::<Bn128Field, _>(&mut reader, &small),
+        Err(Error::Type(
+            "Expected array of size 4, found a larger array at `inputs[0]`".into()
+        ))
+    );
+    assert!(reader.read < input.len() / 100);
+
+    // the binary format checks the length prefix before reading any element
+    let mut binary = vec![];
+    encode_binary(&streamed, &mut binary).unwrap();
+
+    let (decoded, binary_additional) =
+        transient(|| decode_binary::<Bn128Field, _>(binary.as_slice(), SIZE).unwrap());
+    assert_eq!(decoded, streamed);
+    assert!(binary_additional < 64 * 1024);
+
+    let mut reader = Tracked {
+        inner: &binary,
+        read: 0,
+    };
+
+    assert_eq!(
+        decode_binary::<Bn128Field, _>(&mut reader, SIZE - 1),
+        Err(Error::Type(format!(
+            "Expected {} field elements, found {}",
+            SIZE - 1,
+            SIZE
+        )))
+    );
+    assert_eq!(reader.read, 8);
+}
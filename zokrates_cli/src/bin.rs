@@ -45,14 +45,21 @@ fn cli() -> Result<(), String> {
             compile::subcommand(),
             check::subcommand(),
             compute_witness::subcommand(),
+            finalize_witness::subcommand(),
             #[cfg(feature = "ark")]
             universal_setup::subcommand(),
+            #[cfg(feature = "ark")]
+            srs::subcommand(),
             #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
             setup::subcommand(),
             export_verifier::subcommand(),
             #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
             generate_proof::subcommand(),
             generate_smtlib2::subcommand(),
+            gen_inputs::subcommand(),
+            advise::subcommand(),
+            inspect::subcommand(),
+            attest::subcommand(),
             print_proof::subcommand(),
             #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
             verify::subcommand()])
@@ -62,14 +69,21 @@ fn cli() -> Result<(), String> {
         ("compile", Some(sub_matches)) => compile::exec(sub_matches),
         ("check", Some(sub_matches)) => check::exec(sub_matches),
         ("compute-witness", Some(sub_matches)) => compute_witness::exec(sub_matches),
+        ("finalize-witness", Some(sub_matches)) => finalize_witness::exec(sub_matches),
         #[cfg(feature = "ark")]
         ("universal-setup", Some(sub_matches)) => universal_setup::exec(sub_matches),
+        #[cfg(feature = "ark")]
+        ("srs", Some(sub_matches)) => srs::exec(sub_matches),
         #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
         ("setup", Some(sub_matches)) => setup::exec(sub_matches),
         ("export-verifier", Some(sub_matches)) => export_verifier::exec(sub_matches),
         #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
         ("generate-proof", Some(sub_matches)) => generate_proof::exec(sub_matches),
         ("generate-smtlib2", Some(sub_matches)) => generate_smtlib2::exec(sub_matches),
+        ("gen-inputs", Some(sub_matches)) => gen_inputs::exec(sub_matches),
+        ("advise", Some(sub_matches)) => advise::exec(sub_matches),
+        ("inspect", Some(sub_matches)) => inspect::exec(sub_matches),
+        ("attest", Some(sub_matches)) => attest::exec(sub_matches),
         ("print-proof", Some(sub_matches)) => print_proof::exec(sub_matches),
         #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
         ("verify", Some(sub_matches)) => verify::exec(sub_matches),
@@ -8,6 +8,8 @@ pub const JSON_PROOF_PATH: &str = "proof.json";
 pub const UNIVERSAL_SETUP_DEFAULT_PATH: &str = "universal_setup.dat";
 pub const UNIVERSAL_SETUP_DEFAULT_SIZE: &str = "10";
 pub const SMTLIB2_DEFAULT_PATH: &str = "out.smt2";
+pub const INPUTS_SUITE_DEFAULT_PATH: &str = "inputs-suite.jsonl";
+pub const ATTESTATION_DEFAULT_PATH: &str = "attestation.json";
 
 pub const BELLMAN: &str = "bellman";
 pub const LIBSNARK: &str = "libsnark";
@@ -20,6 +22,18 @@ lazy_static! {
         .into_os_string()
         .into_string()
         .unwrap();
+    pub static ref DEFAULT_SRS_CACHE_PATH: String = dirs::home_dir()
+        .map(|p| p.join(".zokrates/srs"))
+        .unwrap()
+        .into_os_string()
+        .into_string()
+        .unwrap();
+    pub static ref DEFAULT_SRS_PINS_PATH: String = dirs::home_dir()
+        .map(|p| p.join(".zokrates/srs.pins"))
+        .unwrap()
+        .into_os_string()
+        .into_string()
+        .unwrap();
 }
 
 #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
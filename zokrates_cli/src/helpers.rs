@@ -1,5 +1,6 @@
 use crate::constants::*;
 use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub enum CurveParameter {
@@ -115,3 +116,9 @@ impl TryFrom<(&str, &str, &str)> for Parameters {
         }.map(|_: ()| Parameters(backend, curve, proving_scheme))
     }
 }
+
+/// The path of the inputs on the boundaries of the comparisons of the program compiled to `program`, as written by
+/// `compile --input-boundaries`
+pub fn input_suite_path(program: &Path) -> PathBuf {
+    program.with_extension("boundaries.json")
+}
@@ -0,0 +1,106 @@
+use crate::constants::{ABI_SPEC_DEFAULT_PATH, FLATTENED_CODE_DEFAULT_PATH};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use serde_json::from_reader;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use zokrates_core::ir;
+use zokrates_core::ir::{Advisor, ProgEnum};
+use zokrates_core::typed_absy::abi::Abi;
+use zokrates_field::Field;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("advise")
+        .about("Suggests narrower types for range checks which were slack in all recorded executions")
+        .arg(
+            Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .help("Path of the binary")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(FLATTENED_CODE_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("abi-spec")
+                .short("s")
+                .long("abi-spec")
+                .help("Path of the ABI specification, used to find the inputs of `main` whose width is declared")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(ABI_SPEC_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("coverage")
+                .long("coverage")
+                .help("Path of the recorded executions, as a JSON array of runs, each run being an array of arguments like `[[\"1\", \"2\"], [\"3\", \"4\"]]`")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(true),
+        )
+}
+
+pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
+    // read compiled program
+    let path = Path::new(sub_matches.value_of("input").unwrap());
+    let file =
+        File::open(&path).map_err(|why| format!("Could not open {}: {}", path.display(), why))?;
+
+    let mut reader = BufReader::new(file);
+
+    match ProgEnum::deserialize(&mut reader)? {
+        ProgEnum::Bn128Program(p) => cli_advise(p, sub_matches),
+        ProgEnum::Bls12_377Program(p) => cli_advise(p, sub_matches),
+        ProgEnum::Bls12_381Program(p) => cli_advise(p, sub_matches),
+        ProgEnum::Bw6_761Program(p) => cli_advise(p, sub_matches),
+    }
+}
+
+fn cli_advise<T: Field>(ir_prog: ir::Prog<T>, sub_matches: &ArgMatches) -> Result<(), String> {
+    let path = Path::new(sub_matches.value_of("abi-spec").unwrap());
+    let file =
+        File::open(&path).map_err(|why| format!("Could not open {}: {}", path.display(), why))?;
+    let abi: Abi = from_reader(BufReader::new(file)).map_err(|why| why.to_string())?;
+
+    let path = Path::new(sub_matches.value_of("coverage").unwrap());
+    let file =
+        File::open(&path).map_err(|why| format!("Could not open {}: {}", path.display(), why))?;
+
+    let runs: Vec<Vec<String>> =
+        from_reader(BufReader::new(file)).map_err(|why| why.to_string())?;
+
+    let interpreter = ir::Interpreter::default();
+
+    let witnesses = runs
+        .iter()
+        .enumerate()
+        .map(|(index, run)| {
+            let arguments = run
+                .iter()
+                .map(|x| T::try_from_dec_str(x).map_err(|_| x.to_string()))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Could not parse argument of run #{}: {}", index, e))?;
+
+            interpreter
+                .execute(&ir_prog, &arguments)
+                .map_err(|e| format!("Execution of run #{} failed: {}", index, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let suggestions = Advisor::advise(&ir_prog, &abi, &witnesses);
+
+    if suggestions.is_empty() {
+        println!("No suggestion across {} runs", witnesses.len());
+        return Ok(());
+    }
+
+    println!("The following suggestions are advisory and only reflect the recorded executions. Making sure that a change is sound for all inputs is your responsibility.\n");
+
+    for suggestion in suggestions {
+        println!("{}", suggestion);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use crate::constants::{
+    ATTESTATION_DEFAULT_PATH, FLATTENED_CODE_DEFAULT_PATH, VERIFICATION_KEY_DEFAULT_PATH,
+};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use zokrates_core::attestation::{
+    build_record_path, setup_record_path, Attestation, BuildRecord, SetupRecord,
+};
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("attest")
+        .about("Outputs a document binding a verification key to the sources and configuration of its build")
+        .arg(
+            Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .help("Path of the binary")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(FLATTENED_CODE_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("verification-key-path")
+                .long("keys")
+                .help("Path of the verification key")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(VERIFICATION_KEY_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .help("Path of the output file")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(ATTESTATION_DEFAULT_PATH),
+        )
+}
+
+fn open_record(path: &Path, producer: &str) -> Result<BufReader<File>, String> {
+    File::open(path).map(BufReader::new).map_err(|why| {
+        format!(
+            "Could not open {}: {}\nMake sure it was produced by `zokrates {} --record`",
+            path.display(),
+            why,
+            producer
+        )
+    })
+}
+
+pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
+    let program_path = Path::new(sub_matches.value_of("input").unwrap());
+    let vk_path = Path::new(sub_matches.value_of("verification-key-path").unwrap());
+    let output_path = Path::new(sub_matches.value_of("output").unwrap());
+
+    let build_path = build_record_path(program_path);
+    let build: BuildRecord = serde_json::from_reader(open_record(&build_path, "compile")?)
+        .map_err(|why| format!("Could not read {}: {}", build_path.display(), why))?;
+
+    let setup_path = setup_record_path(vk_path);
+    let setup: SetupRecord = serde_json::from_reader(open_record(&setup_path, "setup")?)
+        .map_err(|why| format!("Could not read {}: {}", setup_path.display(), why))?;
+
+    let vk = std::fs::read(vk_path)
+        .map_err(|why| format!("Could not read {}: {}", vk_path.display(), why))?;
+
+    let attestation = Attestation::new(build, setup, &vk).map_err(|e| e.to_string())?;
+
+    let output_file = File::create(output_path)
+        .map_err(|why| format!("Could not create {}: {}", output_path.display(), why))?;
+
+    serde_json::to_writer_pretty(output_file, &attestation)
+        .map_err(|why| format!("Could not write to {}: {}", output_path.display(), why))?;
+
+    println!("Attestation written to '{}'", output_path.display());
+
+    Ok(())
+}
@@ -1,12 +1,17 @@
 use crate::constants;
-use crate::helpers::CurveParameter;
+use crate::helpers::{input_suite_path, CurveParameter};
 use clap::{App, Arg, ArgMatches, SubCommand};
 use serde_json::to_writer_pretty;
 use std::convert::TryFrom;
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
-use zokrates_core::compile::{compile, CompilationArtifacts, CompileConfig, CompileError};
+use std::str::FromStr;
+use zokrates_core::attestation::build_record_path;
+use zokrates_core::compile::{
+    compile, ComparisonStrategy, CompilationArtifacts, CompileConfig, CompileError,
+    OptimizerSchedule,
+};
 use zokrates_field::{Bls12_377Field, Bls12_381Field, Bn128Field, Bw6_761Field, Field};
 use zokrates_fs_resolver::FileSystemResolver;
 
@@ -60,6 +65,39 @@ pub fn subcommand() -> App<'static, 'static> {
         .long("isolate-branches")
         .help("Isolate the execution of branches: a panic in a branch only makes the program panic if this branch is being logically executed")
         .required(false)
+    ).arg(Arg::with_name("timing-report")
+        .long("timing-report")
+        .help("Write a report of the directives whose witness computation is not constant-time and depends on private inputs, along with these inputs")
+        .required(false)
+    ).arg(Arg::with_name("input-boundaries")
+        .long("input-boundaries")
+        .help("Write the inputs on the boundaries of the comparisons of the program next to the output binary, to be encoded by `gen-inputs`")
+        .required(false)
+    ).arg(Arg::with_name("optimize-tuned")
+        .long("optimize-tuned")
+        .help("Try a bounded set of optimizer schedules and keep the one yielding the fewest constraints. The chosen schedule is written next to the output binary")
+        .required(false)
+    ).arg(Arg::with_name("optimizer-schedule")
+        .long("optimizer-schedule")
+        .help("Replay an optimizer schedule, given as a comma-separated list of passes (redefinition, tautology, directive, duplicate)")
+        .value_name("SCHEDULE")
+        .takes_value(true)
+        .required(false)
+        .conflicts_with("optimize-tuned")
+    ).arg(Arg::with_name("comparison-strategy")
+        .long("comparison-strategy")
+        .help("Force the encoding of uint comparisons (bit_compare, difference). The difference encoding is only used where it is sound")
+        .value_name("STRATEGY")
+        .takes_value(true)
+        .required(false)
+    ).arg(Arg::with_name("inline-single-assignments")
+        .long("inline-single-assignments")
+        .help("Substitute the variables defined by a linear expression and used once before converting to ir, which lowers the size of the intermediate programs")
+        .required(false)
+    ).arg(Arg::with_name("record")
+        .long("record")
+        .help("Write a record of this build next to the output binary, to be used by `attest`")
+        .required(false)
     ).arg(Arg::with_name("ztf")
         .long("ztf")
         .help("Write human readable output (ztf)")
@@ -98,6 +136,10 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
     let bin_output_path = Path::new(sub_matches.value_of("output").unwrap());
     let abi_spec_path = Path::new(sub_matches.value_of("abi-spec").unwrap());
     let hr_output_path = bin_output_path.to_path_buf().with_extension("ztf");
+    let timing_report_path = bin_output_path.to_path_buf().with_extension("timing.json");
+    let input_suite_path = input_suite_path(bin_output_path);
+    let schedule_path = bin_output_path.to_path_buf().with_extension("schedule");
+    let build_record_path = build_record_path(bin_output_path);
 
     log::debug!("Load entry point file {}", path.display());
 
@@ -108,6 +150,12 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
     let mut source = String::new();
     reader.read_to_string(&mut source).unwrap();
 
+    // sources are recorded relative to the directory of the entry point, which requires their locations to be absolute
+    let path = path
+        .canonicalize()
+        .map_err(|why| format!("Could not open {}: {}", path.display(), why))?;
+    let root = path.parent().unwrap().to_path_buf();
+
     let fmt_error = |e: &CompileError| {
         let file = e.file().canonicalize().unwrap();
         format!(
@@ -130,7 +178,23 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
 
     let config = CompileConfig::default()
         .allow_unconstrained_variables(sub_matches.is_present("allow-unconstrained-variables"))
-        .isolate_branches(sub_matches.is_present("isolate-branches"));
+        .isolate_branches(sub_matches.is_present("isolate-branches"))
+        .timing_report(sub_matches.is_present("timing-report"))
+        .input_boundaries(sub_matches.is_present("input-boundaries"))
+        .optimize_tuned(sub_matches.is_present("optimize-tuned"))
+        .inline_single_assignments(sub_matches.is_present("inline-single-assignments"))
+        .optimizer_schedule(
+            sub_matches
+                .value_of("optimizer-schedule")
+                .map(OptimizerSchedule::from_str)
+                .transpose()?,
+        )
+        .comparison_strategy(
+            sub_matches
+                .value_of("comparison-strategy")
+                .map(ComparisonStrategy::from_str)
+                .transpose()?,
+        );
 
     let resolver = FileSystemResolver::with_stdlib_root(stdlib_path);
 
@@ -147,6 +211,10 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
             )
         })?;
 
+    for warning in artifacts.warnings() {
+        println!("Warning: {}\n", warning);
+    }
+
     let program_flattened = artifacts.prog();
 
     // number of constraints the flattened program will translate to.
@@ -171,6 +239,17 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
     let mut writer = BufWriter::new(abi_spec_file);
     to_writer_pretty(&mut writer, &abi).map_err(|_| "Unable to write data to file.".to_string())?;
 
+    if sub_matches.is_present("record") {
+        // write the record of this build, used by `attest`
+        log::debug!("Write build record");
+        let build_record_file = File::create(&build_record_path)
+            .map_err(|why| format!("Could not create {}: {}", build_record_path.display(), why))?;
+
+        let mut writer = BufWriter::new(build_record_file);
+        to_writer_pretty(&mut writer, &artifacts.build_record(&config, &root))
+            .map_err(|_| "Unable to write data to file.".to_string())?;
+    }
+
     if sub_matches.is_present("verbose") {
         // debugging output
         println!("Compiled program:\n{}", program_flattened);
@@ -194,6 +273,68 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
         println!("Human readable code to '{}'", hr_output_path.display());
     }
 
+    if let Some(report) = artifacts.timing_report() {
+        // write timing report
+        log::debug!("Serialize timing report");
+        let timing_report_file = File::create(&timing_report_path)
+            .map_err(|why| format!("Could not create {}: {}", timing_report_path.display(), why))?;
+
+        let mut writer = BufWriter::new(timing_report_file);
+        to_writer_pretty(&mut writer, report)
+            .map_err(|_| "Unable to write data to file.".to_string())?;
+
+        if sub_matches.is_present("verbose") {
+            println!("Timing report:\n{}", report);
+        }
+
+        println!(
+            "Timing report ({} non constant-time directive{}) written to '{}'",
+            report.entries.len(),
+            if report.entries.len() == 1 { "" } else { "s" },
+            timing_report_path.display()
+        );
+    }
+
+    if let Some(suite) = artifacts.input_suite() {
+        // write the boundary inputs, encoded against the abi by `gen-inputs`
+        log::debug!("Serialize input boundaries");
+        let input_suite_file = File::create(&input_suite_path)
+            .map_err(|why| format!("Could not create {}: {}", input_suite_path.display(), why))?;
+
+        let mut writer = BufWriter::new(input_suite_file);
+        to_writer_pretty(&mut writer, suite)
+            .map_err(|_| "Unable to write data to file.".to_string())?;
+
+        println!(
+            "Input boundaries ({} candidate{}) written to '{}'",
+            suite.candidates.len(),
+            if suite.candidates.len() == 1 { "" } else { "s" },
+            input_suite_path.display()
+        );
+    }
+
+    if let Some(schedule) = artifacts.optimizer_schedule() {
+        // write the optimizer schedule so that it can be replayed
+        log::debug!("Write optimizer schedule");
+        std::fs::write(&schedule_path, schedule.to_string())
+            .map_err(|why| format!("Could not write {}: {}", schedule_path.display(), why))?;
+
+        println!(
+            "Optimizer schedule `{}` written to '{}'",
+            schedule,
+            schedule_path.display()
+        );
+    }
+
+    let lookup_report = artifacts.lookup_report();
+    if !lookup_report.is_empty() {
+        println!(
+            "Lookups with a dynamic index ({} constraints):\n{}",
+            lookup_report.cost(),
+            lookup_report
+        );
+    }
+
     println!("Number of constraints: {}", num_constraints);
     Ok(())
 }
@@ -57,6 +57,14 @@ pub fn subcommand() -> App<'static, 'static> {
         .help("Read arguments from stdin")
         .conflicts_with("arguments")
         .required(false)
+    ).arg(Arg::with_name("input-format")
+        .long("input-format")
+        .help("Format of the arguments read from stdin. The binary format is the number of field elements as a little-endian u64, followed by each element in little-endian")
+        .takes_value(true)
+        .possible_values(&["json", "binary"])
+        .default_value("json")
+        .conflicts_with("arguments")
+        .required(false)
     )
 }
 
@@ -88,12 +96,19 @@ fn cli_compute<T: Field>(ir_prog: ir::Prog<T>, sub_matches: &ArgMatches) -> Resu
 
     let is_stdin = sub_matches.is_present("stdin");
     let is_abi = sub_matches.is_present("abi");
+    let is_binary = sub_matches.value_of("input-format") == Some("binary");
 
     if !is_stdin && is_abi {
         return Err("ABI input as inline argument is not supported. Please use `--stdin`.".into());
     }
 
-    let signature = match is_abi {
+    if !is_stdin && is_binary {
+        return Err(
+            "Binary input as inline argument is not supported. Please use `--stdin`.".into(),
+        );
+    }
+
+    let abi = match is_abi {
         true => {
             let path = Path::new(sub_matches.value_of("abi-spec").unwrap());
             let file = File::open(&path)
@@ -102,9 +117,14 @@ fn cli_compute<T: Field>(ir_prog: ir::Prog<T>, sub_matches: &ArgMatches) -> Resu
 
             let abi: Abi = from_reader(&mut reader).map_err(|why| why.to_string())?;
 
-            abi.signature()
+            Some(abi)
         }
-        false => ConcreteSignature::new()
+        false => None,
+    };
+
+    let signature = match &abi {
+        Some(abi) => abi.signature(),
+        None => ConcreteSignature::new()
             .inputs(vec![ConcreteType::FieldElement; ir_prog.arguments.len()])
             .outputs(vec![ConcreteType::FieldElement; ir_prog.returns.len()]),
     };
@@ -126,23 +146,20 @@ fn cli_compute<T: Field>(ir_prog: ir::Prog<T>, sub_matches: &ArgMatches) -> Resu
         }
         // take stdin arguments
         true => {
-            let mut stdin = stdin();
+            let stdin = stdin();
             let mut input = String::new();
 
-            match is_abi {
-                true => match stdin.read_to_string(&mut input) {
-                    Ok(_) => {
-                        use zokrates_abi::parse_strict;
-
-                        parse_strict(&input, signature.inputs)
-                            .map(Inputs::Abi)
-                            .map_err(|why| why.to_string())
-                    }
-                    Err(_) => Err(String::from("???")),
-                },
-                false => match ir_prog.arguments_count() {
+            match (is_binary, is_abi) {
+                // the arguments are decoded as they are read, without buffering the whole input
+                (true, _) => zokrates_abi::decode_binary(stdin.lock(), ir_prog.arguments_count())
+                    .map(Inputs::Raw)
+                    .map_err(|why| why.to_string()),
+                (false, true) => zokrates_abi::decode_json(stdin.lock(), &signature.inputs)
+                    .map(Inputs::Raw)
+                    .map_err(|why| why.to_string()),
+                (false, false) => match ir_prog.arguments_count() {
                     0 => Ok(Inputs::Raw(vec![])),
-                    _ => match stdin.read_to_string(&mut input) {
+                    _ => match stdin.lock().read_to_string(&mut input) {
                         Ok(_) => {
                             input.retain(|x| x != '\n');
                             input
@@ -161,9 +178,18 @@ fn cli_compute<T: Field>(ir_prog: ir::Prog<T>, sub_matches: &ArgMatches) -> Resu
 
     let interpreter = ir::Interpreter::default();
 
-    let witness = interpreter
-        .execute(&ir_prog, &arguments.encode())
-        .map_err(|e| format!("Execution failed: {}", e))?;
+    // deferred inputs are placeholders at this point, their value is set by `finalize-witness`
+    let deferred = abi
+        .as_ref()
+        .map(|abi| abi.deferred_arguments())
+        .unwrap_or_default();
+
+    let witness = if deferred.is_empty() {
+        interpreter.execute(&ir_prog, &arguments.encode())
+    } else {
+        interpreter.execute_deferred(&ir_prog, &arguments.encode(), &deferred)
+    }
+    .map_err(|e| format!("Execution failed: {}", e))?;
 
     use zokrates_abi::Decode;
 
@@ -181,10 +207,33 @@ fn cli_compute<T: Field>(ir_prog: ir::Prog<T>, sub_matches: &ArgMatches) -> Resu
 
     let writer = BufWriter::new(output_file);
 
+    // deferred inputs are recorded in the witness, which `generate-proof` refuses until they are set
+    let pending: Vec<_> = abi
+        .map(|abi| {
+            abi.inputs
+                .into_iter()
+                .filter(|i| i.deferred)
+                .map(|i| i.name)
+                .collect()
+        })
+        .unwrap_or_default();
+
     witness
-        .write(writer)
+        .write_pending(&pending, writer)
         .map_err(|why| format!("Could not save witness: {:?}", why))?;
 
     println!("Witness file written to '{}'", output_path.display());
+
+    if !pending.is_empty() {
+        println!(
+            "Witness depends on deferred inputs {}, run `finalize-witness` before generating a proof",
+            pending
+                .iter()
+                .map(|i| format!("`{}`", i))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
     Ok(())
 }
@@ -0,0 +1,181 @@
+use crate::constants::{ABI_SPEC_DEFAULT_PATH, FLATTENED_CODE_DEFAULT_PATH, WITNESS_DEFAULT_PATH};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use serde_json::from_reader;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use zokrates_abi::{parse_strict, Decode, Encode, Inputs};
+use zokrates_core::ir;
+use zokrates_core::ir::ProgEnum;
+use zokrates_core::typed_absy::abi::Abi;
+use zokrates_field::Field;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("finalize-witness")
+        .about("Sets the deferred inputs of a witness, re-executing only the statements which depend on them")
+        .arg(
+            Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .help("Path of the binary")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(FLATTENED_CODE_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("abi-spec")
+                .short("s")
+                .long("abi-spec")
+                .help("Path of the ABI specification")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(ABI_SPEC_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("witness")
+                .short("w")
+                .long("witness")
+                .help("Path of the witness computed with placeholders for the deferred inputs")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(WITNESS_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .help("Path of the output file, defaults to overwriting the witness")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("set")
+                .long("set")
+                .help("Value of a deferred input, ABI-encoded, like `--set main.block_hash=0x2a`")
+                .value_name("NAME=VALUE")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .required(true),
+        )
+}
+
+pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
+    // read compiled program
+    let path = Path::new(sub_matches.value_of("input").unwrap());
+    let file =
+        File::open(&path).map_err(|why| format!("Could not open {}: {}", path.display(), why))?;
+
+    let mut reader = BufReader::new(file);
+
+    match ProgEnum::deserialize(&mut reader)? {
+        ProgEnum::Bn128Program(p) => cli_finalize(p, sub_matches),
+        ProgEnum::Bls12_377Program(p) => cli_finalize(p, sub_matches),
+        ProgEnum::Bls12_381Program(p) => cli_finalize(p, sub_matches),
+        ProgEnum::Bw6_761Program(p) => cli_finalize(p, sub_matches),
+    }
+}
+
+// the flattened arguments set by `assignment`, of the form `[main.]name=value`
+fn parse_assignment<T: Field>(abi: &Abi, assignment: &str) -> Result<Vec<(usize, T)>, String> {
+    let (name, value) = assignment
+        .split_once('=')
+        .ok_or_else(|| format!("Expected NAME=VALUE, found `{}`", assignment))?;
+
+    let name = name.strip_prefix("main.").unwrap_or(name);
+
+    let index = abi
+        .inputs
+        .iter()
+        .position(|i| i.name == name)
+        .ok_or_else(|| format!("Unknown input `{}`", name))?;
+
+    if !abi.inputs[index].deferred {
+        return Err(format!("Input `{}` is not deferred", name));
+    }
+
+    // values which are not JSON, like hexadecimal field elements, are read as strings
+    let value = serde_json::from_str(value)
+        .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+
+    let values = parse_strict(
+        &serde_json::Value::Array(vec![value]).to_string(),
+        vec![abi.inputs[index].ty.clone()],
+    )
+    .map_err(|why| format!("Could not parse value of `{}`: {}", name, why))?;
+
+    Ok(abi
+        .argument_range(index)
+        .zip(Inputs::Abi(values).encode())
+        .collect())
+}
+
+fn cli_finalize<T: Field>(ir_prog: ir::Prog<T>, sub_matches: &ArgMatches) -> Result<(), String> {
+    println!("Finalizing witness...");
+
+    let path = Path::new(sub_matches.value_of("abi-spec").unwrap());
+    let file =
+        File::open(&path).map_err(|why| format!("Could not open {}: {}", path.display(), why))?;
+    let abi: Abi = from_reader(BufReader::new(file)).map_err(|why| why.to_string())?;
+
+    let witness_path = Path::new(sub_matches.value_of("witness").unwrap());
+    let witness_file = File::open(&witness_path)
+        .map_err(|why| format!("Could not open {}: {}", witness_path.display(), why))?;
+    let (witness, _) = ir::Witness::read_pending(witness_file)
+        .map_err(|why| format!("Could not load witness: {:?}", why))?;
+
+    let values = sub_matches
+        .values_of("set")
+        .unwrap()
+        .map(|assignment| parse_assignment(&abi, assignment))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    // all deferred inputs must be set, as the constraints depending on them are checked again
+    if let Some(missing) = abi.inputs.iter().enumerate().find(|(index, i)| {
+        i.deferred
+            && !abi
+                .argument_range(*index)
+                .all(|a| values.iter().any(|(b, _)| a == *b))
+    }) {
+        return Err(format!(
+            "Missing value for deferred input `{}`",
+            missing.1.name
+        ));
+    }
+
+    let interpreter = ir::Interpreter::default();
+
+    let witness = interpreter
+        .finalize(&ir_prog, witness, &values)
+        .map_err(|e| format!("Execution failed: {}", e))?;
+
+    // `--verbose` is a global flag of the cli
+    if sub_matches.is_present("verbose") {
+        let results_json_value: serde_json::Value =
+            zokrates_abi::Values::decode(witness.return_values(), abi.outputs).into_serde_json();
+        println!("\nWitness: \n{}\n", results_json_value);
+    }
+
+    // write witness to file
+    let output_path = Path::new(
+        sub_matches
+            .value_of("output")
+            .unwrap_or_else(|| sub_matches.value_of("witness").unwrap()),
+    );
+    let output_file = File::create(&output_path)
+        .map_err(|why| format!("Could not create {}: {}", output_path.display(), why))?;
+
+    witness
+        .write(BufWriter::new(output_file))
+        .map_err(|why| format!("Could not save witness: {:?}", why))?;
+
+    println!("Witness file written to '{}'", output_path.display());
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use crate::constants::{
+    ABI_SPEC_DEFAULT_PATH, FLATTENED_CODE_DEFAULT_PATH, INPUTS_SUITE_DEFAULT_PATH,
+};
+use crate::helpers::input_suite_path;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use serde_json::from_reader;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+use zokrates_abi::Decode;
+use zokrates_core::compile::InputSuite;
+use zokrates_core::ir;
+use zokrates_core::ir::ProgEnum;
+use zokrates_core::typed_absy::abi::Abi;
+use zokrates_field::Field;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("gen-inputs")
+        .about("Generates inputs on the boundaries of the comparisons of a program compiled with `--input-boundaries`. Each line of the output is a JSON array of arguments in the ABI format")
+        .arg(
+            Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .help("Path of the binary")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(FLATTENED_CODE_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("abi-spec")
+                .short("s")
+                .long("abi")
+                .help("Path of the ABI specification")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(ABI_SPEC_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .help("Path of the output file")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(INPUTS_SUITE_DEFAULT_PATH),
+        )
+}
+
+pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
+    // read compiled program
+    let path = Path::new(sub_matches.value_of("input").unwrap());
+    let file =
+        File::open(&path).map_err(|why| format!("Could not open {}: {}", path.display(), why))?;
+
+    let mut reader = BufReader::new(file);
+
+    match ProgEnum::deserialize(&mut reader)? {
+        ProgEnum::Bn128Program(p) => cli_gen_inputs(p, sub_matches),
+        ProgEnum::Bls12_377Program(p) => cli_gen_inputs(p, sub_matches),
+        ProgEnum::Bls12_381Program(p) => cli_gen_inputs(p, sub_matches),
+        ProgEnum::Bw6_761Program(p) => cli_gen_inputs(p, sub_matches),
+    }
+}
+
+fn cli_gen_inputs<T: Field>(ir_prog: ir::Prog<T>, sub_matches: &ArgMatches) -> Result<(), String> {
+    let path = Path::new(sub_matches.value_of("input").unwrap());
+    let abi_spec_path = Path::new(sub_matches.value_of("abi-spec").unwrap());
+    let output_path = Path::new(sub_matches.value_of("output").unwrap());
+
+    println!("Generating inputs for {}\n", path.display());
+
+    // the predicates do not survive compilation, so the boundaries are computed by `compile --input-boundaries`
+    let suite_path = input_suite_path(path);
+    let suite_file = File::open(&suite_path).map_err(|why| {
+        format!(
+            "Could not open {}: {}. Compile the program with `--input-boundaries` first",
+            suite_path.display(),
+            why
+        )
+    })?;
+    let suite: InputSuite<T> =
+        from_reader(BufReader::new(suite_file)).map_err(|why| why.to_string())?;
+
+    let abi_spec_file = File::open(&abi_spec_path)
+        .map_err(|why| format!("Could not open {}: {}", abi_spec_path.display(), why))?;
+    let abi: Abi = from_reader(BufReader::new(abi_spec_file)).map_err(|why| why.to_string())?;
+
+    let signature = abi.signature();
+
+    let output_file = File::create(&output_path)
+        .map_err(|why| format!("Could not create {}: {}", output_path.display(), why))?;
+
+    let mut writer = BufWriter::new(output_file);
+
+    for candidate in suite.candidates.iter() {
+        // a suite written for another build of the program would be decoded silently
+        if candidate.values.len() != ir_prog.arguments.len() {
+            return Err(format!(
+                "Expected {} inputs for {}, found {} in {}",
+                ir_prog.arguments.len(),
+                path.display(),
+                candidate.values.len(),
+                suite_path.display()
+            ));
+        }
+
+        let values =
+            zokrates_abi::Values::decode(candidate.values.clone(), signature.inputs.clone())
+                .into_serde_json();
+
+        writeln!(writer, "{}", values).map_err(|_| "Unable to write data to file.".to_string())?;
+    }
+
+    writer
+        .flush()
+        .map_err(|_| "Unable to flush buffer".to_string())?;
+
+    for predicate in suite.skipped.iter() {
+        println!("Skipped predicate: {}", predicate);
+    }
+
+    println!(
+        "{} input{} written to '{}'",
+        suite.candidates.len(),
+        if suite.candidates.len() == 1 { "" } else { "s" },
+        output_path.display()
+    );
+
+    Ok(())
+}
@@ -153,12 +153,25 @@ fn cli_generate_proof<T: Field, S: Scheme<T>, B: Backend<T, S>>(
 
     // deserialize witness
     let witness_path = Path::new(sub_matches.value_of("witness").unwrap());
+
     let witness_file = File::open(&witness_path)
         .map_err(|why| format!("Could not open {}: {}", witness_path.display(), why))?;
 
-    let witness = ir::Witness::read(witness_file)
+    let (witness, deferred) = ir::Witness::read_pending(witness_file)
         .map_err(|why| format!("Could not load witness: {:?}", why))?;
 
+    if !deferred.is_empty() {
+        return Err(format!(
+            "Witness {} depends on deferred inputs {} which were not set, run `finalize-witness` first",
+            witness_path.display(),
+            deferred
+                .iter()
+                .map(|i| format!("`{}`", i))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
     let pk_path = Path::new(sub_matches.value_of("proving-key-path").unwrap());
     let proof_path = Path::new(sub_matches.value_of("proof-path").unwrap());
 
@@ -171,7 +184,7 @@ fn cli_generate_proof<T: Field, S: Scheme<T>, B: Backend<T, S>>(
         .read_to_end(&mut pk)
         .map_err(|why| format!("Could not read {}: {}", pk_path.display(), why))?;
 
-    let proof = B::generate_proof(program, witness, pk);
+    let proof = B::generate_proof(&program, witness, pk);
     let mut proof_file = File::create(proof_path).unwrap();
 
     let proof = serde_json::to_string_pretty(&proof).unwrap();
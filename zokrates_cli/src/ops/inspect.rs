@@ -0,0 +1,154 @@
+use crate::constants::{ABI_SPEC_DEFAULT_PATH, FLATTENED_CODE_DEFAULT_PATH};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use serde_json::from_reader;
+use std::fs::File;
+use std::io::{stdout, BufReader, BufWriter, Write};
+use std::ops::Range;
+use std::path::Path;
+use zokrates_core::flat_absy::FlatVariable;
+use zokrates_core::ir;
+use zokrates_core::ir::ProgEnum;
+use zokrates_core::typed_absy::abi::Abi;
+use zokrates_field::Field;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("inspect")
+        .about("Displays selected statements of a compiled program")
+        .arg(
+            Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .help("Path of the binary")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(FLATTENED_CODE_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("abi-spec")
+                .short("s")
+                .long("abi-spec")
+                .help("Path of the ABI specification, used to find the variables of the inputs of `main`")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(ABI_SPEC_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("show-constraints")
+                .long("show-constraints")
+                .help("Displays the statements in a range of indices like `1000..1050`")
+                .value_name("RANGE")
+                .takes_value(true)
+                .required(false)
+                .conflicts_with("show-variable"),
+        )
+        .arg(
+            Arg::with_name("show-variable")
+                .long("show-variable")
+                .help("Displays the statements referencing a variable, either like `_42` or as an input of `main` like `main.x`")
+                .value_name("VARIABLE")
+                .takes_value(true)
+                .required(false),
+        )
+}
+
+pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
+    // read compiled program
+    let path = Path::new(sub_matches.value_of("input").unwrap());
+    let file =
+        File::open(&path).map_err(|why| format!("Could not open {}: {}", path.display(), why))?;
+
+    let mut reader = BufReader::new(file);
+
+    match ProgEnum::deserialize(&mut reader)? {
+        ProgEnum::Bn128Program(p) => cli_inspect(p, sub_matches),
+        ProgEnum::Bls12_377Program(p) => cli_inspect(p, sub_matches),
+        ProgEnum::Bls12_381Program(p) => cli_inspect(p, sub_matches),
+        ProgEnum::Bw6_761Program(p) => cli_inspect(p, sub_matches),
+    }
+}
+
+fn parse_range(s: &str) -> Result<Range<usize>, String> {
+    let error = || format!("Invalid range `{}`, expected a range like `1000..1050`", s);
+
+    let mut bounds = s.splitn(2, "..");
+    let start = bounds.next().unwrap().parse().map_err(|_| error())?;
+    let end = bounds
+        .next()
+        .ok_or_else(error)?
+        .parse()
+        .map_err(|_| error())?;
+
+    Ok(start..end)
+}
+
+// the variables of the input `name` of `main`, which are laid out in the order of the abi
+fn input_variables<T: Field>(
+    ir_prog: &ir::Prog<T>,
+    abi_path: &Path,
+    name: &str,
+) -> Result<Vec<FlatVariable>, String> {
+    let file = File::open(&abi_path)
+        .map_err(|why| format!("Could not open {}: {}", abi_path.display(), why))?;
+    let abi: Abi = from_reader(BufReader::new(file)).map_err(|why| why.to_string())?;
+
+    let mut offset = 0;
+
+    for input in &abi.inputs {
+        let count = input.ty.get_primitive_count();
+        if input.name == name {
+            return ir_prog
+                .arguments
+                .get(offset..offset + count)
+                .map(|arguments| arguments.iter().map(|p| p.id).collect())
+                .ok_or_else(|| {
+                    format!(
+                        "Expected at least {} inputs to match {}, found {} in the program",
+                        offset + count,
+                        abi_path.display(),
+                        ir_prog.arguments.len()
+                    )
+                });
+        }
+        offset += count;
+    }
+
+    Err(format!("Input `{}` not found in main", name))
+}
+
+fn cli_inspect<T: Field>(ir_prog: ir::Prog<T>, sub_matches: &ArgMatches) -> Result<(), String> {
+    let stdout = stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    let res = match (
+        sub_matches.value_of("show-constraints"),
+        sub_matches.value_of("show-variable"),
+    ) {
+        (Some(range), _) => {
+            let range = parse_range(range)?;
+            write!(writer, "{}", ir_prog.display_range(range))
+        }
+        (None, Some(variable)) => {
+            let variables = match variable.strip_prefix("main.") {
+                Some(name) => input_variables(
+                    &ir_prog,
+                    Path::new(sub_matches.value_of("abi-spec").unwrap()),
+                    name,
+                )?,
+                None => vec![FlatVariable::try_from_human_readable(variable)
+                    .map_err(|_| format!("Invalid variable `{}`", variable))?],
+            };
+
+            write!(
+                writer,
+                "{}",
+                ir_prog.display_matching(|s| variables.iter().any(|v| s.references(v)))
+            )
+        }
+        (None, None) => writeln!(writer, "{}", ir_prog),
+    };
+
+    res.and_then(|_| writer.flush())
+        .map_err(|why| format!("Could not display program: {}", why))
+}
@@ -1,14 +1,21 @@
+pub mod advise;
+pub mod attest;
 pub mod check;
 pub mod compile;
 pub mod compute_witness;
 pub mod export_verifier;
+pub mod finalize_witness;
+pub mod gen_inputs;
 #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
 pub mod generate_proof;
 pub mod generate_smtlib2;
+pub mod inspect;
 pub mod print_proof;
 #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
 pub mod setup;
 #[cfg(feature = "ark")]
+pub mod srs;
+#[cfg(feature = "ark")]
 pub mod universal_setup;
 #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
 pub mod verify;
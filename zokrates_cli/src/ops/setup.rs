@@ -1,10 +1,13 @@
 use crate::constants;
 use crate::helpers::*;
+#[cfg(feature = "ark")]
+use crate::ops::srs;
 use clap::{App, Arg, ArgMatches, SubCommand};
 use std::convert::TryFrom;
 use std::fs::File;
 use std::io::{BufReader, Write};
 use std::path::Path;
+use zokrates_core::attestation::{setup_record_path, SetupRecord};
 use zokrates_core::ir;
 use zokrates_core::ir::ProgEnum;
 #[cfg(feature = "ark")]
@@ -17,8 +20,9 @@ use zokrates_core::proof_system::libsnark::Libsnark;
 use zokrates_core::proof_system::*;
 use zokrates_field::Field;
 
+#[allow(clippy::let_and_return)]
 pub fn subcommand() -> App<'static, 'static> {
-    SubCommand::with_name("setup")
+    let app = SubCommand::with_name("setup")
         .about("Performs a trusted setup for a given constraint system")
         .arg(
             Arg::with_name("input")
@@ -70,6 +74,12 @@ pub fn subcommand() -> App<'static, 'static> {
                 .possible_values(constants::SCHEMES)
                 .default_value(constants::G16),
         )
+        .arg(
+            Arg::with_name("record")
+                .long("record")
+                .help("Write a record of this setup next to the verification key, to be used by `attest`")
+                .required(false),
+        )
         .arg(
             Arg::with_name("universal-setup-path")
                 .short("u")
@@ -79,7 +89,22 @@ pub fn subcommand() -> App<'static, 'static> {
                 .takes_value(true)
                 .required(false)
                 .default_value(constants::UNIVERSAL_SETUP_DEFAULT_PATH),
+        );
+
+    #[cfg(feature = "ark")]
+    let app = app
+        .arg(
+            Arg::with_name("universal-setup-size")
+                .short("n")
+                .long("universal-setup-size")
+                .help("Size of the universal setup to take from the cache, passed as an exponent. Takes precedence over the universal setup path")
+                .takes_value(true)
+                .required(false),
         )
+        .arg(srs::cache_arg())
+        .arg(srs::pins_arg());
+
+    app
 }
 
 pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
@@ -91,14 +116,16 @@ pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
     let mut reader = BufReader::new(file);
     let prog = ProgEnum::deserialize(&mut reader)?;
 
+    let curve = match prog {
+        ProgEnum::Bn128Program(_) => constants::BN128,
+        ProgEnum::Bls12_377Program(_) => constants::BLS12_377,
+        ProgEnum::Bls12_381Program(_) => constants::BLS12_381,
+        ProgEnum::Bw6_761Program(_) => constants::BW6_761,
+    };
+
     let parameters = Parameters::try_from((
         sub_matches.value_of("backend").unwrap(),
-        match prog {
-            ProgEnum::Bn128Program(_) => constants::BN128,
-            ProgEnum::Bls12_377Program(_) => constants::BLS12_377,
-            ProgEnum::Bls12_381Program(_) => constants::BLS12_381,
-            ProgEnum::Bw6_761Program(_) => constants::BW6_761,
-        },
+        curve,
         sub_matches.value_of("proving-scheme").unwrap(),
     ))?;
 
@@ -122,18 +149,29 @@ pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
         },
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, _, SchemeParameter::MARLIN) => {
-            let setup_path = Path::new(sub_matches.value_of("universal-setup-path").unwrap());
-            let setup_file = File::open(&setup_path)
-                .map_err(|why| format!("Couldn't open {}: {}\nExpected an universal setup, make sure `zokrates universal-setup` was run`", setup_path.display(), why))?;
+            let setup = match sub_matches.value_of("universal-setup-size") {
+                Some(size) => {
+                    let size = srs::parse_size(size)?;
+                    srs::lookup(sub_matches, curve, size)?
+                }
+                None => {
+                    let setup_path =
+                        Path::new(sub_matches.value_of("universal-setup-path").unwrap());
+                    let setup_file = File::open(&setup_path)
+                        .map_err(|why| format!("Couldn't open {}: {}\nExpected an universal setup, make sure `zokrates universal-setup` was run`", setup_path.display(), why))?;
 
-            let mut reader = BufReader::new(setup_file);
+                    let mut reader = BufReader::new(setup_file);
 
-            let mut setup = vec![];
-            use std::io::Read;
+                    let mut setup = vec![];
+                    use std::io::Read;
 
-            reader
-                .read_to_end(&mut setup)
-                .map_err(|_| "Cannot read universal setup".to_string())?;
+                    reader
+                        .read_to_end(&mut setup)
+                        .map_err(|_| "Cannot read universal setup".to_string())?;
+
+                    setup
+                }
+            };
 
             match prog {
                 ProgEnum::Bls12_377Program(p) => {
@@ -186,21 +224,20 @@ fn cli_setup_non_universal<T: Field, S: NonUniversalScheme<T>, B: NonUniversalBa
     let vk_path = Path::new(sub_matches.value_of("verification-key-path").unwrap());
 
     // run setup phase
-    let keypair = B::setup(program);
+    let keypair = B::setup(&program);
 
     // write verification key
+    let vk = serde_json::to_string_pretty(&keypair.vk).unwrap();
     let mut vk_file = File::create(vk_path)
         .map_err(|why| format!("Could not create {}: {}", vk_path.display(), why))?;
     vk_file
-        .write_all(
-            serde_json::to_string_pretty(&keypair.vk)
-                .unwrap()
-                .as_bytes(),
-        )
+        .write_all(vk.as_bytes())
         .map_err(|why| format!("Could not write to {}: {}", vk_path.display(), why))?;
 
     println!("Verification key written to '{}'", vk_path.display());
 
+    write_setup_record(&program, vk.as_bytes(), vk_path, sub_matches)?;
+
     // write proving key
     let mut pk_file = File::create(pk_path)
         .map_err(|why| format!("Could not create {}: {}", pk_path.display(), why))?;
@@ -231,21 +268,20 @@ fn cli_setup_universal<T: Field, S: UniversalScheme<T>, B: UniversalBackend<T, S
     let vk_path = Path::new(sub_matches.value_of("verification-key-path").unwrap());
 
     // run setup phase
-    let keypair = B::setup(srs, program)?;
+    let keypair = B::setup(srs, &program)?;
 
     // write verification key
+    let vk = serde_json::to_string_pretty(&keypair.vk).unwrap();
     let mut vk_file = File::create(vk_path)
         .map_err(|why| format!("Could not create {}: {}", vk_path.display(), why))?;
     vk_file
-        .write_all(
-            serde_json::to_string_pretty(&keypair.vk)
-                .unwrap()
-                .as_bytes(),
-        )
+        .write_all(vk.as_bytes())
         .map_err(|why| format!("Could not write to {}: {}", vk_path.display(), why))?;
 
     println!("Verification key written to '{}'", vk_path.display());
 
+    write_setup_record(&program, vk.as_bytes(), vk_path, sub_matches)?;
+
     // write proving key
     let mut pk_file = File::create(pk_path)
         .map_err(|why| format!("Could not create {}: {}", pk_path.display(), why))?;
@@ -258,3 +294,31 @@ fn cli_setup_universal<T: Field, S: UniversalScheme<T>, B: UniversalBackend<T, S
 
     Ok(())
 }
+
+// write the record of this setup if requested, used by `attest`
+fn write_setup_record<T: Field>(
+    program: &ir::Prog<T>,
+    vk: &[u8],
+    vk_path: &Path,
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    if !sub_matches.is_present("record") {
+        return Ok(());
+    }
+
+    log::debug!("Write setup record");
+
+    let record = SetupRecord::new(
+        sub_matches.value_of("backend").unwrap(),
+        sub_matches.value_of("proving-scheme").unwrap(),
+        program,
+        vk,
+    );
+
+    let record_path = setup_record_path(vk_path);
+    let record_file = File::create(&record_path)
+        .map_err(|why| format!("Could not create {}: {}", record_path.display(), why))?;
+
+    serde_json::to_writer_pretty(record_file, &record)
+        .map_err(|why| format!("Could not write to {}: {}", record_path.display(), why))
+}
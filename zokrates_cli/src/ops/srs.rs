@@ -0,0 +1,208 @@
+use crate::constants;
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use zokrates_core::proof_system::srs::{SrsCache, SrsError, SrsPins};
+
+pub fn cache_arg() -> Arg<'static, 'static> {
+    Arg::with_name("srs-cache")
+        .long("srs-cache")
+        .help("Path of the universal setup cache")
+        .value_name("PATH")
+        .takes_value(true)
+        .required(false)
+        .env("ZOKRATES_SRS_CACHE")
+        .default_value(constants::DEFAULT_SRS_CACHE_PATH.as_str())
+}
+
+pub fn pins_arg() -> Arg<'static, 'static> {
+    Arg::with_name("srs-pins")
+        .long("srs-pins")
+        .help("Path of the file pinning the expected hash of each universal setup read from the cache, with one `<curve> <size> <sha256>` line per setup")
+        .value_name("FILE")
+        .takes_value(true)
+        .required(false)
+        .env("ZOKRATES_SRS_PINS")
+        .default_value(constants::DEFAULT_SRS_PINS_PATH.as_str())
+}
+
+fn curve_arg() -> Arg<'static, 'static> {
+    Arg::with_name("curve")
+        .short("c")
+        .long("curve")
+        .help("Curve of the universal setup")
+        .takes_value(true)
+        .required(false)
+        .possible_values(constants::CURVES)
+        .default_value(constants::BN128)
+}
+
+fn size_arg() -> Arg<'static, 'static> {
+    Arg::with_name("size")
+        .short("n")
+        .long("size")
+        .help("Size of the universal setup passed as an exponent, either as `8` or `2^8` for 2**8")
+        .takes_value(true)
+        .required(true)
+}
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("srs")
+        .about("Manages the cache of universal setups shared across circuits")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("import")
+                .about("Copies a universal setup available locally to the cache after checking its hash")
+                .arg(curve_arg())
+                .arg(size_arg())
+                .arg(
+                    Arg::with_name("path")
+                        .long("path")
+                        .help("Path of the universal setup, optionally as a `file://` url. Remote urls are not supported")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("sha256")
+                        .long("sha256")
+                        .help("Expected hex encoded SHA-256 hash of the universal setup")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(cache_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("list")
+                .about("Lists the universal setups in the cache")
+                .arg(cache_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("verify")
+                .about("Checks the universal setups in the cache against their pinned hash")
+                .arg(cache_arg())
+                .arg(pins_arg()),
+        )
+}
+
+pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
+    match sub_matches.subcommand() {
+        ("import", Some(sub_matches)) => cli_import(sub_matches),
+        ("list", Some(sub_matches)) => cli_list(sub_matches),
+        ("verify", Some(sub_matches)) => cli_verify(sub_matches),
+        _ => unreachable!(),
+    }
+}
+
+/// Parses the size of a universal setup, given as an exponent with an optional `2^` prefix
+pub fn parse_size(size: &str) -> Result<u32, String> {
+    size.trim_start_matches("2^")
+        .parse::<u32>()
+        .map_err(|_| format!("Universal setup size {} is invalid", size))
+}
+
+pub fn cache(sub_matches: &ArgMatches) -> SrsCache {
+    SrsCache::new(sub_matches.value_of("srs-cache").unwrap())
+}
+
+pub fn pins(sub_matches: &ArgMatches) -> Result<SrsPins, String> {
+    SrsPins::load(sub_matches.value_of("srs-pins").unwrap()).map_err(|e| e.to_string())
+}
+
+/// Looks up the universal setup for `curve` and `size`, failing with a hint on how to add it if it is missing
+pub fn lookup(sub_matches: &ArgMatches, curve: &str, size: u32) -> Result<Vec<u8>, String> {
+    let cache = cache(sub_matches);
+    let pins_path = sub_matches.value_of("srs-pins").unwrap();
+
+    cache
+        .lookup(curve, size, &pins(sub_matches)?)
+        .map_err(|e| match e {
+            e @ SrsError::MissingPin(_) => format!(
+                "{}\nPin its hash by adding `{} {} <HASH>` to {}",
+                e, curve, size, pins_path
+            ),
+            e => e.to_string(),
+        })?
+        .ok_or_else(|| {
+            format!(
+                "No universal setup of size 2^{} for curve {} found in {}\nAdd one with `zokrates srs import --curve {} --size {} --path <PATH> --sha256 <HASH>` or generate one with `zokrates universal-setup --curve {} --size {} --cache`, then pin its hash in {}",
+                size,
+                curve,
+                cache.root().display(),
+                curve,
+                size,
+                curve,
+                size,
+                pins_path
+            )
+        })
+}
+
+fn cli_import(sub_matches: &ArgMatches) -> Result<(), String> {
+    let cache = cache(sub_matches);
+    let curve = sub_matches.value_of("curve").unwrap();
+    let size = parse_size(sub_matches.value_of("size").unwrap())?;
+    let path = sub_matches.value_of("path").unwrap();
+    let hash = sub_matches.value_of("sha256").unwrap();
+
+    println!("Importing universal setup from {}...", path);
+
+    let entry = cache
+        .import(curve, size, path, hash)
+        .map_err(|e| e.to_string())?;
+
+    println!("Universal setup written to '{}'", entry.path.display());
+    println!(
+        "Reading it from the cache requires pinning its hash with a `{} {} {}` line in the pins file",
+        curve,
+        size,
+        hash.trim().to_lowercase()
+    );
+
+    Ok(())
+}
+
+fn cli_list(sub_matches: &ArgMatches) -> Result<(), String> {
+    let entries = cache(sub_matches).list().map_err(|e| e.to_string())?;
+
+    if entries.is_empty() {
+        println!("No universal setup in cache");
+    }
+
+    for entry in entries {
+        println!(
+            "{}\t2^{}\t{}",
+            entry.curve,
+            entry.size,
+            entry.path.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn cli_verify(sub_matches: &ArgMatches) -> Result<(), String> {
+    let cache = cache(sub_matches);
+    let pins = pins(sub_matches)?;
+    let entries = cache.list().map_err(|e| e.to_string())?;
+
+    let mut failures = 0;
+
+    for entry in entries {
+        match cache.verify(&entry.curve, entry.size, &pins) {
+            Ok(_) => println!("{}\t2^{}\tOK", entry.curve, entry.size),
+            Err(e @ SrsError::HashMismatch { .. }) | Err(e @ SrsError::MissingPin(..)) => {
+                failures += 1;
+                println!("{}\t2^{}\tFAILED: {}", entry.curve, entry.size, e)
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        n => Err(format!(
+            "{} universal setup{} failed verification",
+            n,
+            if n == 1 { "" } else { "s" }
+        )),
+    }
+}
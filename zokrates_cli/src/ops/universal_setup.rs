@@ -1,10 +1,12 @@
 use crate::constants;
 use crate::helpers::*;
+use crate::ops::srs;
 use clap::{App, Arg, ArgMatches, SubCommand};
 use std::convert::TryFrom;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
+use zokrates_core::attestation::sha256;
 #[cfg(feature = "ark")]
 use zokrates_core::proof_system::ark::Ark;
 #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
@@ -53,6 +55,13 @@ pub fn subcommand() -> App<'static, 'static> {
                 .required(false)
                 .default_value(constants::UNIVERSAL_SETUP_DEFAULT_SIZE),
         )
+        .arg(
+            Arg::with_name("cache")
+                .long("cache")
+                .help("Also store the generated universal setup in the cache, from which `setup` can take it with `--universal-setup-size`")
+                .required(false),
+        )
+        .arg(srs::cache_arg())
 }
 
 pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
@@ -88,14 +97,27 @@ fn cli_universal_setup<T: Field, S: UniversalScheme<T>, B: UniversalBackend<T, S
     let u_path = Path::new(sub_matches.value_of("universal-setup-path").unwrap());
 
     // get the size of the setup
-    let size = sub_matches.value_of("size").unwrap();
-    let size = size
-        .parse::<u32>()
-        .map_err(|_| format!("Universal setup size {} is invalid", size))?;
+    let size = srs::parse_size(sub_matches.value_of("size").unwrap())?;
 
     // run universal setup phase
     let setup = B::universal_setup(size);
 
+    if sub_matches.is_present("cache") {
+        let curve = sub_matches.value_of("curve").unwrap();
+
+        let entry = srs::cache(sub_matches)
+            .insert(curve, size, &setup)
+            .map_err(|e| e.to_string())?;
+
+        println!("Universal setup cached at '{}'", entry.path.display());
+        println!(
+            "Reading it from the cache requires pinning its hash with a `{} {} {}` line in the pins file",
+            curve,
+            size,
+            sha256(&setup)
+        );
+    }
+
     // write proving key
     let mut u_file = File::create(u_path)
         .map_err(|why| format!("Could not create {}: {}", u_path.display(), why))?;
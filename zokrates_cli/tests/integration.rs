@@ -447,4 +447,116 @@ mod integration {
         .succeeds()
         .unwrap();
     }
+
+    #[test]
+    #[ignore]
+    fn test_deferred_witness() {
+        let tmp_dir = TempDir::new(".tmp").unwrap();
+        let tmp_base = tmp_dir.path();
+        let flattened_path = tmp_base.join("out");
+        let abi_spec_path = tmp_base.join("abi.json");
+        let witness_path = tmp_base.join("witness");
+        let expected_witness_path = tmp_base.join("expected_witness");
+        let proving_key_path = tmp_base.join("proving.key");
+        let verification_key_path = tmp_base.join("verification.key");
+        let proof_path = tmp_base.join("proof.json");
+
+        let zokrates = env!("CARGO_BIN_EXE_zokrates");
+
+        assert_cli::Assert::command(&[
+            zokrates,
+            "compile",
+            "-i",
+            "./examples/book/deferred_input.zok",
+            "-s",
+            abi_spec_path.to_str().unwrap(),
+            "-o",
+            flattened_path.to_str().unwrap(),
+        ])
+        .succeeds()
+        .unwrap();
+
+        let compute = |witness_path: &Path, arguments: &str| {
+            assert_cli::Assert::command(&[
+                zokrates,
+                "compute-witness",
+                "-i",
+                flattened_path.to_str().unwrap(),
+                "-s",
+                abi_spec_path.to_str().unwrap(),
+                "-o",
+                witness_path.to_str().unwrap(),
+                "--stdin",
+                "--abi",
+            ])
+            .stdin(arguments)
+            .succeeds()
+            .unwrap();
+        };
+
+        // compute the witness with a placeholder for `block_hash`
+        compute(&witness_path, r#"["3", "0"]"#);
+        assert!(fs::read_to_string(&witness_path)
+            .unwrap()
+            .starts_with("deferred block_hash"));
+
+        assert_cli::Assert::command(&[
+            zokrates,
+            "setup",
+            "-i",
+            flattened_path.to_str().unwrap(),
+            "-p",
+            proving_key_path.to_str().unwrap(),
+            "-v",
+            verification_key_path.to_str().unwrap(),
+        ])
+        .succeeds()
+        .unwrap();
+
+        let generate_proof = [
+            zokrates,
+            "generate-proof",
+            "-i",
+            flattened_path.to_str().unwrap(),
+            "-w",
+            witness_path.to_str().unwrap(),
+            "-p",
+            proving_key_path.to_str().unwrap(),
+            "-j",
+            proof_path.to_str().unwrap(),
+        ];
+
+        // proving is rejected until the witness is finalized
+        assert_cli::Assert::command(&generate_proof)
+            .fails()
+            .and()
+            .stdout()
+            .contains("finalize-witness")
+            .unwrap();
+
+        assert_cli::Assert::command(&[
+            zokrates,
+            "finalize-witness",
+            "-i",
+            flattened_path.to_str().unwrap(),
+            "-s",
+            abi_spec_path.to_str().unwrap(),
+            "-w",
+            witness_path.to_str().unwrap(),
+            "--set",
+            "main.block_hash=0x2a",
+        ])
+        .succeeds()
+        .unwrap();
+
+        compute(&expected_witness_path, r#"["3", "42"]"#);
+        assert_eq!(
+            fs::read_to_string(&witness_path).unwrap(),
+            fs::read_to_string(&expected_witness_path).unwrap()
+        );
+
+        assert_cli::Assert::command(&generate_proof)
+            .succeeds()
+            .unwrap();
+    }
 }
@@ -1,6 +1,9 @@
 use crate::absy;
+use crate::flat_absy::RuntimeError;
+use crate::parser::Position;
 
 use num_bigint::BigUint;
+use std::collections::HashSet;
 use std::path::Path;
 use zokrates_pest_ast as pest;
 
@@ -10,6 +13,8 @@ impl<'ast> From<pest::File<'ast>> for absy::Module<'ast> {
             pest::SymbolDeclaration::Import(i) => import_directive_to_symbol_vec(i),
             pest::SymbolDeclaration::Constant(c) => vec![c.into()],
             pest::SymbolDeclaration::Struct(s) => vec![s.into()],
+            pest::SymbolDeclaration::Impl(i) => vec![i.into()],
+            pest::SymbolDeclaration::MethodImpl(i) => method_impl_to_symbol_vec(i),
             pest::SymbolDeclaration::Function(f) => vec![f.into()],
         }))
     }
@@ -180,6 +185,8 @@ impl<'ast> From<pest::FunctionDefinition<'ast>> for absy::SymbolDeclarationNode<
                 .flat_map(statements_from_statement)
                 .collect(),
             signature,
+            deprecated: deprecation(&function.attributes),
+            implementation: None,
         }
         .span(span.clone());
 
@@ -191,6 +198,60 @@ impl<'ast> From<pest::FunctionDefinition<'ast>> for absy::SymbolDeclarationNode<
     }
 }
 
+impl<'ast> From<pest::ImplDefinition<'ast>> for absy::SymbolDeclarationNode<'ast> {
+    fn from(definition: pest::ImplDefinition<'ast>) -> absy::SymbolDeclarationNode<'ast> {
+        use crate::absy::NodeValue;
+
+        let span = definition.span;
+
+        let implementation = absy::Implementation {
+            ty: definition.ty.id.span.as_str(),
+            operator: Some(definition.operator.span.as_str()),
+            name: definition.function.id.span.as_str(),
+        };
+
+        implementation_to_symbol(definition.function, implementation)
+            .value
+            .span(span)
+    }
+}
+
+// each method is a function taking the struct as first argument, `a.method(b)` being checked like `method(a, b)`
+fn method_impl_to_symbol_vec(
+    definition: pest::MethodImplDefinition,
+) -> Vec<absy::SymbolDeclarationNode> {
+    let ty = definition.ty.id.span.as_str();
+
+    definition
+        .functions
+        .into_iter()
+        .map(|function| {
+            let implementation = absy::Implementation {
+                ty,
+                operator: None,
+                name: function.id.span.as_str(),
+            };
+
+            implementation_to_symbol(function, implementation)
+        })
+        .collect()
+}
+
+fn implementation_to_symbol<'ast>(
+    function: pest::FunctionDefinition<'ast>,
+    implementation: absy::Implementation<'ast>,
+) -> absy::SymbolDeclarationNode<'ast> {
+    let mut declaration = absy::SymbolDeclarationNode::from(function);
+
+    if let absy::Symbol::Here(absy::SymbolDefinition::Function(ref mut f)) =
+        declaration.value.symbol
+    {
+        f.value.implementation = Some(implementation);
+    }
+
+    declaration
+}
+
 impl<'ast> From<pest::IdentifierExpression<'ast>> for absy::ConstantGenericNode<'ast> {
     fn from(g: pest::IdentifierExpression<'ast>) -> absy::ConstantGenericNode<'ast> {
         use absy::NodeValue;
@@ -219,16 +280,227 @@ impl<'ast> From<pest::Parameter<'ast>> for absy::ParameterNode<'ast> {
         )
         .span(param.id.span);
 
-        absy::Parameter::new(variable, private).span(param.span)
+        absy::Parameter {
+            deferred: param.deferred.is_some(),
+            ..absy::Parameter::new(variable, private)
+        }
+        .span(param.span)
     }
 }
 
-fn statements_from_statement(statement: pest::Statement) -> Vec<absy::StatementNode> {
+/// An attribute which is unknown, given more than once, or not allowed where it is used
+#[derive(Debug, PartialEq)]
+pub struct AttributeError {
+    pos: (Position, Position),
+    message: String,
+}
+
+impl AttributeError {
+    fn new(span: &pest::Span, message: String) -> Self {
+        let from = span.start_pos().line_col();
+        let to = span.end_pos().line_col();
+
+        AttributeError {
+            pos: (
+                Position {
+                    line: from.0,
+                    col: from.1,
+                },
+                Position {
+                    line: to.0,
+                    col: to.1,
+                },
+            ),
+            message,
+        }
+    }
+
+    pub fn pos(&self) -> (Position, Position) {
+        self.pos
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+// the source of an attribute, without the newlines which may follow it
+fn attribute_source<'ast>(attribute: &pest::Attribute<'ast>) -> &'ast str {
+    attribute.span.as_str().trim_end()
+}
+
+fn check_unique<'a, 'ast: 'a>(
+    mut ids: impl Iterator<Item = (&'a pest::IdentifierExpression<'ast>, &'a pest::Span<'ast>)>,
+    kind: &str,
+) -> Result<(), AttributeError> {
+    let mut seen = HashSet::new();
+
+    match ids.find(|(id, _)| !seen.insert(id.span.as_str())) {
+        Some((id, span)) => Err(AttributeError::new(
+            span,
+            format!("{} `{}` is given more than once", kind, id.span.as_str()),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Checks the attributes of the functions and statements of `file`: each attribute must be known, allowed where it is
+/// used, and given at most once
+pub fn check_attributes(file: &pest::File) -> Result<(), AttributeError> {
+    file.declarations
+        .iter()
+        .try_for_each(|declaration| match declaration {
+            pest::SymbolDeclaration::Function(f) => check_function_attributes(f),
+            pest::SymbolDeclaration::Impl(i) => check_function_attributes(&i.function),
+            pest::SymbolDeclaration::MethodImpl(i) => {
+                i.functions.iter().try_for_each(check_function_attributes)
+            }
+            _ => Ok(()),
+        })
+}
+
+fn check_function_attributes(function: &pest::FunctionDefinition) -> Result<(), AttributeError> {
+    check_unique(
+        function.attributes.iter().map(|a| (&a.id, &a.span)),
+        "Attribute",
+    )?;
+
+    for attribute in &function.attributes {
+        match attribute.id.span.as_str() {
+            "deprecated" => {
+                check_unique(
+                    attribute.arguments.iter().map(|a| (&a.id, &a.span)),
+                    "Argument",
+                )?;
+
+                for argument in &attribute.arguments {
+                    match (argument.id.span.as_str(), &argument.value) {
+                        ("since", Some(_)) | ("note", Some(_)) => {}
+                        _ => {
+                            return Err(AttributeError::new(
+                                &argument.span,
+                                format!(
+                                    "Invalid argument `{}` of `#[deprecated]`, expected `since = \"...\"` or `note = \"...\"`",
+                                    argument.span.as_str()
+                                ),
+                            ))
+                        }
+                    }
+                }
+            }
+            _ => {
+                return Err(AttributeError::new(
+                    &attribute.span,
+                    format!(
+                        "Unknown function attribute `{}`",
+                        attribute_source(attribute)
+                    ),
+                ))
+            }
+        }
+    }
+
+    function
+        .statements
+        .iter()
+        .try_for_each(check_statement_attributes)
+}
+
+fn check_statement_attributes(statement: &pest::Statement) -> Result<(), AttributeError> {
+    let attributes = statement.attributes();
+
+    check_unique(attributes.iter().map(|a| (&a.id, &a.span)), "Attribute")?;
+
+    for attribute in attributes {
+        match (attribute.id.span.as_str(), single_flag(attribute)) {
+            ("allow", Some("deprecated")) => {}
+            ("optimizer", Some("barrier")) => {
+                if !matches!(statement, pest::Statement::Assertion(_)) {
+                    return Err(AttributeError::new(
+                        &attribute.span,
+                        format!(
+                            "Attribute `{}` is only allowed on `assert` statements",
+                            attribute_source(attribute)
+                        ),
+                    ));
+                }
+            }
+            _ => {
+                return Err(AttributeError::new(
+                    &attribute.span,
+                    format!(
+                        "Unknown statement attribute `{}`",
+                        attribute_source(attribute)
+                    ),
+                ))
+            }
+        }
+    }
+
     match statement {
+        pest::Statement::Iteration(s) => {
+            s.statements.iter().try_for_each(check_statement_attributes)
+        }
+        _ => Ok(()),
+    }
+}
+
+// the name of the only argument of `attribute`, like `deprecated` in `#[allow(deprecated)]`
+fn single_flag<'ast>(attribute: &pest::Attribute<'ast>) -> Option<&'ast str> {
+    match attribute.arguments.as_slice() {
+        [argument] if argument.value.is_none() => Some(argument.id.span.as_str()),
+        _ => None,
+    }
+}
+
+fn has_attribute(attributes: &[pest::Attribute], id: &str, flag: &str) -> bool {
+    attributes
+        .iter()
+        .any(|a| a.id.span.as_str() == id && single_flag(a) == Some(flag))
+}
+
+fn deprecation<'ast>(attributes: &[pest::Attribute<'ast>]) -> Option<absy::Deprecation<'ast>> {
+    attributes
+        .iter()
+        .find(|a| a.id.span.as_str() == "deprecated")
+        .map(|attribute| {
+            attribute
+                .arguments
+                .iter()
+                .fold(absy::Deprecation::default(), |d, argument| {
+                    match (argument.id.span.as_str(), &argument.value) {
+                        ("since", Some(since)) => absy::Deprecation {
+                            since: Some(since.span.as_str()),
+                            ..d
+                        },
+                        ("note", Some(note)) => absy::Deprecation {
+                            note: Some(note.span.as_str()),
+                            ..d
+                        },
+                        _ => d,
+                    }
+                })
+        })
+}
+
+fn statements_from_statement(statement: pest::Statement) -> Vec<absy::StatementNode> {
+    let allow_deprecated = has_attribute(statement.attributes(), "allow", "deprecated");
+
+    let statements = match statement {
         pest::Statement::Definition(s) => statements_from_definition(s),
         pest::Statement::Iteration(s) => vec![absy::StatementNode::from(s)],
         pest::Statement::Assertion(s) => vec![absy::StatementNode::from(s)],
+        pest::Statement::StaticAssertion(s) => vec![absy::StatementNode::from(s)],
+        pest::Statement::EqualityAssertion(s) => vec![absy::StatementNode::from(s)],
         pest::Statement::Return(s) => vec![absy::StatementNode::from(s)],
+    };
+
+    match allow_deprecated {
+        true => statements
+            .into_iter()
+            .map(|s| absy::Node::new(s.start, s.end, absy::Statement::AllowDeprecated(box s)))
+            .collect(),
+        false => statements,
     }
 }
 
@@ -256,10 +528,12 @@ fn statements_from_definition(definition: pest::DefinitionStatement) -> Vec<absy
                     .span(definition.span.clone());
 
                     let s = match e.value {
-                        absy::Expression::FunctionCall(..) => absy::Statement::MultipleDefinition(
-                            vec![absy::AssigneeNode::from(i.identifier.clone())],
-                            e,
-                        ),
+                        absy::Expression::FunctionCall(..) | absy::Expression::MethodCall(..) => {
+                            absy::Statement::MultipleDefinition(
+                                vec![absy::AssigneeNode::from(i.identifier.clone())],
+                                e,
+                            )
+                        }
                         _ => absy::Statement::Definition(
                             absy::AssigneeNode::from(i.identifier.clone()),
                             e,
@@ -270,10 +544,12 @@ fn statements_from_definition(definition: pest::DefinitionStatement) -> Vec<absy
                 }
                 pest::TypedIdentifierOrAssignee::Assignee(a) => {
                     let s = match e.value {
-                        absy::Expression::FunctionCall(..) => absy::Statement::MultipleDefinition(
-                            vec![absy::AssigneeNode::from(a)],
-                            e,
-                        ),
+                        absy::Expression::FunctionCall(..) | absy::Expression::MethodCall(..) => {
+                            absy::Statement::MultipleDefinition(
+                                vec![absy::AssigneeNode::from(a)],
+                                e,
+                            )
+                        }
                         _ => absy::Statement::Definition(absy::AssigneeNode::from(a), e),
                     };
 
@@ -346,11 +622,60 @@ impl<'ast> From<pest::AssertionStatement<'ast>> for absy::StatementNode<'ast> {
     fn from(statement: pest::AssertionStatement<'ast>) -> absy::StatementNode<'ast> {
         use crate::absy::NodeValue;
 
-        absy::Statement::Assertion(absy::ExpressionNode::from(statement.expression))
+        let error = match has_attribute(&statement.attributes, "optimizer", "barrier") {
+            true => RuntimeError::SourceBarrier,
+            false => RuntimeError::Source,
+        };
+
+        absy::Statement::Assertion(absy::ExpressionNode::from(statement.expression), error)
             .span(statement.span)
     }
 }
 
+impl<'ast> From<pest::StaticAssertionStatement<'ast>> for absy::StatementNode<'ast> {
+    fn from(statement: pest::StaticAssertionStatement<'ast>) -> absy::StatementNode<'ast> {
+        use crate::absy::NodeValue;
+
+        let condition_text = statement.expression.span().as_str();
+
+        absy::Statement::StaticAssertion(
+            absy::ExpressionNode::from(statement.expression),
+            condition_text,
+        )
+        .span(statement.span)
+    }
+}
+
+impl<'ast> From<pest::EqualityAssertionStatement<'ast>> for absy::StatementNode<'ast> {
+    fn from(statement: pest::EqualityAssertionStatement<'ast>) -> absy::StatementNode<'ast> {
+        use crate::absy::NodeValue;
+
+        let left_text = statement.left.span().as_str();
+        let right_text = statement.right.span().as_str();
+
+        let equality = absy::Expression::Eq(
+            box absy::ExpressionNode::from(statement.left),
+            box absy::ExpressionNode::from(statement.right),
+        )
+        .span(statement.span.clone());
+
+        // `assert_eq!(a, b)` is `assert(a == b)` and `assert_ne!(a, b)` is `assert(a != b)`, reporting the values of
+        // `a` and `b` when they fail
+        let (condition, error) = match statement.kind {
+            pest::EqualityAssertionMacro::Eq(_) => (
+                equality,
+                RuntimeError::SourceAssertEq(format!("{} == {}", left_text, right_text)),
+            ),
+            pest::EqualityAssertionMacro::Ne(_) => (
+                absy::Expression::Not(box equality).span(statement.span.clone()),
+                RuntimeError::SourceAssertNe(format!("{} != {}", left_text, right_text)),
+            ),
+        };
+
+        absy::Statement::Assertion(condition, error).span(statement.span)
+    }
+}
+
 impl<'ast> From<pest::IterationStatement<'ast>> for absy::StatementNode<'ast> {
     fn from(statement: pest::IterationStatement<'ast>) -> absy::StatementNode<'ast> {
         use crate::absy::NodeValue;
@@ -375,6 +700,7 @@ impl<'ast> From<pest::Expression<'ast>> for absy::ExpressionNode<'ast> {
         match expression {
             pest::Expression::Binary(e) => absy::ExpressionNode::from(e),
             pest::Expression::Ternary(e) => absy::ExpressionNode::from(e),
+            pest::Expression::Match(e) => absy::ExpressionNode::from(e),
             pest::Expression::Literal(e) => absy::ExpressionNode::from(e),
             pest::Expression::Identifier(e) => absy::ExpressionNode::from(e),
             pest::Expression::Postfix(e) => absy::ExpressionNode::from(e),
@@ -487,6 +813,45 @@ impl<'ast> From<pest::TernaryExpression<'ast>> for absy::ExpressionNode<'ast> {
     }
 }
 
+impl<'ast> From<pest::MatchExpression<'ast>> for absy::ExpressionNode<'ast> {
+    fn from(expression: pest::MatchExpression<'ast>) -> absy::ExpressionNode<'ast> {
+        use crate::absy::NodeValue;
+
+        let width = expression.expressions.len();
+
+        let arms = expression
+            .arms
+            .into_iter()
+            .map(|arm| {
+                let pattern = match arm.pattern {
+                    // a single wildcard matches the whole tuple
+                    pest::MatchPattern::Wildcard(_) => vec![None; width],
+                    pest::MatchPattern::Tuple(t) => t
+                        .elements
+                        .into_iter()
+                        .map(|e| match e {
+                            pest::PatternElement::Literal(b) => Some(b.value.parse().unwrap()),
+                            pest::PatternElement::Wildcard(_) => None,
+                        })
+                        .collect(),
+                };
+
+                (pattern, absy::ExpressionNode::from(arm.expression))
+            })
+            .collect();
+
+        absy::Expression::Match(
+            expression
+                .expressions
+                .into_iter()
+                .map(absy::ExpressionNode::from)
+                .collect(),
+            arms,
+        )
+        .span(expression.span)
+    }
+}
+
 impl<'ast> From<pest::Spread<'ast>> for absy::SpreadNode<'ast> {
     fn from(spread: pest::Spread<'ast>) -> absy::SpreadNode<'ast> {
         use crate::absy::NodeValue;
@@ -607,33 +972,47 @@ impl<'ast> From<pest::PostfixExpression<'ast>> for absy::ExpressionNode<'ast> {
 
         // we start with the id, and we fold the array of accesses by wrapping the current value
         expression.accesses.into_iter().fold(id, |acc, a| match a {
-            pest::Access::Call(a) => match acc.value {
-                absy::Expression::Identifier(_) => absy::Expression::FunctionCall(
-                    &id_str,
-                    a.explicit_generics.map(|explicit_generics| {
-                        explicit_generics
-                            .values
-                            .into_iter()
-                            .map(|i| match i {
-                                pest::ConstantGenericValue::Underscore(_) => None,
-                                pest::ConstantGenericValue::Value(v) => {
-                                    Some(absy::ExpressionNode::from(v))
-                                }
-                                pest::ConstantGenericValue::Identifier(i) => {
-                                    Some(absy::Expression::Identifier(i.span.as_str()).span(i.span))
-                                }
-                            })
-                            .collect()
-                    }),
-                    a.arguments
-                        .expressions
+            pest::Access::Call(a) => {
+                let generics = a.explicit_generics.map(|explicit_generics| {
+                    explicit_generics
+                        .values
                         .into_iter()
-                        .map(absy::ExpressionNode::from)
-                        .collect(),
-                ),
-                e => unimplemented!("only identifiers are callable, found \"{}\"", e),
+                        .map(|i| match i {
+                            pest::ConstantGenericValue::Underscore(_) => None,
+                            pest::ConstantGenericValue::Value(v) => {
+                                Some(absy::ExpressionNode::from(v))
+                            }
+                            pest::ConstantGenericValue::Identifier(i) => {
+                                Some(absy::Expression::Identifier(i.span.as_str()).span(i.span))
+                            }
+                        })
+                        .collect()
+                });
+
+                let arguments = a
+                    .arguments
+                    .expressions
+                    .into_iter()
+                    .map(absy::ExpressionNode::from);
+
+                match acc.value {
+                    absy::Expression::Identifier(_) => {
+                        absy::Expression::FunctionCall(&id_str, generics, arguments.collect())
+                    }
+                    absy::Expression::Member(box receiver, box method) => {
+                        absy::Expression::MethodCall(
+                            box receiver,
+                            method,
+                            generics,
+                            arguments.collect(),
+                        )
+                    }
+                    e => {
+                        unimplemented!("only identifiers and methods are callable, found \"{}\"", e)
+                    }
+                }
+                .span(a.span)
             }
-            .span(a.span),
             pest::Access::Select(a) => {
                 absy::Expression::Select(box acc, box absy::RangeOrExpression::from(a.expression))
                     .span(a.span)
@@ -857,6 +1236,8 @@ mod tests {
                         signature: UnresolvedSignature::new()
                             .inputs(vec![])
                             .outputs(vec![UnresolvedType::FieldElement.mock()]),
+                        deprecated: None,
+                        implementation: None,
                     }
                     .into(),
                 )),
@@ -886,6 +1267,8 @@ mod tests {
                         signature: UnresolvedSignature::new()
                             .inputs(vec![])
                             .outputs(vec![UnresolvedType::Boolean.mock()]),
+                        deprecated: None,
+                        implementation: None,
                     }
                     .into(),
                 )),
@@ -938,6 +1321,8 @@ mod tests {
                                 UnresolvedType::Boolean.mock(),
                             ])
                             .outputs(vec![UnresolvedType::FieldElement.mock()]),
+                        deprecated: None,
+                        implementation: None,
                     }
                     .into(),
                 )),
@@ -970,6 +1355,8 @@ mod tests {
                             )
                             .into()],
                             signature: UnresolvedSignature::new().inputs(vec![ty.mock()]),
+                            deprecated: None,
+                            implementation: None,
                         }
                         .into(),
                     )),
@@ -1040,6 +1427,8 @@ mod tests {
                             )
                             .into()],
                             signature: UnresolvedSignature::new(),
+                            deprecated: None,
+                            implementation: None,
                         }
                         .into(),
                     )),
@@ -1152,6 +1541,7 @@ mod tests {
         // A `Definition` is generated and no `Declaration`s
 
         let definition = pest::DefinitionStatement {
+            attributes: vec![],
             lhs: vec![pest::TypedIdentifierOrAssignee::Assignee(pest::Assignee {
                 id: pest::IdentifierExpression {
                     value: String::from("a"),
@@ -1186,6 +1576,7 @@ mod tests {
         // A MultiDef is generated
 
         let definition = pest::DefinitionStatement {
+            attributes: vec![],
             lhs: vec![pest::TypedIdentifierOrAssignee::Assignee(pest::Assignee {
                 id: pest::IdentifierExpression {
                     value: String::from("a"),
@@ -1227,6 +1618,7 @@ mod tests {
         // `field a, b = foo()`
 
         let definition = pest::DefinitionStatement {
+            attributes: vec![],
             lhs: vec![
                 pest::TypedIdentifierOrAssignee::TypedIdentifier(pest::TypedIdentifier {
                     ty: pest::Type::Basic(pest::BasicType::Field(pest::FieldType {
@@ -1275,4 +1667,127 @@ mod tests {
             }
         };
     }
+
+    mod attributes {
+        use super::*;
+
+        fn check(source: &str) -> Result<(), String> {
+            let ast = pest::generate_ast(source).unwrap();
+            check_attributes(&ast).map_err(|e| e.message().to_string())
+        }
+
+        #[test]
+        fn any_order() {
+            let source = r#"#[deprecated(note = "use g", since = "0.8.0")]
+def f() -> field:
+    return 1
+
+def main() -> field:
+    #[optimizer(barrier)] #[allow(deprecated)] assert(f() == 1)
+    #[allow(deprecated)]
+    #[optimizer(barrier)] assert(f() == 1)
+    #[allow(deprecated)] assert_eq!(f(), 1)
+    return 1
+"#;
+
+            assert_eq!(check(source), Ok(()));
+
+            let module = absy::Module::from(pest::generate_ast(source).unwrap());
+
+            let f = match &module.symbols[0].value.symbol {
+                absy::Symbol::Here(absy::SymbolDefinition::Function(f)) => &f.value,
+                _ => unreachable!(),
+            };
+            assert_eq!(
+                f.deprecated,
+                Some(absy::Deprecation {
+                    since: Some("0.8.0"),
+                    note: Some("use g")
+                })
+            );
+
+            let main = match &module.symbols[1].value.symbol {
+                absy::Symbol::Here(absy::SymbolDefinition::Function(f)) => &f.value,
+                _ => unreachable!(),
+            };
+            for s in &main.statements[..2] {
+                match &s.value {
+                    absy::Statement::AllowDeprecated(s) => match &s.value {
+                        absy::Statement::Assertion(_, error) => assert!(error.is_barrier()),
+                        s => panic!("should be an Assertion, found {}", s),
+                    },
+                    s => panic!("should allow deprecated calls, found {}", s),
+                }
+            }
+            assert!(matches!(
+                main.statements[2].value,
+                absy::Statement::AllowDeprecated(_)
+            ));
+        }
+
+        #[test]
+        fn duplicate_deprecated_argument() {
+            let source = r#"#[deprecated(since = "0.7.0", since = "0.8.0")]
+def main() -> field:
+    return 1
+"#;
+
+            assert_eq!(
+                check(source),
+                Err("Argument `since` is given more than once".to_string())
+            );
+        }
+
+        #[test]
+        fn unknown() {
+            let source = r#"#[inline]
+def main() -> field:
+    return 1
+"#;
+
+            assert_eq!(
+                check(source),
+                Err("Unknown function attribute `#[inline]`".to_string())
+            );
+
+            let source = r#"def main() -> field:
+    #[allow(unused)] field a = 1
+    return a
+"#;
+
+            assert_eq!(
+                check(source),
+                Err("Unknown statement attribute `#[allow(unused)]`".to_string())
+            );
+        }
+
+        #[test]
+        fn misplaced() {
+            let source = r#"def main() -> field:
+    #[optimizer(barrier)] assert_eq!(1, 1)
+    return 1
+"#;
+
+            assert_eq!(
+                check(source),
+                Err(
+                    "Attribute `#[optimizer(barrier)]` is only allowed on `assert` statements"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn duplicate() {
+            let source = r#"def main() -> field:
+    #[allow(deprecated)] #[allow(deprecated)] assert(1 == 1)
+    return 1
+"#;
+
+            assert_eq!(
+                check(source),
+                Err("Attribute `allow` is given more than once".to_string())
+            );
+        }
+    }
 }
@@ -8,15 +8,20 @@
 mod from_ast;
 mod node;
 pub mod parameter;
+pub mod pattern;
 pub mod types;
 pub mod variable;
 
+pub use crate::absy::from_ast::{check_attributes, AttributeError};
 pub use crate::absy::node::{Node, NodeValue};
 pub use crate::absy::parameter::{Parameter, ParameterNode};
+pub use crate::absy::pattern::Pattern;
 use crate::absy::types::{FunctionIdentifier, UnresolvedSignature, UnresolvedType, UserTypeId};
 pub use crate::absy::variable::{Variable, VariableNode};
 use crate::embed::FlatEmbed;
+use crate::flat_absy::RuntimeError;
 use std::path::{Path, PathBuf};
+use typed_arena::Arena;
 
 use std::fmt;
 
@@ -192,6 +197,20 @@ impl<'ast> Module<'ast> {
             symbols: i.into_iter().collect(),
         }
     }
+
+    /// Renames the functions of `impl` blocks to their qualified name, as in `Foo::norm` or `<Foo as Add>::add`, which
+    /// cannot be written in the source: they can then neither collide with a free function nor be called by name
+    pub fn qualify_implementations(mut self, arena: &'ast Arena<String>) -> Self {
+        for declaration in &mut self.symbols {
+            if let Symbol::Here(SymbolDefinition::Function(ref f)) = declaration.value.symbol {
+                if let Some(implementation) = f.value.implementation {
+                    declaration.value.id = arena.alloc(implementation.to_string()).as_str();
+                }
+            }
+        }
+
+        self
+    }
 }
 
 pub type UnresolvedTypeNode<'ast> = Node<UnresolvedType<'ast>>;
@@ -274,12 +293,41 @@ pub struct Function<'ast> {
     pub statements: Vec<StatementNode<'ast>>,
     /// function signature
     pub signature: UnresolvedSignature<'ast>,
+    /// the `#[deprecated]` annotation of the function, if any
+    pub deprecated: Option<Deprecation<'ast>>,
+    /// the `impl` block the function is declared in, if any
+    pub implementation: Option<Implementation<'ast>>,
 }
 
 pub type FunctionNode<'ast> = Node<Function<'ast>>;
 
+/// The `impl` block of a function, either `impl Foo:` declaring methods of `Foo` or `impl Add for Foo:` implementing an
+/// operator for `Foo`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Implementation<'ast> {
+    /// the struct the block is for
+    pub ty: Identifier<'ast>,
+    /// the operator trait implemented by the block, if any
+    pub operator: Option<Identifier<'ast>>,
+    /// the name of the function in the block
+    pub name: Identifier<'ast>,
+}
+
+impl<'ast> fmt::Display for Implementation<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.operator {
+            Some(operator) => write!(f, "<{} as {}>::{}", self.ty, operator, self.name),
+            None => write!(f, "{}::{}", self.ty, self.name),
+        }
+    }
+}
+
 impl<'ast> fmt::Display for Function<'ast> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref deprecation) = self.deprecated {
+            write!(f, "{} ", deprecation)?;
+        }
+
         if !self.signature.generics.is_empty() {
             write!(
                 f,
@@ -310,6 +358,29 @@ impl<'ast> fmt::Display for Function<'ast> {
     }
 }
 
+/// A `#[deprecated]` annotation, with its optional `since` and `note` arguments
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Deprecation<'ast> {
+    pub since: Option<&'ast str>,
+    pub note: Option<&'ast str>,
+}
+
+impl<'ast> fmt::Display for Deprecation<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let arguments: Vec<_> = self
+            .since
+            .map(|since| format!("since = \"{}\"", since))
+            .into_iter()
+            .chain(self.note.map(|note| format!("note = \"{}\"", note)))
+            .collect();
+
+        match arguments.is_empty() {
+            true => write!(f, "#[deprecated]"),
+            false => write!(f, "#[deprecated({})]", arguments.join(", ")),
+        }
+    }
+}
+
 /// Something that we can assign to
 #[derive(Debug, Clone, PartialEq)]
 pub enum Assignee<'ast> {
@@ -337,7 +408,9 @@ pub enum Statement<'ast> {
     Return(ExpressionListNode<'ast>),
     Declaration(VariableNode<'ast>),
     Definition(AssigneeNode<'ast>, ExpressionNode<'ast>),
-    Assertion(ExpressionNode<'ast>),
+    Assertion(ExpressionNode<'ast>, RuntimeError),
+    /// a `static_assert`, whose condition must evaluate to `true` at compile time, along with the source of the condition
+    StaticAssertion(ExpressionNode<'ast>, &'ast str),
     For(
         VariableNode<'ast>,
         ExpressionNode<'ast>,
@@ -345,6 +418,8 @@ pub enum Statement<'ast> {
         Vec<StatementNode<'ast>>,
     ),
     MultipleDefinition(Vec<AssigneeNode<'ast>>, ExpressionNode<'ast>),
+    /// a statement annotated with `#[allow(deprecated)]`, in which calls to deprecated functions are not reported
+    AllowDeprecated(Box<StatementNode<'ast>>),
 }
 
 pub type StatementNode<'ast> = Node<Statement<'ast>>;
@@ -355,7 +430,13 @@ impl<'ast> fmt::Display for Statement<'ast> {
             Statement::Return(ref expr) => write!(f, "return {}", expr),
             Statement::Declaration(ref var) => write!(f, "{}", var),
             Statement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
-            Statement::Assertion(ref e) => write!(f, "assert({})", e),
+            Statement::Assertion(ref e, ref error) => {
+                if error.is_barrier() {
+                    write!(f, "#[optimizer(barrier)] ")?;
+                }
+                write!(f, "assert({})", e)
+            }
+            Statement::StaticAssertion(ref e, _) => write!(f, "static_assert({})", e),
             Statement::For(ref var, ref start, ref stop, ref list) => {
                 writeln!(f, "for {} in {}..{} do", var, start, stop)?;
                 for l in list {
@@ -372,6 +453,18 @@ impl<'ast> fmt::Display for Statement<'ast> {
                 }
                 write!(f, " = {}", rhs)
             }
+            Statement::AllowDeprecated(ref s) => write!(f, "#[allow(deprecated)] {}", s),
+        }
+    }
+}
+
+impl<'ast> Statement<'ast> {
+    /// Returns `true` if this is a return statement, annotated or not
+    pub fn is_return(&self) -> bool {
+        match self {
+            Statement::Return(..) => true,
+            Statement::AllowDeprecated(s) => s.value.is_return(),
+            _ => false,
         }
     }
 }
@@ -478,11 +571,21 @@ pub enum Expression<'ast> {
         Box<ExpressionNode<'ast>>,
         Box<ExpressionNode<'ast>>,
     ),
+    Match(
+        Vec<ExpressionNode<'ast>>,
+        Vec<(Pattern, ExpressionNode<'ast>)>,
+    ),
     FunctionCall(
         FunctionIdentifier<'ast>,
         Option<Vec<Option<ExpressionNode<'ast>>>>,
         Vec<ExpressionNode<'ast>>,
     ),
+    MethodCall(
+        Box<ExpressionNode<'ast>>,
+        Identifier<'ast>,
+        Option<Vec<Option<ExpressionNode<'ast>>>>,
+        Vec<ExpressionNode<'ast>>,
+    ),
     Lt(Box<ExpressionNode<'ast>>, Box<ExpressionNode<'ast>>),
     Le(Box<ExpressionNode<'ast>>, Box<ExpressionNode<'ast>>),
     Eq(Box<ExpressionNode<'ast>>, Box<ExpressionNode<'ast>>),
@@ -529,6 +632,24 @@ impl<'ast> fmt::Display for Expression<'ast> {
                 "if {} then {} else {} fi",
                 condition, consequent, alternative
             ),
+            Expression::Match(ref expressions, ref arms) => {
+                write!(
+                    f,
+                    "match ({}) {{ ",
+                    expressions
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )?;
+                for (i, (pattern, e)) in arms.iter().enumerate() {
+                    write!(f, "{} => {}", pattern::pattern_to_string(pattern), e)?;
+                    if i < arms.len() - 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, " }}")
+            }
             Expression::FunctionCall(ref i, ref g, ref p) => {
                 if let Some(g) = g {
                     write!(
@@ -552,6 +673,30 @@ impl<'ast> fmt::Display for Expression<'ast> {
                 }
                 write!(f, ")")
             }
+            Expression::MethodCall(ref receiver, ref method, ref g, ref p) => {
+                write!(f, "{}.{}", receiver, method)?;
+                if let Some(g) = g {
+                    write!(
+                        f,
+                        "::<{}>",
+                        g.iter()
+                            .map(|g| g
+                                .as_ref()
+                                .map(|g| g.to_string())
+                                .unwrap_or_else(|| "_".into()))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    )?;
+                }
+                write!(
+                    f,
+                    "({})",
+                    p.iter()
+                        .map(|p| p.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
             Expression::Lt(ref lhs, ref rhs) => write!(f, "({} < {})", lhs, rhs),
             Expression::Le(ref lhs, ref rhs) => write!(f, "({} <= {})", lhs, rhs),
             Expression::Eq(ref lhs, ref rhs) => write!(f, "({} == {})", lhs, rhs),
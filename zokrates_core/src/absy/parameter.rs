@@ -5,17 +5,23 @@ use std::fmt;
 pub struct Parameter<'ast> {
     pub id: VariableNode<'ast>,
     pub private: bool,
+    pub deferred: bool,
 }
 
 impl<'ast> Parameter<'ast> {
     pub fn new(v: VariableNode<'ast>, private: bool) -> Self {
-        Parameter { id: v, private }
+        Parameter {
+            id: v,
+            private,
+            deferred: false,
+        }
     }
 
     pub fn public(v: VariableNode<'ast>) -> Self {
         Parameter {
             id: v,
             private: false,
+            deferred: false,
         }
     }
 
@@ -23,6 +29,7 @@ impl<'ast> Parameter<'ast> {
         Parameter {
             id: v,
             private: true,
+            deferred: false,
         }
     }
 }
@@ -31,10 +38,12 @@ pub type ParameterNode<'ast> = Node<Parameter<'ast>>;
 
 impl<'ast> fmt::Display for Parameter<'ast> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let deferred = if self.deferred { "#[deferred] " } else { "" };
         let visibility = if self.private { "private " } else { "" };
         write!(
             f,
-            "{}{} {}",
+            "{}{}{} {}",
+            deferred,
             visibility,
             self.id.value.get_type(),
             self.id.value.id
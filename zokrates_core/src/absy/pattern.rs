@@ -0,0 +1,205 @@
+//! Module containing the patterns of `match` expressions over tuples of booleans, and their compilation to decision
+//! trees
+//!
+//! A `match` expression is lowered to nested `if/else` expressions following a decision tree: each node tests one
+//! element of the tuple, and each leaf is the first arm matching all the values tested on its path. The element tested
+//! is always one the first remaining arm depends on, so elements matched by wildcards are not tested, and a leaf is
+//! reached as soon as an arm is known to match.
+//!
+//! An element can be tested at several nodes and an arm can be selected by several leaves, so the lowered tree refers
+//! to them through the expressions it is given rather than repeating them. The semantic checker binds the elements
+//! and the arms selected by several leaves to variables, and lowers the tree over these variables.
+
+use crate::typed_absy::{BooleanExpression, IfElse};
+use std::collections::BTreeSet;
+
+/// A pattern over a tuple of booleans, `None` standing for the wildcard `_`
+pub type Pattern = Vec<Option<bool>>;
+
+pub fn pattern_to_string(p: &[Option<bool>]) -> String {
+    format!(
+        "({})",
+        p.iter()
+            .map(|e| e.map(|b| b.to_string()).unwrap_or_else(|| "_".into()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DecisionTree {
+    /// the index of the arm selected
+    Leaf(usize),
+    /// the index of the element tested, the subtree if it is `true`, the subtree if it is `false`
+    Node(usize, Box<DecisionTree>, Box<DecisionTree>),
+}
+
+impl DecisionTree {
+    /// Builds the decision tree selecting the first matching arm among `patterns`, which must all have `width`
+    /// elements. Returns a tuple matched by no pattern if they are not exhaustive.
+    pub fn build(patterns: &[Pattern], width: usize) -> Result<Self, Vec<bool>> {
+        let rows: Vec<(usize, &Pattern)> = patterns.iter().enumerate().collect();
+        Self::build_rows(&rows, &mut vec![None; width])
+    }
+
+    fn build_rows(
+        rows: &[(usize, &Pattern)],
+        tested: &mut Vec<Option<bool>>,
+    ) -> Result<Self, Vec<bool>> {
+        let (arm, first) = match rows.first() {
+            Some(row) => row,
+            // no arm is left for the values tested so far: the untested elements can take any value
+            None => return Err(tested.iter().map(|v| v.unwrap_or(false)).collect()),
+        };
+
+        // test the first element the first remaining arm depends on
+        let index = match (0..tested.len()).find(|i| tested[*i].is_none() && first[*i].is_some()) {
+            Some(index) => index,
+            None => return Ok(DecisionTree::Leaf(*arm)),
+        };
+
+        let mut branch = |value: bool| {
+            tested[index] = Some(value);
+            let rows: Vec<_> = rows
+                .iter()
+                .filter(|(_, p)| p[index].map(|v| v == value).unwrap_or(true))
+                .cloned()
+                .collect();
+            let tree = Self::build_rows(&rows, tested);
+            tested[index] = None;
+            tree
+        };
+
+        let consequence = branch(true)?;
+        let alternative = branch(false)?;
+
+        Ok(DecisionTree::Node(index, box consequence, box alternative))
+    }
+
+    /// The arms selected by the leaves, from left to right
+    pub fn leaves(&self) -> Vec<usize> {
+        match self {
+            DecisionTree::Leaf(arm) => vec![*arm],
+            DecisionTree::Node(_, box consequence, box alternative) => consequence
+                .leaves()
+                .into_iter()
+                .chain(alternative.leaves())
+                .collect(),
+        }
+    }
+
+    /// The arms selected by at least one leaf
+    pub fn arms(&self) -> BTreeSet<usize> {
+        self.leaves().into_iter().collect()
+    }
+
+    /// Lowers the tree to nested `if/else` expressions testing the matched `elements` and selecting among `arms`
+    pub fn lower<'ast, T: Clone, E: IfElse<'ast, T> + Clone>(
+        &self,
+        elements: &[BooleanExpression<'ast, T>],
+        arms: &[E],
+    ) -> E {
+        match self {
+            DecisionTree::Leaf(arm) => arms[*arm].clone(),
+            DecisionTree::Node(index, box consequence, box alternative) => E::if_else(
+                elements[*index].clone(),
+                consequence.lower(elements, arms),
+                alternative.lower(elements, arms),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DecisionTree::*;
+    use super::*;
+    use crate::typed_absy::FieldElementExpression;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn build() {
+        // (true, true, _) => 0, (false, _, true) => 1, _ => 2
+        let patterns = vec![
+            vec![Some(true), Some(true), None],
+            vec![Some(false), None, Some(true)],
+            vec![None, None, None],
+        ];
+
+        assert_eq!(
+            DecisionTree::build(&patterns, 3),
+            Ok(Node(
+                0,
+                box Node(1, box Leaf(0), box Leaf(2)),
+                box Node(2, box Leaf(1), box Leaf(2))
+            ))
+        );
+    }
+
+    #[test]
+    fn lower() {
+        // (true, true, _) => 0, (false, _, true) => 1, _ => 2
+        let patterns = vec![
+            vec![Some(true), Some(true), None],
+            vec![Some(false), None, Some(true)],
+            vec![None, None, None],
+        ];
+
+        let tree = DecisionTree::build(&patterns, 3).unwrap();
+
+        // the last arm is selected by two leaves
+        assert_eq!(tree.leaves(), vec![0, 2, 1, 2]);
+
+        let elements: Vec<BooleanExpression<Bn128Field>> = vec![
+            BooleanExpression::Identifier("a".into()),
+            BooleanExpression::Identifier("b".into()),
+            BooleanExpression::Identifier("c".into()),
+        ];
+        let arms: Vec<FieldElementExpression<Bn128Field>> = vec![
+            FieldElementExpression::Number(Bn128Field::from(1)),
+            FieldElementExpression::Number(Bn128Field::from(2)),
+            FieldElementExpression::Identifier("default".into()),
+        ];
+
+        assert_eq!(
+            tree.lower(&elements, &arms),
+            FieldElementExpression::if_else(
+                elements[0].clone(),
+                FieldElementExpression::if_else(
+                    elements[1].clone(),
+                    arms[0].clone(),
+                    arms[2].clone()
+                ),
+                FieldElementExpression::if_else(
+                    elements[2].clone(),
+                    arms[1].clone(),
+                    arms[2].clone()
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn wildcards_are_not_tested() {
+        // the second element is never tested
+        let patterns = vec![vec![Some(true), None], vec![None, None]];
+
+        assert_eq!(
+            DecisionTree::build(&patterns, 2),
+            Ok(Node(0, box Leaf(0), box Leaf(1)))
+        );
+        assert_eq!(
+            DecisionTree::build(&patterns, 2).unwrap().arms(),
+            vec![0, 1].into_iter().collect()
+        );
+
+        assert_eq!(DecisionTree::build(&[vec![None, None]], 2), Ok(Leaf(0)));
+    }
+
+    #[test]
+    fn non_exhaustive() {
+        let patterns = vec![vec![Some(true), None], vec![Some(false), Some(true)]];
+
+        assert_eq!(DecisionTree::build(&patterns, 2), Err(vec![false, false]));
+    }
+}
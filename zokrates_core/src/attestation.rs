@@ -0,0 +1,237 @@
+//! Module containing build attestations, the documents binding a verification key to the sources, configuration and
+//! compiler it was produced with
+//!
+//! The ingredients are recorded where they are produced: compiling records a `BuildRecord`, holding the hash of every
+//! module read through the resolver, and the setup records a `SetupRecord`, holding the hash of the verification key.
+//! An `Attestation` combines both after checking that they refer to the same program, so that a third party can
+//! build the same sources with the same configuration and compare the result. All hashes are hex encoded SHA-256
+//! digests, sources are recorded relative to the root of the project, and maps are ordered so that the serialization
+//! is stable.
+
+use crate::compile::{CompileConfig, OptimizerSchedule};
+use crate::ir;
+use crate::typed_absy::abi::Abi;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use zokrates_common::Resolver;
+use zokrates_field::Field;
+
+/// The version of the compiler, as recorded in attestations
+pub const COMPILER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Returns the hex encoded SHA-256 digest of `bytes`
+pub fn sha256<B: AsRef<[u8]>>(bytes: B) -> String {
+    // reading from a slice cannot fail
+    sha256_reader(bytes.as_ref()).unwrap()
+}
+
+/// Returns the hex encoded SHA-256 digest of the content of `reader`, which is read in chunks
+pub fn sha256_reader<R: Read>(mut reader: R) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 8192];
+
+    loop {
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// The hashes of the sources of a program, by module location
+pub type SourceHashes = BTreeMap<String, String>;
+
+/// Returns `path` relative to `root`, going up with `..` where `path` is not below `root`
+///
+/// Paths are compared component by component, so `path` is returned unchanged unless both are absolute or both are
+/// relative.
+pub(crate) fn relative_path(path: &Path, root: &Path) -> PathBuf {
+    if path.is_absolute() != root.is_absolute() {
+        return path.to_path_buf();
+    }
+
+    let path: Vec<_> = path
+        .components()
+        .filter(|c| *c != Component::CurDir)
+        .collect();
+    let root: Vec<_> = root
+        .components()
+        .filter(|c| *c != Component::CurDir)
+        .collect();
+
+    let common = path
+        .iter()
+        .zip(root.iter())
+        .take_while(|(p, r)| p == r)
+        .count();
+
+    root[common..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .chain(path[common..].iter().cloned())
+        .collect()
+}
+
+/// A resolver recording the hash of every source it resolves
+pub struct RecordingResolver<'a, E> {
+    inner: &'a dyn Resolver<E>,
+    sources: RefCell<SourceHashes>,
+}
+
+impl<'a, E> RecordingResolver<'a, E> {
+    pub fn new(inner: &'a dyn Resolver<E>) -> Self {
+        RecordingResolver {
+            inner,
+            sources: RefCell::new(SourceHashes::new()),
+        }
+    }
+
+    pub fn into_sources(self) -> SourceHashes {
+        self.sources.into_inner()
+    }
+}
+
+impl<E> Resolver<E> for RecordingResolver<'_, E> {
+    fn resolve(
+        &self,
+        current_location: PathBuf,
+        import_location: PathBuf,
+    ) -> Result<(String, PathBuf), E> {
+        let (source, location) = self.inner.resolve(current_location, import_location)?;
+
+        self.sources
+            .borrow_mut()
+            .insert(location.display().to_string(), sha256(&source));
+
+        Ok((source, location))
+    }
+}
+
+impl<T: Field> ir::Prog<T> {
+    /// Returns the hash of the serialized program
+    pub fn hash(&self) -> String {
+        let mut buffer = vec![];
+        self.serialize(&mut buffer);
+        sha256(buffer)
+    }
+}
+
+/// The ingredients of an attestation known after compiling
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BuildRecord {
+    pub compiler_version: String,
+    pub curve: String,
+    pub sources: SourceHashes,
+    pub config: CompileConfig,
+    pub optimizer_schedule: Option<OptimizerSchedule>,
+    pub program_hash: String,
+    /// the layout of the inputs and outputs of the program
+    pub abi: Abi,
+}
+
+/// The ingredients of an attestation known after the setup
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SetupRecord {
+    pub backend: String,
+    pub scheme: String,
+    pub program_hash: String,
+    pub verification_key_hash: String,
+}
+
+impl SetupRecord {
+    pub fn new<T: Field>(
+        backend: &str,
+        scheme: &str,
+        program: &ir::Prog<T>,
+        verification_key: &[u8],
+    ) -> Self {
+        SetupRecord {
+            backend: backend.to_string(),
+            scheme: scheme.to_string(),
+            program_hash: program.hash(),
+            verification_key_hash: sha256(verification_key),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Attestation {
+    #[serde(flatten)]
+    pub build: BuildRecord,
+    pub backend: String,
+    pub scheme: String,
+    pub verification_key_hash: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AttestationError {
+    ProgramMismatch { build: String, setup: String },
+    VerificationKeyMismatch { setup: String, found: String },
+}
+
+impl fmt::Display for AttestationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AttestationError::ProgramMismatch { build, setup } => write!(
+                f,
+                "The setup was run on program {}, but the build produced program {}",
+                setup, build
+            ),
+            AttestationError::VerificationKeyMismatch { setup, found } => write!(
+                f,
+                "The setup produced verification key {}, but the verification key provided is {}",
+                setup, found
+            ),
+        }
+    }
+}
+
+impl Attestation {
+    /// Combines the records of a build and of a setup, checking that the setup was run on the program built and
+    /// produced `verification_key`
+    pub fn new(
+        build: BuildRecord,
+        setup: SetupRecord,
+        verification_key: &[u8],
+    ) -> Result<Self, AttestationError> {
+        if build.program_hash != setup.program_hash {
+            return Err(AttestationError::ProgramMismatch {
+                build: build.program_hash,
+                setup: setup.program_hash,
+            });
+        }
+
+        let found = sha256(verification_key);
+        if found != setup.verification_key_hash {
+            return Err(AttestationError::VerificationKeyMismatch {
+                setup: setup.verification_key_hash,
+                found,
+            });
+        }
+
+        Ok(Attestation {
+            build,
+            backend: setup.backend,
+            scheme: setup.scheme,
+            verification_key_hash: setup.verification_key_hash,
+        })
+    }
+}
+
+/// Returns the path of the build record of the program at `program_path`
+pub fn build_record_path(program_path: &Path) -> PathBuf {
+    program_path.with_extension("build.json")
+}
+
+/// Returns the path of the setup record of the verification key at `verification_key_path`
+pub fn setup_record_path(verification_key_path: &Path) -> PathBuf {
+    verification_key_path.with_extension("setup.json")
+}
@@ -3,14 +3,30 @@
 //! @file compile.rs
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
 //! @date 2018
-use crate::absy::{Module, OwnedModuleId, Program};
+use crate::absy::{check_attributes, AttributeError, Module, OwnedModuleId, Program};
+use crate::attestation::{
+    relative_path, sha256, BuildRecord, RecordingResolver, SourceHashes, COMPILER_VERSION,
+};
+use crate::emit;
+pub use crate::flatten::ComparisonStrategy;
 use crate::flatten::Flattener;
 use crate::imports::{self, Importer};
 use crate::ir;
 use crate::macros;
+pub use crate::optimizer::{OptimizerPass, OptimizerSchedule};
+use crate::parser::Position;
 use crate::semantics::{self, Checker};
 use crate::static_analysis;
 use crate::static_analysis::Analyse;
+use crate::static_analysis::FieldComparisonAuditor;
+use crate::static_analysis::InputGenerator;
+use crate::static_analysis::SingleAssignmentInliner;
+use crate::static_analysis::UncheckedCoercionChecker;
+use crate::static_analysis::UnreachableCodeDetector;
+pub use crate::static_analysis::{
+    FieldComparisonReport, InputCandidate, InputSuite, LookupReport, LookupReportEntry,
+    TimingReport, TimingReportEntry,
+};
 use crate::typed_absy::abi::Abi;
 use crate::zir::ZirProgram;
 use macros::process_macros;
@@ -28,6 +44,12 @@ use zokrates_pest_ast as pest;
 pub struct CompilationArtifacts<T: Field> {
     prog: ir::Prog<T>,
     abi: Abi,
+    timing_report: Option<TimingReport>,
+    input_suite: Option<InputSuite<T>>,
+    optimizer_schedule: Option<OptimizerSchedule>,
+    warnings: Vec<Warning>,
+    lookup_report: LookupReport,
+    sources: SourceHashes,
 }
 
 impl<T: Field> CompilationArtifacts<T> {
@@ -38,6 +60,103 @@ impl<T: Field> CompilationArtifacts<T> {
     pub fn abi(&self) -> &Abi {
         &self.abi
     }
+
+    pub fn timing_report(&self) -> Option<&TimingReport> {
+        self.timing_report.as_ref()
+    }
+
+    /// The inputs on the boundaries of the predicates of the program, if they were requested
+    pub fn input_suite(&self) -> Option<&InputSuite<T>> {
+        self.input_suite.as_ref()
+    }
+
+    /// The optimizer schedule used to compile the program, if it was tuned or replayed
+    pub fn optimizer_schedule(&self) -> Option<&OptimizerSchedule> {
+        self.optimizer_schedule.as_ref()
+    }
+
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// The lookups in constant tables with a dynamic index, and their cost
+    pub fn lookup_report(&self) -> &LookupReport {
+        &self.lookup_report
+    }
+
+    /// The hashes of the sources read to compile the program
+    pub fn sources(&self) -> &SourceHashes {
+        &self.sources
+    }
+
+    /// Returns the record of this build, which was compiled with `config`. Sources are recorded relative to `root`,
+    /// usually the directory of the entry point, so that the record does not depend on where the project is located
+    pub fn build_record(&self, config: &CompileConfig, root: &Path) -> BuildRecord {
+        BuildRecord {
+            compiler_version: COMPILER_VERSION.to_string(),
+            curve: T::name().to_string(),
+            sources: self
+                .sources
+                .iter()
+                .map(|(location, hash)| {
+                    (
+                        relative_path(Path::new(location), root)
+                            .display()
+                            .to_string(),
+                        hash.clone(),
+                    )
+                })
+                .collect(),
+            config: config.clone(),
+            optimizer_schedule: self.optimizer_schedule.clone(),
+            program_hash: self.prog.hash(),
+            abi: self.abi.clone(),
+        }
+    }
+}
+
+/// A construct which compiles but is likely to be a mistake
+#[derive(Debug, PartialEq, Clone)]
+pub enum Warning {
+    /// a private field input is returned without appearing in any assertion
+    UncheckedCoercion { parameter: String },
+    /// a function annotated with `#[deprecated]` is called outside of `#[allow(deprecated)]`
+    DeprecatedUsage {
+        item: String,
+        span: (Position, Position),
+        since: Option<String>,
+        note: Option<String>,
+    },
+    /// a statement follows a statement which always returns or aborts
+    UnreachableCode { span: (Position, Position) },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Warning::UncheckedCoercion { parameter } => write!(
+                f,
+                "Private input `{}` is returned without being constrained by any assertion, so the prover can choose its value freely",
+                parameter
+            ),
+            Warning::DeprecatedUsage {
+                item,
+                span,
+                since,
+                note,
+            } => {
+                write!(f, "Use of deprecated function `{}` at {}", item, span.0)?;
+                if let Some(since) = since {
+                    write!(f, " (deprecated since {})", since)?;
+                }
+                if let Some(note) = note {
+                    write!(f, ": {}", note)?;
+                }
+                Ok(())
+            }
+            Warning::UnreachableCode { span } => write!(f, "Unreachable code at {}", span.0),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -54,6 +173,7 @@ pub enum CompileErrorInner {
     ParserError(pest::Error),
     ImportError(imports::Error),
     MacroError(macros::Error),
+    AttributeError(AttributeError),
     SemanticError(semantics::ErrorInner),
     ReadError(io::Error),
     AnalysisError(static_analysis::Error),
@@ -122,6 +242,12 @@ impl From<macros::Error> for CompileErrorInner {
     }
 }
 
+impl From<AttributeError> for CompileErrorInner {
+    fn from(error: AttributeError) -> Self {
+        CompileErrorInner::AttributeError(error)
+    }
+}
+
 impl From<semantics::Error> for CompileError {
     fn from(error: semantics::Error) -> Self {
         CompileError {
@@ -142,6 +268,9 @@ impl fmt::Display for CompileErrorInner {
         match *self {
             CompileErrorInner::ParserError(ref e) => write!(f, "\n\t{}", e),
             CompileErrorInner::MacroError(ref e) => write!(f, "\n\t{}", e),
+            CompileErrorInner::AttributeError(ref e) => {
+                write!(f, "{}\n\t{}", e.pos().0, e.message())
+            }
             CompileErrorInner::SemanticError(ref e) => {
                 let location = e
                     .pos()
@@ -166,6 +295,18 @@ impl fmt::Display for CompileErrorInner {
 pub struct CompileConfig {
     pub allow_unconstrained_variables: bool,
     pub isolate_branches: bool,
+    #[serde(default)]
+    pub timing_report: bool,
+    #[serde(default)]
+    pub input_boundaries: bool,
+    #[serde(default)]
+    pub optimize_tuned: bool,
+    #[serde(default)]
+    pub optimizer_schedule: Option<OptimizerSchedule>,
+    #[serde(default)]
+    pub comparison_strategy: Option<ComparisonStrategy>,
+    #[serde(default)]
+    pub inline_single_assignments: bool,
 }
 
 impl CompileConfig {
@@ -177,6 +318,30 @@ impl CompileConfig {
         self.isolate_branches = flag;
         self
     }
+    pub fn timing_report(mut self, flag: bool) -> Self {
+        self.timing_report = flag;
+        self
+    }
+    pub fn input_boundaries(mut self, flag: bool) -> Self {
+        self.input_boundaries = flag;
+        self
+    }
+    pub fn optimize_tuned(mut self, flag: bool) -> Self {
+        self.optimize_tuned = flag;
+        self
+    }
+    pub fn optimizer_schedule(mut self, schedule: Option<OptimizerSchedule>) -> Self {
+        self.optimizer_schedule = schedule;
+        self
+    }
+    pub fn comparison_strategy(mut self, strategy: Option<ComparisonStrategy>) -> Self {
+        self.comparison_strategy = strategy;
+        self
+    }
+    pub fn inline_single_assignments(mut self, flag: bool) -> Self {
+        self.inline_single_assignments = flag;
+        self
+    }
 }
 
 type FilePath = PathBuf;
@@ -189,7 +354,38 @@ pub fn compile<T: Field, E: Into<imports::Error>>(
 ) -> Result<CompilationArtifacts<T>, CompileErrors> {
     let arena = Arena::new();
 
-    let (typed_ast, abi) = check_with_arena(source, location.clone(), resolver, config, &arena)?;
+    // record the hash of every source read, for attestations
+    let mut sources = SourceHashes::new();
+    sources.insert(location.display().to_string(), sha256(&source));
+    let resolver = resolver.map(RecordingResolver::new);
+
+    let (typed_ast, abi, mut warnings) = check_with_arena(
+        source,
+        location.clone(),
+        resolver.as_ref().map(|r| r as &dyn Resolver<E>),
+        config,
+        &arena,
+    )?;
+
+    sources.extend(
+        resolver
+            .map(RecordingResolver::into_sources)
+            .unwrap_or_default(),
+    );
+
+    log::debug!("Check unchecked coercions");
+    warnings.extend(UncheckedCoercionChecker::check(&typed_ast));
+
+    log::debug!("Generate lookup report");
+    let lookup_report = LookupReport::generate(&typed_ast);
+
+    // derive inputs from the predicates, which do not survive flattening
+    let input_suite = if config.input_boundaries {
+        log::debug!("Generate inputs");
+        Some(InputGenerator::generate(typed_ast.clone()))
+    } else {
+        None
+    };
 
     // flatten input program
     log::debug!("Flatten");
@@ -199,13 +395,31 @@ pub fn compile<T: Field, E: Into<imports::Error>>(
     log::debug!("Propagate flat program");
     let program_flattened = program_flattened.propagate();
 
+    // substitute the variables defined by a linear expression and used once. The redefinition optimizer removes most
+    // of them from the ir program as well, so this is opt-in
+    let program_flattened = match config.inline_single_assignments {
+        true => {
+            log::debug!("Inline single assignments");
+            SingleAssignmentInliner::inline(program_flattened)
+        }
+        false => program_flattened,
+    };
+
     // convert to ir
     log::debug!("Convert to IR");
     let ir_prog = ir::Prog::from(program_flattened);
 
-    // optimize
+    // optimize, replaying or searching for a schedule if requested
     log::debug!("Optimise IR");
-    let optimized_ir_prog = ir_prog.optimize();
+    let (optimized_ir_prog, optimizer_schedule) =
+        match (&config.optimizer_schedule, config.optimize_tuned) {
+            (Some(schedule), _) => (ir_prog.optimize_with(schedule), Some(schedule.clone())),
+            (None, true) => {
+                let (prog, schedule) = ir_prog.optimize_tuned();
+                (prog, Some(schedule))
+            }
+            (None, false) => (ir_prog.optimize(), None),
+        };
 
     // analyse ir (check constraints)
     log::debug!("Analyse IR");
@@ -213,9 +427,23 @@ pub fn compile<T: Field, E: Into<imports::Error>>(
         .analyse()
         .map_err(|e| CompileErrorInner::from(e).in_file(location.as_path()))?;
 
+    // report directives which are not constant-time
+    let timing_report = if config.timing_report {
+        log::debug!("Generate timing report");
+        Some(TimingReport::generate(&optimized_ir_prog, &abi))
+    } else {
+        None
+    };
+
     Ok(CompilationArtifacts {
         prog: optimized_ir_prog,
         abi,
+        timing_report,
+        input_suite,
+        optimizer_schedule,
+        warnings,
+        lookup_report,
+        sources,
     })
 }
 
@@ -230,33 +458,76 @@ pub fn check<T: Field, E: Into<imports::Error>>(
     check_with_arena::<T, _>(source, location, resolver, config, &arena).map(|_| ())
 }
 
+pub fn audit_field_comparisons<T: Field, E: Into<imports::Error>>(
+    source: String,
+    location: FilePath,
+    resolver: Option<&dyn Resolver<E>>,
+    config: &CompileConfig,
+) -> Result<Vec<FieldComparisonReport>, CompileErrors> {
+    let arena = Arena::new();
+
+    let (typed_ast, _, _) = check_with_arena::<T, _>(source, location, resolver, config, &arena)?;
+
+    log::debug!("Audit field comparisons");
+    Ok(FieldComparisonAuditor::audit(&typed_ast))
+}
+
+/// Checks the program for the field `T` and writes it to `writer` as a Cairo 1.0 program
+///
+/// Cairo field elements are `felt252`, elements of the field of the Stark prime, whatever `T` is: the Cairo program is
+/// only equivalent to the ZoKrates program as long as no field value wraps around either prime.
+pub fn compile_to_cairo<T: Field, E: Into<imports::Error>>(
+    source: String,
+    location: FilePath,
+    resolver: Option<&dyn Resolver<E>>,
+    config: &CompileConfig,
+    writer: &mut impl io::Write,
+) -> Result<Abi, CompileErrors> {
+    let arena = Arena::new();
+
+    let (typed_ast, abi, _) = check_with_arena(source, location.clone(), resolver, config, &arena)?;
+
+    log::debug!("Emit Cairo");
+    emit::cairo::write(&typed_ast, config, writer)
+        .map_err(|e| CompileErrors::from(CompileErrorInner::from(e).in_file(&location)))?;
+
+    Ok(abi)
+}
+
 fn check_with_arena<'ast, T: Field, E: Into<imports::Error>>(
     source: String,
     location: FilePath,
     resolver: Option<&dyn Resolver<E>>,
     config: &CompileConfig,
     arena: &'ast Arena<String>,
-) -> Result<(ZirProgram<'ast, T>, Abi), CompileErrors> {
+) -> Result<(ZirProgram<'ast, T>, Abi, Vec<Warning>), CompileErrors> {
     let source = arena.alloc(source);
 
     log::debug!("Parse program with entry file {}", location.display());
 
     let compiled = parse_program::<T, E>(source, location, resolver, &arena)?;
 
+    log::debug!("Detect unreachable code");
+    let unreachable_code = UnreachableCodeDetector::detect(&compiled);
+
     log::debug!("Check semantics");
 
     // check semantics
-    let typed_ast = Checker::check(compiled)
+    let (typed_ast, mut warnings) = Checker::check(compiled)
         .map_err(|errors| CompileErrors(errors.into_iter().map(CompileError::from).collect()))?;
 
+    warnings.extend(unreachable_code);
+
     let main_module = typed_ast.main.clone();
 
     log::debug!("Run static analysis");
 
     // analyse (unroll and constant propagation)
-    typed_ast
+    let (typed_ast, abi) = typed_ast
         .analyse(config)
-        .map_err(|e| CompileErrors(vec![CompileErrorInner::from(e).in_file(&main_module)]))
+        .map_err(|e| CompileErrors(vec![CompileErrorInner::from(e).in_file(&main_module)]))?;
+
+    Ok((typed_ast, abi, warnings))
 }
 
 pub fn parse_program<'ast, T: Field, E: Into<imports::Error>>(
@@ -294,9 +565,14 @@ pub fn parse_module<'ast, T: Field, E: Into<imports::Error>>(
     let ast = process_macros::<T>(ast)
         .map_err(|e| CompileErrors::from(CompileErrorInner::from(e).in_file(&location)))?;
 
+    log::debug!("Check attributes for {}", location.display());
+
+    check_attributes(&ast)
+        .map_err(|e| CompileErrors::from(CompileErrorInner::from(e).in_file(&location)))?;
+
     log::debug!("Generate absy for {}", location.display());
 
-    let module_without_imports: Module = Module::from(ast);
+    let module_without_imports: Module = Module::from(ast).qualify_implementations(arena);
 
     log::debug!("Apply imports to absy for {}", location.display());
 
@@ -314,6 +590,21 @@ mod test {
     use super::*;
     use zokrates_field::Bn128Field;
 
+    #[test]
+    fn deserialize_config_without_optional_flags() {
+        // configurations written before the optional flags were added must keep their values
+        let config: CompileConfig = serde_json::from_str(
+            r#"{"allow_unconstrained_variables": true, "isolate_branches": true}"#,
+        )
+        .unwrap();
+
+        assert!(config.allow_unconstrained_variables);
+        assert!(config.isolate_branches);
+        assert!(!config.timing_report);
+        assert!(!config.input_boundaries);
+        assert!(!config.inline_single_assignments);
+    }
+
     #[test]
     fn no_resolver_with_imports() {
         let source = r#"
@@ -436,6 +727,7 @@ struct Bar { field a }
                     inputs: vec![AbiInput {
                         name: "f".into(),
                         public: true,
+                        deferred: false,
                         ty: ConcreteType::Struct(ConcreteStructType::new(
                             "foo".into(),
                             "Foo".into(),
@@ -0,0 +1,872 @@
+//! Module emitting a zir program as a Cairo 1.0 program
+//!
+//! Field elements are emitted as `felt252`, so field arithmetic is carried out modulo the Stark prime rather than the
+//! prime of the field the program was compiled for. None of the fields supported by ZoKrates is the Stark field: both
+//! agree as long as no intermediate value wraps around, and in particular divisions, negative values and comparisons
+//! only match for values which fit in both fields. The emitted program records the field it was compiled for.
+//! Unsigned integers keep their wrapping semantics by going through `u128`, and booleans map to `bool`.
+//!
+//! The statements of both branches of a conditional are emitted unconditionally when compiling without branch
+//! isolation, so that an assertion failing in the branch not taken fails the program, as it does in the flattened
+//! program. With branch isolation, each branch is guarded by a boolean holding whether it is taken: its assertions are
+//! only checked and its definitions only evaluated when it is, taking a default value otherwise. Among embeds, only
+//! the uint bit conversions and `unpack` to at most 128 bits are supported.
+
+use crate::compile::CompileConfig;
+use crate::embed::FlatEmbed;
+use crate::zir::types::{Type, UBitwidth};
+use crate::zir::*;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use zokrates_field::Field;
+
+const PRELUDE: &str = r#"use core::traits::Into;
+use core::traits::TryInto;
+use core::option::OptionTrait;
+
+fn zokrates_field_div(a: felt252, b: felt252) -> felt252 {
+    felt252_div(a, b.try_into().unwrap())
+}
+
+fn zokrates_field_lt(a: felt252, b: felt252) -> bool {
+    let a: u256 = a.into();
+    let b: u256 = b.into();
+    a < b
+}
+
+fn zokrates_field_le(a: felt252, b: felt252) -> bool {
+    !zokrates_field_lt(b, a)
+}
+
+fn zokrates_field_to_u128(a: felt252) -> u128 {
+    a.try_into().unwrap()
+}
+
+fn zokrates_pow(base: felt252, exponent: u128) -> felt252 {
+    if exponent == 0 {
+        return 1;
+    }
+    let half = zokrates_pow(base, exponent / 2);
+    if exponent % 2 == 0 {
+        half * half
+    } else {
+        half * half * base
+    }
+}
+
+fn zokrates_bit(b: bool) -> u128 {
+    if b {
+        1
+    } else {
+        0
+    }
+}
+"#;
+
+const KEYWORDS: &[&str] = &[
+    "as",
+    "assert",
+    "break",
+    "const",
+    "continue",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "fn",
+    "if",
+    "impl",
+    "implicits",
+    "let",
+    "loop",
+    "match",
+    "mod",
+    "mut",
+    "nopanic",
+    "of",
+    "ref",
+    "return",
+    "self",
+    "struct",
+    "trait",
+    "true",
+    "type",
+    "use",
+    "while",
+];
+
+const BITWIDTHS: &[UBitwidth] = &[
+    UBitwidth::B8,
+    UBitwidth::B16,
+    UBitwidth::B32,
+    UBitwidth::B64,
+];
+
+/// Writes `prog` to `writer` as a Cairo 1.0 program whose `main` function performs the same computation, as long as
+/// no field value wraps around
+pub(crate) fn write<T: Field>(
+    prog: &ZirProgram<T>,
+    config: &CompileConfig,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    Emitter {
+        isolate_branches: config.isolate_branches,
+        ..Emitter::default()
+    }
+    .emit_program(prog, writer)
+}
+
+fn ty(t: &Type) -> String {
+    match t {
+        Type::FieldElement => "felt252".into(),
+        Type::Boolean => "bool".into(),
+        Type::Uint(bitwidth) => format!("u{}", bitwidth),
+    }
+}
+
+// the value of a variable of type `t` defined in a branch which is not taken
+fn default(t: &Type) -> String {
+    match t {
+        Type::FieldElement => "0".into(),
+        Type::Boolean => "false".into(),
+        Type::Uint(bitwidth) => format!("0_u{}", bitwidth),
+    }
+}
+
+fn wrap(bitwidth: UBitwidth, e: String) -> String {
+    format!("zokrates_u{}_wrap({})", bitwidth, e)
+}
+
+fn unsupported(embed: &FlatEmbed) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!(
+            "Embed `{}` is not supported by the Cairo target",
+            embed.id()
+        ),
+    )
+}
+
+#[derive(Default)]
+struct Emitter<'ast> {
+    // the Cairo name of each zir identifier
+    names: HashMap<Identifier<'ast>, String>,
+    taken: HashSet<String>,
+    isolate_branches: bool,
+    // the boolean holding whether the branch being emitted is taken, if branches are isolated
+    guard: Option<String>,
+}
+
+impl<'ast> Emitter<'ast> {
+    fn name(&mut self, id: &Identifier<'ast>) -> String {
+        if let Some(name) = self.names.get(id) {
+            return name.clone();
+        }
+
+        let name = self.fresh(&id.to_string());
+        self.names.insert(id.clone(), name.clone());
+        name
+    }
+
+    /// Returns `value`, evaluated only if the current branch is taken and `default` otherwise
+    fn guarded(&self, value: String, default: &str) -> String {
+        match &self.guard {
+            Some(guard) => format!("(if {} {{ {} }} else {{ {} }})", guard, value, default),
+            None => value,
+        }
+    }
+
+    /// Turns `base` into a valid Cairo identifier which is not used yet
+    fn fresh(&mut self, base: &str) -> String {
+        let mut name: String = base
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+
+        // do not shadow the helpers or start with a digit or an underscore
+        if name.starts_with("zokrates_") || !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            name = format!("v_{}", name);
+        }
+
+        if KEYWORDS.contains(&name.as_str()) {
+            name.push('_');
+        }
+
+        let mut candidate = name.clone();
+        let mut suffix = 0;
+        while self.taken.contains(&candidate) {
+            suffix += 1;
+            candidate = format!("{}_{}", name, suffix);
+        }
+
+        self.taken.insert(candidate.clone());
+        candidate
+    }
+
+    fn emit_program<T: Field>(
+        &mut self,
+        prog: &ZirProgram<'ast, T>,
+        w: &mut impl Write,
+    ) -> io::Result<()> {
+        let main = &prog.main;
+
+        writeln!(w, "// Auto generated by ZoKrates")?;
+        writeln!(
+            w,
+            "// Compiled for the {} field, whose elements are emitted as felt252: field arithmetic is only equivalent as long as no value wraps around",
+            T::name()
+        )?;
+        writeln!(w)?;
+        write!(w, "{}", PRELUDE)?;
+        for bitwidth in BITWIDTHS {
+            writeln!(w)?;
+            writeln!(w, "fn zokrates_u{0}_wide(a: u{0}) -> u128 {{", bitwidth)?;
+            writeln!(w, "    a.into()")?;
+            writeln!(w, "}}")?;
+            writeln!(w)?;
+            writeln!(w, "fn zokrates_u{0}_wrap(a: u128) -> u{0} {{", bitwidth)?;
+            writeln!(
+                w,
+                "    (a % {}).try_into().unwrap()",
+                1u128 << bitwidth.to_usize()
+            )?;
+            writeln!(w, "}}")?;
+        }
+        writeln!(w)?;
+
+        let arguments = main
+            .arguments
+            .iter()
+            .map(|a| format!("{}: {}", self.name(&a.id.id), ty(&a.id._type)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let outputs = main.signature.outputs.iter().map(ty).collect::<Vec<_>>();
+        let outputs = match outputs.len() {
+            0 => "".to_string(),
+            1 => format!(" -> {}", outputs[0]),
+            _ => format!(" -> ({})", outputs.join(", ")),
+        };
+
+        writeln!(w, "fn main({}){} {{", arguments, outputs)?;
+        for s in &main.statements {
+            self.emit_statement(s, w)?;
+        }
+        writeln!(w, "}}")
+    }
+
+    fn emit_statement<T: Field>(
+        &mut self,
+        s: &ZirStatement<'ast, T>,
+        w: &mut impl Write,
+    ) -> io::Result<()> {
+        match s {
+            ZirStatement::Return(expressions) => {
+                let expressions = expressions
+                    .iter()
+                    .map(|e| self.expression(e))
+                    .collect::<Vec<_>>();
+
+                match expressions.len() {
+                    0 => writeln!(w, "    return ();"),
+                    1 => writeln!(w, "    return {};", expressions[0]),
+                    _ => writeln!(w, "    return ({});", expressions.join(", ")),
+                }
+            }
+            ZirStatement::Definition(variable, e) => {
+                let e = self.expression(e);
+                let e = self.guarded(e, &default(&variable._type));
+                let name = self.name(&variable.id);
+                writeln!(w, "    let {}: {} = {};", name, ty(&variable._type), e)
+            }
+            ZirStatement::IfElse(condition, consequence, alternative) if self.isolate_branches => {
+                // the guards of nested branches also require the enclosing branch to be taken
+                let condition = self.boolean(condition);
+                let taken = self.fresh("condition");
+                let condition = self.guarded(condition, "false");
+                writeln!(w, "    let {}: bool = {};", taken, condition)?;
+                let not_taken = self.fresh("condition");
+                let condition = self.guarded(format!("!{}", taken), "false");
+                writeln!(w, "    let {}: bool = {};", not_taken, condition)?;
+
+                let guard = self.guard.replace(taken);
+                for s in consequence {
+                    self.emit_statement(s, w)?;
+                }
+                self.guard = Some(not_taken);
+                for s in alternative {
+                    self.emit_statement(s, w)?;
+                }
+                self.guard = guard;
+                Ok(())
+            }
+            ZirStatement::IfElse(_, consequence, alternative) => {
+                for s in consequence.iter().chain(alternative.iter()) {
+                    self.emit_statement(s, w)?;
+                }
+                Ok(())
+            }
+            ZirStatement::Assertion(e, _) => {
+                let e = self.boolean(e);
+                let e = self.guarded(e, "true");
+                writeln!(w, "    assert({}, 'User assertion failed');", e)
+            }
+            ZirStatement::MultipleDefinition(
+                variables,
+                ZirExpressionList::EmbedCall(embed, generics, arguments),
+            ) => self.emit_embed_call(variables, embed, generics, arguments, w),
+        }
+    }
+
+    fn emit_embed_call<T: Field>(
+        &mut self,
+        variables: &[Variable<'ast>],
+        embed: &FlatEmbed,
+        generics: &[u32],
+        arguments: &[ZirExpression<'ast, T>],
+        w: &mut impl Write,
+    ) -> io::Result<()> {
+        match embed {
+            FlatEmbed::U8ToBits
+            | FlatEmbed::U16ToBits
+            | FlatEmbed::U32ToBits
+            | FlatEmbed::U64ToBits => {
+                let value = match &arguments[0] {
+                    ZirExpression::Uint(e) => self.wide(e),
+                    _ => unreachable!(),
+                };
+                let value = self.guarded(value, "0");
+                let bits = self.fresh("bits");
+                writeln!(w, "    let {}: u128 = {};", bits, value)?;
+                self.emit_bits(variables, &bits, w)
+            }
+            FlatEmbed::Unpack => {
+                let bitwidth = generics[0];
+                if bitwidth > 128 {
+                    return Err(unsupported(embed));
+                }
+
+                let value = self.expression(&arguments[0]);
+                let value = self.guarded(format!("zokrates_field_to_u128({})", value), "0");
+                let bits = self.fresh("bits");
+                writeln!(w, "    let {}: u128 = {};", bits, value)?;
+                if bitwidth < 128 {
+                    writeln!(
+                        w,
+                        "    assert({} < {}, 'Unpack check failed');",
+                        bits,
+                        1u128 << bitwidth
+                    )?;
+                }
+                self.emit_bits(variables, &bits, w)
+            }
+            FlatEmbed::U8FromBits
+            | FlatEmbed::U16FromBits
+            | FlatEmbed::U32FromBits
+            | FlatEmbed::U64FromBits => {
+                let bitwidth = UBitwidth::from(arguments.len());
+                let sum = arguments
+                    .iter()
+                    .enumerate()
+                    .map(|(i, a)| {
+                        let a = self.expression(a);
+                        format!(
+                            "zokrates_bit({}) * {}",
+                            a,
+                            1u128 << (arguments.len() - 1 - i)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" + ");
+                let value = self.guarded(wrap(bitwidth, sum), &format!("0_u{}", bitwidth));
+                let name = self.name(&variables[0].id);
+                writeln!(w, "    let {}: u{} = {};", name, bitwidth, value)
+            }
+            embed => Err(unsupported(embed)),
+        }
+    }
+
+    /// Defines `variables` as the big-endian bits of `value`
+    fn emit_bits(
+        &mut self,
+        variables: &[Variable<'ast>],
+        value: &str,
+        w: &mut impl Write,
+    ) -> io::Result<()> {
+        for (i, v) in variables.iter().enumerate() {
+            let name = self.name(&v.id);
+            writeln!(
+                w,
+                "    let {}: bool = ({} / {}) % 2 == 1;",
+                name,
+                value,
+                1u128 << (variables.len() - 1 - i)
+            )?;
+        }
+        Ok(())
+    }
+
+    fn expression<T: Field>(&mut self, e: &ZirExpression<'ast, T>) -> String {
+        match e {
+            ZirExpression::Boolean(e) => self.boolean(e),
+            ZirExpression::FieldElement(e) => self.field(e),
+            ZirExpression::Uint(e) => self.uint(e),
+        }
+    }
+
+    fn select<T: Field, E>(
+        &mut self,
+        values: &[E],
+        index: &UExpression<'ast, T>,
+        f: impl Fn(&mut Self, &E) -> String,
+    ) -> String {
+        let bitwidth = index.bitwidth;
+        let index = self.uint(index);
+
+        let mut res = String::from("(");
+        for (i, v) in values.iter().enumerate() {
+            let v = f(self, v);
+            res.push_str(&format!(
+                "if {} == {}_u{} {{ {} }} else ",
+                index, i, bitwidth, v
+            ));
+        }
+        res.push_str("{ panic_with_felt252('Out of bounds array access') })");
+        res
+    }
+
+    fn field<T: Field>(&mut self, e: &FieldElementExpression<'ast, T>) -> String {
+        match e {
+            FieldElementExpression::Number(n) => n.to_compact_dec_string(),
+            FieldElementExpression::Identifier(id) => self.name(id),
+            FieldElementExpression::Select(values, box index)
+            | FieldElementExpression::Lookup(values, box index) => {
+                self.select(values, index, |s, v| s.field(v))
+            }
+            FieldElementExpression::Add(box left, box right) => {
+                format!("({} + {})", self.field(left), self.field(right))
+            }
+            FieldElementExpression::Sub(box left, box right) => {
+                format!("({} - {})", self.field(left), self.field(right))
+            }
+            FieldElementExpression::Mult(box left, box right) => {
+                format!("({} * {})", self.field(left), self.field(right))
+            }
+            FieldElementExpression::Div(box left, box right) => {
+                format!(
+                    "zokrates_field_div({}, {})",
+                    self.field(left),
+                    self.field(right)
+                )
+            }
+            FieldElementExpression::Pow(box base, box exponent) => {
+                format!(
+                    "zokrates_pow({}, {})",
+                    self.field(base),
+                    self.wide(exponent)
+                )
+            }
+            FieldElementExpression::IfElse(box condition, box consequence, box alternative) => {
+                format!(
+                    "(if {} {{ {} }} else {{ {} }})",
+                    self.boolean(condition),
+                    self.field(consequence),
+                    self.field(alternative)
+                )
+            }
+        }
+    }
+
+    /// Returns `e` converted to a `u128`
+    fn wide<T: Field>(&mut self, e: &UExpression<'ast, T>) -> String {
+        format!("zokrates_u{}_wide({})", e.bitwidth, self.uint(e))
+    }
+
+    fn uint<T: Field>(&mut self, e: &UExpression<'ast, T>) -> String {
+        let bitwidth = e.bitwidth;
+        let modulus = 1u128 << bitwidth.to_usize();
+
+        match &e.inner {
+            UExpressionInner::Value(v) => format!("{}_u{}", v, bitwidth),
+            UExpressionInner::Identifier(id) => self.name(id),
+            UExpressionInner::Select(values, box index)
+            | UExpressionInner::Lookup(values, box index) => {
+                self.select(values, index, |s, v| s.uint(v))
+            }
+            UExpressionInner::Add(box left, box right) => {
+                let (left, right) = (self.wide(left), self.wide(right));
+                wrap(bitwidth, format!("{} + {}", left, right))
+            }
+            UExpressionInner::Sub(box left, box right) => {
+                let (left, right) = (self.wide(left), self.wide(right));
+                wrap(bitwidth, format!("{} + {} - {}", left, modulus, right))
+            }
+            UExpressionInner::Mult(box left, box right) => {
+                let (left, right) = (self.wide(left), self.wide(right));
+                wrap(bitwidth, format!("{} * {}", left, right))
+            }
+            UExpressionInner::Div(box left, box right) => {
+                format!("({} / {})", self.uint(left), self.uint(right))
+            }
+            UExpressionInner::Rem(box left, box right) => {
+                format!("({} % {})", self.uint(left), self.uint(right))
+            }
+            UExpressionInner::Xor(box left, box right) => {
+                let (left, right) = (self.wide(left), self.wide(right));
+                wrap(bitwidth, format!("{} ^ {}", left, right))
+            }
+            UExpressionInner::And(box left, box right) => {
+                let (left, right) = (self.wide(left), self.wide(right));
+                wrap(bitwidth, format!("{} & {}", left, right))
+            }
+            UExpressionInner::Or(box left, box right) => {
+                let (left, right) = (self.wide(left), self.wide(right));
+                wrap(bitwidth, format!("{} | {}", left, right))
+            }
+            UExpressionInner::LeftShift(box e, by) => match *by as usize >= bitwidth.to_usize() {
+                true => format!("0_u{}", bitwidth),
+                false => {
+                    let e = self.wide(e);
+                    wrap(bitwidth, format!("{} * {}", e, 1u128 << by))
+                }
+            },
+            UExpressionInner::RightShift(box e, by) => match *by as usize >= bitwidth.to_usize() {
+                true => format!("0_u{}", bitwidth),
+                false => {
+                    let e = self.wide(e);
+                    wrap(bitwidth, format!("{} / {}", e, 1u128 << by))
+                }
+            },
+            UExpressionInner::Not(box e) => {
+                let e = self.wide(e);
+                wrap(bitwidth, format!("{} - {}", modulus - 1, e))
+            }
+            UExpressionInner::IfElse(box condition, box consequence, box alternative) => {
+                format!(
+                    "(if {} {{ {} }} else {{ {} }})",
+                    self.boolean(condition),
+                    self.uint(consequence),
+                    self.uint(alternative)
+                )
+            }
+        }
+    }
+
+    fn boolean<T: Field>(&mut self, e: &BooleanExpression<'ast, T>) -> String {
+        match e {
+            BooleanExpression::Value(b) => b.to_string(),
+            BooleanExpression::Identifier(id) => self.name(id),
+            BooleanExpression::Select(values, box index)
+            | BooleanExpression::Lookup(values, box index) => {
+                self.select(values, index, |s, v| s.boolean(v))
+            }
+            BooleanExpression::FieldLt(box left, box right) => {
+                format!(
+                    "zokrates_field_lt({}, {})",
+                    self.field(left),
+                    self.field(right)
+                )
+            }
+            BooleanExpression::FieldLe(box left, box right) => {
+                format!(
+                    "zokrates_field_le({}, {})",
+                    self.field(left),
+                    self.field(right)
+                )
+            }
+            BooleanExpression::FieldGe(box left, box right) => {
+                let (left, right) = (self.field(left), self.field(right));
+                format!("zokrates_field_le({}, {})", right, left)
+            }
+            BooleanExpression::FieldGt(box left, box right) => {
+                let (left, right) = (self.field(left), self.field(right));
+                format!("zokrates_field_lt({}, {})", right, left)
+            }
+            BooleanExpression::FieldEq(box left, box right) => {
+                format!("({} == {})", self.field(left), self.field(right))
+            }
+            BooleanExpression::UintLt(box left, box right) => {
+                format!("({} < {})", self.uint(left), self.uint(right))
+            }
+            BooleanExpression::UintLe(box left, box right) => {
+                format!("({} <= {})", self.uint(left), self.uint(right))
+            }
+            BooleanExpression::UintGe(box left, box right) => {
+                format!("({} >= {})", self.uint(left), self.uint(right))
+            }
+            BooleanExpression::UintGt(box left, box right) => {
+                format!("({} > {})", self.uint(left), self.uint(right))
+            }
+            BooleanExpression::UintEq(box left, box right) => {
+                format!("({} == {})", self.uint(left), self.uint(right))
+            }
+            BooleanExpression::BoolEq(box left, box right) => {
+                format!("({} == {})", self.boolean(left), self.boolean(right))
+            }
+            BooleanExpression::Or(box left, box right) => {
+                format!("({} || {})", self.boolean(left), self.boolean(right))
+            }
+            BooleanExpression::And(box left, box right) => {
+                format!("({} && {})", self.boolean(left), self.boolean(right))
+            }
+            BooleanExpression::Not(box e) => format!("!{}", self.boolean(e)),
+            BooleanExpression::IfElse(box condition, box consequence, box alternative) => {
+                format!(
+                    "(if {} {{ {} }} else {{ {} }})",
+                    self.boolean(condition),
+                    self.boolean(consequence),
+                    self.boolean(alternative)
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::RuntimeError;
+    use crate::zir::types::Signature;
+    use zokrates_field::Bn128Field;
+
+    fn emit_with(prog: &ZirProgram<Bn128Field>, config: &CompileConfig) -> String {
+        let mut buffer = vec![];
+        write(prog, config, &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn emit(prog: &ZirProgram<Bn128Field>) -> String {
+        emit_with(prog, &CompileConfig::default())
+    }
+
+    #[test]
+    fn field() {
+        // def main(private field a, field b) -> field:
+        //     field c = a * a
+        //     assert(c == b)
+        //     return c / 2
+
+        let prog = ZirProgram {
+            main: ZirFunction {
+                arguments: vec![
+                    Parameter::private(Variable::field_element("a")),
+                    Parameter {
+                        id: Variable::field_element("b"),
+                        private: false,
+                    },
+                ],
+                statements: vec![
+                    ZirStatement::Definition(
+                        Variable::field_element("c"),
+                        FieldElementExpression::Mult(
+                            box FieldElementExpression::Identifier("a".into()),
+                            box FieldElementExpression::Identifier("a".into()),
+                        )
+                        .into(),
+                    ),
+                    ZirStatement::Assertion(
+                        BooleanExpression::FieldEq(
+                            box FieldElementExpression::Identifier("c".into()),
+                            box FieldElementExpression::Identifier("b".into()),
+                        ),
+                        RuntimeError::Source,
+                    ),
+                    ZirStatement::Return(vec![FieldElementExpression::Div(
+                        box FieldElementExpression::Identifier("c".into()),
+                        box FieldElementExpression::Number(Bn128Field::from(2)),
+                    )
+                    .into()]),
+                ],
+                signature: Signature {
+                    inputs: vec![Type::FieldElement, Type::FieldElement],
+                    outputs: vec![Type::FieldElement],
+                },
+            },
+        };
+
+        let cairo = emit(&prog);
+
+        assert!(cairo.starts_with(
+            "// Auto generated by ZoKrates\n// Compiled for the bn128 field, whose elements are emitted as felt252"
+        ));
+        assert!(cairo.contains(PRELUDE));
+        assert!(cairo.ends_with(
+            r#"fn main(a: felt252, b: felt252) -> felt252 {
+    let c: felt252 = (a * a);
+    assert((c == b), 'User assertion failed');
+    return zokrates_field_div(c, 2);
+}
+"#
+        ));
+    }
+
+    #[test]
+    fn uint() {
+        // def main(u8 a) -> (u8, bool):
+        //     return a + 255, a < 3
+
+        let prog = ZirProgram {
+            main: ZirFunction {
+                arguments: vec![Parameter::private(Variable::uint("a".into(), 8))],
+                statements: vec![ZirStatement::Return(vec![
+                    UExpressionInner::Add(
+                        box UExpressionInner::Identifier("a".into()).annotate(8),
+                        box UExpressionInner::Value(255).annotate(8),
+                    )
+                    .annotate(8)
+                    .into(),
+                    BooleanExpression::UintLt(
+                        box UExpressionInner::Identifier("a".into()).annotate(8),
+                        box UExpressionInner::Value(3).annotate(8),
+                    )
+                    .into(),
+                ])],
+                signature: Signature {
+                    inputs: vec![Type::Uint(UBitwidth::B8)],
+                    outputs: vec![Type::Uint(UBitwidth::B8), Type::Boolean],
+                },
+            },
+        };
+
+        let cairo = emit(&prog);
+
+        assert!(cairo.contains(
+            r#"fn zokrates_u8_wrap(a: u128) -> u8 {
+    (a % 256).try_into().unwrap()
+}"#
+        ));
+        assert!(cairo.ends_with(
+            r#"fn main(a: u8) -> (u8, bool) {
+    return (zokrates_u8_wrap(zokrates_u8_wide(a) + zokrates_u8_wide(255_u8)), (a < 3_u8));
+}
+"#
+        ));
+    }
+
+    #[test]
+    fn branches() {
+        // def main(field a) -> field:
+        //     if a == 0 then {c = 1} else {d = 1 / a; assert(d * a == 1)} fi
+        //     return if a == 0 then c else d fi
+
+        let condition = || {
+            BooleanExpression::FieldEq(
+                box FieldElementExpression::Identifier("a".into()),
+                box FieldElementExpression::Number(Bn128Field::from(0)),
+            )
+        };
+
+        let prog = ZirProgram {
+            main: ZirFunction {
+                arguments: vec![Parameter::private(Variable::field_element("a"))],
+                statements: vec![
+                    ZirStatement::IfElse(
+                        condition(),
+                        vec![ZirStatement::Definition(
+                            Variable::field_element("c"),
+                            FieldElementExpression::Number(Bn128Field::from(1)).into(),
+                        )],
+                        vec![
+                            ZirStatement::Definition(
+                                Variable::field_element("d"),
+                                FieldElementExpression::Div(
+                                    box FieldElementExpression::Number(Bn128Field::from(1)),
+                                    box FieldElementExpression::Identifier("a".into()),
+                                )
+                                .into(),
+                            ),
+                            ZirStatement::Assertion(
+                                BooleanExpression::FieldEq(
+                                    box FieldElementExpression::Mult(
+                                        box FieldElementExpression::Identifier("d".into()),
+                                        box FieldElementExpression::Identifier("a".into()),
+                                    ),
+                                    box FieldElementExpression::Number(Bn128Field::from(1)),
+                                ),
+                                RuntimeError::Source,
+                            ),
+                        ],
+                    ),
+                    ZirStatement::Return(vec![FieldElementExpression::IfElse(
+                        box condition(),
+                        box FieldElementExpression::Identifier("c".into()),
+                        box FieldElementExpression::Identifier("d".into()),
+                    )
+                    .into()]),
+                ],
+                signature: Signature {
+                    inputs: vec![Type::FieldElement],
+                    outputs: vec![Type::FieldElement],
+                },
+            },
+        };
+
+        // without isolation, both branches are executed like in the flattened program
+        assert!(emit(&prog).ends_with(
+            r#"fn main(a: felt252) -> felt252 {
+    let c: felt252 = 1;
+    let d: felt252 = zokrates_field_div(1, a);
+    assert(((d * a) == 1), 'User assertion failed');
+    return (if (a == 0) { c } else { d });
+}
+"#
+        ));
+
+        // with isolation, the division and the assertion are only evaluated when `a` is not zero
+        assert!(
+            emit_with(&prog, &CompileConfig::default().isolate_branches(true)).ends_with(
+                r#"fn main(a: felt252) -> felt252 {
+    let condition: bool = (a == 0);
+    let condition_1: bool = !condition;
+    let c: felt252 = (if condition { 1 } else { 0 });
+    let d: felt252 = (if condition_1 { zokrates_field_div(1, a) } else { 0 });
+    assert((if condition_1 { ((d * a) == 1) } else { true }), 'User assertion failed');
+    return (if (a == 0) { c } else { d });
+}
+"#
+            )
+        );
+    }
+
+    #[test]
+    fn identifiers() {
+        let mut emitter = Emitter::default();
+
+        assert_eq!(emitter.name(&"a".into()), "a");
+        assert_eq!(emitter.name(&"a".into()), "a");
+        assert_eq!(emitter.name(&"let".into()), "let_");
+        assert_eq!(emitter.name(&"zokrates_pow".into()), "v_zokrates_pow");
+        assert_eq!(emitter.fresh("a"), "a_1");
+    }
+
+    #[test]
+    fn unsupported_embed() {
+        let prog: ZirProgram<Bn128Field> = ZirProgram {
+            main: ZirFunction {
+                arguments: vec![],
+                statements: vec![ZirStatement::MultipleDefinition(
+                    vec![Variable::boolean("b".into())],
+                    ZirExpressionList::EmbedCall(
+                        FlatEmbed::BitArrayLe,
+                        vec![1],
+                        vec![
+                            BooleanExpression::Value(true).into(),
+                            BooleanExpression::Value(true).into(),
+                        ],
+                    ),
+                )],
+                signature: Signature {
+                    inputs: vec![],
+                    outputs: vec![],
+                },
+            },
+        };
+
+        assert_eq!(
+            write(&prog, &CompileConfig::default(), &mut vec![])
+                .unwrap_err()
+                .to_string(),
+            "Embed `_BIT_ARRAY_LT` is not supported by the Cairo target"
+        );
+    }
+}
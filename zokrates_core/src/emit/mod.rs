@@ -0,0 +1,4 @@
+//! Module containing code generators targeting other languages
+
+pub mod cairo;
+pub mod vampir;
@@ -0,0 +1,181 @@
+//! Module emitting an ir program as a VAMP-IR circuit
+//!
+//! Each constraint `(a) * (b) == c` of the program becomes an equation `a * b = c;`, and the public arguments and
+//! return values are declared with `pub`. Variables are named after their ir name, `_3` becoming `x_3` and `~out_0`
+//! becoming `out_0`, while `~one` becomes the constant `1`.
+//!
+//! VAMP-IR has no equivalent of directives, so they are not emitted: their outputs are free variables, whose values
+//! come from the witness computed by ZoKrates. Coefficients are emitted as small signed integers where possible, but
+//! the circuit is only equivalent when it is compiled for the field the program was compiled for.
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::{LinComb, Prog, QuadComb, Statement};
+use std::io::{self, Write};
+use zokrates_field::Field;
+
+/// Writes the constraints of `prog` to `writer` as a VAMP-IR circuit
+pub fn write<T: Field>(prog: &Prog<T>, writer: &mut impl Write) -> io::Result<()> {
+    let public = prog
+        .arguments
+        .iter()
+        .filter(|a| !a.private)
+        .map(|a| &a.id)
+        .chain(prog.returns.iter());
+
+    for v in public {
+        writeln!(writer, "pub {};", variable(v))?;
+    }
+
+    for s in &prog.statements {
+        match s {
+            Statement::Constraint(quad, lin, _) => {
+                writeln!(writer, "{} = {};", quad_comb(quad), lin_comb(lin))?
+            }
+            Statement::Directive(..) => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn variable(v: &FlatVariable) -> String {
+    let name = v.to_string();
+
+    match name.strip_prefix('~') {
+        Some(output) => output.to_string(),
+        None => format!("x{}", name),
+    }
+}
+
+// `c` as a signed integer, `-c` being preferred when it is smaller than `c`
+fn signed<T: Field>(c: &T) -> (bool, T) {
+    let negated = T::zero() - c.clone();
+
+    match negated.to_biguint() < c.to_biguint() {
+        true => (true, negated),
+        false => (false, c.clone()),
+    }
+}
+
+fn lin_comb<T: Field>(lin: &LinComb<T>) -> String {
+    let terms: Vec<_> = lin
+        .clone()
+        .into_canonical()
+        .0
+        .into_iter()
+        .map(|(v, c)| {
+            let (negative, c) = signed(&c);
+
+            let term = match (v == FlatVariable::one(), c == T::one()) {
+                (true, _) => c.to_dec_string(),
+                (false, true) => variable(&v),
+                (false, false) => format!("{} * {}", c.to_dec_string(), variable(&v)),
+            };
+
+            (negative, term)
+        })
+        .collect();
+
+    match terms.split_first() {
+        None => "0".to_string(),
+        Some(((negative, first), rest)) => rest.iter().fold(
+            format!("{}{}", if *negative { "-" } else { "" }, first),
+            |acc, (negative, term)| {
+                format!("{} {} {}", acc, if *negative { "-" } else { "+" }, term)
+            },
+        ),
+    }
+}
+
+fn quad_comb<T: Field>(quad: &QuadComb<T>) -> String {
+    // linear constraints are represented with `~one` as one of the factors
+    let one = LinComb::one().into_canonical();
+
+    match (
+        quad.left.clone().into_canonical() == one,
+        quad.right.clone().into_canonical() == one,
+    ) {
+        (true, _) => lin_comb(&quad.right),
+        (false, true) => lin_comb(&quad.left),
+        (false, false) => format!("({}) * ({})", lin_comb(&quad.left), lin_comb(&quad.right)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::{FlatParameter, RuntimeError};
+    use crate::ir::Directive;
+    use crate::solvers::Solver;
+    use zokrates_field::Bn128Field;
+
+    fn emit(prog: &Prog<Bn128Field>) -> String {
+        let mut buffer = vec![];
+        write(prog, &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn constraints() {
+        // def main(private _0, _1) -> (1):
+        //     # _2 = Div(_1, _0)
+        //     (1 * _0) * (1 * _2) == 1 * _1
+        //     (1 * ~one) * (1 * _0 + -1 * _1 + 3 * ~one) == 1 * ~out_0
+        //     return ~out_0
+
+        let a = FlatVariable::new(0);
+        let b = FlatVariable::new(1);
+        let c = FlatVariable::new(2);
+        let out = FlatVariable::public(0);
+
+        let prog: Prog<Bn128Field> = Prog {
+            arguments: vec![FlatParameter::private(a), FlatParameter::public(b)],
+            statements: vec![
+                Statement::Directive(Directive {
+                    inputs: vec![b.into(), a.into()],
+                    outputs: vec![c],
+                    solver: Solver::Div,
+                }),
+                Statement::Constraint(
+                    QuadComb::from_linear_combinations(a.into(), c.into()),
+                    b.into(),
+                    Some(RuntimeError::Source),
+                ),
+                Statement::definition(
+                    out,
+                    LinComb::from(a) - LinComb::from(b) + LinComb::summand(3, FlatVariable::one()),
+                ),
+            ],
+            returns: vec![out],
+        };
+
+        assert_eq!(
+            emit(&prog),
+            "pub x_1;
+pub out_0;
+(x_0) * (x_2) = x_1;
+3 + x_0 - x_1 = out_0;
+"
+        );
+    }
+
+    #[test]
+    fn empty_linear_combination() {
+        // def main(private _0) -> (0):
+        //     (1 * _0) * (1 * _0) == 0
+        //     return
+
+        let a = FlatVariable::new(0);
+
+        let prog: Prog<Bn128Field> = Prog {
+            arguments: vec![FlatParameter::private(a)],
+            statements: vec![Statement::constraint(
+                QuadComb::from_linear_combinations(a.into(), a.into()),
+                LinComb::zero(),
+            )],
+            returns: vec![],
+        };
+
+        assert_eq!(emit(&prog), "(x_0) * (x_0) = 0;\n");
+    }
+}
@@ -46,15 +46,32 @@ pub enum RuntimeError {
     Source,
     ArgumentBitness,
     SelectRangeCheck,
+    SourceBarrier,
+    /// a failed `assert_eq!`, with its condition in the source
+    SourceAssertEq(String),
+    /// a failed `assert_ne!`, with its condition in the source
+    SourceAssertNe(String),
 }
 
 impl RuntimeError {
+    /// Returns `true` for constraints which optimizers must leave untouched
+    pub fn is_barrier(&self) -> bool {
+        matches!(self, RuntimeError::SourceBarrier)
+    }
+
     fn is_malicious(&self) -> bool {
         use RuntimeError::*;
 
         !matches!(
             self,
-            Source | Inverse | LtSum | SelectRangeCheck | ArgumentBitness
+            Source
+                | SourceBarrier
+                | SourceAssertEq(..)
+                | SourceAssertNe(..)
+                | Inverse
+                | LtSum
+                | SelectRangeCheck
+                | ArgumentBitness
         )
     }
 }
@@ -90,6 +107,10 @@ impl fmt::Display for RuntimeError {
             Source => "User assertion failed",
             ArgumentBitness => "Argument bitness check failed",
             SelectRangeCheck => "Out of bounds array access",
+            SourceBarrier => "User assertion failed (optimizer barrier)",
+            SourceAssertEq(condition) | SourceAssertNe(condition) => {
+                return write!(f, "User assertion failed: {}", condition)
+            }
         };
 
         write!(f, "{}", msg)?;
@@ -0,0 +1,106 @@
+//! Module containing the strategies to lower `a < b`, and the chooser picking one for each comparison
+//!
+//! The bit-compare encoding decomposes `2 * (a - b)` over the full field width and is always sound. The difference
+//! encoding decomposes `2**w - 1 + b - a` into `w + 1` bits, whose highest bit is set if and only if `a < b`. It is
+//! only sound when both operands are known to be lower than `2**w`, with `2**(w + 1)` lower than the field modulus,
+//! so that the decomposition cannot wrap around.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use zokrates_field::Field;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComparisonStrategy {
+    /// decompose the doubled difference of the operands over the full field width
+    BitCompare,
+    /// range check the difference of the operands over their bitwidth
+    Difference,
+}
+
+use self::ComparisonStrategy::*;
+
+impl ComparisonStrategy {
+    fn name(&self) -> &'static str {
+        match self {
+            BitCompare => "bit_compare",
+            Difference => "difference",
+        }
+    }
+
+    /// Chooses the strategy to lower a comparison whose operands are lower than `2**bitwidth`, if such a bound is
+    /// known. `forced` overrides the choice, except that the difference encoding is never chosen when unsound.
+    pub fn choose<T: Field>(bitwidth: Option<usize>, forced: Option<ComparisonStrategy>) -> Self {
+        let is_sound = bitwidth
+            .map(|w| w + 1 < T::get_required_bits())
+            .unwrap_or(false);
+
+        match (forced, is_sound) {
+            (Some(BitCompare), _) | (_, false) => BitCompare,
+            (Some(Difference), true) | (None, true) => Difference,
+        }
+    }
+}
+
+impl fmt::Display for ComparisonStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for ComparisonStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [BitCompare, Difference]
+            .iter()
+            .find(|c| c.name() == s)
+            .cloned()
+            .ok_or_else(|| {
+                format!(
+                    "Unknown comparison strategy `{}`, expected one of bit_compare, difference",
+                    s
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn choose() {
+        // bounded operands use the difference encoding unless forced otherwise
+        assert_eq!(
+            ComparisonStrategy::choose::<Bn128Field>(Some(8), None),
+            Difference
+        );
+        assert_eq!(
+            ComparisonStrategy::choose::<Bn128Field>(Some(8), Some(BitCompare)),
+            BitCompare
+        );
+
+        // unbounded operands, or operands too wide for the difference to fit in the field, require bit-compare
+        assert_eq!(
+            ComparisonStrategy::choose::<Bn128Field>(None, Some(Difference)),
+            BitCompare
+        );
+        assert_eq!(
+            ComparisonStrategy::choose::<Bn128Field>(
+                Some(Bn128Field::get_required_bits() - 1),
+                Some(Difference)
+            ),
+            BitCompare
+        );
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("difference".parse(), Ok(Difference));
+        assert_eq!(BitCompare.to_string(), "bit_compare");
+        assert!("foo".parse::<ComparisonStrategy>().is_err());
+    }
+}
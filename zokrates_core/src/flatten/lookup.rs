@@ -0,0 +1,66 @@
+//! Module containing the cost model of `lookup` in a constant table with a dynamic index
+//!
+//! The index is range checked against the size of the table: its bits above the depth of the selection tree are
+//! constrained to zero, and when the size is not a power of two the remaining bits are compared to `size - 1`. The
+//! element is then selected by a tree of selections on the bits of the index, from the least significant one.
+//! Selections between constants are linear, so the first level of the tree is free and each selection above it costs
+//! one constraint. The decomposition of the index is not counted, as it is shared with other uses of the index, and
+//! constraints are counted after constant propagation on the flat program, before the inlining of single assignments
+//! and optimization, which can remove some of those of the range check.
+
+/// The number of bits of the index used to select in a table of `size` elements
+pub fn lookup_depth(size: usize) -> usize {
+    let mut depth = 0;
+    while (1 << depth) < size {
+        depth += 1;
+    }
+    depth
+}
+
+/// The number of constraints of a lookup in a table of `size` elements
+pub fn lookup_cost(size: usize) -> usize {
+    assert!(size > 0);
+
+    let depth = lookup_depth(size);
+
+    // the bits above the depth are zero
+    let high_bits = 32 - depth;
+
+    // the comparison of the low bits to `size - 1`, see `Flattener::constant_le_check`: two definitions for each bit
+    // but the last one, which does not update the run, and the check of the result
+    let low_bits = match size == 1 << depth {
+        true => 0,
+        false => 2 * depth,
+    };
+
+    // the selections of the first level are free, the `size - 1` selections of the tree are otherwise one constraint
+    let selections = (size - 1) - size / 2;
+
+    high_bits + low_bits + selections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn depth() {
+        assert_eq!(lookup_depth(1), 0);
+        assert_eq!(lookup_depth(2), 1);
+        assert_eq!(lookup_depth(5), 3);
+        assert_eq!(lookup_depth(256), 8);
+        assert_eq!(lookup_depth(257), 9);
+    }
+
+    #[test]
+    fn cost() {
+        // a single element only requires the index to be zero
+        assert_eq!(lookup_cost(1), 32);
+        // two elements are selected linearly
+        assert_eq!(lookup_cost(2), 31);
+        // 24 zero bits and 127 selections
+        assert_eq!(lookup_cost(256), 24 + 127);
+        // 29 zero bits, 6 for the comparison to `0b100` and 2 selections
+        assert_eq!(lookup_cost(5), 29 + 6 + 2);
+    }
+}
@@ -5,8 +5,13 @@
 //! @author Jacob Eberhardt <jacob.eberhardt@tu-berlin.de>
 //! @date 2017
 
+mod comparison;
+mod lookup;
 mod utils;
 
+pub use self::comparison::ComparisonStrategy;
+pub use self::lookup::lookup_cost;
+use self::lookup::lookup_depth;
 use self::utils::flat_expression_from_bits;
 use crate::ir::Interpreter;
 
@@ -422,6 +427,15 @@ impl<'ast, T: Field> Flattener<'ast, T> {
 
                     output
                 }
+                // the checks of `assert_eq!` and `assert_ne!` are unconditional, so they are left to the constraints
+                FlatStatement::Directive(FlatDirective {
+                    solver: Solver::AssertEq(..),
+                    ..
+                })
+                | FlatStatement::Directive(FlatDirective {
+                    solver: Solver::AssertNe(..),
+                    ..
+                }) => vec![],
                 s => vec![s],
             })
             .collect()
@@ -632,6 +646,85 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         )
     }
 
+    /// The number of bits of a reduced uint expression, which is bounded by both its metadata and its type
+    fn reduced_uint_bitwidth(e: &UExpression<'ast, T>) -> usize {
+        let type_bitwidth = e.bitwidth.to_usize();
+
+        e.metadata
+            .as_ref()
+            .map(|m| m.bitwidth() as usize)
+            .map(|b| std::cmp::min(b, type_bitwidth))
+            .unwrap_or(type_bitwidth)
+    }
+
+    /// Compute a check that `lhs < rhs` for operands in `[0, 2**bitwidth[`, by decomposing
+    /// `2**bitwidth - 1 + rhs - lhs` into `bitwidth + 1` bits. The result lies in `[0, 2**(bitwidth + 1) - 1[`, and
+    /// its highest bit is set if and only if `lhs < rhs`. The caller must make sure that `2**(bitwidth + 1)` is
+    /// lower than the field modulus, see `ComparisonStrategy::choose`.
+    ///
+    /// # Returns
+    /// * a `FlatExpression` which evaluates to `1` if `lhs < rhs`, and to `0` otherwise
+    fn difference_lt_check(
+        &mut self,
+        statements_flattened: &mut FlatStatements<T>,
+        lhs: FlatVariable,
+        rhs: FlatVariable,
+        bitwidth: usize,
+    ) -> FlatExpression<T> {
+        let width = bitwidth + 1;
+
+        let difference = FlatExpression::Sub(
+            box FlatExpression::Add(
+                box FlatExpression::Number(T::from(2).pow(bitwidth) - T::one()),
+                box FlatExpression::Identifier(rhs),
+            ),
+            box FlatExpression::Identifier(lhs),
+        );
+
+        // define variables for the bits
+        let bits_be: Vec<FlatVariable> = (0..width).map(|_| self.use_sym()).collect();
+
+        // add a directive to get the bits
+        statements_flattened.push(FlatStatement::Directive(FlatDirective::new(
+            bits_be.clone(),
+            Solver::bits(width),
+            vec![difference.clone()],
+        )));
+
+        // bitness checks
+        for bit in bits_be.iter() {
+            statements_flattened.push(FlatStatement::Condition(
+                FlatExpression::Identifier(*bit),
+                FlatExpression::Mult(
+                    box FlatExpression::Identifier(*bit),
+                    box FlatExpression::Identifier(*bit),
+                ),
+                RuntimeError::LtFinalBitness,
+            ));
+        }
+
+        // bit decomposition check
+        let mut sum = FlatExpression::Number(T::from(0));
+
+        for (i, bit) in bits_be.iter().enumerate() {
+            sum = FlatExpression::Add(
+                box sum,
+                box FlatExpression::Mult(
+                    box FlatExpression::Identifier(*bit),
+                    box FlatExpression::Number(T::from(2).pow(width - i - 1)),
+                ),
+            );
+        }
+
+        statements_flattened.push(FlatStatement::Condition(
+            difference,
+            sum,
+            RuntimeError::LtFinalSum,
+        ));
+
+        FlatExpression::Identifier(bits_be[0])
+    }
+
     /// Flattens a boolean expression
     ///
     /// # Arguments
@@ -660,6 +753,9 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             BooleanExpression::Select(a, box index) => self
                 .flatten_select_expression(statements_flattened, a, index)
                 .get_field_unchecked(),
+            BooleanExpression::Lookup(a, box index) => self
+                .flatten_lookup_expression(statements_flattened, a, index)
+                .get_field_unchecked(),
             BooleanExpression::FieldLt(box lhs, box rhs) => {
                 // Get the bit width to know the size of the binary decompositions for this Field
                 let bit_width = T::get_required_bits();
@@ -935,17 +1031,37 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 BooleanExpression::FieldLe(rhs, lhs),
             ),
             BooleanExpression::UintLt(box lhs, box rhs) => {
+                // both operands are reduced, so they are bounded by their metadata and their type
+                let operand_bitwidth = std::cmp::max(
+                    Self::reduced_uint_bitwidth(&lhs),
+                    Self::reduced_uint_bitwidth(&rhs),
+                );
+
+                let strategy = ComparisonStrategy::choose::<T>(
+                    Some(operand_bitwidth),
+                    self.config.comparison_strategy,
+                );
+
                 let lhs_flattened = self.flatten_uint_expression(statements_flattened, lhs);
                 let rhs_flattened = self.flatten_uint_expression(statements_flattened, rhs);
 
-                // Get the bit width to know the size of the binary decompositions for this Field
-                // This is not this uint bitwidth
-                let bit_width = T::get_required_bits();
-
                 // lhs
                 let lhs_id = self.define(lhs_flattened.get_field_unchecked(), statements_flattened);
                 let rhs_id = self.define(rhs_flattened.get_field_unchecked(), statements_flattened);
 
+                if strategy == ComparisonStrategy::Difference {
+                    return self.difference_lt_check(
+                        statements_flattened,
+                        lhs_id,
+                        rhs_id,
+                        operand_bitwidth,
+                    );
+                }
+
+                // Get the bit width to know the size of the binary decompositions for this Field
+                // This is not this uint bitwidth
+                let bit_width = T::get_required_bits();
+
                 // sym := (lhs * 2) - (rhs * 2)
                 let subtraction_result = FlatExpression::Sub(
                     box FlatExpression::Mult(
@@ -1478,6 +1594,9 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             UExpressionInner::Select(a, box index) => {
                 self.flatten_select_expression(statements_flattened, a, index)
             }
+            UExpressionInner::Lookup(a, box index) => {
+                self.flatten_lookup_expression(statements_flattened, a, index)
+            }
             UExpressionInner::Not(box e) => {
                 let e = self.flatten_uint_expression(statements_flattened, e);
 
@@ -2064,6 +2183,108 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         FlatUExpression::with_field(result)
     }
 
+    /// Flattens a lookup in a constant table with a dynamic index, see `lookup_cost`
+    fn flatten_lookup_expression<U: Flatten<'ast, T>>(
+        &mut self,
+        statements_flattened: &mut FlatStatements<T>,
+        a: Vec<U>,
+        index: UExpression<'ast, T>,
+    ) -> FlatUExpression<T> {
+        let size = a.len();
+        let depth = lookup_depth(size);
+
+        let index = self.flatten_uint_expression(statements_flattened, index);
+
+        // the bits of the index, from the least significant one
+        let bits: Vec<_> = self
+            .get_bits(&index, 32, UBitwidth::B32, statements_flattened)
+            .into_iter()
+            .rev()
+            .collect();
+
+        // the bits above the depth of the tree are zero
+        for bit in &bits[depth..] {
+            statements_flattened.push(FlatStatement::Condition(
+                FlatExpression::Number(T::zero()),
+                bit.clone(),
+                RuntimeError::SelectRangeCheck,
+            ));
+        }
+
+        // unless the tree is complete, the other bits are at most `size - 1`
+        if size != 1 << depth {
+            let bits_be: Vec<_> = bits[..depth]
+                .iter()
+                .rev()
+                .map(|bit| self.define(bit.clone(), statements_flattened))
+                .collect();
+            let bound_be: Vec<_> = (0..depth).rev().map(|i| (size - 1) >> i & 1 == 1).collect();
+
+            let conditions = self.constant_le_check(statements_flattened, &bits_be, &bound_be);
+            let conditions_count = conditions.len();
+            let conditions_sum = conditions
+                .into_iter()
+                .fold(FlatExpression::from(T::zero()), |acc, e| {
+                    FlatExpression::Add(box acc, box e)
+                });
+
+            statements_flattened.push(FlatStatement::Condition(
+                FlatExpression::Number(T::from(conditions_count)),
+                conditions_sum,
+                RuntimeError::SelectRangeCheck,
+            ));
+        }
+
+        let mut level: Vec<_> = a
+            .into_iter()
+            .map(|e| e.flatten(self, statements_flattened).flat())
+            .collect();
+
+        for bit in &bits[..depth] {
+            let mut next = vec![];
+            for pair in level.chunks(2) {
+                next.push(match pair {
+                    [a, b] => {
+                        self.select_on_bit(statements_flattened, bit.clone(), a.clone(), b.clone())
+                    }
+                    // the last element of an odd level has no sibling, as the indices of one would be out of range
+                    [a] => a.clone(),
+                    _ => unreachable!(),
+                });
+            }
+            level = next;
+        }
+
+        assert_eq!(level.len(), 1);
+
+        FlatUExpression::with_field(level.pop().unwrap())
+    }
+
+    /// Returns `b` if `bit` is `1` and `a` if it is `0`, as `a + bit * (b - a)`, which is linear when `a` and `b` are
+    /// constants
+    fn select_on_bit(
+        &mut self,
+        statements_flattened: &mut FlatStatements<T>,
+        bit: FlatExpression<T>,
+        a: FlatExpression<T>,
+        b: FlatExpression<T>,
+    ) -> FlatExpression<T> {
+        match (a, b) {
+            (FlatExpression::Number(a), FlatExpression::Number(b)) => FlatExpression::Add(
+                box FlatExpression::Number(a.clone()),
+                box FlatExpression::Mult(box bit, box FlatExpression::Number(b - a)),
+            ),
+            (a, b) => {
+                let selected = self.use_sym();
+                statements_flattened.push(FlatStatement::Definition(
+                    selected,
+                    FlatExpression::Mult(box bit, box FlatExpression::Sub(box b, box a.clone())),
+                ));
+                FlatExpression::Add(box a, box selected.into())
+            }
+        }
+    }
+
     /// Flattens a field expression
     ///
     /// # Arguments
@@ -2083,6 +2304,9 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             FieldElementExpression::Select(a, box index) => self
                 .flatten_select_expression(statements_flattened, a, index)
                 .get_field_unchecked(),
+            FieldElementExpression::Lookup(a, box index) => self
+                .flatten_lookup_expression(statements_flattened, a, index)
+                .get_field_unchecked(),
             FieldElementExpression::Add(box left, box right) => {
                 let left_flattened = self.flatten_field_expression(statements_flattened, left);
                 let right_flattened = self.flatten_field_expression(statements_flattened, right);
@@ -2367,13 +2591,13 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                         .insert(FlatExpression::Identifier(var), bits);
                 }
             }
-            ZirStatement::Assertion(e) => {
+            ZirStatement::Assertion(e, error) => {
                 match e {
                     BooleanExpression::And(..) => {
                         for boolean in e.into_conjunction_iterator() {
                             self.flatten_statement(
                                 statements_flattened,
-                                ZirStatement::Assertion(boolean),
+                                ZirStatement::Assertion(boolean, error.clone()),
                             )
                         }
                     }
@@ -2381,7 +2605,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                         let lhs = self.flatten_field_expression(statements_flattened, lhs);
                         let rhs = self.flatten_field_expression(statements_flattened, rhs);
 
-                        self.flatten_equality_assertion(statements_flattened, lhs, rhs)
+                        self.flatten_equality_assertion(statements_flattened, lhs, rhs, error)
                     }
                     BooleanExpression::UintEq(box lhs, box rhs) => {
                         let lhs = self
@@ -2391,13 +2615,41 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                             .flatten_uint_expression(statements_flattened, rhs)
                             .get_field_unchecked();
 
-                        self.flatten_equality_assertion(statements_flattened, lhs, rhs)
+                        self.flatten_equality_assertion(statements_flattened, lhs, rhs, error)
                     }
                     BooleanExpression::BoolEq(box lhs, box rhs) => {
                         let lhs = self.flatten_boolean_expression(statements_flattened, lhs);
                         let rhs = self.flatten_boolean_expression(statements_flattened, rhs);
 
-                        self.flatten_equality_assertion(statements_flattened, lhs, rhs)
+                        self.flatten_equality_assertion(statements_flattened, lhs, rhs, error)
+                    }
+                    BooleanExpression::Not(box BooleanExpression::FieldEq(box lhs, box rhs))
+                        if matches!(error, RuntimeError::SourceAssertNe(..)) =>
+                    {
+                        let lhs = self.flatten_field_expression(statements_flattened, lhs);
+                        let rhs = self.flatten_field_expression(statements_flattened, rhs);
+
+                        self.flatten_inequality_assertion(statements_flattened, lhs, rhs, error)
+                    }
+                    BooleanExpression::Not(box BooleanExpression::UintEq(box lhs, box rhs))
+                        if matches!(error, RuntimeError::SourceAssertNe(..)) =>
+                    {
+                        let lhs = self
+                            .flatten_uint_expression(statements_flattened, lhs)
+                            .get_field_unchecked();
+                        let rhs = self
+                            .flatten_uint_expression(statements_flattened, rhs)
+                            .get_field_unchecked();
+
+                        self.flatten_inequality_assertion(statements_flattened, lhs, rhs, error)
+                    }
+                    BooleanExpression::Not(box BooleanExpression::BoolEq(box lhs, box rhs))
+                        if matches!(error, RuntimeError::SourceAssertNe(..)) =>
+                    {
+                        let lhs = self.flatten_boolean_expression(statements_flattened, lhs);
+                        let rhs = self.flatten_boolean_expression(statements_flattened, rhs);
+
+                        self.flatten_inequality_assertion(statements_flattened, lhs, rhs, error)
                     }
                     _ => {
                         // naive approach: flatten the boolean to a single field element and constrain it to 1
@@ -2407,14 +2659,14 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                             statements_flattened.push(FlatStatement::Condition(
                                 e,
                                 FlatExpression::Number(T::from(1)),
-                                RuntimeError::Source,
+                                error,
                             ));
                         } else {
                             // swap so that left side is linear
                             statements_flattened.push(FlatStatement::Condition(
                                 FlatExpression::Number(T::from(1)),
                                 e,
-                                RuntimeError::Source,
+                                error,
                             ));
                         }
                     }
@@ -2526,24 +2778,76 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         statements_flattened: &mut FlatStatements<T>,
         lhs: FlatExpression<T>,
         rhs: FlatExpression<T>,
+        error: RuntimeError,
     ) {
-        let (lhs, rhs) = match (lhs, rhs) {
-            (FlatExpression::Mult(box x, box y), z) | (z, FlatExpression::Mult(box x, box y)) => (
-                self.identify_expression(z, statements_flattened),
-                FlatExpression::Mult(
+        // the constraint is `linear == quadratic`, and `operands` are the sides of the equality in the source order
+        let (linear, quadratic, operands) = match (lhs, rhs) {
+            (FlatExpression::Mult(box x, box y), z) => {
+                let z = self.identify_expression(z, statements_flattened);
+                let quadratic = FlatExpression::Mult(
                     box self.identify_expression(x, statements_flattened),
                     box self.identify_expression(y, statements_flattened),
-                ),
-            ),
-            (x, z) => (
-                self.identify_expression(z, statements_flattened),
-                FlatExpression::Mult(
+                );
+                (z.clone(), quadratic.clone(), vec![quadratic, z])
+            }
+            (z, FlatExpression::Mult(box x, box y)) => {
+                let z = self.identify_expression(z, statements_flattened);
+                let quadratic = FlatExpression::Mult(
                     box self.identify_expression(x, statements_flattened),
-                    box FlatExpression::Number(T::from(1)),
-                ),
-            ),
+                    box self.identify_expression(y, statements_flattened),
+                );
+                (z.clone(), quadratic.clone(), vec![z, quadratic])
+            }
+            (x, z) => {
+                let z = self.identify_expression(z, statements_flattened);
+                let x = self.identify_expression(x, statements_flattened);
+                (
+                    z.clone(),
+                    FlatExpression::Mult(box x.clone(), box FlatExpression::Number(T::from(1))),
+                    vec![x, z],
+                )
+            }
         };
-        statements_flattened.push(FlatStatement::Condition(lhs, rhs, RuntimeError::Source));
+
+        // `assert_eq!` reports the values of its operands when computing the witness
+        if let RuntimeError::SourceAssertEq(condition) = &error {
+            statements_flattened.push(FlatStatement::Directive(FlatDirective::new(
+                vec![],
+                Solver::AssertEq(condition.clone()),
+                operands,
+            )));
+        }
+
+        statements_flattened.push(FlatStatement::Condition(linear, quadratic, error));
+    }
+
+    /// Flattens an `assert_ne!` of `lhs` and `rhs`, reporting the values of its operands when computing the witness
+    fn flatten_inequality_assertion(
+        &mut self,
+        statements_flattened: &mut FlatStatements<T>,
+        lhs: FlatExpression<T>,
+        rhs: FlatExpression<T>,
+        error: RuntimeError,
+    ) {
+        let lhs = self.define(lhs, statements_flattened);
+        let rhs = self.define(rhs, statements_flattened);
+
+        if let RuntimeError::SourceAssertNe(condition) = &error {
+            statements_flattened.push(FlatStatement::Directive(FlatDirective::new(
+                vec![],
+                Solver::AssertNe(condition.clone()),
+                vec![lhs, rhs],
+            )));
+        }
+
+        // `lhs != rhs` holds when the equality check is `0`
+        let eq = self.eq_check(statements_flattened, lhs.into(), rhs.into());
+
+        statements_flattened.push(FlatStatement::Condition(
+            FlatExpression::Number(T::zero()),
+            eq,
+            error,
+        ));
     }
 
     /// Identifies a non-linear expression by assigning it to a new identifier.
@@ -2676,10 +2980,13 @@ mod tests {
                     Variable::boolean("y".into()),
                     BooleanExpression::Value(true).into(),
                 ),
-                ZirStatement::Assertion(BooleanExpression::BoolEq(
-                    box BooleanExpression::Identifier("x".into()),
-                    box BooleanExpression::Identifier("y".into()),
-                )),
+                ZirStatement::Assertion(
+                    BooleanExpression::BoolEq(
+                        box BooleanExpression::Identifier("x".into()),
+                        box BooleanExpression::Identifier("y".into()),
+                    ),
+                    RuntimeError::Source,
+                ),
             ],
             signature: Signature {
                 inputs: vec![],
@@ -2738,13 +3045,16 @@ mod tests {
                     Variable::field_element("y"),
                     FieldElementExpression::Number(Bn128Field::from(2)).into(),
                 ),
-                ZirStatement::Assertion(BooleanExpression::FieldEq(
-                    box FieldElementExpression::Add(
-                        box FieldElementExpression::Identifier("x".into()),
-                        box FieldElementExpression::Number(Bn128Field::from(1)),
+                ZirStatement::Assertion(
+                    BooleanExpression::FieldEq(
+                        box FieldElementExpression::Add(
+                            box FieldElementExpression::Identifier("x".into()),
+                            box FieldElementExpression::Number(Bn128Field::from(1)),
+                        ),
+                        box FieldElementExpression::Identifier("y".into()),
                     ),
-                    box FieldElementExpression::Identifier("y".into()),
-                )),
+                    RuntimeError::Source,
+                ),
             ],
             signature: Signature {
                 inputs: vec![],
@@ -2808,12 +3118,15 @@ mod tests {
                             .metadata(metadata.clone()),
                     ),
                 ),
-                ZirStatement::Assertion(BooleanExpression::UintEq(
-                    box UExpressionInner::Identifier("x".into())
-                        .annotate(32)
-                        .metadata(metadata.clone()),
-                    box UExpressionInner::Value(42).annotate(32).metadata(metadata),
-                )),
+                ZirStatement::Assertion(
+                    BooleanExpression::UintEq(
+                        box UExpressionInner::Identifier("x".into())
+                            .annotate(32)
+                            .metadata(metadata.clone()),
+                        box UExpressionInner::Value(42).annotate(32).metadata(metadata),
+                    ),
+                    RuntimeError::Source,
+                ),
             ],
             signature: Signature {
                 inputs: vec![],
@@ -2868,10 +3181,13 @@ mod tests {
                     Variable::field_element("y"),
                     FieldElementExpression::Number(Bn128Field::from(2)).into(),
                 ),
-                ZirStatement::Assertion(BooleanExpression::FieldEq(
-                    box FieldElementExpression::Identifier("x".into()),
-                    box FieldElementExpression::Identifier("y".into()),
-                )),
+                ZirStatement::Assertion(
+                    BooleanExpression::FieldEq(
+                        box FieldElementExpression::Identifier("x".into()),
+                        box FieldElementExpression::Identifier("y".into()),
+                    ),
+                    RuntimeError::Source,
+                ),
             ],
             signature: Signature {
                 inputs: vec![],
@@ -2936,13 +3252,16 @@ mod tests {
                     Variable::field_element("z"),
                     FieldElementExpression::Number(Bn128Field::from(4)).into(),
                 ),
-                ZirStatement::Assertion(BooleanExpression::FieldEq(
-                    box FieldElementExpression::Mult(
-                        box FieldElementExpression::Identifier("x".into()),
-                        box FieldElementExpression::Identifier("y".into()),
+                ZirStatement::Assertion(
+                    BooleanExpression::FieldEq(
+                        box FieldElementExpression::Mult(
+                            box FieldElementExpression::Identifier("x".into()),
+                            box FieldElementExpression::Identifier("y".into()),
+                        ),
+                        box FieldElementExpression::Identifier("z".into()),
                     ),
-                    box FieldElementExpression::Identifier("z".into()),
-                )),
+                    RuntimeError::Source,
+                ),
             ],
             signature: Signature {
                 inputs: vec![],
@@ -3011,13 +3330,16 @@ mod tests {
                     Variable::field_element("z"),
                     FieldElementExpression::Number(Bn128Field::from(4)).into(),
                 ),
-                ZirStatement::Assertion(BooleanExpression::FieldEq(
-                    box FieldElementExpression::Identifier("z".into()),
-                    box FieldElementExpression::Mult(
-                        box FieldElementExpression::Identifier("x".into()),
-                        box FieldElementExpression::Identifier("y".into()),
+                ZirStatement::Assertion(
+                    BooleanExpression::FieldEq(
+                        box FieldElementExpression::Identifier("z".into()),
+                        box FieldElementExpression::Mult(
+                            box FieldElementExpression::Identifier("x".into()),
+                            box FieldElementExpression::Identifier("y".into()),
+                        ),
                     ),
-                )),
+                    RuntimeError::Source,
+                ),
             ],
             signature: Signature {
                 inputs: vec![],
@@ -3093,16 +3415,19 @@ mod tests {
                     Variable::field_element("t"),
                     FieldElementExpression::Number(Bn128Field::from(2)).into(),
                 ),
-                ZirStatement::Assertion(BooleanExpression::FieldEq(
-                    box FieldElementExpression::Mult(
-                        box FieldElementExpression::Identifier("x".into()),
-                        box FieldElementExpression::Identifier("y".into()),
-                    ),
-                    box FieldElementExpression::Mult(
-                        box FieldElementExpression::Identifier("z".into()),
-                        box FieldElementExpression::Identifier("t".into()),
+                ZirStatement::Assertion(
+                    BooleanExpression::FieldEq(
+                        box FieldElementExpression::Mult(
+                            box FieldElementExpression::Identifier("x".into()),
+                            box FieldElementExpression::Identifier("y".into()),
+                        ),
+                        box FieldElementExpression::Mult(
+                            box FieldElementExpression::Identifier("z".into()),
+                            box FieldElementExpression::Identifier("t".into()),
+                        ),
                     ),
-                )),
+                    RuntimeError::Source,
+                ),
             ],
             signature: Signature {
                 inputs: vec![],
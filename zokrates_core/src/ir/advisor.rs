@@ -0,0 +1,207 @@
+//! Module containing the `Advisor`, which cross-references recorded executions of a program with its
+//! range checks to find declared widths which are larger than what real inputs need
+//!
+//! Suggestions are advisory: they are derived from the observed executions only, and making sure
+//! that a narrower encoding is still sound for all inputs is the responsibility of the user.
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::{Prog, Statement, Witness};
+use crate::solvers::Solver;
+use crate::typed_absy::abi::Abi;
+use crate::typed_absy::types::ConcreteType;
+use std::collections::HashMap;
+use std::fmt;
+use zokrates_field::Field;
+
+const UINT_BITWIDTHS: &[usize] = &[8, 16, 32, 64];
+
+#[derive(Debug, PartialEq)]
+pub struct Suggestion {
+    /// the index of the decomposition directive in the program statements
+    pub statement: usize,
+    /// the input of `main` whose width is declared, like `amount` or `balances[2]`
+    pub input: String,
+    pub bitwidth: usize,
+    pub observed_max: String,
+    pub runs: usize,
+    pub suggested_bitwidth: usize,
+}
+
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "input `{}` (u{}, decomposed by the directive at statement #{}) — observed max value {} across {} run{} and asserted bound 2^{}; consider declaring u{}",
+            self.input,
+            self.bitwidth,
+            self.statement,
+            self.observed_max,
+            self.runs,
+            if self.runs == 1 { "" } else { "s" },
+            self.bitwidth,
+            self.suggested_bitwidth
+        )
+    }
+}
+
+// the name and bitwidth of each flattened input of type `ty`, `None` for the inputs which are not uints
+fn declared_widths(name: String, ty: &ConcreteType) -> Vec<(String, Option<usize>)> {
+    match ty {
+        ConcreteType::Uint(bitwidth) => vec![(name, Some(bitwidth.to_usize()))],
+        ConcreteType::Array(array_type) => (0..array_type.size)
+            .flat_map(|i| declared_widths(format!("{}[{}]", name, i), &array_type.ty))
+            .collect(),
+        ConcreteType::Struct(struct_type) => struct_type
+            .members
+            .iter()
+            .flat_map(|m| declared_widths(format!("{}.{}", name, m.id), &m.ty))
+            .collect(),
+        _ => vec![(name, None)],
+    }
+}
+
+pub struct Advisor;
+
+impl Advisor {
+    /// Only the decompositions of the inputs of `main` are considered: a width is not declared for the other ones,
+    /// like the decomposition of the result of a multiplication
+    pub fn advise<T: Field>(
+        prog: &Prog<T>,
+        abi: &Abi,
+        witnesses: &[Witness<T>],
+    ) -> Vec<Suggestion> {
+        if witnesses.is_empty() {
+            return vec![];
+        }
+
+        // the flattened inputs are laid out in the order of the abi
+        let declared: HashMap<FlatVariable, (String, usize)> = prog
+            .arguments
+            .iter()
+            .zip(
+                abi.inputs
+                    .iter()
+                    .flat_map(|i| declared_widths(i.name.clone(), &i.ty)),
+            )
+            .filter_map(|(p, (name, bitwidth))| bitwidth.map(|b| (p.id, (name, b))))
+            .collect();
+
+        prog.statements
+            .iter()
+            .enumerate()
+            .filter_map(|(index, s)| match s {
+                Statement::Directive(d) => match d.solver {
+                    Solver::Bits(bitwidth) if UINT_BITWIDTHS.contains(&bitwidth) => {
+                        let input = match d.inputs[0].clone().try_linear().map(|l| l.try_summand())
+                        {
+                            Ok(Ok((v, coefficient))) if coefficient == T::one() => declared
+                                .get(&v)
+                                .filter(|(_, declared)| *declared == bitwidth)
+                                .map(|(name, _)| name.clone()),
+                            _ => None,
+                        }?;
+
+                        let observed_max = witnesses
+                            .iter()
+                            .filter_map(|w| d.inputs[0].evaluate(&w.0).ok())
+                            .max()?;
+
+                        let used_bits = observed_max.bits() as usize;
+
+                        UINT_BITWIDTHS
+                            .iter()
+                            .find(|b| **b >= used_bits)
+                            .filter(|b| **b < bitwidth)
+                            .map(|suggested_bitwidth| Suggestion {
+                                statement: index,
+                                input,
+                                bitwidth,
+                                observed_max: observed_max.to_dec_string(),
+                                runs: witnesses.len(),
+                                suggested_bitwidth: *suggested_bitwidth,
+                            })
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compile::{compile, CompilationArtifacts, CompileConfig};
+    use crate::ir::Interpreter;
+    use std::io;
+    use zokrates_common::Resolver;
+    use zokrates_field::Bn128Field;
+
+    fn advise(source: &str, runs: &[&[u32]]) -> Vec<Suggestion> {
+        let artifacts: CompilationArtifacts<Bn128Field> = compile(
+            source.to_string(),
+            "main".into(),
+            None::<&dyn Resolver<io::Error>>,
+            &CompileConfig::default(),
+        )
+        .unwrap();
+
+        let interpreter = Interpreter::default();
+
+        let witnesses: Vec<_> = runs
+            .iter()
+            .map(|r| {
+                let arguments: Vec<_> = r.iter().map(|a| Bn128Field::from(*a)).collect();
+                interpreter.execute(artifacts.prog(), &arguments).unwrap()
+            })
+            .collect();
+
+        Advisor::advise(artifacts.prog(), artifacts.abi(), &witnesses)
+    }
+
+    #[test]
+    fn oversized_width() {
+        let suggestions = advise(
+            r#"
+            def main(u32 amount) -> u32:
+                return amount
+        "#,
+            &[&[0], &[17], &[255], &[3]],
+        );
+
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].input, "amount");
+        assert_eq!(suggestions[0].bitwidth, 32);
+        assert_eq!(suggestions[0].observed_max, "255");
+        assert_eq!(suggestions[0].runs, 4);
+        assert_eq!(suggestions[0].suggested_bitwidth, 8);
+    }
+
+    #[test]
+    fn right_sized_width() {
+        let suggestions = advise(
+            r#"
+            def main(u8 amount) -> u8:
+                return amount
+        "#,
+            &[&[0], &[17], &[255], &[3]],
+        );
+
+        assert!(suggestions.is_empty());
+    }
+
+    #[test]
+    fn intermediate_values() {
+        // the product is decomposed on 64 bits and always fits in 32 bits here, but its width is not declared
+        let suggestions = advise(
+            r#"
+            def main(u32 a, u32 b) -> u32:
+                return a * b
+        "#,
+            &[&[4294967295, 1], &[1, 4294967295]],
+        );
+
+        assert!(suggestions.is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+//! Module containing the batch check of witnesses against a program
+//!
+//! Each witness is given as the arguments of the program, and is checked by executing the program on them. The
+//! executions are independent, so they run in parallel when the `multicore` feature is enabled, all reading the same
+//! program.
+
+use crate::ir::{Error, Interpreter, Prog};
+#[cfg(feature = "multicore")]
+use rayon::prelude::*;
+use zokrates_field::Field;
+
+/// The reason why a witness does not satisfy a program
+pub type WitnessError = Error;
+
+impl<T: Field + Send + Sync> Prog<T> {
+    /// Checks each of `witnesses` against the program, returning a result per witness, in order
+    pub fn batch_check_witnesses(&self, witnesses: &[Vec<T>]) -> Vec<Result<(), WitnessError>> {
+        let interpreter = Interpreter::default();
+
+        #[cfg(feature = "multicore")]
+        let witnesses = witnesses.par_iter();
+        #[cfg(not(feature = "multicore"))]
+        let witnesses = witnesses.iter();
+
+        witnesses
+            .map(|inputs| interpreter.execute(self, inputs).map(|_| ()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::{FlatParameter, FlatVariable};
+    use crate::ir::{QuadComb, Statement};
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn batch() {
+        // def main(_0, _1) -> (1):
+        //     _1 == _0 * _0
+        //     ~out_0 == _1
+        //     return ~out_0
+        let prog: Prog<Bn128Field> = Prog {
+            arguments: vec![
+                FlatParameter::private(FlatVariable::new(0)),
+                FlatParameter::public(FlatVariable::new(1)),
+            ],
+            statements: vec![
+                Statement::constraint(
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::new(0).into(),
+                    ),
+                    FlatVariable::new(1),
+                ),
+                Statement::definition(FlatVariable::public(0), FlatVariable::new(1)),
+            ],
+            returns: vec![FlatVariable::public(0)],
+        };
+
+        let witnesses: Vec<Vec<Bn128Field>> = vec![
+            vec![3.into(), 9.into()],
+            vec![3.into(), 8.into()],
+            vec![0.into(), 0.into()],
+            vec![1.into()],
+        ];
+
+        let results = prog.batch_check_witnesses(&witnesses);
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0], Ok(()));
+        assert!(matches!(
+            results[1],
+            Err(WitnessError::UnsatisfiedConstraint { .. })
+        ));
+        assert_eq!(results[2], Ok(()));
+        assert_eq!(
+            results[3],
+            Err(WitnessError::WrongInputCount {
+                expected: 2,
+                received: 1
+            })
+        );
+    }
+}
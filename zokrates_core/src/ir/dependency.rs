@@ -0,0 +1,151 @@
+//! Module containing the dependency slice of a program, the statements whose execution depends on a set of variables
+//!
+//! The slice follows the definition rule of the interpreter: a constraint whose right hand side is a single
+//! undefined variable with coefficient one defines that variable, and a directive defines its outputs. Any other
+//! constraint is a check.
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::{LinComb, Prog, QuadComb, Statement};
+use std::collections::{BTreeSet, HashSet};
+use zokrates_field::Field;
+
+#[derive(Debug, PartialEq, Default)]
+pub struct DependencySlice {
+    /// the indices of the statements reading a dependent variable, in execution order
+    pub statements: BTreeSet<usize>,
+    /// the variables defined by these statements
+    pub variables: BTreeSet<FlatVariable>,
+}
+
+fn lin_variables<T>(lin: &LinComb<T>) -> impl Iterator<Item = &FlatVariable> {
+    lin.0.iter().map(|(v, _)| v)
+}
+
+fn quad_variables<T>(quad: &QuadComb<T>) -> impl Iterator<Item = &FlatVariable> {
+    lin_variables(&quad.left).chain(lin_variables(&quad.right))
+}
+
+impl<T: Field> Prog<T> {
+    /// Returns the statements of the program which transitively depend on `sources`, which should be arguments
+    pub fn dependency_slice(&self, sources: &[FlatVariable]) -> DependencySlice {
+        let mut defined: HashSet<FlatVariable> = self.arguments.iter().map(|p| p.id).collect();
+        defined.insert(FlatVariable::one());
+
+        let mut dependent: HashSet<FlatVariable> = sources.iter().cloned().collect();
+        let mut slice = DependencySlice::default();
+
+        for (index, statement) in self.statements.iter().enumerate() {
+            match statement {
+                Statement::Constraint(quad, lin, _) => {
+                    match statement.assignee(|v| defined.contains(v)) {
+                        Some(v) => {
+                            defined.insert(v);
+                            if quad_variables(quad).any(|v| dependent.contains(v)) {
+                                slice.statements.insert(index);
+                                slice.variables.insert(v);
+                                dependent.insert(v);
+                            }
+                        }
+                        None => {
+                            if quad_variables(quad)
+                                .chain(lin_variables(lin))
+                                .any(|v| dependent.contains(v))
+                            {
+                                slice.statements.insert(index);
+                            }
+                        }
+                    }
+                }
+                Statement::Directive(d) => {
+                    defined.extend(d.outputs.iter().cloned());
+                    if d.inputs
+                        .iter()
+                        .flat_map(quad_variables)
+                        .any(|v| dependent.contains(v))
+                    {
+                        slice.statements.insert(index);
+                        slice.variables.extend(d.outputs.iter().cloned());
+                        dependent.extend(d.outputs.iter().cloned());
+                    }
+                }
+            }
+        }
+
+        slice
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatParameter;
+    use crate::ir::Directive;
+    use crate::solvers::Solver;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn slice() {
+        // def main(_0, _1) -> (1):
+        //     _2 == _0 * _0
+        //     # _3 = Div(_1, _2)
+        //     _1 == _3 * _2
+        //     _4 == _1 * _1
+        //     ~out_0 == _4 * _4
+        //     return ~out_0
+
+        let prog: Prog<Bn128Field> = Prog {
+            arguments: vec![
+                FlatParameter::public(FlatVariable::new(0)),
+                FlatParameter::private(FlatVariable::new(1)),
+            ],
+            statements: vec![
+                Statement::definition(
+                    FlatVariable::new(2),
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::new(0).into(),
+                    ),
+                ),
+                Statement::Directive(Directive {
+                    inputs: vec![FlatVariable::new(1).into(), FlatVariable::new(2).into()],
+                    outputs: vec![FlatVariable::new(3)],
+                    solver: Solver::Div,
+                }),
+                Statement::constraint(
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(3).into(),
+                        FlatVariable::new(2).into(),
+                    ),
+                    FlatVariable::new(1),
+                ),
+                Statement::definition(
+                    FlatVariable::new(4),
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(1).into(),
+                        FlatVariable::new(1).into(),
+                    ),
+                ),
+                Statement::definition(
+                    FlatVariable::public(0),
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(4).into(),
+                        FlatVariable::new(4).into(),
+                    ),
+                ),
+            ],
+            returns: vec![FlatVariable::public(0)],
+        };
+
+        let slice = prog.dependency_slice(&[FlatVariable::new(0)]);
+
+        assert_eq!(slice.statements, vec![0, 1, 2].into_iter().collect());
+        assert_eq!(
+            slice.variables,
+            vec![FlatVariable::new(2), FlatVariable::new(3)]
+                .into_iter()
+                .collect()
+        );
+
+        assert_eq!(prog.dependency_slice(&[]), DependencySlice::default());
+    }
+}
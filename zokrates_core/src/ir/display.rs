@@ -0,0 +1,172 @@
+//! Module containing filtered displays of programs, for inspecting programs too large to be displayed whole
+//!
+//! Statements are written to the formatter one by one, each prefixed with its index in the program, so that the
+//! output of a selection can be related to the rest of the program.
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::{LinComb, Prog, QuadComb, Statement};
+use std::fmt;
+use std::ops::Range;
+use zokrates_field::Field;
+
+/// Displays the statements of a program selected by a predicate, along with their index
+pub struct StatementsDisplay<'a, T, P> {
+    statements: &'a [Statement<T>],
+    // the index of the first statement of `statements` in the program
+    offset: usize,
+    predicate: P,
+}
+
+impl<T: Field, P: Fn(&Statement<T>) -> bool> fmt::Display for StatementsDisplay<'_, T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, s) in self.statements.iter().enumerate() {
+            if (self.predicate)(s) {
+                writeln!(f, "{}: {}", self.offset + index, s)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn lin_references<T>(lin: &LinComb<T>, v: &FlatVariable) -> bool {
+    lin.0.iter().any(|(w, _)| w == v)
+}
+
+fn quad_references<T>(quad: &QuadComb<T>, v: &FlatVariable) -> bool {
+    lin_references(&quad.left, v) || lin_references(&quad.right, v)
+}
+
+impl<T> Statement<T> {
+    /// Returns `true` if `v` appears in this statement, as an input or as an output
+    pub fn references(&self, v: &FlatVariable) -> bool {
+        match self {
+            Statement::Constraint(quad, lin, _) => {
+                quad_references(quad, v) || lin_references(lin, v)
+            }
+            Statement::Directive(d) => {
+                d.inputs.iter().any(|i| quad_references(i, v)) || d.outputs.contains(v)
+            }
+        }
+    }
+}
+
+impl<T: Field> Prog<T> {
+    /// Displays the statements in `range`, which is truncated to the statements of the program
+    pub fn display_range(
+        &self,
+        range: Range<usize>,
+    ) -> StatementsDisplay<T, fn(&Statement<T>) -> bool> {
+        let end = range.end.min(self.statements.len());
+        let start = range.start.min(end);
+
+        StatementsDisplay {
+            statements: &self.statements[start..end],
+            offset: start,
+            predicate: |_| true,
+        }
+    }
+
+    /// Displays the statements satisfying `predicate`
+    pub fn display_matching<P: Fn(&Statement<T>) -> bool>(
+        &self,
+        predicate: P,
+    ) -> StatementsDisplay<T, P> {
+        StatementsDisplay {
+            statements: &self.statements,
+            offset: 0,
+            predicate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatParameter;
+    use crate::ir::Directive;
+    use crate::solvers::Solver;
+    use zokrates_field::Bn128Field;
+
+    // def main(_0) -> (1):
+    //     # _1 = Div(_0, ~one)
+    //     ~one * _1 == _0
+    //     _2 == _0 * _0
+    //     ~out_0 == _1 * _2
+    //     return ~out_0
+    fn prog() -> Prog<Bn128Field> {
+        Prog {
+            arguments: vec![FlatParameter::private(FlatVariable::new(0))],
+            statements: vec![
+                Statement::Directive(Directive {
+                    inputs: vec![FlatVariable::new(0).into(), FlatVariable::one().into()],
+                    outputs: vec![FlatVariable::new(1)],
+                    solver: Solver::Div,
+                }),
+                Statement::constraint(FlatVariable::new(1), FlatVariable::new(0)),
+                Statement::definition(
+                    FlatVariable::new(2),
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::new(0).into(),
+                    ),
+                ),
+                Statement::definition(
+                    FlatVariable::public(0),
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(1).into(),
+                        FlatVariable::new(2).into(),
+                    ),
+                ),
+            ],
+            returns: vec![FlatVariable::public(0)],
+        }
+    }
+
+    #[test]
+    fn range() {
+        assert_eq!(
+            prog().display_range(1..3).to_string(),
+            "1: (1 * ~one) * (1 * _1) == 1 * _0\n2: (1 * _0) * (1 * _0) == 1 * _2\n"
+        );
+
+        // the range is truncated to the program
+        assert_eq!(
+            prog().display_range(3..1000).to_string(),
+            "3: (1 * _1) * (1 * _2) == 1 * ~out_0\n"
+        );
+        assert_eq!(prog().display_range(1000..1050).to_string(), "");
+    }
+
+    #[test]
+    fn matching_variable() {
+        let p = prog();
+
+        let selected: Vec<_> = p
+            .statements
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.references(&FlatVariable::new(1)))
+            .map(|(index, _)| index)
+            .collect();
+
+        assert_eq!(selected, vec![0, 1, 3]);
+
+        assert_eq!(
+            p.display_matching(|s| s.references(&FlatVariable::new(2)))
+                .to_string(),
+            "2: (1 * _0) * (1 * _0) == 1 * _2\n3: (1 * _1) * (1 * _2) == 1 * ~out_0\n"
+        );
+    }
+
+    #[test]
+    fn matching_solver() {
+        assert_eq!(
+            prog()
+                .display_matching(
+                    |s| matches!(s, Statement::Directive(d) if d.solver == Solver::Div)
+                )
+                .to_string(),
+            "0: # _1 = Div((1 * ~one) * (1 * _0), (1 * ~one) * (1 * ~one))\n"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Module containing a probabilistic equivalence check between programs, used to make sure a transformation such as
+//! an optimizer pass preserves the semantics of a program
+//!
+//! Two programs are compared on the relation between their arguments and their outputs, as their internal variables
+//! may differ. Each trial draws arguments at random and executes both programs: they must either both reject the
+//! arguments, or both accept them and return the same values. Following the Schwartz-Zippel lemma, two programs whose
+//! outputs are distinct polynomials of degree `d` in their arguments agree on a uniformly random point with
+//! probability at most `d / |F|`, so a single trial already gives high confidence for such programs.
+//!
+//! Executing a program only shows the witness an honest prover computes, while the constraints may accept others: a
+//! constraint dropped by an optimizer goes unnoticed when a directive still computes the value it defined. Each trial
+//! therefore also evaluates the constraints of both programs on a random witness extension, where the outputs of
+//! directives are replaced by uniformly random values as a dishonest prover could choose them. A program is
+//! under-constrained on the arguments if such a witness satisfies all of its constraints while returning values other
+//! than the honest ones. By the same lemma, random directive outputs satisfy the constraints checking them with
+//! negligible probability, so this only happens when some directive output is not constrained at all. Both programs
+//! must be under-constrained on the same arguments to be considered equivalent.
+//!
+//! Uniformly random arguments almost never satisfy range checks or assertions. Half of the arguments are therefore
+//! drawn as small values, so that programs taking uints or booleans get accepting trials too. The check remains
+//! approximate: it can only show that programs differ, not prove that they are equivalent.
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::{Interpreter, Prog, Statement};
+use num_bigint::BigUint;
+use rand_0_7::Rng;
+use std::collections::BTreeMap;
+use zokrates_field::Field;
+
+pub struct ConstraintSystem;
+
+impl ConstraintSystem {
+    /// Returns `false` if `a` and `b` were found to accept different witnesses on one of `trials` random arguments
+    pub fn approximately_equivalent<T: Field>(
+        a: &Prog<T>,
+        b: &Prog<T>,
+        rng: &mut impl Rng,
+        trials: usize,
+    ) -> bool {
+        if a.arguments.len() != b.arguments.len() || a.returns.len() != b.returns.len() {
+            return false;
+        }
+
+        let interpreter = Interpreter::default();
+
+        (0..trials).all(|_| {
+            let inputs: Vec<T> = (0..a.arguments.len()).map(|_| random(rng)).collect();
+
+            let honest_a = interpreter
+                .execute(a, &inputs)
+                .ok()
+                .map(|w| w.return_values());
+            let honest_b = interpreter
+                .execute(b, &inputs)
+                .ok()
+                .map(|w| w.return_values());
+
+            honest_a == honest_b
+                && under_constrained(a, &inputs, &honest_a, rng)
+                    == under_constrained(b, &inputs, &honest_b, rng)
+        })
+    }
+}
+
+// whether a random witness extension of `inputs` satisfies the constraints of `prog` and returns values other than
+// `honest`, the values returned by the interpreter if it accepts `inputs`
+fn under_constrained<T: Field>(
+    prog: &Prog<T>,
+    inputs: &[T],
+    honest: &Option<Vec<T>>,
+    rng: &mut impl Rng,
+) -> bool {
+    match (forge(prog, inputs, rng), honest) {
+        (Some(forged), Some(honest)) => forged != *honest,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+// the values returned by `prog` on `inputs` when the outputs of directives are uniformly random, if all constraints are
+// satisfied. Constraints defining a variable set it, like the interpreter does, and all others are checked.
+fn forge<T: Field>(prog: &Prog<T>, inputs: &[T], rng: &mut impl Rng) -> Option<Vec<T>> {
+    let mut witness = BTreeMap::new();
+    witness.insert(FlatVariable::one(), T::one());
+
+    for (argument, value) in prog.arguments.iter().zip(inputs) {
+        witness.insert(argument.id, value.clone());
+    }
+
+    for s in &prog.statements {
+        match s {
+            Statement::Directive(d) => {
+                for o in &d.outputs {
+                    witness.insert(*o, uniform(rng));
+                }
+            }
+            Statement::Constraint(quad, lin, _) => {
+                let value = quad.evaluate(&witness).ok()?;
+
+                match s.assignee(|v| witness.contains_key(v)) {
+                    Some(v) => {
+                        witness.insert(v, value);
+                    }
+                    None if value != lin.evaluate(&witness).ok()? => return None,
+                    None => {}
+                }
+            }
+        }
+    }
+
+    prog.returns
+        .iter()
+        .map(|v| witness.get(v).cloned())
+        .collect()
+}
+
+// a random field element, either uniform or small
+fn random<T: Field>(rng: &mut impl Rng) -> T {
+    match rng.gen::<bool>() {
+        true => T::from(rng.gen_range(0u32, 256)),
+        false => uniform(rng),
+    }
+}
+
+// a uniformly random field element
+fn uniform<T: Field>(rng: &mut impl Rng) -> T {
+    // sample more bits than the field size to make the reduction close to uniform
+    let mut bytes = vec![0u8; T::get_required_bits() / 8 + 16];
+    rng.fill_bytes(&mut bytes);
+
+    T::try_from_dec_str(&BigUint::from_bytes_le(&bytes).to_str_radix(10)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::{FlatParameter, FlatVariable};
+    use crate::ir::{Directive, QuadComb};
+    use crate::solvers::Solver;
+    use rand_0_7::rngs::StdRng;
+    use rand_0_7::SeedableRng;
+    use zokrates_field::Bn128Field;
+
+    // def main(_0) -> (1):
+    //     ~out_0 == _0 * _0 (+ _0 if `add` is set)
+    //     return ~out_0
+    fn square(add: bool) -> Prog<Bn128Field> {
+        let mut out = QuadComb::from_linear_combinations(
+            FlatVariable::new(0).into(),
+            FlatVariable::new(0).into(),
+        );
+
+        if add {
+            out.right = out.right + FlatVariable::one().into();
+        }
+
+        Prog {
+            arguments: vec![FlatParameter::private(FlatVariable::new(0))],
+            statements: vec![Statement::definition(FlatVariable::public(0), out)],
+            returns: vec![FlatVariable::public(0)],
+        }
+    }
+
+    #[test]
+    fn equivalent() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        // an intermediate variable does not change the relation between arguments and outputs
+        let mut b = square(false);
+        b.statements = vec![
+            Statement::definition(
+                FlatVariable::new(1),
+                QuadComb::from_linear_combinations(
+                    FlatVariable::new(0).into(),
+                    FlatVariable::new(0).into(),
+                ),
+            ),
+            Statement::definition(FlatVariable::public(0), FlatVariable::new(1)),
+        ];
+
+        assert!(ConstraintSystem::approximately_equivalent(
+            &square(false),
+            &b,
+            &mut rng,
+            10
+        ));
+    }
+
+    #[test]
+    fn not_equivalent() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        // _0 * _0 and _0 * (_0 + 1) only agree on 0
+        assert!(!ConstraintSystem::approximately_equivalent(
+            &square(false),
+            &square(true),
+            &mut rng,
+            10
+        ));
+    }
+
+    #[test]
+    fn under_constrained_program() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        // def main(_0) -> (1):
+        //     # _1 = Div(_0 * _0, 1)
+        //     ~out_0 == _1
+        //     return ~out_0
+        //
+        // the interpreter computes the same outputs, but nothing constrains `_1`
+        let b = Prog {
+            arguments: vec![FlatParameter::private(FlatVariable::new(0))],
+            statements: vec![
+                Statement::Directive(Directive {
+                    inputs: vec![
+                        QuadComb::from_linear_combinations(
+                            FlatVariable::new(0).into(),
+                            FlatVariable::new(0).into(),
+                        ),
+                        FlatVariable::one().into(),
+                    ],
+                    outputs: vec![FlatVariable::new(1)],
+                    solver: Solver::Div,
+                }),
+                Statement::definition(FlatVariable::public(0), FlatVariable::new(1)),
+            ],
+            returns: vec![FlatVariable::public(0)],
+        };
+
+        assert_eq!(
+            Interpreter::default()
+                .execute(&b, &[Bn128Field::from(3)])
+                .unwrap()
+                .return_values(),
+            vec![Bn128Field::from(9)]
+        );
+
+        assert!(!ConstraintSystem::approximately_equivalent(
+            &square(false),
+            &b,
+            &mut rng,
+            10
+        ));
+    }
+
+    #[test]
+    fn optimizer_preserves_semantics() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let prog = square(true);
+
+        assert!(ConstraintSystem::approximately_equivalent(
+            &prog,
+            &prog.clone().optimize(),
+            &mut rng,
+            10
+        ));
+    }
+}
@@ -197,18 +197,17 @@ impl<T: Field> QuadComb<T> {
 
 impl<T: Field> fmt::Display for LinComb<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self.is_zero() {
-            true => write!(f, "0"),
-            false => write!(
-                f,
-                "{}",
-                self.0
-                    .iter()
-                    .map(|(k, v)| format!("{} * {}", v.to_compact_dec_string(), k))
-                    .collect::<Vec<_>>()
-                    .join(" + ")
-            ),
+        if self.is_zero() {
+            return write!(f, "0");
         }
+
+        for (i, (k, v)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " + ")?;
+            }
+            write!(f, "{} * {}", v.to_compact_dec_string(), k)?;
+        }
+        Ok(())
     }
 }
 
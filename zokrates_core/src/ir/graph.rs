@@ -0,0 +1,298 @@
+//! Module containing the computation graph of a program, whose nodes are its statements and whose edges are the
+//! variables flowing between them
+//!
+//! Variables are defined following the rule of the interpreter: a directive defines its outputs, and a constraint whose
+//! right hand side is a single undefined variable with coefficient one defines that variable. Arguments are defined
+//! by dedicated nodes, and the return values flow to a final node. As a variable is always defined before it is read,
+//! every edge goes from a statement to a later one and the graph is acyclic.
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::{LinComb, Prog, QuadComb, Statement};
+use crate::solvers::Solver;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use zokrates_field::Field;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintKind {
+    /// a constraint defining the single variable on its right hand side
+    Definition,
+    /// a constraint checking variables which are already defined
+    Check,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    /// an argument of the program
+    Argument,
+    /// a directive, labeled by its solver
+    Directive(Solver),
+    /// a constraint
+    Constraint(ConstraintKind),
+    /// the return values of the program
+    Return,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Edge {
+    /// the index of the node defining the variable
+    pub from: usize,
+    /// the index of the node reading the variable
+    pub to: usize,
+    pub variable: FlatVariable,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ComputationGraph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+fn lin_variables<T>(lin: &LinComb<T>) -> impl Iterator<Item = &FlatVariable> {
+    lin.0.iter().map(|(v, _)| v)
+}
+
+fn quad_variables<T>(quad: &QuadComb<T>) -> impl Iterator<Item = &FlatVariable> {
+    lin_variables(&quad.left).chain(lin_variables(&quad.right))
+}
+
+// builds the graph node by node, keeping track of the node defining each variable
+#[derive(Default)]
+struct Builder {
+    graph: ComputationGraph,
+    definitions: HashMap<FlatVariable, usize>,
+}
+
+impl Builder {
+    // adds a node reading `reads`, returning its index
+    fn node<'a>(&mut self, node: Node, reads: impl Iterator<Item = &'a FlatVariable>) -> usize {
+        let index = self.graph.nodes.len();
+        self.graph.nodes.push(node);
+
+        // `~one` is not defined by any node, and a variable read several times only needs one edge
+        let edges: BTreeSet<_> = reads
+            .filter_map(|v| {
+                self.definitions.get(v).map(|from| Edge {
+                    from: *from,
+                    to: index,
+                    variable: *v,
+                })
+            })
+            .collect();
+
+        self.graph.edges.extend(edges);
+
+        index
+    }
+
+    fn define(&mut self, v: FlatVariable, index: usize) {
+        self.definitions.insert(v, index);
+    }
+}
+
+impl<T: Field> Prog<T> {
+    pub fn to_computation_graph(&self) -> ComputationGraph {
+        let mut builder = Builder::default();
+
+        for p in &self.arguments {
+            let index = builder.node(Node::Argument, std::iter::empty());
+            builder.define(p.id, index);
+        }
+
+        for statement in &self.statements {
+            match statement {
+                Statement::Constraint(quad, lin, _) => {
+                    match statement.assignee(|v| builder.definitions.contains_key(v)) {
+                        Some(v) => {
+                            let index = builder.node(
+                                Node::Constraint(ConstraintKind::Definition),
+                                quad_variables(quad),
+                            );
+                            builder.define(v, index);
+                        }
+                        None => {
+                            builder.node(
+                                Node::Constraint(ConstraintKind::Check),
+                                quad_variables(quad).chain(lin_variables(lin)),
+                            );
+                        }
+                    }
+                }
+                Statement::Directive(d) => {
+                    let index = builder.node(
+                        Node::Directive(d.solver.clone()),
+                        d.inputs.iter().flat_map(quad_variables),
+                    );
+                    for o in &d.outputs {
+                        builder.define(*o, index);
+                    }
+                }
+            }
+        }
+
+        builder.node(Node::Return, self.returns.iter());
+
+        builder.graph
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Node::Argument => write!(f, "argument"),
+            Node::Directive(solver) => write!(f, "directive {}", solver),
+            Node::Constraint(ConstraintKind::Definition) => write!(f, "definition"),
+            Node::Constraint(ConstraintKind::Check) => write!(f, "check"),
+            Node::Return => write!(f, "return"),
+        }
+    }
+}
+
+// escapes the characters of `s` which are not allowed in XML character data and attribute values
+fn escape(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&apos;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Displays a computation graph in the GraphML format
+pub struct GraphMLDisplay<'a>(pub &'a ComputationGraph);
+
+impl fmt::Display for GraphMLDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            f,
+            r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+        )?;
+        writeln!(
+            f,
+            r#"  <key id="kind" for="node" attr.name="kind" attr.type="string"/>"#
+        )?;
+        writeln!(
+            f,
+            r#"  <key id="variable" for="edge" attr.name="variable" attr.type="string"/>"#
+        )?;
+        writeln!(f, r#"  <graph id="main" edgedefault="directed">"#)?;
+        for (index, node) in self.0.nodes.iter().enumerate() {
+            writeln!(
+                f,
+                r#"    <node id="n{}"><data key="kind">{}</data></node>"#,
+                index,
+                escape(&node.to_string())
+            )?;
+        }
+        for edge in &self.0.edges {
+            writeln!(
+                f,
+                r#"    <edge source="n{}" target="n{}"><data key="variable">{}</data></edge>"#,
+                edge.from,
+                edge.to,
+                escape(&edge.variable.to_string())
+            )?;
+        }
+        writeln!(f, "  </graph>")?;
+        write!(f, "</graphml>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatParameter;
+    use crate::ir::Directive;
+    use zokrates_field::Bn128Field;
+
+    // def main(_0) -> (1):
+    //     # _1 = Div(_0, ~one)
+    //     _1 * ~one == _0
+    //     ~out_0 == _1 * _1
+    //     return ~out_0
+    fn prog() -> Prog<Bn128Field> {
+        Prog {
+            arguments: vec![FlatParameter::private(FlatVariable::new(0))],
+            statements: vec![
+                Statement::Directive(Directive {
+                    inputs: vec![FlatVariable::new(0).into(), FlatVariable::one().into()],
+                    outputs: vec![FlatVariable::new(1)],
+                    solver: Solver::Div,
+                }),
+                Statement::constraint(FlatVariable::new(1), FlatVariable::new(0)),
+                Statement::definition(
+                    FlatVariable::public(0),
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(1).into(),
+                        FlatVariable::new(1).into(),
+                    ),
+                ),
+            ],
+            returns: vec![FlatVariable::public(0)],
+        }
+    }
+
+    #[test]
+    fn to_computation_graph() {
+        let graph = prog().to_computation_graph();
+
+        assert_eq!(
+            graph.nodes,
+            vec![
+                Node::Argument,
+                Node::Directive(Solver::Div),
+                Node::Constraint(ConstraintKind::Check),
+                Node::Constraint(ConstraintKind::Definition),
+                Node::Return
+            ]
+        );
+
+        let edge = |from, to, variable| Edge { from, to, variable };
+
+        assert_eq!(
+            graph.edges,
+            vec![
+                edge(0, 1, FlatVariable::new(0)),
+                edge(0, 2, FlatVariable::new(0)),
+                edge(1, 2, FlatVariable::new(1)),
+                edge(1, 3, FlatVariable::new(1)),
+                edge(3, 4, FlatVariable::public(0)),
+            ]
+        );
+
+        // every edge goes forward
+        assert!(graph.edges.iter().all(|e| e.from < e.to));
+    }
+
+    #[test]
+    fn graphml() {
+        let graphml = GraphMLDisplay(&prog().to_computation_graph()).to_string();
+
+        assert!(graphml.contains(r#"<node id="n1"><data key="kind">directive Div</data></node>"#));
+        assert!(graphml.contains(
+            r#"<edge source="n3" target="n4"><data key="variable">~out_0</data></edge>"#
+        ));
+        assert!(graphml.ends_with("</graphml>"));
+    }
+
+    #[test]
+    fn graphml_escapes_labels() {
+        let graph = ComputationGraph {
+            nodes: vec![Node::Directive(Solver::AssertEq(String::from(
+                "a < b && c == \"d\"",
+            )))],
+            edges: vec![],
+        };
+
+        let graphml = GraphMLDisplay(&graph).to_string();
+
+        assert!(graphml.contains(
+            r#"<data key="kind">directive AssertEq(&quot;a &lt; b &amp;&amp; c == \&quot;d\&quot;&quot;)</data>"#
+        ));
+    }
+}
@@ -35,6 +35,71 @@ impl Interpreter {
 impl Interpreter {
     pub fn execute<T: Field>(&self, program: &Prog<T>, inputs: &[T]) -> ExecutionResult<T> {
         self.check_inputs(&program, &inputs)?;
+        let mut witness = Self::init_witness(program, inputs);
+
+        for statement in program.statements.iter() {
+            self.execute_statement(statement, &mut witness)?;
+        }
+
+        Ok(Witness(witness))
+    }
+
+    /// Executes the program with placeholder values for the arguments at indices `deferred`, whose actual values are
+    /// only known when proving. The constraints of the statements depending on these arguments are not checked, so the
+    /// resulting witness is not valid until it is completed with `Interpreter::finalize`.
+    pub fn execute_deferred<T: Field>(
+        &self,
+        program: &Prog<T>,
+        inputs: &[T],
+        deferred: &[usize],
+    ) -> ExecutionResult<T> {
+        self.check_inputs(&program, &inputs)?;
+        let slice = program.dependency_slice(&Self::argument_ids(program, deferred));
+        let mut witness = Self::init_witness(program, inputs);
+
+        for (index, statement) in program.statements.iter().enumerate() {
+            match self.execute_statement(statement, &mut witness) {
+                Err(Error::UnsatisfiedConstraint { .. }) | Err(Error::Assertion(..))
+                    if slice.statements.contains(&index) => {}
+                r => r?,
+            }
+        }
+
+        Ok(Witness(witness))
+    }
+
+    /// Completes a witness computed by `Interpreter::execute_deferred`, setting the deferred arguments to `values`,
+    /// given as pairs of an argument index and a value, and executing only the statements which depend on them
+    pub fn finalize<T: Field>(
+        &self,
+        program: &Prog<T>,
+        witness: Witness<T>,
+        values: &[(usize, T)],
+    ) -> ExecutionResult<T> {
+        let indices: Vec<_> = values.iter().map(|(index, _)| *index).collect();
+        let slice = program.dependency_slice(&Self::argument_ids(program, &indices));
+        let mut witness = witness.0;
+
+        for v in &slice.variables {
+            witness.remove(v);
+        }
+
+        for (index, value) in values {
+            witness.insert(program.arguments[*index].id, value.clone());
+        }
+
+        for index in &slice.statements {
+            self.execute_statement(&program.statements[*index], &mut witness)?;
+        }
+
+        Ok(Witness(witness))
+    }
+
+    fn argument_ids<T>(program: &Prog<T>, indices: &[usize]) -> Vec<FlatVariable> {
+        indices.iter().map(|i| program.arguments[*i].id).collect()
+    }
+
+    fn init_witness<T: Field>(program: &Prog<T>, inputs: &[T]) -> BTreeMap<FlatVariable, T> {
         let mut witness = BTreeMap::new();
         witness.insert(FlatVariable::one(), T::one());
 
@@ -42,14 +107,22 @@ impl Interpreter {
             witness.insert(arg.id, value.clone());
         }
 
-        for statement in program.statements.iter() {
-            match statement {
-                Statement::Constraint(quad, lin, message) => match lin.is_assignee(&witness) {
-                    true => {
+        witness
+    }
+
+    fn execute_statement<T: Field>(
+        &self,
+        statement: &Statement<T>,
+        witness: &mut BTreeMap<FlatVariable, T>,
+    ) -> Result<(), Error> {
+        match statement {
+            Statement::Constraint(quad, lin, message) => {
+                match statement.assignee(|v| witness.contains_key(v)) {
+                    Some(v) => {
                         let val = quad.evaluate(&witness).unwrap();
-                        witness.insert(lin.0.get(0).unwrap().0, val);
+                        witness.insert(v, val);
                     }
-                    false => {
+                    None => {
                         let lhs_value = quad.evaluate(&witness).unwrap();
                         let rhs_value = lin.evaluate(&witness).unwrap();
                         if lhs_value != rhs_value {
@@ -63,33 +136,33 @@ impl Interpreter {
                             });
                         }
                     }
-                },
-                Statement::Directive(ref d) => {
-                    let mut inputs: Vec<_> = d
-                        .inputs
-                        .iter()
-                        .map(|i| i.evaluate(&witness).unwrap())
-                        .collect();
-
-                    let res = match (&d.solver, self.should_try_out_of_range) {
-                        (Solver::Bits(bitwidth), true) if *bitwidth >= T::get_required_bits() => {
-                            Ok(Self::try_solve_with_out_of_range_bits(
-                                *bitwidth,
-                                inputs.pop().unwrap(),
-                            ))
-                        }
-                        _ => Self::execute_solver(&d.solver, &inputs),
-                    }
-                    .map_err(|_| Error::Solver)?;
+                }
+            }
+            Statement::Directive(ref d) => {
+                let mut inputs: Vec<_> = d
+                    .inputs
+                    .iter()
+                    .map(|i| i.evaluate(&witness).unwrap())
+                    .collect();
+
+                let res = match (&d.solver, self.should_try_out_of_range) {
+                    (Solver::Bits(bitwidth), true) if *bitwidth >= T::get_required_bits() => Ok(
+                        Self::try_solve_with_out_of_range_bits(*bitwidth, inputs.pop().unwrap()),
+                    ),
+                    _ => Self::execute_solver(&d.solver, &inputs),
+                }
+                .map_err(|message| match d.solver {
+                    Solver::AssertEq(..) | Solver::AssertNe(..) => Error::Assertion(message),
+                    _ => Error::Solver,
+                })?;
 
-                    for (i, o) in d.outputs.iter().enumerate() {
-                        witness.insert(*o, res[i].clone());
-                    }
+                for (i, o) in d.outputs.iter().enumerate() {
+                    witness.insert(*o, res[i].clone());
                 }
             }
         }
 
-        Ok(Witness(witness))
+        Ok(())
     }
 
     fn try_solve_with_out_of_range_bits<T: Field>(bit_width: usize, input: T) -> Vec<T> {
@@ -202,6 +275,20 @@ impl Interpreter {
                 let r = n - d * &q;
                 vec![T::try_from(q).unwrap(), T::try_from(r).unwrap()]
             }
+            Solver::AssertEq(condition) | Solver::AssertNe(condition) => {
+                let equal = matches!(solver, Solver::AssertEq(..));
+
+                if (inputs[0] == inputs[1]) != equal {
+                    return Err(format!(
+                        "assertion failed: {} (left: {}, right: {})",
+                        condition,
+                        inputs[0].to_dec_string(),
+                        inputs[1].to_dec_string()
+                    ));
+                }
+
+                vec![]
+            }
             #[cfg(feature = "bellman")]
             Solver::Sha256Round => {
                 use pairing_ce::bn256::Bn256;
@@ -252,7 +339,7 @@ impl Interpreter {
 pub struct EvaluationError;
 
 impl<T: Field> LinComb<T> {
-    fn evaluate(&self, witness: &BTreeMap<FlatVariable, T>) -> Result<T, EvaluationError> {
+    pub fn evaluate(&self, witness: &BTreeMap<FlatVariable, T>) -> Result<T, EvaluationError> {
         self.0
             .iter()
             .map(|(var, mult)| {
@@ -264,12 +351,6 @@ impl<T: Field> LinComb<T> {
             .collect::<Result<Vec<_>, _>>() // fail if any term isn't found
             .map(|v| v.iter().fold(T::from(0), |acc, t| acc + t)) // return the sum
     }
-
-    fn is_assignee<U>(&self, witness: &BTreeMap<FlatVariable, U>) -> bool {
-        self.0.len() == 1
-            && self.0.get(0).unwrap().1 == T::from(1)
-            && !witness.contains_key(&self.0.get(0).unwrap().0)
-    }
 }
 
 impl<T: Field> QuadComb<T> {
@@ -288,6 +369,8 @@ pub enum Error {
         message: String,
     },
     Solver,
+    /// an `assert_eq!` or `assert_ne!` failed, with a message giving the values of its operands
+    Assertion(String),
     WrongInputCount {
         expected: usize,
         received: usize,
@@ -303,6 +386,7 @@ impl fmt::Display for Error {
                 ref message,
             } => write!(f, "{}: expected {} to equal {}", message, left, right),
             Error::Solver => write!(f, ""),
+            Error::Assertion(ref message) => write!(f, "{}", message),
             Error::WrongInputCount { expected, received } => write!(
                 f,
                 "Program takes {} input{} but was passed {} value{}",
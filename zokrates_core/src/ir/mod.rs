@@ -6,17 +6,29 @@ use std::fmt;
 use std::hash::Hash;
 use zokrates_field::Field;
 
+mod advisor;
+mod batch;
+mod dependency;
+mod display;
+mod equivalence;
 mod expression;
 pub mod folder;
 mod from_flat;
+mod graph;
 mod interpreter;
 mod serialize;
 pub mod smtlib2;
 pub mod visitor;
 mod witness;
 
+pub use self::advisor::{Advisor, Suggestion};
+pub use self::batch::WitnessError;
+pub use self::dependency::DependencySlice;
+pub use self::display::StatementsDisplay;
+pub use self::equivalence::ConstraintSystem;
 pub use self::expression::QuadComb;
 pub use self::expression::{CanonicalLinComb, LinComb};
+pub use self::graph::{ComputationGraph, ConstraintKind, Edge, GraphMLDisplay, Node};
 pub use self::serialize::ProgEnum;
 
 pub use self::interpreter::{Error, ExecutionResult, Interpreter};
@@ -36,6 +48,26 @@ impl<T: Field> Statement<T> {
     pub fn constraint<U: Into<QuadComb<T>>, V: Into<LinComb<T>>>(quad: U, lin: V) -> Self {
         Statement::Constraint(quad.into(), lin.into(), None)
     }
+
+    /// Returns the variable this statement defines, given the variables which are already defined
+    ///
+    /// This is the rule followed by the interpreter: a constraint whose right hand side is a single undefined variable
+    /// with coefficient one defines that variable. Directives define their outputs, which are not returned here.
+    pub fn assignee(&self, is_defined: impl Fn(&FlatVariable) -> bool) -> Option<FlatVariable> {
+        match self {
+            Statement::Constraint(_, lin, _) => match lin.0.as_slice() {
+                [(v, c)] if *c == T::one() && !is_defined(v) => Some(*v),
+                _ => None,
+            },
+            Statement::Directive(..) => None,
+        }
+    }
+
+    /// Returns `true` if this statement was annotated with `#[optimizer(barrier)]` in the source,
+    /// in which case optimizers must keep it verbatim
+    pub fn is_barrier(&self) -> bool {
+        matches!(self, Statement::Constraint(_, _, Some(error)) if error.is_barrier())
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Hash, PartialEq, Eq)]
@@ -47,27 +79,30 @@ pub struct Directive<T> {
 
 impl<T: Field> fmt::Display for Directive<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "# {} = {}({})",
-            self.outputs
-                .iter()
-                .map(|o| format!("{}", o))
-                .collect::<Vec<_>>()
-                .join(", "),
-            self.solver,
-            self.inputs
-                .iter()
-                .map(|i| format!("{}", i))
-                .collect::<Vec<_>>()
-                .join(", ")
-        )
+        write!(f, "# ")?;
+        for (i, o) in self.outputs.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", o)?;
+        }
+        write!(f, " = {}(", self.solver)?;
+        for (i, input) in self.inputs.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", input)?;
+        }
+        write!(f, ")")
     }
 }
 
 impl<T: Field> fmt::Display for Statement<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            Statement::Constraint(ref quad, ref lin, Some(ref error)) if error.is_barrier() => {
+                write!(f, "#[optimizer(barrier)] {} == {}", quad, lin)
+            }
             Statement::Constraint(ref quad, ref lin, _) => write!(f, "{} == {}", quad, lin),
             Statement::Directive(ref s) => write!(f, "{}", s),
         }
@@ -89,6 +124,10 @@ impl<T: Field> Prog<T> {
             .count()
     }
 
+    pub fn barrier_count(&self) -> usize {
+        self.statements.iter().filter(|s| s.is_barrier()).count()
+    }
+
     pub fn arguments_count(&self) -> usize {
         self.arguments.len()
     }
@@ -105,26 +144,29 @@ impl<T: Field> Prog<T> {
 
 impl<T: Field> fmt::Display for Prog<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "def main({}) -> ({}):\n{}\n\treturn {}",
-            self.arguments
-                .iter()
-                .map(|v| format!("{}", v))
-                .collect::<Vec<_>>()
-                .join(", "),
-            self.returns.len(),
-            self.statements
-                .iter()
-                .map(|s| format!("\t{}", s))
-                .collect::<Vec<_>>()
-                .join("\n"),
-            self.returns
-                .iter()
-                .map(|e| format!("{}", e))
-                .collect::<Vec<_>>()
-                .join(", ")
-        )
+        // statements are written one by one, as the whole program may not fit in a single string
+        write!(f, "def main(")?;
+        for (i, v) in self.arguments.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", v)?;
+        }
+        writeln!(f, ") -> ({}):", self.returns.len())?;
+        for (i, s) in self.statements.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "\t{}", s)?;
+        }
+        write!(f, "\n\treturn ")?;
+        for (i, e) in self.returns.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", e)?;
+        }
+        Ok(())
     }
 }
 
@@ -148,5 +190,18 @@ mod tests {
             );
             assert_eq!(format!("{}", c), "(1 * _42) * (1 * _42) == 1 * _42")
         }
+
+        #[test]
+        fn print_barrier() {
+            let c: Statement<Bn128Field> = Statement::Constraint(
+                LinComb::from(FlatVariable::new(42)).into(),
+                FlatVariable::new(21).into(),
+                Some(RuntimeError::SourceBarrier),
+            );
+            assert_eq!(
+                format!("{}", c),
+                "#[optimizer(barrier)] (1 * ~one) * (1 * _42) == 1 * _21"
+            )
+        }
     }
 }
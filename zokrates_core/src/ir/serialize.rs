@@ -4,7 +4,10 @@ use std::io::{Read, Write};
 use zokrates_field::*;
 
 const ZOKRATES_MAGIC: &[u8; 4] = &[0x5a, 0x4f, 0x4b, 0];
+// version 1 predates the `AssertEq` and `AssertNe` solvers, which shifted the encoding of the solvers
+// following them
 const ZOKRATES_VERSION_1: &[u8; 4] = &[0, 0, 0, 1];
+const ZOKRATES_VERSION_2: &[u8; 4] = &[0, 0, 0, 2];
 
 #[derive(PartialEq, Debug)]
 pub enum ProgEnum {
@@ -17,7 +20,7 @@ pub enum ProgEnum {
 impl<T: Field> Prog<T> {
     pub fn serialize<W: Write>(&self, mut w: W) {
         w.write_all(ZOKRATES_MAGIC).unwrap();
-        w.write_all(ZOKRATES_VERSION_1).unwrap();
+        w.write_all(ZOKRATES_VERSION_2).unwrap();
         w.write_all(&T::id()).unwrap();
 
         serialize_into(&mut w, self, Infinite).unwrap();
@@ -32,32 +35,36 @@ impl ProgEnum {
             .map_err(|_| String::from("Cannot read magic number"))?;
 
         if &magic == ZOKRATES_MAGIC {
-            // Check the version, 1
+            // Check the version, 2
             let mut version = [0; 4];
             r.read_exact(&mut version)
                 .map_err(|_| String::from("Cannot read version"))?;
 
-            if &version == ZOKRATES_VERSION_1 {
+            if &version == ZOKRATES_VERSION_2 {
                 // Check the curve identifier, deserializing accordingly
                 let mut curve = [0; 4];
                 r.read_exact(&mut curve)
                     .map_err(|_| String::from("Cannot read curve identifier"))?;
 
                 match curve {
-                    m if m == Bls12_381Field::id() => Ok(ProgEnum::Bls12_381Program(
-                        deserialize_from(&mut r, Infinite).unwrap(),
-                    )),
-                    m if m == Bn128Field::id() => Ok(ProgEnum::Bn128Program(
-                        deserialize_from(&mut r, Infinite).unwrap(),
-                    )),
-                    m if m == Bls12_377Field::id() => Ok(ProgEnum::Bls12_377Program(
-                        deserialize_from(&mut r, Infinite).unwrap(),
-                    )),
-                    m if m == Bw6_761Field::id() => Ok(ProgEnum::Bw6_761Program(
-                        deserialize_from(&mut r, Infinite).unwrap(),
-                    )),
+                    m if m == Bls12_381Field::id() => {
+                        deserialize_program(&mut r).map(ProgEnum::Bls12_381Program)
+                    }
+                    m if m == Bn128Field::id() => {
+                        deserialize_program(&mut r).map(ProgEnum::Bn128Program)
+                    }
+                    m if m == Bls12_377Field::id() => {
+                        deserialize_program(&mut r).map(ProgEnum::Bls12_377Program)
+                    }
+                    m if m == Bw6_761Field::id() => {
+                        deserialize_program(&mut r).map(ProgEnum::Bw6_761Program)
+                    }
                     _ => Err(String::from("Unknown curve identifier")),
                 }
+            } else if &version == ZOKRATES_VERSION_1 {
+                Err(String::from(
+                    "Version 1 of the program format is not supported anymore, please recompile the program",
+                ))
             } else {
                 Err(String::from("Unknown version"))
             }
@@ -67,6 +74,10 @@ impl ProgEnum {
     }
 }
 
+fn deserialize_program<T: Field, R: Read>(r: &mut R) -> Result<Prog<T>, String> {
+    deserialize_from(r, Infinite).map_err(|e| format!("Cannot deserialize program: {}", e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,7 +86,7 @@ mod tests {
     use zokrates_field::{Bls12_381Field, Bn128Field};
 
     #[test]
-    fn ser_deser_v1() {
+    fn ser_deser_v2() {
         let p: ir::Prog<Bn128Field> = ir::Prog::default();
 
         let mut buffer = Cursor::new(vec![]);
@@ -102,4 +113,251 @@ mod tests {
 
         assert_eq!(ProgEnum::Bls12_381Program(p), deserialized_p);
     }
+
+    #[test]
+    fn truncated() {
+        let mut buffer = Cursor::new(vec![]);
+        ir::Prog::<Bn128Field>::default().serialize(&mut buffer);
+
+        let truncated = &buffer.get_ref()[..16];
+
+        assert!(ProgEnum::deserialize(truncated)
+            .unwrap_err()
+            .starts_with("Cannot deserialize program"));
+    }
+
+    #[test]
+    fn reject_v1() {
+        let mut buffer = Cursor::new(vec![]);
+        ir::Prog::<Bn128Field>::default().serialize(&mut buffer);
+
+        // overwrite the version
+        buffer.get_mut()[4..8].copy_from_slice(ZOKRATES_VERSION_1);
+
+        buffer.seek(SeekFrom::Start(0)).unwrap();
+
+        assert_eq!(
+            ProgEnum::deserialize(buffer),
+            Err(String::from(
+                "Version 1 of the program format is not supported anymore, please recompile the program"
+            ))
+        );
+    }
+
+    // `golden_program.out` is checked in, so that changes to the encoding of existing programs, such as inserting a
+    // solver before others, fail this test rather than silently decoding old programs to different ones
+    #[test]
+    #[cfg(all(feature = "bellman", feature = "ark"))]
+    fn golden_program() {
+        use crate::flat_absy::{FlatParameter, FlatVariable};
+        use crate::ir::{Directive, Statement};
+        use crate::solvers::Solver;
+
+        let expected: ir::Prog<Bn128Field> = ir::Prog {
+            statements: vec![
+                Solver::ConditionEq,
+                Solver::Bits(8),
+                Solver::Div,
+                Solver::Xor,
+                Solver::Or,
+                Solver::ShaAndXorAndXorAnd,
+                Solver::ShaCh,
+                Solver::EuclideanDiv,
+                Solver::AssertEq(String::from("a == b")),
+                Solver::AssertNe(String::from("a != b")),
+                Solver::Sha256Round,
+                Solver::SnarkVerifyBls12377(1),
+            ]
+            .into_iter()
+            .map(|solver| {
+                Statement::Directive(Directive {
+                    inputs: vec![],
+                    outputs: vec![],
+                    solver,
+                })
+            })
+            .collect(),
+            arguments: vec![FlatParameter::private(FlatVariable::new(0))],
+            returns: vec![FlatVariable::public(0)],
+        };
+
+        let golden = &include_bytes!("golden_program.out")[..];
+
+        assert_eq!(
+            ProgEnum::deserialize(golden).unwrap(),
+            ProgEnum::Bn128Program(expected.clone())
+        );
+
+        let mut buffer = vec![];
+        expected.serialize(&mut buffer);
+        assert_eq!(buffer, golden);
+    }
+
+    mod round_trip {
+        use super::*;
+        use crate::flat_absy::{FlatParameter, FlatVariable, RuntimeError};
+        use crate::ir::{Directive, Interpreter, LinComb, QuadComb, Statement, Witness};
+        use crate::solvers::Solver;
+        use proptest::collection::vec;
+        use proptest::option;
+        use proptest::prelude::*;
+        use proptest::sample::select;
+
+        fn round_trip(p: &ir::Prog<Bn128Field>) -> ir::Prog<Bn128Field> {
+            let mut buffer = Cursor::new(vec![]);
+            p.serialize(&mut buffer);
+
+            buffer.seek(SeekFrom::Start(0)).unwrap();
+
+            match ProgEnum::deserialize(buffer).unwrap() {
+                ProgEnum::Bn128Program(p) => p,
+                _ => unreachable!(),
+            }
+        }
+
+        // whether all constraints of `p` hold for `witness`
+        fn is_satisfied(p: &ir::Prog<Bn128Field>, witness: &Witness<Bn128Field>) -> bool {
+            p.statements.iter().all(|s| match s {
+                Statement::Constraint(quad, lin, _) => {
+                    quad.evaluate(&witness.0).unwrap() == lin.evaluate(&witness.0).unwrap()
+                }
+                Statement::Directive(..) => true,
+            })
+        }
+
+        fn field_element() -> impl Strategy<Value = Bn128Field> {
+            prop_oneof![
+                any::<u128>().prop_map(Bn128Field::from),
+                any::<i32>().prop_map(Bn128Field::from),
+            ]
+        }
+
+        fn variable() -> impl Strategy<Value = FlatVariable> {
+            prop_oneof![
+                Just(FlatVariable::one()),
+                (0..16_usize).prop_map(FlatVariable::new),
+                (0..4_usize).prop_map(FlatVariable::public),
+            ]
+        }
+
+        fn lin_comb() -> impl Strategy<Value = LinComb<Bn128Field>> {
+            vec((variable(), field_element()), 0..4).prop_map(LinComb)
+        }
+
+        fn quad_comb() -> impl Strategy<Value = QuadComb<Bn128Field>> {
+            (lin_comb(), lin_comb())
+                .prop_map(|(left, right)| QuadComb::from_linear_combinations(left, right))
+        }
+
+        fn statement() -> impl Strategy<Value = Statement<Bn128Field>> {
+            prop_oneof![
+                (
+                    quad_comb(),
+                    lin_comb(),
+                    option::of(select(vec![
+                        RuntimeError::Sum,
+                        RuntimeError::Euclidean,
+                        RuntimeError::Source,
+                        RuntimeError::SourceBarrier,
+                    ]))
+                )
+                    .prop_map(|(quad, lin, error)| Statement::Constraint(quad, lin, error)),
+                (
+                    vec(quad_comb(), 0..3),
+                    vec(variable(), 0..3),
+                    select(Solver::registry())
+                )
+                    .prop_map(|(inputs, outputs, solver)| Statement::Directive(
+                        Directive {
+                            inputs,
+                            outputs,
+                            solver
+                        }
+                    )),
+            ]
+        }
+
+        fn program() -> impl Strategy<Value = ir::Prog<Bn128Field>> {
+            (
+                vec((variable(), any::<bool>()), 0..4),
+                vec(statement(), 0..16),
+                vec(variable(), 0..3),
+            )
+                .prop_map(|(arguments, statements, returns)| ir::Prog {
+                    arguments: arguments
+                        .into_iter()
+                        .map(|(id, private)| FlatParameter { id, private })
+                        .collect(),
+                    statements,
+                    returns,
+                })
+        }
+
+        // a program defining each new variable as the product of two linear combinations of the previous ones, and
+        // returning the last one, along with inputs for it
+        fn satisfiable_program() -> impl Strategy<Value = (ir::Prog<Bn128Field>, Vec<Bn128Field>)> {
+            let lin_comb_indices = || vec((any::<usize>(), field_element()), 1..4);
+
+            (
+                vec(field_element(), 1..4),
+                vec((lin_comb_indices(), lin_comb_indices()), 0..8),
+            )
+                .prop_map(|(inputs, definitions)| {
+                    let arguments_count = inputs.len();
+
+                    // only the variables defined before a definition can appear in it
+                    let lin_comb = |indices: Vec<(usize, Bn128Field)>, defined: usize| {
+                        LinComb(
+                            indices
+                                .into_iter()
+                                .map(|(i, c)| (FlatVariable::new(i % defined), c))
+                                .collect(),
+                        )
+                    };
+
+                    let mut statements: Vec<_> = definitions
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, (left, right))| {
+                            let defined = arguments_count + i;
+                            Statement::definition(
+                                FlatVariable::new(defined),
+                                QuadComb::from_linear_combinations(
+                                    lin_comb(left, defined),
+                                    lin_comb(right, defined),
+                                ),
+                            )
+                        })
+                        .collect();
+
+                    let last = FlatVariable::new(arguments_count + statements.len() - 1);
+                    statements.push(Statement::definition(FlatVariable::public(0), last));
+
+                    let p = ir::Prog {
+                        arguments: (0..arguments_count)
+                            .map(|i| FlatParameter::private(FlatVariable::new(i)))
+                            .collect(),
+                        statements,
+                        returns: vec![FlatVariable::public(0)],
+                    };
+
+                    (p, inputs)
+                })
+        }
+
+        proptest! {
+            #[test]
+            fn deserialized_program_is_equal(p in program()) {
+                prop_assert_eq!(round_trip(&p), p);
+            }
+
+            #[test]
+            fn deserialized_program_is_satisfied((p, inputs) in satisfiable_program()) {
+                let witness = Interpreter::default().execute(&p, &inputs).unwrap();
+
+                prop_assert!(is_satisfied(&p, &witness));
+                prop_assert!(is_satisfied(&round_trip(&p), &witness));
+            }
+        }
+    }
 }
@@ -5,6 +5,9 @@ use std::io;
 use std::io::{Read, Write};
 use zokrates_field::Field;
 
+/// The keyword of the first line of a pending witness, followed by the deferred inputs it was computed without
+const DEFERRED: &str = "deferred";
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Witness<T>(pub BTreeMap<FlatVariable, T>);
 
@@ -38,12 +41,22 @@ impl<T: Field> Witness<T> {
     }
 
     pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.write_pending(&[], writer)
+    }
+
+    /// Writes a witness computed with placeholders for the `deferred` inputs. It is refused by `read` until
+    /// it is written again without them
+    pub fn write_pending<W: Write>(&self, deferred: &[String], writer: W) -> io::Result<()> {
         let mut wtr = csv::WriterBuilder::new()
             .delimiter(b' ')
             .flexible(true)
             .has_headers(false)
             .from_writer(writer);
 
+        if !deferred.is_empty() {
+            wtr.write_record(std::iter::once(DEFERRED).chain(deferred.iter().map(|i| i.as_str())))?;
+        }
+
         // Write each line of the witness to the file
         for (variable, value) in &self.0 {
             wtr.serialize((variable.to_string(), value.to_dec_string()))?;
@@ -52,40 +65,66 @@ impl<T: Field> Witness<T> {
         Ok(())
     }
 
-    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+    pub fn read<R: Read>(reader: R) -> io::Result<Self> {
+        let (witness, deferred) = Self::read_pending(reader)?;
+
+        match deferred.is_empty() {
+            true => Ok(witness),
+            false => Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!(
+                    "Witness depends on deferred inputs which were not set: {}",
+                    deferred.join(", ")
+                ),
+            )),
+        }
+    }
+
+    /// Reads a witness which may be pending, along with the deferred inputs it was computed without
+    pub fn read_pending<R: Read>(mut reader: R) -> io::Result<(Self, Vec<String>)> {
         let mut rdr = csv::ReaderBuilder::new()
             .delimiter(b' ')
             .flexible(true)
             .has_headers(false)
             .from_reader(&mut reader);
 
-        let map = rdr
-            .deserialize::<(String, String)>()
-            .map(|r| {
-                r.map(|(variable, value)| {
-                    let variable =
-                        FlatVariable::try_from_human_readable(&variable).map_err(|why| {
-                            io::Error::new(
-                                io::ErrorKind::Other,
-                                format!("Invalid variable in witness: {}", why),
-                            )
-                        })?;
-                    let value = T::try_from_dec_str(&value).map_err(|_| {
-                        io::Error::new(
-                            io::ErrorKind::Other,
-                            format!("Invalid value in witness: {}", value),
-                        )
-                    })?;
-                    Ok((variable, value))
-                })
-                .map_err(|e| match e.into_kind() {
-                    csv::ErrorKind::Io(e) => e,
-                    e => io::Error::new(io::ErrorKind::Other, format!("{:?}", e)),
-                })?
-            })
-            .collect::<io::Result<BTreeMap<FlatVariable, T>>>()?;
+        let csv_error = |e: csv::Error| match e.into_kind() {
+            csv::ErrorKind::Io(e) => e,
+            e => io::Error::new(io::ErrorKind::Other, format!("{:?}", e)),
+        };
+
+        let mut deferred = vec![];
+        let mut map = BTreeMap::new();
+
+        for (index, record) in rdr.records().enumerate() {
+            let record = record.map_err(csv_error)?;
+
+            if index == 0 && record.get(0) == Some(DEFERRED) {
+                deferred = record.iter().skip(1).map(String::from).collect();
+                continue;
+            }
+
+            let (variable, value) = record
+                .deserialize::<(String, String)>(None)
+                .map_err(csv_error)?;
+
+            let variable = FlatVariable::try_from_human_readable(&variable).map_err(|why| {
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("Invalid variable in witness: {}", why),
+                )
+            })?;
+            let value = T::try_from_dec_str(&value).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("Invalid value in witness: {}", value),
+                )
+            })?;
+
+            map.insert(variable, value);
+        }
 
-        Ok(Witness(map))
+        Ok((Witness(map), deferred))
     }
 }
 
@@ -134,6 +173,33 @@ mod tests {
             assert_eq!(w, r);
         }
 
+        #[test]
+        fn pending() {
+            let w = Witness(
+                vec![
+                    (FlatVariable::new(42), Bn128Field::from(42)),
+                    (FlatVariable::one(), Bn128Field::from(1)),
+                ]
+                .into_iter()
+                .collect(),
+            );
+
+            let deferred = vec!["block_hash".to_string()];
+
+            let mut buff = Cursor::new(vec![]);
+
+            w.write_pending(&deferred, &mut buff).unwrap();
+            buff.set_position(0);
+
+            let (r, d) = Witness::read_pending(buff.clone()).unwrap();
+
+            assert_eq!(w, r);
+            assert_eq!(d, deferred);
+
+            // a pending witness is refused until its deferred inputs are set
+            assert!(Witness::<Bn128Field>::read(buff).is_err());
+        }
+
         #[test]
         fn wrong_value() {
             let mut buff = Cursor::new(vec![]);
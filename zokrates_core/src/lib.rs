@@ -20,7 +20,9 @@ mod static_analysis;
 mod zir;
 
 pub mod absy;
+pub mod attestation;
 pub mod compile;
+pub mod emit;
 pub mod flat_absy;
 pub mod ir;
 pub mod proof_system;
@@ -12,9 +12,11 @@
 use crate::flat_absy::flat_variable::FlatVariable;
 use crate::ir::folder::*;
 use crate::ir::*;
+use crate::optimizer::barrier_variables;
 use crate::optimizer::canonicalizer::Canonicalizer;
 use crate::solvers::Solver;
 use std::collections::hash_map::{Entry, HashMap};
+use std::collections::HashSet;
 use zokrates_field::Field;
 
 #[derive(Debug)]
@@ -22,6 +24,8 @@ pub struct DirectiveOptimizer<T: Field> {
     calls: HashMap<(Solver, Vec<QuadComb<T>>), Vec<FlatVariable>>,
     /// Map of renamings for reassigned variables while processing the program.
     substitution: HashMap<FlatVariable, FlatVariable>,
+    /// Set of variables used in barriers, whose directives must be kept
+    barrier: HashSet<FlatVariable>,
 }
 
 impl<T: Field> DirectiveOptimizer<T> {
@@ -29,6 +33,7 @@ impl<T: Field> DirectiveOptimizer<T> {
         DirectiveOptimizer {
             calls: HashMap::new(),
             substitution: HashMap::new(),
+            barrier: HashSet::new(),
         }
     }
 
@@ -39,6 +44,8 @@ impl<T: Field> DirectiveOptimizer<T> {
 
 impl<T: Field> Folder<T> for DirectiveOptimizer<T> {
     fn fold_module(&mut self, p: Prog<T>) -> Prog<T> {
+        self.barrier = barrier_variables(&p);
+
         // in order to correctly identify duplicates, we need to first canonicalize the statements
 
         let mut canonicalizer = Canonicalizer;
@@ -47,7 +54,10 @@ impl<T: Field> Folder<T> for DirectiveOptimizer<T> {
             statements: p
                 .statements
                 .into_iter()
-                .flat_map(|s| canonicalizer.fold_statement(s))
+                .flat_map(|s| match s.is_barrier() {
+                    true => vec![s],
+                    false => canonicalizer.fold_statement(s),
+                })
                 .collect(),
             ..p
         };
@@ -61,9 +71,15 @@ impl<T: Field> Folder<T> for DirectiveOptimizer<T> {
 
     fn fold_statement(&mut self, s: Statement<T>) -> Vec<Statement<T>> {
         match s {
+            s if s.is_barrier() => vec![s],
             Statement::Directive(d) => {
                 let d = self.fold_directive(d);
 
+                // outputs used in barriers cannot be renamed, so the directive has to stay
+                if d.outputs.iter().any(|o| self.barrier.contains(o)) {
+                    return vec![Statement::Directive(d)];
+                }
+
                 match self.calls.entry((d.solver.clone(), d.inputs.clone())) {
                     Entry::Vacant(e) => {
                         e.insert(d.outputs.clone());
@@ -42,7 +42,10 @@ impl<T: Field> Folder<T> for DuplicateOptimizer {
             statements: p
                 .statements
                 .into_iter()
-                .flat_map(|s| canonicalizer.fold_statement(s))
+                .flat_map(|s| match s.is_barrier() {
+                    true => vec![s],
+                    false => canonicalizer.fold_statement(s),
+                })
                 .collect(),
             ..p
         };
@@ -51,6 +54,11 @@ impl<T: Field> Folder<T> for DuplicateOptimizer {
     }
 
     fn fold_statement(&mut self, s: Statement<T>) -> Vec<Statement<T>> {
+        // barriers are never merged with other statements
+        if s.is_barrier() {
+            return vec![s];
+        }
+
         let hashed = hash(&s);
         let result = match self.seen.get(&hashed) {
             Some(_) => vec![],
@@ -65,7 +73,7 @@ impl<T: Field> Folder<T> for DuplicateOptimizer {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::flat_absy::FlatVariable;
+    use crate::flat_absy::{FlatVariable, RuntimeError};
     use zokrates_field::Bn128Field;
 
     #[test]
@@ -141,4 +149,43 @@ mod tests {
 
         assert_eq!(DuplicateOptimizer::optimize(p), expected);
     }
+
+    #[test]
+    fn keep_barriers() {
+        let constraint = Statement::constraint(
+            QuadComb::from_linear_combinations(
+                LinComb::summand(3, FlatVariable::new(3)),
+                LinComb::summand(3, FlatVariable::new(3)),
+            ),
+            LinComb::one(),
+        );
+
+        let barrier = Statement::Constraint(
+            QuadComb::from_linear_combinations(
+                LinComb::summand(3, FlatVariable::new(3)),
+                LinComb::summand(3, FlatVariable::new(3)),
+            ),
+            LinComb::one(),
+            Some(RuntimeError::SourceBarrier),
+        );
+
+        let p: Prog<Bn128Field> = Prog {
+            statements: vec![
+                constraint.clone(),
+                barrier.clone(),
+                barrier.clone(),
+                constraint.clone(),
+            ],
+            returns: vec![],
+            arguments: vec![],
+        };
+
+        let expected = Prog {
+            statements: vec![constraint, barrier.clone(), barrier],
+            returns: vec![],
+            arguments: vec![],
+        };
+
+        assert_eq!(DuplicateOptimizer::optimize(p), expected);
+    }
 }
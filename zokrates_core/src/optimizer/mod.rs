@@ -8,43 +8,44 @@ mod canonicalizer;
 mod directive;
 mod duplicate;
 mod redefinition;
+mod schedule;
 mod tautology;
+mod zir_duplicate;
 
-use self::directive::DirectiveOptimizer;
-use self::duplicate::DuplicateOptimizer;
-use self::redefinition::RedefinitionOptimizer;
-use self::tautology::TautologyOptimizer;
+pub use self::schedule::{OptimizerPass, OptimizerSchedule};
+pub use self::tautology::TautologyOptimizer;
+pub use self::zir_duplicate::ZirDuplicateOptimizer;
 
-use crate::ir::Prog;
+use crate::flat_absy::FlatVariable;
+use crate::ir::{Prog, Statement};
+use std::collections::HashSet;
 use zokrates_field::Field;
 
+/// Returns the variables appearing in barrier constraints, which must not be substituted away
+pub(crate) fn barrier_variables<T: Field>(p: &Prog<T>) -> HashSet<FlatVariable> {
+    p.statements
+        .iter()
+        .filter(|s| s.is_barrier())
+        .flat_map(|s| match s {
+            Statement::Constraint(quad, lin, _) => quad
+                .left
+                .0
+                .iter()
+                .chain(quad.right.0.iter())
+                .chain(lin.0.iter())
+                .map(|(v, _)| *v)
+                .collect::<Vec<_>>(),
+            Statement::Directive(..) => unreachable!(),
+        })
+        .collect()
+}
+
 impl<T: Field> Prog<T> {
+    /// Optimizes the program with the default schedule
     pub fn optimize(self) -> Self {
-        // remove redefinitions
-        log::debug!("Constraints: {}", self.constraint_count());
-        log::debug!("Optimizer: Remove redefinitions");
-        let r = RedefinitionOptimizer::optimize(self);
-        log::debug!("Done");
-
-        // remove constraints that are always satisfied
-        log::debug!("Constraints: {}", r.constraint_count());
-        log::debug!("Optimizer: Remove tautologies");
-        let r = TautologyOptimizer::optimize(r);
-        log::debug!("Done");
-
-        // deduplicate directives which take the same input
-        log::debug!("Constraints: {}", r.constraint_count());
-        log::debug!("Optimizer: Remove duplicate directive");
-        let r = DirectiveOptimizer::optimize(r);
-        log::debug!("Done");
-
-        // remove duplicate constraints
-        log::debug!("Constraints: {}", r.constraint_count());
-        log::debug!("Optimizer: Remove duplicate constraints");
-        let r = DuplicateOptimizer::optimize(r);
-        log::debug!("Done");
-
-        log::debug!("Constraints: {}", r.constraint_count());
+        log::debug!("Optimizer barriers: {}", self.barrier_count());
+        let r = self.optimize_with(&OptimizerSchedule::default());
+        log::debug!("Optimizer barriers kept: {}", r.barrier_count());
         r
     }
 }
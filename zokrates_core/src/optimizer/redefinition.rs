@@ -36,11 +36,17 @@
 //     - `q == k * v if v isn't in i`: insert `v` into `i` and return `c_0`
 //     - otherwise return `c_0`
 
+// ## Barriers
+
+// Constraints annotated with `#[optimizer(barrier)]` are returned unchanged. The variables they use are inserted into `i`,
+// and directives defining any of them are kept even if their inputs are constant.
+
 use crate::flat_absy::flat_variable::FlatVariable;
 use crate::flat_absy::FlatParameter;
 use crate::ir::folder::{fold_module, Folder};
 use crate::ir::LinComb;
 use crate::ir::*;
+use crate::optimizer::barrier_variables;
 use std::collections::{HashMap, HashSet};
 use zokrates_field::Field;
 
@@ -50,6 +56,8 @@ pub struct RedefinitionOptimizer<T: Field> {
     substitution: HashMap<FlatVariable, CanonicalLinComb<T>>,
     /// Set of variables that should not be substituted
     ignore: HashSet<FlatVariable>,
+    /// Set of variables used in barriers
+    barrier: HashSet<FlatVariable>,
 }
 
 impl<T: Field> RedefinitionOptimizer<T> {
@@ -57,6 +65,7 @@ impl<T: Field> RedefinitionOptimizer<T> {
         RedefinitionOptimizer {
             substitution: HashMap::new(),
             ignore: HashSet::new(),
+            barrier: HashSet::new(),
         }
     }
 
@@ -73,6 +82,10 @@ impl<T: Field> Folder<T> for RedefinitionOptimizer<T> {
         // to prevent the optimiser from replacing ~one, add it to the ignored set
         self.ignore.insert(FlatVariable::one());
 
+        // to prevent the optimiser from replacing variables used in barriers, add them to the ignored set
+        self.barrier = barrier_variables(&p);
+        self.ignore.extend(self.barrier.iter().cloned());
+
         fold_module(self, p)
     }
 
@@ -84,6 +97,7 @@ impl<T: Field> Folder<T> for RedefinitionOptimizer<T> {
 
     fn fold_statement(&mut self, s: Statement<T>) -> Vec<Statement<T>> {
         match s {
+            s if s.is_barrier() => vec![s],
             Statement::Constraint(quad, lin, message) => {
                 let quad = self.fold_quadratic_combination(quad);
                 let lin = self.fold_linear_combination(lin);
@@ -152,20 +166,35 @@ impl<T: Field> Folder<T> for RedefinitionOptimizer<T> {
                     })
                     .collect::<Vec<Result<T, QuadComb<T>>>>();
 
-                match inputs.iter().all(|i| i.is_ok()) {
+                let is_barrier = d.outputs.iter().any(|o| self.barrier.contains(o));
+
+                match inputs.iter().all(|i| i.is_ok()) && !is_barrier {
                     true => {
                         // unwrap inputs to their constant value
                         let inputs: Vec<_> = inputs.into_iter().map(|i| i.unwrap()).collect();
                         // run the solver
-                        let outputs = Interpreter::execute_solver(&d.solver, &inputs).unwrap();
-                        assert_eq!(outputs.len(), d.outputs.len());
-
-                        // insert the results in the substitution
-                        for (output, value) in d.outputs.into_iter().zip(outputs.into_iter()) {
-                            self.substitution
-                                .insert(output, LinComb::from(value).into_canonical());
+                        match Interpreter::execute_solver(&d.solver, &inputs) {
+                            Ok(outputs) => {
+                                assert_eq!(outputs.len(), d.outputs.len());
+
+                                // insert the results in the substitution
+                                for (output, value) in
+                                    d.outputs.into_iter().zip(outputs.into_iter())
+                                {
+                                    self.substitution
+                                        .insert(output, LinComb::from(value).into_canonical());
+                                }
+                                vec![]
+                            }
+                            // a failing assertion is kept, so that it is reported when computing the witness
+                            Err(_) => vec![Statement::Directive(Directive {
+                                inputs: inputs
+                                    .into_iter()
+                                    .map(|v| LinComb::summand(v, FlatVariable::one()).into())
+                                    .collect(),
+                                ..d
+                            })],
                         }
-                        vec![]
                     }
                     false => {
                         //reconstruct the input expressions
@@ -213,7 +242,7 @@ impl<T: Field> Folder<T> for RedefinitionOptimizer<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::flat_absy::FlatParameter;
+    use crate::flat_absy::{FlatParameter, RuntimeError};
     use zokrates_field::Bn128Field;
 
     #[test]
@@ -305,6 +334,36 @@ mod tests {
         assert_eq!(optimizer.fold_module(p), optimized);
     }
 
+    #[test]
+    fn keep_barrier() {
+        // def main(x) -> (1):
+        //    y = x
+        //    z = y
+        //    #[optimizer(barrier)] z == y
+        //    return z
+
+        // y and z are used in a barrier, so nothing is removed
+
+        let x = FlatParameter::public(FlatVariable::new(0));
+        let y = FlatVariable::new(1);
+        let z = FlatVariable::new(2);
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![x],
+            statements: vec![
+                Statement::definition(y, x.id),
+                Statement::definition(z, y),
+                Statement::Constraint(z.into(), y.into(), Some(RuntimeError::SourceBarrier)),
+            ],
+            returns: vec![z],
+        };
+
+        let optimized = p.clone();
+
+        let mut optimizer = RedefinitionOptimizer::new();
+        assert_eq!(optimizer.fold_module(p), optimized);
+    }
+
     #[test]
     fn remove_multiple_synonyms() {
         // def main(x) -> (2):
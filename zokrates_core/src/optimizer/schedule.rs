@@ -0,0 +1,270 @@
+//! Module containing optimizer schedules, the ordered lists of passes run by the optimizer
+//!
+//! The default schedule is the one used by `Prog::optimize`. `Prog::optimize_tuned` tries a bounded set of
+//! alternative schedules and keeps the best result, which can then be replayed with `Prog::optimize_with`.
+
+use super::directive::DirectiveOptimizer;
+use super::duplicate::DuplicateOptimizer;
+use super::redefinition::RedefinitionOptimizer;
+use super::tautology::TautologyOptimizer;
+use crate::ir::{Prog, Statement};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use zokrates_field::Field;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptimizerPass {
+    /// remove redefinitions
+    Redefinition,
+    /// remove constraints that are always satisfied
+    Tautology,
+    /// deduplicate directives which take the same input
+    Directive,
+    /// remove duplicate constraints
+    Duplicate,
+}
+
+use self::OptimizerPass::*;
+
+impl OptimizerPass {
+    fn name(&self) -> &'static str {
+        match self {
+            Redefinition => "redefinition",
+            Tautology => "tautology",
+            Directive => "directive",
+            Duplicate => "duplicate",
+        }
+    }
+
+    fn apply<T: Field>(self, p: Prog<T>) -> Prog<T> {
+        log::debug!("Optimizer: Run {} pass", self);
+        let p = match self {
+            Redefinition => RedefinitionOptimizer::optimize(p),
+            Tautology => TautologyOptimizer::optimize(p),
+            Directive => DirectiveOptimizer::optimize(p),
+            Duplicate => DuplicateOptimizer::optimize(p),
+        };
+        log::debug!("Constraints: {}", p.constraint_count());
+        p
+    }
+}
+
+impl fmt::Display for OptimizerPass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for OptimizerPass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Redefinition, Tautology, Directive, Duplicate]
+            .iter()
+            .find(|p| p.name() == s)
+            .cloned()
+            .ok_or_else(|| {
+                format!(
+                    "Unknown optimizer pass `{}`, expected one of redefinition, tautology, directive, duplicate",
+                    s
+                )
+            })
+    }
+}
+
+/// An ordered list of optimizer passes, written as a comma-separated list of pass names
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OptimizerSchedule(pub Vec<OptimizerPass>);
+
+impl Default for OptimizerSchedule {
+    fn default() -> Self {
+        OptimizerSchedule(vec![Redefinition, Tautology, Directive, Duplicate])
+    }
+}
+
+impl OptimizerSchedule {
+    /// The schedules tried by the tuner, starting with the default one
+    pub fn candidates() -> Vec<Self> {
+        vec![
+            OptimizerSchedule::default(),
+            OptimizerSchedule(vec![Duplicate, Redefinition, Tautology, Directive]),
+            OptimizerSchedule(vec![
+                Redefinition,
+                Duplicate,
+                Tautology,
+                Directive,
+                Duplicate,
+            ]),
+            OptimizerSchedule(vec![Directive, Duplicate, Redefinition, Tautology]),
+            OptimizerSchedule(vec![
+                Redefinition,
+                Tautology,
+                Directive,
+                Duplicate,
+                Redefinition,
+            ]),
+            OptimizerSchedule(vec![
+                Tautology,
+                Duplicate,
+                Redefinition,
+                Tautology,
+                Directive,
+                Duplicate,
+            ]),
+            OptimizerSchedule(vec![
+                Redefinition,
+                Tautology,
+                Directive,
+                Duplicate,
+                Redefinition,
+                Tautology,
+                Duplicate,
+            ]),
+            OptimizerSchedule(vec![
+                Redefinition,
+                Tautology,
+                Directive,
+                Duplicate,
+                Redefinition,
+                Tautology,
+                Directive,
+                Duplicate,
+            ]),
+        ]
+    }
+}
+
+impl fmt::Display for OptimizerSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.0
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+}
+
+impl FromStr for OptimizerSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|p| p.trim().parse())
+            .collect::<Result<_, _>>()
+            .map(OptimizerSchedule)
+    }
+}
+
+/// The number of terms in the constraints of `p`, used to break ties between schedules
+fn density<T: Field>(p: &Prog<T>) -> usize {
+    p.statements
+        .iter()
+        .map(|s| match s {
+            Statement::Constraint(quad, lin, _) => {
+                quad.left.0.len() + quad.right.0.len() + lin.0.len()
+            }
+            Statement::Directive(..) => 0,
+        })
+        .sum()
+}
+
+impl<T: Field> Prog<T> {
+    /// Optimizes the program by running the passes of `schedule` in order
+    pub fn optimize_with(self, schedule: &OptimizerSchedule) -> Self {
+        log::debug!("Optimizer schedule: {}", schedule);
+        log::debug!("Constraints: {}", self.constraint_count());
+        schedule.0.iter().fold(self, |p, pass| pass.apply(p))
+    }
+
+    /// Optimizes the program with each candidate schedule and keeps the result with the fewest constraints,
+    /// then the fewest terms. Ties are resolved in favour of the earliest candidate, which makes the result never
+    /// worse than the default schedule.
+    pub fn optimize_tuned(self) -> (Self, OptimizerSchedule) {
+        OptimizerSchedule::candidates()
+            .into_iter()
+            .map(|schedule| {
+                let p = self.clone().optimize_with(&schedule);
+                log::debug!(
+                    "Optimizer schedule {} yields {} constraints",
+                    schedule,
+                    p.constraint_count()
+                );
+                (p, schedule)
+            })
+            .min_by_key(|(p, _)| (p.constraint_count(), density(p)))
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::{FlatParameter, FlatVariable};
+    use crate::ir::{LinComb, QuadComb};
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn parse_schedule() {
+        let schedule: OptimizerSchedule = "redefinition, duplicate,tautology".parse().unwrap();
+        assert_eq!(
+            schedule,
+            OptimizerSchedule(vec![Redefinition, Duplicate, Tautology])
+        );
+        assert_eq!(schedule.to_string(), "redefinition,duplicate,tautology");
+
+        assert!("redefinition,foo".parse::<OptimizerSchedule>().is_err());
+        assert!("".parse::<OptimizerSchedule>().is_err());
+    }
+
+    #[test]
+    fn candidates() {
+        let candidates = OptimizerSchedule::candidates();
+        assert!(candidates.len() <= 8);
+        assert_eq!(candidates[0], OptimizerSchedule::default());
+    }
+
+    #[test]
+    fn tuned_not_worse_than_default() {
+        // def main(_0) -> (1):
+        //     _1 == _0
+        //     _1 == _0
+        //     (1 * ~one) * (42 * ~one) == 42 * ~one
+        //     ~out_0 == _1 * _1
+        //     return ~out_0
+
+        let prog: Prog<Bn128Field> = Prog {
+            arguments: vec![FlatParameter::private(FlatVariable::new(0))],
+            statements: vec![
+                Statement::definition(FlatVariable::new(1), FlatVariable::new(0)),
+                Statement::definition(FlatVariable::new(1), FlatVariable::new(0)),
+                Statement::constraint(
+                    QuadComb::from_linear_combinations(
+                        LinComb::summand(1, FlatVariable::one()),
+                        LinComb::summand(42, FlatVariable::one()),
+                    ),
+                    LinComb::summand(42, FlatVariable::one()),
+                ),
+                Statement::constraint(
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(1).into(),
+                        FlatVariable::new(1).into(),
+                    ),
+                    FlatVariable::public(0),
+                ),
+            ],
+            returns: vec![FlatVariable::public(0)],
+        };
+
+        let default = prog.clone().optimize();
+        let (tuned, schedule) = prog.clone().optimize_tuned();
+
+        assert!(tuned.constraint_count() <= default.constraint_count());
+        assert_eq!(prog.optimize_with(&schedule), tuned);
+    }
+}
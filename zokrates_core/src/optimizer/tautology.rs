@@ -3,6 +3,8 @@
 // a * 1 == a
 // ```
 //
+// Both sides are compared in their canonical form, so that `(1 * _1 + 2 * _0) * 1 == 2 * _0 + 1 * _1` is also removed.
+//
 // This makes the assumption that ~one has value 1, as should be guaranteed by the verifier
 
 use crate::ir::folder::fold_statement;
@@ -10,24 +12,35 @@ use crate::ir::folder::Folder;
 use crate::ir::*;
 use zokrates_field::Field;
 
-pub struct TautologyOptimizer {}
+pub struct TautologyOptimizer {
+    // the number of constraints removed
+    count: usize,
+}
 
 impl TautologyOptimizer {
     fn new() -> TautologyOptimizer {
-        TautologyOptimizer {}
+        TautologyOptimizer { count: 0 }
     }
 
     pub fn optimize<T: Field>(p: Prog<T>) -> Prog<T> {
-        TautologyOptimizer::new().fold_module(p)
+        let mut optimizer = TautologyOptimizer::new();
+
+        let p = optimizer.fold_module(p);
+
+        log::debug!("Removed {} identity constraint(s)", optimizer.count);
+
+        p
     }
 }
 
 impl<T: Field> Folder<T> for TautologyOptimizer {
     fn fold_statement(&mut self, s: Statement<T>) -> Vec<Statement<T>> {
         match s {
+            s if s.is_barrier() => vec![s],
             Statement::Constraint(quad, lin, message) => match quad.try_linear() {
                 Ok(l) => {
-                    if l == lin {
+                    if l.clone().into_canonical() == lin.clone().into_canonical() {
+                        self.count += 1;
                         vec![]
                     } else {
                         vec![Statement::Constraint(l.into(), lin, message)]
@@ -41,4 +54,62 @@ impl<T: Field> Folder<T> for TautologyOptimizer {
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatVariable;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn remove_tautologies() {
+        // (1 * ~one) * (1 * _0) == 1 * _0
+        // (1 * _1 + 2 * _0) * (1 * ~one) == 2 * _0 + 1 * _1
+        // (1 * ~one) * (1 * _0) == 1 * _1
+
+        let _0 = FlatVariable::new(0);
+        let _1 = FlatVariable::new(1);
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![],
+            statements: vec![
+                Statement::constraint(LinComb::from(_0), LinComb::from(_0)),
+                Statement::constraint(
+                    QuadComb::from_linear_combinations(
+                        LinComb::summand(1, _1) + LinComb::summand(2, _0),
+                        LinComb::one(),
+                    ),
+                    LinComb::summand(2, _0) + LinComb::summand(1, _1),
+                ),
+                Statement::constraint(LinComb::from(_0), LinComb::from(_1)),
+            ],
+            returns: vec![],
+        };
+
+        let expected = Prog {
+            arguments: vec![],
+            statements: vec![Statement::constraint(LinComb::from(_0), LinComb::from(_1))],
+            returns: vec![],
+        };
+
+        assert_eq!(TautologyOptimizer::optimize(p), expected);
+    }
+
+    #[test]
+    fn keep_quadratic_constraints() {
+        // (1 * _0) * (1 * _0) == 1 * _0
+
+        let _0 = FlatVariable::new(0);
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![],
+            statements: vec![Statement::constraint(
+                QuadComb::from_linear_combinations(LinComb::from(_0), LinComb::from(_0)),
+                LinComb::from(_0),
+            )],
+            returns: vec![],
+        };
+
+        let expected = p.clone();
+
+        assert_eq!(TautologyOptimizer::optimize(p), expected);
+    }
+}
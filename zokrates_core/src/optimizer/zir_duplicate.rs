@@ -0,0 +1,374 @@
+//! Module containing the `ZirDuplicateOptimizer` to remove duplicate statements from a ZIR program
+//!
+//! ZIR programs are in SSA form, so a variable defined to an expression which was already assigned to another
+//! variable can be replaced by that variable everywhere, and its definition removed. Expressions are compared
+//! structurally after this replacement, so that whole sequences of statements repeating previous ones are removed
+//! one statement at a time. Embed calls are deduplicated the same way, and repeated assertions are dropped.
+//!
+//! Statements inside a branch are only executed conditionally, so they can reuse the statements preceding the
+//! conditional, but are not reused outside of their branch.
+//!
+//! Assertions annotated with `#[optimizer(barrier)]` are kept verbatim: no variable is substituted into them, and
+//! the definitions and embed calls of the variables they read are neither removed nor replaced.
+
+use crate::zir::folder::*;
+use crate::zir::*;
+use std::collections::{HashMap, HashSet};
+use zokrates_field::Field;
+
+#[derive(Debug, Clone, Default)]
+struct Scope<'ast, T> {
+    /// the variable holding each defined expression
+    definitions: HashMap<ZirExpression<'ast, T>, Identifier<'ast>>,
+    /// the variables holding the results of each embed call
+    embed_calls: HashMap<ZirExpressionList<'ast, T>, Vec<Identifier<'ast>>>,
+    /// the assertions made so far
+    assertions: HashSet<ZirStatement<'ast, T>>,
+}
+
+#[derive(Debug, Default)]
+pub struct ZirDuplicateOptimizer<'ast, T> {
+    /// the variable replacing each removed variable
+    substitution: HashMap<Identifier<'ast>, Identifier<'ast>>,
+    scope: Scope<'ast, T>,
+    /// the variables read by barrier assertions
+    barrier: HashSet<Identifier<'ast>>,
+    count: usize,
+}
+
+// collects the identifiers read by barrier assertions, including those inside branches
+fn barrier_identifiers<'ast, T: Field>(
+    statements: &[ZirStatement<'ast, T>],
+    collector: &mut BarrierIdentifiers<'ast>,
+) {
+    for s in statements {
+        match s {
+            ZirStatement::Assertion(e, error) if error.is_barrier() => {
+                collector.fold_boolean_expression(e.clone());
+            }
+            ZirStatement::IfElse(_, consequence, alternative) => {
+                barrier_identifiers(consequence, collector);
+                barrier_identifiers(alternative, collector);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(Default)]
+struct BarrierIdentifiers<'ast> {
+    identifiers: HashSet<Identifier<'ast>>,
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for BarrierIdentifiers<'ast> {
+    fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
+        self.identifiers.insert(n.clone());
+        n
+    }
+}
+
+impl<'ast, T: Field> ZirDuplicateOptimizer<'ast, T> {
+    pub fn optimize(p: ZirProgram<'ast, T>) -> ZirProgram<'ast, T> {
+        let mut collector = BarrierIdentifiers::default();
+        barrier_identifiers(&p.main.statements, &mut collector);
+
+        let mut optimizer = Self {
+            barrier: collector.identifiers,
+            ..Self::default()
+        };
+
+        let p = optimizer.fold_program(p);
+
+        log::debug!("Removed {} duplicate zir statement(s)", optimizer.count);
+
+        p
+    }
+
+    // folds the statements of a branch, which cannot be reused after it
+    fn fold_branch(
+        &mut self,
+        statements: Vec<ZirStatement<'ast, T>>,
+    ) -> Vec<ZirStatement<'ast, T>> {
+        let scope = self.scope.clone();
+
+        let statements = statements
+            .into_iter()
+            .flat_map(|s| self.fold_statement(s))
+            .collect();
+
+        self.scope = scope;
+
+        statements
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for ZirDuplicateOptimizer<'ast, T> {
+    fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
+        self.substitution.get(&n).cloned().unwrap_or(n)
+    }
+
+    fn fold_statement(&mut self, s: ZirStatement<'ast, T>) -> Vec<ZirStatement<'ast, T>> {
+        match s {
+            ZirStatement::Definition(a, e) => {
+                let e = self.fold_expression(e);
+
+                match self.scope.definitions.get(&e) {
+                    // variables read by barriers keep their own definition
+                    Some(_) if self.barrier.contains(&a.id) => {
+                        vec![ZirStatement::Definition(a, e)]
+                    }
+                    Some(id) => {
+                        self.substitution.insert(a.id, id.clone());
+                        self.count += 1;
+                        vec![]
+                    }
+                    None => {
+                        self.scope.definitions.insert(e.clone(), a.id.clone());
+                        vec![ZirStatement::Definition(a, e)]
+                    }
+                }
+            }
+            ZirStatement::MultipleDefinition(variables, list) => {
+                let list = self.fold_expression_list(list);
+
+                match self.scope.embed_calls.get(&list) {
+                    Some(_) if variables.iter().any(|v| self.barrier.contains(&v.id)) => {
+                        vec![ZirStatement::MultipleDefinition(variables, list)]
+                    }
+                    Some(ids) => {
+                        self.substitution
+                            .extend(variables.into_iter().map(|v| v.id).zip(ids.clone()));
+                        self.count += 1;
+                        vec![]
+                    }
+                    None => {
+                        self.scope.embed_calls.insert(
+                            list.clone(),
+                            variables.iter().map(|v| v.id.clone()).collect(),
+                        );
+                        vec![ZirStatement::MultipleDefinition(variables, list)]
+                    }
+                }
+            }
+            // barriers are kept verbatim and never merged with other statements
+            ZirStatement::Assertion(e, error) if error.is_barrier() => {
+                vec![ZirStatement::Assertion(e, error)]
+            }
+            ZirStatement::Assertion(e, error) => {
+                let s = ZirStatement::Assertion(self.fold_boolean_expression(e), error);
+
+                if self.scope.assertions.insert(s.clone()) {
+                    vec![s]
+                } else {
+                    self.count += 1;
+                    vec![]
+                }
+            }
+            ZirStatement::IfElse(condition, consequence, alternative) => {
+                vec![ZirStatement::IfElse(
+                    self.fold_boolean_expression(condition),
+                    self.fold_branch(consequence),
+                    self.fold_branch(alternative),
+                )]
+            }
+            s => fold_statement(self, s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embed::FlatEmbed;
+    use crate::flat_absy::RuntimeError;
+    use crate::zir::types::{Signature, Type};
+    use zokrates_field::Bn128Field;
+
+    fn square(id: &str) -> ZirExpression<Bn128Field> {
+        FieldElementExpression::Mult(
+            box FieldElementExpression::Identifier(id.into()),
+            box FieldElementExpression::Identifier(id.into()),
+        )
+        .into()
+    }
+
+    fn program(statements: Vec<ZirStatement<Bn128Field>>) -> ZirProgram<Bn128Field> {
+        ZirProgram {
+            main: ZirFunction {
+                arguments: vec![Parameter::private(Variable::field_element("a"))],
+                statements,
+                signature: Signature {
+                    inputs: vec![Type::FieldElement],
+                    outputs: vec![Type::FieldElement],
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn remove_duplicate_sequence() {
+        // b = a * a
+        // c = b * b
+        // d = a * a
+        // e = d * d
+        // return c + e
+
+        let p = program(vec![
+            ZirStatement::Definition(Variable::field_element("b"), square("a")),
+            ZirStatement::Definition(Variable::field_element("c"), square("b")),
+            ZirStatement::Definition(Variable::field_element("d"), square("a")),
+            ZirStatement::Definition(Variable::field_element("e"), square("d")),
+            ZirStatement::Return(vec![FieldElementExpression::Add(
+                box FieldElementExpression::Identifier("c".into()),
+                box FieldElementExpression::Identifier("e".into()),
+            )
+            .into()]),
+        ]);
+
+        // `d` is replaced by `b`, which makes `e` a duplicate of `c`
+        let expected = program(vec![
+            ZirStatement::Definition(Variable::field_element("b"), square("a")),
+            ZirStatement::Definition(Variable::field_element("c"), square("b")),
+            ZirStatement::Return(vec![FieldElementExpression::Add(
+                box FieldElementExpression::Identifier("c".into()),
+                box FieldElementExpression::Identifier("c".into()),
+            )
+            .into()]),
+        ]);
+
+        assert_eq!(ZirDuplicateOptimizer::optimize(p), expected);
+    }
+
+    #[test]
+    fn remove_duplicate_embed_calls_and_assertions() {
+        // b = unpack(a)
+        // c = unpack(a)
+        // assert(b == c)
+        // assert(b == c)
+        // #[optimizer(barrier)] assert(b == c)
+        // return a
+
+        let unpack = || {
+            ZirExpressionList::EmbedCall(
+                FlatEmbed::Unpack,
+                vec![1],
+                vec![FieldElementExpression::Identifier("a".into()).into()],
+            )
+        };
+
+        let assertion = |error| {
+            ZirStatement::Assertion(
+                BooleanExpression::BoolEq(
+                    box BooleanExpression::Identifier("b".into()),
+                    box BooleanExpression::Identifier("c".into()),
+                ),
+                error,
+            )
+        };
+
+        let ret =
+            || ZirStatement::Return(vec![FieldElementExpression::Identifier("a".into()).into()]);
+
+        let p = program(vec![
+            ZirStatement::MultipleDefinition(vec![Variable::boolean("b".into())], unpack()),
+            ZirStatement::MultipleDefinition(vec![Variable::boolean("c".into())], unpack()),
+            assertion(RuntimeError::Source),
+            assertion(RuntimeError::Source),
+            assertion(RuntimeError::SourceBarrier),
+            ret(),
+        ]);
+
+        // `c` is read by the barrier, so its embed call is kept and it is not replaced by `b`
+        let expected = program(vec![
+            ZirStatement::MultipleDefinition(vec![Variable::boolean("b".into())], unpack()),
+            ZirStatement::MultipleDefinition(vec![Variable::boolean("c".into())], unpack()),
+            assertion(RuntimeError::Source),
+            assertion(RuntimeError::SourceBarrier),
+            ret(),
+        ]);
+
+        assert_eq!(ZirDuplicateOptimizer::optimize(p), expected);
+    }
+
+    #[test]
+    fn keep_definitions_read_by_barriers() {
+        // b = a * a
+        // c = a * a
+        // #[optimizer(barrier)] assert(b == c)
+        // return a
+
+        let statements = || {
+            vec![
+                ZirStatement::Definition(Variable::field_element("b"), square("a")),
+                ZirStatement::Definition(Variable::field_element("c"), square("a")),
+                ZirStatement::Assertion(
+                    BooleanExpression::FieldEq(
+                        box FieldElementExpression::Identifier("b".into()),
+                        box FieldElementExpression::Identifier("c".into()),
+                    ),
+                    RuntimeError::SourceBarrier,
+                ),
+                ZirStatement::Return(vec![FieldElementExpression::Identifier("a".into()).into()]),
+            ]
+        };
+
+        assert_eq!(
+            ZirDuplicateOptimizer::optimize(program(statements())),
+            program(statements())
+        );
+    }
+
+    #[test]
+    fn keep_branch_definitions_local() {
+        // b = a * a
+        // if a == 1 then {c = a * a; d = c * c} else {} fi
+        // e = b * b
+        // return d + e
+
+        let condition = || {
+            BooleanExpression::FieldEq(
+                box FieldElementExpression::Identifier("a".into()),
+                box FieldElementExpression::Number(Bn128Field::from(1)),
+            )
+        };
+
+        let ret = || {
+            ZirStatement::Return(vec![FieldElementExpression::Add(
+                box FieldElementExpression::Identifier("d".into()),
+                box FieldElementExpression::Identifier("e".into()),
+            )
+            .into()])
+        };
+
+        let p = program(vec![
+            ZirStatement::Definition(Variable::field_element("b"), square("a")),
+            ZirStatement::IfElse(
+                condition(),
+                vec![
+                    ZirStatement::Definition(Variable::field_element("c"), square("a")),
+                    ZirStatement::Definition(Variable::field_element("d"), square("c")),
+                ],
+                vec![],
+            ),
+            ZirStatement::Definition(Variable::field_element("e"), square("b")),
+            ret(),
+        ]);
+
+        // the branch reuses `b`, but `e` does not reuse `d` which is only defined in the branch
+        let expected = program(vec![
+            ZirStatement::Definition(Variable::field_element("b"), square("a")),
+            ZirStatement::IfElse(
+                condition(),
+                vec![ZirStatement::Definition(
+                    Variable::field_element("d"),
+                    square("b"),
+                )],
+                vec![],
+            ),
+            ZirStatement::Definition(Variable::field_element("e"), square("b")),
+            ret(),
+        ]);
+
+        assert_eq!(ZirDuplicateOptimizer::optimize(p), expected);
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt;
 
-#[derive(Clone, PartialEq, Copy)]
+#[derive(Clone, PartialEq, Eq, Hash, Copy)]
 pub struct Position {
     pub line: usize,
     pub col: usize,
@@ -15,7 +15,7 @@ use crate::proof_system::Scheme;
 use crate::proof_system::{Backend, NonUniversalBackend, Proof, SetupKeypair};
 
 impl<T: Field + ArkFieldExtensions + NotBw6_761Field> NonUniversalBackend<T, GM17> for Ark {
-    fn setup(program: Prog<T>) -> SetupKeypair<<GM17 as Scheme<T>>::VerificationKey> {
+    fn setup(program: &Prog<T>) -> SetupKeypair<<GM17 as Scheme<T>>::VerificationKey> {
         let parameters = Computation::without_witness(program).setup();
 
         let mut pk: Vec<u8> = Vec::new();
@@ -41,7 +41,7 @@ impl<T: Field + ArkFieldExtensions + NotBw6_761Field> NonUniversalBackend<T, GM1
 
 impl<T: Field + ArkFieldExtensions + NotBw6_761Field> Backend<T, GM17> for Ark {
     fn generate_proof(
-        program: Prog<T>,
+        program: &Prog<T>,
         witness: Witness<T>,
         proving_key: Vec<u8>,
     ) -> Proof<<GM17 as Scheme<T>>::ProofPoints> {
@@ -109,7 +109,7 @@ impl<T: Field + ArkFieldExtensions + NotBw6_761Field> Backend<T, GM17> for Ark {
 
 impl NonUniversalBackend<Bw6_761Field, GM17> for Ark {
     fn setup(
-        program: Prog<Bw6_761Field>,
+        program: &Prog<Bw6_761Field>,
     ) -> SetupKeypair<<GM17 as Scheme<Bw6_761Field>>::VerificationKey> {
         let parameters = Computation::without_witness(program).setup();
 
@@ -136,7 +136,7 @@ impl NonUniversalBackend<Bw6_761Field, GM17> for Ark {
 
 impl Backend<Bw6_761Field, GM17> for Ark {
     fn generate_proof(
-        program: Prog<Bw6_761Field>,
+        program: &Prog<Bw6_761Field>,
         witness: Witness<Bw6_761Field>,
         proving_key: Vec<u8>,
     ) -> Proof<<GM17 as Scheme<Bw6_761Field>>::ProofPoints> {
@@ -267,7 +267,7 @@ mod tests {
             )],
         };
 
-        let keypair = <Ark as NonUniversalBackend<Bls12_377Field, GM17>>::setup(program.clone());
+        let keypair = <Ark as NonUniversalBackend<Bls12_377Field, GM17>>::setup(&program);
         let interpreter = Interpreter::default();
 
         let witness = interpreter
@@ -275,7 +275,7 @@ mod tests {
             .unwrap();
 
         let proof =
-            <Ark as Backend<Bls12_377Field, GM17>>::generate_proof(program, witness, keypair.pk);
+            <Ark as Backend<Bls12_377Field, GM17>>::generate_proof(&program, witness, keypair.pk);
         let ans = <Ark as Backend<Bls12_377Field, GM17>>::verify(keypair.vk, proof);
 
         assert!(ans);
@@ -292,7 +292,7 @@ mod tests {
             )],
         };
 
-        let keypair = <Ark as NonUniversalBackend<Bw6_761Field, GM17>>::setup(program.clone());
+        let keypair = <Ark as NonUniversalBackend<Bw6_761Field, GM17>>::setup(&program);
         let interpreter = Interpreter::default();
 
         let witness = interpreter
@@ -300,7 +300,7 @@ mod tests {
             .unwrap();
 
         let proof =
-            <Ark as Backend<Bw6_761Field, GM17>>::generate_proof(program, witness, keypair.pk);
+            <Ark as Backend<Bw6_761Field, GM17>>::generate_proof(&program, witness, keypair.pk);
         let ans = <Ark as Backend<Bw6_761Field, GM17>>::verify(keypair.vk, proof);
 
         assert!(ans);
@@ -47,7 +47,7 @@ impl<T: Field + ArkFieldExtensions> UniversalBackend<T, marlin::Marlin> for Ark
 
     fn setup(
         universal_srs: Vec<u8>,
-        program: Prog<T>,
+        program: &Prog<T>,
     ) -> Result<SetupKeypair<<marlin::Marlin as Scheme<T>>::VerificationKey>, String> {
         if program.constraint_count() < MINIMUM_CONSTRAINT_COUNT {
             return Err(format!("Programs must have a least {} constraints. This program is too small to generate a setup with Marlin, see [this issue](https://github.com/arkworks-rs/marlin/issues/79)", MINIMUM_CONSTRAINT_COUNT));
@@ -92,7 +92,7 @@ impl<T: Field + ArkFieldExtensions> UniversalBackend<T, marlin::Marlin> for Ark
 
 impl<T: Field + ArkFieldExtensions> Backend<T, marlin::Marlin> for Ark {
     fn generate_proof(
-        program: Prog<T>,
+        program: &Prog<T>,
         witness: Witness<T>,
         proving_key: Vec<u8>,
     ) -> Proof<<marlin::Marlin as Scheme<T>>::ProofPoints> {
@@ -214,7 +214,7 @@ mod tests {
 
         let srs = <Ark as UniversalBackend<Bls12_377Field, Marlin>>::universal_setup(5);
         let keypair =
-            <Ark as UniversalBackend<Bls12_377Field, Marlin>>::setup(srs, program.clone()).unwrap();
+            <Ark as UniversalBackend<Bls12_377Field, Marlin>>::setup(srs, &program).unwrap();
         let interpreter = Interpreter::default();
 
         let witness = interpreter
@@ -222,7 +222,7 @@ mod tests {
             .unwrap();
 
         let proof =
-            <Ark as Backend<Bls12_377Field, Marlin>>::generate_proof(program, witness, keypair.pk);
+            <Ark as Backend<Bls12_377Field, Marlin>>::generate_proof(&program, witness, keypair.pk);
         let ans = <Ark as Backend<Bls12_377Field, Marlin>>::verify(keypair.vk, proof);
 
         assert!(ans);
@@ -247,7 +247,7 @@ mod tests {
 
         let srs = <Ark as UniversalBackend<Bw6_761Field, Marlin>>::universal_setup(5);
         let keypair =
-            <Ark as UniversalBackend<Bw6_761Field, Marlin>>::setup(srs, program.clone()).unwrap();
+            <Ark as UniversalBackend<Bw6_761Field, Marlin>>::setup(srs, &program).unwrap();
         let interpreter = Interpreter::default();
 
         let witness = interpreter
@@ -255,7 +255,7 @@ mod tests {
             .unwrap();
 
         let proof =
-            <Ark as Backend<Bw6_761Field, Marlin>>::generate_proof(program, witness, keypair.pk);
+            <Ark as Backend<Bw6_761Field, Marlin>>::generate_proof(&program, witness, keypair.pk);
         let ans = <Ark as Backend<Bw6_761Field, Marlin>>::verify(keypair.vk, proof);
 
         assert!(ans);
@@ -24,20 +24,20 @@ use rand_0_7::SeedableRng;
 pub struct Ark;
 
 #[derive(Clone)]
-pub struct Computation<T> {
-    program: Prog<T>,
+pub struct Computation<'a, T> {
+    program: &'a Prog<T>,
     witness: Option<Witness<T>>,
 }
 
-impl<T: Field> Computation<T> {
-    pub fn with_witness(program: Prog<T>, witness: Witness<T>) -> Self {
+impl<'a, T: Field> Computation<'a, T> {
+    pub fn with_witness(program: &'a Prog<T>, witness: Witness<T>) -> Self {
         Computation {
             program,
             witness: Some(witness),
         }
     }
 
-    pub fn without_witness(program: Prog<T>) -> Self {
+    pub fn without_witness(program: &'a Prog<T>) -> Self {
         Computation {
             program,
             witness: None,
@@ -81,7 +81,7 @@ fn ark_combination<T: Field + ArkFieldExtensions>(
 
 impl<T: Field + ArkFieldExtensions> Prog<T> {
     pub fn generate_constraints(
-        self,
+        &self,
         cs: ConstraintSystemRef<<<T as ArkFieldExtensions>::ArkEngine as PairingEngine>::Fr>,
         witness: Option<Witness<T>>,
     ) -> Result<(), SynthesisError> {
@@ -116,7 +116,7 @@ impl<T: Field + ArkFieldExtensions> Prog<T> {
                     (p.id, wire)
                 }));
 
-                for statement in self.statements {
+                for statement in &self.statements {
                     if let Statement::Constraint(quad, lin, _) = statement {
                         let a = ark_combination(
                             quad.left.clone().into_canonical(),
@@ -131,7 +131,7 @@ impl<T: Field + ArkFieldExtensions> Prog<T> {
                             &mut witness,
                         );
                         let c = ark_combination(
-                            lin.into_canonical(),
+                            lin.clone().into_canonical(),
                             &mut cs,
                             &mut symbols,
                             &mut witness,
@@ -148,7 +148,7 @@ impl<T: Field + ArkFieldExtensions> Prog<T> {
     }
 }
 
-impl<T: Field + ArkFieldExtensions> Computation<T> {
+impl<'a, T: Field + ArkFieldExtensions> Computation<'a, T> {
     pub fn prove(self, params: &ProvingKey<T::ArkEngine>) -> Proof<T::ArkEngine> {
         let rng = &mut rand_0_7::rngs::StdRng::from_entropy();
 
@@ -180,9 +180,9 @@ impl<T: Field + ArkFieldExtensions> Computation<T> {
     }
 }
 
-impl<T: Field + ArkFieldExtensions>
+impl<'a, T: Field + ArkFieldExtensions>
     ConstraintSynthesizer<<<T as ArkFieldExtensions>::ArkEngine as PairingEngine>::Fr>
-    for Computation<T>
+    for Computation<'a, T>
 {
     fn generate_constraints(
         self,
@@ -2,13 +2,15 @@ use bellman::groth16::{
     prepare_verifying_key, verify_proof, Parameters, PreparedVerifyingKey, Proof as BellmanProof,
     VerifyingKey,
 };
-use pairing::{CurveAffine, Engine};
+use pairing::{CurveAffine, EncodedPoint, Engine};
+use std::collections::HashSet;
 
 use crate::proof_system::{Backend, NonUniversalBackend, Proof, SetupKeypair};
 use zokrates_field::BellmanFieldExtensions;
 use zokrates_field::Field;
 
-use crate::ir::{Prog, Witness};
+use crate::flat_absy::FlatVariable;
+use crate::ir::{Prog, Statement, Witness};
 use crate::proof_system::bellman::Bellman;
 use crate::proof_system::bellman::Computation;
 use crate::proof_system::bellman::{parse_fr, parse_g1, parse_g2};
@@ -19,7 +21,7 @@ const G16_WARNING: &str = "WARNING: You are using the G16 scheme which is subjec
 
 impl<T: Field + BellmanFieldExtensions> Backend<T, G16> for Bellman {
     fn generate_proof(
-        program: Prog<T>,
+        program: &Prog<T>,
         witness: Witness<T>,
         proving_key: Vec<u8>,
     ) -> Proof<<G16 as Scheme<T>>::ProofPoints> {
@@ -84,7 +86,7 @@ impl<T: Field + BellmanFieldExtensions> Backend<T, G16> for Bellman {
 }
 
 impl<T: Field + BellmanFieldExtensions> NonUniversalBackend<T, G16> for Bellman {
-    fn setup(program: Prog<T>) -> SetupKeypair<<G16 as Scheme<T>>::VerificationKey> {
+    fn setup(program: &Prog<T>) -> SetupKeypair<<G16 as Scheme<T>>::VerificationKey> {
         println!("{}", G16_WARNING);
 
         let parameters = Computation::without_witness(program).setup();
@@ -108,6 +110,104 @@ impl<T: Field + BellmanFieldExtensions> NonUniversalBackend<T, G16> for Bellman
     }
 }
 
+/// The number of group elements of the G16 proving key of a program, and the size of its serialization
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ProvingKeySizeEstimate {
+    pub num_g1_elements: usize,
+    pub num_g2_elements: usize,
+    pub total_bytes: usize,
+}
+
+impl Bellman {
+    /// Computes the size of the G16 proving key written by `setup` for `program`, without running the setup
+    ///
+    /// The key is the serialization of bellman's `Parameters`. The verification key has three elements of each group
+    /// and a G1 element for each public input, `~one` included. The H query has a G1 element for each power of tau
+    /// but the last one, up to the size of the evaluation domain, and the L query a G1 element for each private
+    /// variable. The A, B in G1 and B in G2 queries have an element for each variable appearing in the corresponding
+    /// side of a constraint, points at infinity being removed: the public inputs are always in A, through the
+    /// constraints bellman adds for each of them. Each query is prefixed with its length on 4 bytes.
+    pub fn estimate_proving_key_size<T: Field + BellmanFieldExtensions>(
+        program: &Prog<T>,
+    ) -> ProvingKeySizeEstimate {
+        // variables are allocated as in `Prog::synthesize`: the arguments first, then the others as they appear
+        let mut public: HashSet<FlatVariable> = std::iter::once(FlatVariable::one())
+            .chain(
+                program
+                    .arguments
+                    .iter()
+                    .filter(|p| !p.private)
+                    .map(|p| p.id),
+            )
+            .collect();
+        let mut private: HashSet<FlatVariable> = program
+            .arguments
+            .iter()
+            .filter(|p| p.private)
+            .map(|p| p.id)
+            .collect();
+
+        let mut a = HashSet::new();
+        let mut b = HashSet::new();
+        let mut constraint_count = 0;
+
+        for statement in &program.statements {
+            if let Statement::Constraint(quad, lin, _) = statement {
+                constraint_count += 1;
+
+                let quad = quad.clone().into_canonical();
+                let lin = lin.clone().into_canonical();
+
+                a.extend(quad.left.0.keys().cloned());
+                b.extend(quad.right.0.keys().cloned());
+
+                for v in quad
+                    .left
+                    .0
+                    .keys()
+                    .chain(quad.right.0.keys())
+                    .chain(lin.0.keys())
+                {
+                    if public.contains(v) {
+                        continue;
+                    }
+                    if v.is_output() {
+                        public.insert(*v);
+                    } else {
+                        private.insert(*v);
+                    }
+                }
+            }
+        }
+
+        let domain_size = (constraint_count + public.len()).next_power_of_two();
+
+        let ic = public.len();
+        let h = domain_size - 1;
+        let l = private.len();
+        let a = public.len() + a.difference(&public).count();
+        let b = b.len();
+
+        let num_g1_elements = 3 + ic + h + l + a + b;
+        let num_g2_elements = 3 + b;
+
+        let g1_size = uncompressed_size::<<T::BellmanEngine as Engine>::G1Affine>();
+        let g2_size = uncompressed_size::<<T::BellmanEngine as Engine>::G2Affine>();
+
+        ProvingKeySizeEstimate {
+            num_g1_elements,
+            num_g2_elements,
+            // the lengths of the IC, H, L, A, B in G1 and B in G2 queries
+            total_bytes: num_g1_elements * g1_size + num_g2_elements * g2_size + 6 * 4,
+        }
+    }
+}
+
+// the size of the serialization of a point of `G` in `Parameters`
+fn uncompressed_size<G: CurveAffine>() -> usize {
+    <G::Uncompressed as EncodedPoint>::size()
+}
+
 mod serialization {
     use pairing::{from_hex, CurveAffine, Engine};
 
@@ -133,11 +233,11 @@ mod serialization {
 
 #[cfg(test)]
 mod tests {
-    use zokrates_field::Bn128Field;
+    use zokrates_field::{Bls12_381Field, Bn128Field};
 
     use super::*;
     use crate::flat_absy::{FlatParameter, FlatVariable};
-    use crate::ir::{Interpreter, Prog, Statement};
+    use crate::ir::{Interpreter, LinComb, Prog, QuadComb, Statement};
 
     #[test]
     fn verify() {
@@ -150,7 +250,7 @@ mod tests {
             )],
         };
 
-        let keypair = <Bellman as NonUniversalBackend<Bn128Field, G16>>::setup(program.clone());
+        let keypair = <Bellman as NonUniversalBackend<Bn128Field, G16>>::setup(&program);
         let interpreter = Interpreter::default();
 
         let witness = interpreter
@@ -158,9 +258,92 @@ mod tests {
             .unwrap();
 
         let proof =
-            <Bellman as Backend<Bn128Field, G16>>::generate_proof(program, witness, keypair.pk);
+            <Bellman as Backend<Bn128Field, G16>>::generate_proof(&program, witness, keypair.pk);
         let ans = <Bellman as Backend<Bn128Field, G16>>::verify(keypair.vk, proof);
 
         assert!(ans);
     }
+
+    fn assert_exact_estimate<T: Field + BellmanFieldExtensions>(program: &Prog<T>) {
+        let keypair = <Bellman as NonUniversalBackend<T, G16>>::setup(program);
+        let parameters = Parameters::<T::BellmanEngine>::read(keypair.pk.as_slice(), true).unwrap();
+
+        let estimate = Bellman::estimate_proving_key_size(program);
+
+        assert_eq!(
+            estimate,
+            ProvingKeySizeEstimate {
+                num_g1_elements: 3
+                    + parameters.vk.ic.len()
+                    + parameters.h.len()
+                    + parameters.l.len()
+                    + parameters.a.len()
+                    + parameters.b_g1.len(),
+                num_g2_elements: 3 + parameters.b_g2.len(),
+                total_bytes: keypair.pk.len(),
+            }
+        );
+    }
+
+    // def main(public _0, private _1, private _2) -> (1):
+    //     _3 == _0 * _1
+    //     _4 == ~one * _1
+    //     ~out_0 == ~one * (_3 + _4)
+    //     return ~out_0
+    fn program<T: Field>() -> Prog<T> {
+        Prog {
+            arguments: vec![
+                FlatParameter::public(FlatVariable::new(0)),
+                FlatParameter::private(FlatVariable::new(1)),
+                FlatParameter::private(FlatVariable::new(2)),
+            ],
+            returns: vec![FlatVariable::public(0)],
+            statements: vec![
+                Statement::definition(
+                    FlatVariable::new(3),
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::new(1).into(),
+                    ),
+                ),
+                Statement::definition(FlatVariable::new(4), FlatVariable::new(1)),
+                Statement::definition(
+                    FlatVariable::public(0),
+                    LinComb::from(FlatVariable::new(3)) + LinComb::from(FlatVariable::new(4)),
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn estimate_proving_key_size() {
+        // 1 + 2 public inputs and 4 private variables, `_2` being unused
+        // 3 + 3 constraints, so 7 powers of tau in H
+        // `~one`, `_0` and `~out_0` in A through the input constraints, and `_1`, `_3` and `_4` in B
+        assert_eq!(
+            Bellman::estimate_proving_key_size(&program::<Bn128Field>()),
+            ProvingKeySizeEstimate {
+                num_g1_elements: 3 + 3 + 7 + 4 + 3 + 3,
+                num_g2_elements: 3 + 3,
+                total_bytes: (3 + 3 + 7 + 4 + 3 + 3) * 64 + (3 + 3) * 128 + 6 * 4,
+            }
+        );
+    }
+
+    #[test]
+    fn exact_estimate() {
+        assert_exact_estimate(&program::<Bn128Field>());
+        assert_exact_estimate(&program::<Bls12_381Field>());
+
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![FlatParameter::public(FlatVariable::new(0))],
+            returns: vec![FlatVariable::public(0)],
+            statements: vec![Statement::constraint(
+                FlatVariable::new(0),
+                FlatVariable::public(0),
+            )],
+        };
+
+        assert_exact_estimate(&program);
+    }
 }
@@ -20,20 +20,20 @@ pub use self::parse::*;
 pub struct Bellman;
 
 #[derive(Clone)]
-pub struct Computation<T> {
-    program: Prog<T>,
+pub struct Computation<'a, T> {
+    program: &'a Prog<T>,
     witness: Option<Witness<T>>,
 }
 
-impl<T: Field> Computation<T> {
-    pub fn with_witness(program: Prog<T>, witness: Witness<T>) -> Self {
+impl<'a, T: Field> Computation<'a, T> {
+    pub fn with_witness(program: &'a Prog<T>, witness: Witness<T>) -> Self {
         Computation {
             program,
             witness: Some(witness),
         }
     }
 
-    pub fn without_witness(program: Prog<T>) -> Self {
+    pub fn without_witness(program: &'a Prog<T>) -> Self {
         Computation {
             program,
             witness: None,
@@ -83,7 +83,7 @@ fn bellman_combination<T: BellmanFieldExtensions, CS: ConstraintSystem<T::Bellma
 
 impl<T: BellmanFieldExtensions + Field> Prog<T> {
     pub fn synthesize<CS: ConstraintSystem<T::BellmanEngine>>(
-        self,
+        &self,
         cs: &mut CS,
         witness: Option<Witness<T>>,
     ) -> Result<(), SynthesisError> {
@@ -121,21 +121,26 @@ impl<T: BellmanFieldExtensions + Field> Prog<T> {
             (p.id, wire)
         }));
 
-        for statement in self.statements {
+        for statement in &self.statements {
             if let Statement::Constraint(quad, lin, _) = statement {
                 let a = &bellman_combination(
-                    quad.left.into_canonical(),
+                    quad.left.clone().into_canonical(),
                     cs,
                     &mut symbols,
                     &mut witness,
                 );
                 let b = &bellman_combination(
-                    quad.right.into_canonical(),
+                    quad.right.clone().into_canonical(),
+                    cs,
+                    &mut symbols,
+                    &mut witness,
+                );
+                let c = &bellman_combination(
+                    lin.clone().into_canonical(),
                     cs,
                     &mut symbols,
                     &mut witness,
                 );
-                let c = &bellman_combination(lin.into_canonical(), cs, &mut symbols, &mut witness);
 
                 cs.enforce(|| "Constraint", |lc| lc + a, |lc| lc + b, |lc| lc + c);
             }
@@ -145,17 +150,17 @@ impl<T: BellmanFieldExtensions + Field> Prog<T> {
     }
 }
 
-impl<T: BellmanFieldExtensions + Field> Computation<T> {
+impl<'a, T: BellmanFieldExtensions + Field> Computation<'a, T> {
     fn get_random_seed(&self) -> Result<[u32; 8], getrandom::Error> {
         let mut seed = [0u8; 32];
         getrandom::getrandom(&mut seed)?;
 
-        use std::mem::transmute;
-        // This is safe because we are just reinterpreting the bytes (u8[32] -> u32[8]),
-        // byte order or the actual content does not matter here as this is used
-        // as a random seed for the rng.
-        let seed: [u32; 8] = unsafe { transmute(seed) };
-        Ok(seed)
+        // read the bytes as little-endian words, so that a seed gives the same rng on any platform
+        let mut words = [0u32; 8];
+        for (word, bytes) in words.iter_mut().zip(seed.chunks_exact(4)) {
+            *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        Ok(words)
     }
 
     pub fn prove(self, params: &Parameters<T::BellmanEngine>) -> Proof<T::BellmanEngine> {
@@ -192,7 +197,7 @@ impl<T: BellmanFieldExtensions + Field> Computation<T> {
     }
 }
 
-impl<T: BellmanFieldExtensions + Field> Circuit<T::BellmanEngine> for Computation<T> {
+impl<'a, T: BellmanFieldExtensions + Field> Circuit<T::BellmanEngine> for Computation<'a, T> {
     fn synthesize<CS: ConstraintSystem<T::BellmanEngine>>(
         self,
         cs: &mut CS,
@@ -275,7 +280,7 @@ mod tests {
             let interpreter = Interpreter::default();
 
             let witness = interpreter.execute(&program, &[]).unwrap();
-            let computation = Computation::with_witness(program, witness);
+            let computation = Computation::with_witness(&program, witness);
 
             let params = computation.clone().setup();
             let _proof = computation.prove(&params);
@@ -298,7 +303,7 @@ mod tests {
                 .execute(&program, &[Bn128Field::from(0)])
                 .unwrap();
 
-            let computation = Computation::with_witness(program, witness);
+            let computation = Computation::with_witness(&program, witness);
 
             let params = computation.clone().setup();
             let _proof = computation.prove(&params);
@@ -321,7 +326,7 @@ mod tests {
                 .execute(&program, &[Bn128Field::from(0)])
                 .unwrap();
 
-            let computation = Computation::with_witness(program, witness);
+            let computation = Computation::with_witness(&program, witness);
 
             let params = computation.clone().setup();
             let _proof = computation.prove(&params);
@@ -341,7 +346,7 @@ mod tests {
             let interpreter = Interpreter::default();
 
             let witness = interpreter.execute(&program, &[]).unwrap();
-            let computation = Computation::with_witness(program, witness);
+            let computation = Computation::with_witness(&program, witness);
 
             let params = computation.clone().setup();
             let _proof = computation.prove(&params);
@@ -374,7 +379,7 @@ mod tests {
             let witness = interpreter
                 .execute(&program, &[Bn128Field::from(3), Bn128Field::from(4)])
                 .unwrap();
-            let computation = Computation::with_witness(program, witness);
+            let computation = Computation::with_witness(&program, witness);
 
             let params = computation.clone().setup();
             let _proof = computation.prove(&params);
@@ -397,7 +402,7 @@ mod tests {
                 .execute(&program, &[Bn128Field::from(3)])
                 .unwrap();
 
-            let computation = Computation::with_witness(program, witness);
+            let computation = Computation::with_witness(&program, witness);
 
             let params = computation.clone().setup();
             let _proof = computation.prove(&params);
@@ -422,7 +427,7 @@ mod tests {
             let witness = interpreter
                 .execute(&program, &[Bn128Field::from(3), Bn128Field::from(4)])
                 .unwrap();
-            let computation = Computation::with_witness(program, witness);
+            let computation = Computation::with_witness(&program, witness);
 
             let params = computation.clone().setup();
             let _proof = computation.prove(&params);
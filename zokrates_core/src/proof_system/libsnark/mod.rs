@@ -33,7 +33,7 @@ pub fn prepare_public_inputs<T: Field>(public_inputs: Vec<T>) -> (Vec<[u8; 32]>,
 
 // proof-system-independent preparation for the setup phase
 pub fn prepare_setup<T: Field>(
-    program: ir::Prog<T>,
+    program: &ir::Prog<T>,
 ) -> (
     Vec<u8>,
     Vec<u8>,
@@ -97,10 +97,9 @@ pub fn prepare_setup<T: Field>(
     let mut a_arr: Vec<u8> = vec![0u8; STRUCT_SIZE * a_vec.len()];
     let mut b_arr: Vec<u8> = vec![0u8; STRUCT_SIZE * b_vec.len()];
     let mut c_arr: Vec<u8> = vec![0u8; STRUCT_SIZE * c_vec.len()];
-    use std::mem::transmute;
     for (id, (row, idx, val)) in a_vec.iter().enumerate() {
-        let row_bytes: [u8; ROW_SIZE] = unsafe { transmute(row.to_le()) };
-        let idx_bytes: [u8; IDX_SIZE] = unsafe { transmute(idx.to_le()) };
+        let row_bytes: [u8; ROW_SIZE] = row.to_le_bytes();
+        let idx_bytes: [u8; IDX_SIZE] = idx.to_le_bytes();
 
         for x in 0..ROW_SIZE {
             a_arr[id * STRUCT_SIZE + x] = row_bytes[x];
@@ -113,8 +112,8 @@ pub fn prepare_setup<T: Field>(
         }
     }
     for (id, (row, idx, val)) in b_vec.iter().enumerate() {
-        let row_bytes: [u8; ROW_SIZE] = unsafe { transmute(row.to_le()) };
-        let idx_bytes: [u8; IDX_SIZE] = unsafe { transmute(idx.to_le()) };
+        let row_bytes: [u8; ROW_SIZE] = row.to_le_bytes();
+        let idx_bytes: [u8; IDX_SIZE] = idx.to_le_bytes();
 
         for x in 0..ROW_SIZE {
             b_arr[id * STRUCT_SIZE + x] = row_bytes[x];
@@ -127,8 +126,8 @@ pub fn prepare_setup<T: Field>(
         }
     }
     for (id, (row, idx, val)) in c_vec.iter().enumerate() {
-        let row_bytes: [u8; ROW_SIZE] = unsafe { transmute(row.to_le()) };
-        let idx_bytes: [u8; IDX_SIZE] = unsafe { transmute(idx.to_le()) };
+        let row_bytes: [u8; ROW_SIZE] = row.to_le_bytes();
+        let idx_bytes: [u8; IDX_SIZE] = idx.to_le_bytes();
 
         for x in 0..ROW_SIZE {
             c_arr[id * STRUCT_SIZE + x] = row_bytes[x];
@@ -156,7 +155,7 @@ pub fn prepare_setup<T: Field>(
 
 // proof-system-independent preparation for proof generation
 pub fn prepare_generate_proof<T: Field>(
-    program: ir::Prog<T>,
+    program: &ir::Prog<T>,
     witness: ir::Witness<T>,
 ) -> (Vec<[u8; 32]>, usize, Vec<[u8; 32]>, usize) {
     // recover variable order from the program
@@ -213,7 +212,7 @@ pub fn provide_variable_idx(
 ///
 /// * `prog` - The program the representation is calculated for.
 pub fn r1cs_program<T: Field>(
-    prog: ir::Prog<T>,
+    prog: &ir::Prog<T>,
 ) -> (
     Vec<FlatVariable>,
     usize,
@@ -260,28 +259,28 @@ pub fn r1cs_program<T: Field>(
     let mut c = vec![];
 
     // second pass to convert program to raw sparse vectors
-    for (quad, lin) in prog.statements.into_iter().filter_map(|s| match s {
+    for (quad, lin) in prog.statements.iter().filter_map(|s| match s {
         Statement::Constraint(quad, lin, _) => Some((quad, lin)),
         Statement::Directive(..) => None,
     }) {
         a.push(
             quad.left
                 .0
-                .into_iter()
-                .map(|(k, v)| (variables.get(&k).unwrap().clone(), v))
+                .iter()
+                .map(|(k, v)| (variables.get(k).unwrap().clone(), v.clone()))
                 .collect(),
         );
         b.push(
             quad.right
                 .0
-                .into_iter()
-                .map(|(k, v)| (variables.get(&k).unwrap().clone(), v))
+                .iter()
+                .map(|(k, v)| (variables.get(k).unwrap().clone(), v.clone()))
                 .collect(),
         );
         c.push(
             lin.0
-                .into_iter()
-                .map(|(k, v)| (variables.get(&k).unwrap().clone(), v))
+                .iter()
+                .map(|(k, v)| (variables.get(k).unwrap().clone(), v.clone()))
                 .collect(),
         );
     }
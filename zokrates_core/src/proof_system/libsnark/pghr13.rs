@@ -43,12 +43,12 @@ extern "C" {
 
 impl Backend<Bn128Field, PGHR13> for Libsnark {
     fn generate_proof(
-        program: Prog<Bn128Field>,
+        program: &Prog<Bn128Field>,
         witness: Witness<Bn128Field>,
         proving_key: Vec<u8>,
     ) -> Proof<<PGHR13 as Scheme<Bn128Field>>::ProofPoints> {
         let (public_inputs_arr, public_inputs_length, private_inputs_arr, private_inputs_length) =
-            prepare_generate_proof(program.clone(), witness.clone());
+            prepare_generate_proof(program, witness.clone());
 
         let mut pk_buffer = Buffer::from_vec(&proving_key);
 
@@ -157,7 +157,7 @@ impl Backend<Bn128Field, PGHR13> for Libsnark {
 
 impl NonUniversalBackend<Bn128Field, PGHR13> for Libsnark {
     fn setup(
-        program: Prog<Bn128Field>,
+        program: &Prog<Bn128Field>,
     ) -> SetupKeypair<<PGHR13 as Scheme<Bn128Field>>::VerificationKey> {
         let (a_arr, b_arr, c_arr, a_vec, b_vec, c_vec, num_constraints, num_variables, num_inputs) =
             prepare_setup(program);
@@ -237,15 +237,16 @@ mod tests {
             )],
         };
 
-        let keypair = <Libsnark as NonUniversalBackend<Bn128Field, PGHR13>>::setup(program.clone());
+        let keypair = <Libsnark as NonUniversalBackend<Bn128Field, PGHR13>>::setup(&program);
         let interpreter = Interpreter::default();
 
         let witness = interpreter
             .execute(&program, &vec![Bn128Field::from(42)])
             .unwrap();
 
-        let proof =
-            <Libsnark as Backend<Bn128Field, PGHR13>>::generate_proof(program, witness, keypair.pk);
+        let proof = <Libsnark as Backend<Bn128Field, PGHR13>>::generate_proof(
+            &program, witness, keypair.pk,
+        );
 
         let ans = <Libsnark as Backend<Bn128Field, PGHR13>>::verify(keypair.vk, proof);
         assert!(ans);
@@ -4,12 +4,16 @@ pub mod ark;
 pub mod bellman;
 #[cfg(feature = "libsnark")]
 pub mod libsnark;
+#[cfg(feature = "ark")]
+pub mod srs;
 
 mod scheme;
 mod solidity;
+mod streaming;
 
 pub use self::scheme::*;
 pub use self::solidity::*;
+pub use self::streaming::{BufferedProver, ProverError, StreamingProver};
 
 use crate::ir;
 use serde::de::DeserializeOwned;
@@ -81,7 +85,7 @@ impl ToString for G2Affine {
 
 pub trait Backend<T: Field, S: Scheme<T>> {
     fn generate_proof(
-        program: ir::Prog<T>,
+        program: &ir::Prog<T>,
         witness: ir::Witness<T>,
         proving_key: Vec<u8>,
     ) -> Proof<S::ProofPoints>;
@@ -89,7 +93,7 @@ pub trait Backend<T: Field, S: Scheme<T>> {
     fn verify(vk: S::VerificationKey, proof: Proof<S::ProofPoints>) -> bool;
 }
 pub trait NonUniversalBackend<T: Field, S: NonUniversalScheme<T>>: Backend<T, S> {
-    fn setup(program: ir::Prog<T>) -> SetupKeypair<S::VerificationKey>;
+    fn setup(program: &ir::Prog<T>) -> SetupKeypair<S::VerificationKey>;
 }
 
 pub trait UniversalBackend<T: Field, S: UniversalScheme<T>>: Backend<T, S> {
@@ -97,6 +101,6 @@ pub trait UniversalBackend<T: Field, S: UniversalScheme<T>>: Backend<T, S> {
 
     fn setup(
         srs: Vec<u8>,
-        program: ir::Prog<T>,
+        program: &ir::Prog<T>,
     ) -> Result<SetupKeypair<S::VerificationKey>, String>;
 }
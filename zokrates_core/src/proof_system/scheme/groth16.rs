@@ -1,6 +1,9 @@
 use crate::proof_system::scheme::{NonUniversalScheme, Scheme};
 use crate::proof_system::solidity::{SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB};
-use crate::proof_system::{G1Affine, G2Affine, SolidityCompatibleField, SolidityCompatibleScheme};
+use crate::proof_system::{
+    G1Affine, G2Affine, Proof, SolidityCompatibleField, SolidityCompatibleScheme,
+};
+use num_bigint::BigUint;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use zokrates_field::Field;
@@ -129,6 +132,91 @@ impl<T: SolidityCompatibleField> SolidityCompatibleScheme<T> for G16 {
     }
 }
 
+const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+const BYTES_PER_FIELD_ELEMENT: usize = 32;
+
+/// The size of an EIP-4844 blob in bytes
+pub const BLOB_SIZE: usize = FIELD_ELEMENTS_PER_BLOB * BYTES_PER_FIELD_ELEMENT;
+
+// the BLS12-381 scalar field modulus, which every field element of a blob must be lower than
+const BLS_MODULUS: &str =
+    "52435875175126190479447740508185965837690552500527637822603658699938581184513";
+
+impl G16 {
+    /// Encodes a proof and its public inputs as an EIP-4844 blob
+    ///
+    /// Each 32-byte field element of the blob holds a big-endian value: first the number of public inputs, then the
+    /// coordinates of `a`, `b` and `c` in the order of their JSON representation, then the public inputs. The
+    /// remaining field elements are zero. Only proofs whose coordinates fit in a blob field element, such as bn128
+    /// proofs, can be encoded.
+    ///
+    /// The EVM cannot read the content of a blob, only its versioned hash: a verifier contract still receives the
+    /// proof as calldata, and can check it against the blob using the point evaluation precompile.
+    pub fn proof_to_blob(
+        proof: &Proof<ProofPoints<G1Affine, G2Affine>>,
+    ) -> Result<[u8; BLOB_SIZE], String> {
+        let points = &proof.proof;
+
+        let coordinates = vec![
+            &points.a.0,
+            &points.a.1,
+            &(points.b.0).0,
+            &(points.b.0).1,
+            &(points.b.1).0,
+            &(points.b.1).1,
+            &points.c.0,
+            &points.c.1,
+        ];
+
+        let count = 1 + coordinates.len() + proof.inputs.len();
+        if count > FIELD_ELEMENTS_PER_BLOB {
+            return Err(format!(
+                "Proof with {} public inputs does not fit in a blob",
+                proof.inputs.len()
+            ));
+        }
+
+        let modulus = BigUint::parse_bytes(BLS_MODULUS.as_bytes(), 10).unwrap();
+
+        let elements = std::iter::once(Ok(BigUint::from(proof.inputs.len() as u64)))
+            .chain(
+                coordinates
+                    .into_iter()
+                    .chain(proof.inputs.iter())
+                    .map(|e| parse_element(e)),
+            )
+            .map(|e| {
+                e.and_then(|e| {
+                    if e < modulus {
+                        Ok(e)
+                    } else {
+                        Err(format!("{:#x} does not fit in a blob field element", e))
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut blob = [0u8; BLOB_SIZE];
+
+        for (index, e) in elements.iter().enumerate() {
+            let bytes = e.to_bytes_be();
+            let end = (index + 1) * BYTES_PER_FIELD_ELEMENT;
+            blob[end - bytes.len()..end].copy_from_slice(&bytes);
+        }
+
+        Ok(blob)
+    }
+}
+
+// parses a field element given in hexadecimal with a `0x` prefix, or in decimal
+fn parse_element(e: &str) -> Result<BigUint, String> {
+    match e.strip_prefix("0x") {
+        Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16),
+        None => BigUint::parse_bytes(e.as_bytes(), 10),
+    }
+    .ok_or_else(|| format!("Could not parse field element `{}`", e))
+}
+
 const CONTRACT_TEMPLATE: &str = r#"
 contract Verifier {
     using Pairing for *;
@@ -183,3 +271,62 @@ contract Verifier {
     }
 }
 "#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proof(inputs: Vec<String>) -> Proof<ProofPoints<G1Affine, G2Affine>> {
+        let e = |i: u8| format!("0x{:064x}", i);
+
+        Proof {
+            proof: ProofPoints {
+                a: G1Affine(e(1), e(2)),
+                b: G2Affine((e(3), e(4)), (e(5), e(6))),
+                c: G1Affine(e(7), e(8)),
+            },
+            inputs,
+        }
+    }
+
+    #[test]
+    fn blob() {
+        let blob = G16::proof_to_blob(&proof(vec![
+            "0x2a".to_string(),
+            "0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47".to_string(),
+        ]))
+        .unwrap();
+
+        let element = |i: usize| &blob[i * 32..(i + 1) * 32];
+
+        // the number of inputs
+        assert_eq!(element(0)[31], 2);
+        assert!(element(0)[..31].iter().all(|b| *b == 0));
+
+        // the proof points
+        for i in 1..=8 {
+            assert_eq!(element(i)[31], i as u8);
+            assert!(element(i)[..31].iter().all(|b| *b == 0));
+        }
+
+        // the inputs
+        assert_eq!(element(9)[31], 42);
+        assert_eq!(element(10)[0], 0x30);
+        assert_eq!(element(10)[31], 0x47);
+
+        assert!(blob[11 * 32..].iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn blob_errors() {
+        // too many inputs
+        assert!(G16::proof_to_blob(&proof(vec!["0x0".to_string(); 4088])).is_err());
+        assert!(G16::proof_to_blob(&proof(vec!["0x0".to_string(); 4087])).is_ok());
+
+        // larger than the BLS12-381 scalar field modulus
+        assert!(G16::proof_to_blob(&proof(vec![format!("0x{}", "f".repeat(64))])).is_err());
+
+        // not a number
+        assert!(G16::proof_to_blob(&proof(vec!["0xzz".to_string()])).is_err());
+    }
+}
@@ -0,0 +1,466 @@
+//! Module containing the `SrsCache`, a local store of universal setups shared across circuits
+//!
+//! Entries are keyed by curve and size (as an exponent of two) and live at `<root>/<curve>/<size>.srs`.
+//! The cache does not store hashes: every read checks the entry against `SrsPins`, a list of expected SHA-256
+//! digests kept by the user out of band, so that an entry replaced on disk is reported rather than used.
+//!
+//! Importing copies a universal setup from a local path or a `file://` url. The copy goes through a `<size>.srs.part`
+//! file which is resumed from its current length if a previous import was interrupted. Downloading is not
+//! implemented, as it would pull an http client into the compiler: remote setups are fetched with other tools and
+//! imported from the local copy.
+
+use crate::attestation::sha256_reader;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+const EXTENSION: &str = "srs";
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SrsEntry {
+    pub curve: String,
+    pub size: u32,
+    pub path: PathBuf,
+}
+
+#[derive(Debug)]
+pub enum SrsError {
+    Io(PathBuf, io::Error),
+    InvalidUrl(String),
+    InvalidPin(String),
+    HashMismatch {
+        path: PathBuf,
+        expected: String,
+        found: String,
+    },
+    MissingPin(PathBuf),
+}
+
+impl fmt::Display for SrsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SrsError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            SrsError::InvalidUrl(url) => write!(
+                f,
+                "Unsupported url `{}`, expected a local path or a `file://` url",
+                url
+            ),
+            SrsError::InvalidPin(line) => write!(
+                f,
+                "Invalid pin `{}`, expected `<curve> <size> <sha256>`",
+                line
+            ),
+            SrsError::HashMismatch {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Integrity check failed for {}: expected sha256 {}, found {}",
+                path.display(),
+                expected,
+                found
+            ),
+            SrsError::MissingPin(path) => {
+                write!(f, "No pinned hash found for {}", path.display())
+            }
+        }
+    }
+}
+
+fn io_error<P: AsRef<Path>>(path: P) -> impl FnOnce(io::Error) -> SrsError {
+    let path = path.as_ref().to_path_buf();
+    move |e| SrsError::Io(path, e)
+}
+
+/// The expected hashes of universal setups, by curve and size
+///
+/// Pins are written one per line as `<curve> <size> <sha256>`, the size being an exponent of two. Empty lines and
+/// lines starting with `#` are ignored.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct SrsPins(BTreeMap<(String, u32), String>);
+
+impl SrsPins {
+    pub fn parse(s: &str) -> Result<Self, SrsError> {
+        s.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let invalid = || SrsError::InvalidPin(line.to_string());
+
+                match line.split_whitespace().collect::<Vec<_>>()[..] {
+                    [curve, size, hash] => Ok((
+                        (
+                            curve.to_string(),
+                            size.trim_start_matches("2^")
+                                .parse::<u32>()
+                                .map_err(|_| invalid())?,
+                        ),
+                        hash.to_lowercase(),
+                    )),
+                    _ => Err(invalid()),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(SrsPins)
+    }
+
+    /// Reads the pins in the file at `path`, a missing file holding no pins
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, SrsError> {
+        match fs::read_to_string(path.as_ref()) {
+            Ok(s) => Self::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(SrsError::Io(path.as_ref().to_path_buf(), e)),
+        }
+    }
+
+    pub fn insert<S: Into<String>>(&mut self, curve: &str, size: u32, hash: S) {
+        self.0.insert((curve.to_string(), size), hash.into());
+    }
+
+    pub fn get(&self, curve: &str, size: u32) -> Option<&str> {
+        self.0.get(&(curve.to_string(), size)).map(|h| h.as_str())
+    }
+}
+
+pub struct SrsCache {
+    root: PathBuf,
+}
+
+impl SrsCache {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        SrsCache { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn entry_path(&self, curve: &str, size: u32) -> PathBuf {
+        self.root
+            .join(curve)
+            .join(format!("{}.{}", size, EXTENSION))
+    }
+
+    fn partial_path(&self, curve: &str, size: u32) -> PathBuf {
+        self.root
+            .join(curve)
+            .join(format!("{}.{}.part", size, EXTENSION))
+    }
+
+    /// Returns the content of the entry for `curve` and `size` after checking it against `pins`, or `None` if there
+    /// is no such entry
+    pub fn lookup(
+        &self,
+        curve: &str,
+        size: u32,
+        pins: &SrsPins,
+    ) -> Result<Option<Vec<u8>>, SrsError> {
+        let path = self.entry_path(curve, size);
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        self.verify(curve, size, pins)?;
+
+        fs::read(&path).map(Some).map_err(io_error(&path))
+    }
+
+    /// Checks the entry for `curve` and `size` against its hash in `pins`
+    pub fn verify(&self, curve: &str, size: u32, pins: &SrsPins) -> Result<SrsEntry, SrsError> {
+        let path = self.entry_path(curve, size);
+
+        let expected = pins
+            .get(curve, size)
+            .ok_or_else(|| SrsError::MissingPin(path.clone()))?
+            .to_string();
+
+        let file = File::open(&path).map_err(io_error(&path))?;
+        let found = sha256_reader(BufReader::new(file)).map_err(io_error(&path))?;
+
+        if found != expected {
+            return Err(SrsError::HashMismatch {
+                path,
+                expected,
+                found,
+            });
+        }
+
+        Ok(SrsEntry {
+            curve: curve.to_string(),
+            size,
+            path,
+        })
+    }
+
+    /// Stores `setup` as the entry for `curve` and `size`. Reading it back requires pinning its hash.
+    pub fn insert(&self, curve: &str, size: u32, setup: &[u8]) -> Result<SrsEntry, SrsError> {
+        let partial_path = self.partial_path(curve, size);
+
+        fs::create_dir_all(self.root.join(curve)).map_err(io_error(&self.root))?;
+        fs::write(&partial_path, setup).map_err(io_error(&partial_path))?;
+
+        self.commit(curve, size)
+    }
+
+    /// Copies the universal setup found at `url`, a local path or a `file://` url, to the entry for `curve` and
+    /// `size`, provided its hash is `expected`. An interrupted import is resumed from where it stopped.
+    pub fn import(
+        &self,
+        curve: &str,
+        size: u32,
+        url: &str,
+        expected: &str,
+    ) -> Result<SrsEntry, SrsError> {
+        let source_path = Self::source_path(url)?;
+        let partial_path = self.partial_path(curve, size);
+        let expected = expected.trim().to_lowercase();
+
+        fs::create_dir_all(self.root.join(curve)).map_err(io_error(&self.root))?;
+
+        let mut partial = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&partial_path)
+            .map_err(io_error(&partial_path))?;
+
+        let offset = partial.metadata().map_err(io_error(&partial_path))?.len();
+
+        let mut source = File::open(&source_path).map_err(io_error(&source_path))?;
+        source
+            .seek(SeekFrom::Start(offset))
+            .map_err(io_error(&source_path))?;
+
+        io::copy(&mut source, &mut partial).map_err(io_error(&partial_path))?;
+        partial.flush().map_err(io_error(&partial_path))?;
+
+        let file = File::open(&partial_path).map_err(io_error(&partial_path))?;
+        let found = sha256_reader(BufReader::new(file)).map_err(io_error(&partial_path))?;
+
+        if found != expected {
+            // a corrupted copy cannot be resumed, start over next time
+            fs::remove_file(&partial_path).map_err(io_error(&partial_path))?;
+            return Err(SrsError::HashMismatch {
+                path: source_path,
+                expected,
+                found,
+            });
+        }
+
+        self.commit(curve, size)
+    }
+
+    /// Returns all entries in the cache, without verifying them
+    pub fn list(&self) -> Result<Vec<SrsEntry>, SrsError> {
+        let mut entries = vec![];
+
+        if !self.root.exists() {
+            return Ok(entries);
+        }
+
+        for curve in fs::read_dir(&self.root).map_err(io_error(&self.root))? {
+            let curve = curve.map_err(io_error(&self.root))?.path();
+            if !curve.is_dir() {
+                continue;
+            }
+
+            for entry in fs::read_dir(&curve).map_err(io_error(&curve))? {
+                let path = entry.map_err(io_error(&curve))?.path();
+
+                if path.extension().and_then(|e| e.to_str()) != Some(EXTENSION) {
+                    continue;
+                }
+
+                let size = match path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .and_then(|s| s.parse::<u32>().ok())
+                {
+                    Some(size) => size,
+                    None => continue,
+                };
+
+                entries.push(SrsEntry {
+                    curve: curve.file_name().unwrap().to_string_lossy().to_string(),
+                    size,
+                    path,
+                });
+            }
+        }
+
+        entries.sort_by(|a, b| (&a.curve, a.size).cmp(&(&b.curve, b.size)));
+
+        Ok(entries)
+    }
+
+    // move a complete partial file to its entry
+    fn commit(&self, curve: &str, size: u32) -> Result<SrsEntry, SrsError> {
+        let partial_path = self.partial_path(curve, size);
+        let path = self.entry_path(curve, size);
+
+        fs::rename(&partial_path, &path).map_err(io_error(&path))?;
+
+        Ok(SrsEntry {
+            curve: curve.to_string(),
+            size,
+            path,
+        })
+    }
+
+    fn source_path(url: &str) -> Result<PathBuf, SrsError> {
+        match url.find("://") {
+            None => Ok(PathBuf::from(url)),
+            Some(_) => url
+                .strip_prefix("file://")
+                .map(PathBuf::from)
+                .ok_or_else(|| SrsError::InvalidUrl(url.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::sha256;
+
+    fn cache(name: &str) -> SrsCache {
+        let root =
+            std::env::temp_dir().join(format!("zokrates_srs_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        SrsCache::new(root)
+    }
+
+    fn pins(curve: &str, size: u32, setup: &[u8]) -> SrsPins {
+        let mut pins = SrsPins::default();
+        pins.insert(curve, size, sha256(setup));
+        pins
+    }
+
+    #[test]
+    fn miss_then_hit() {
+        let cache = cache("miss_then_hit");
+        let pins = pins("bn128", 4, &[1, 2, 3]);
+
+        assert!(cache.lookup("bn128", 4, &pins).unwrap().is_none());
+
+        let entry = cache.insert("bn128", 4, &[1, 2, 3]).unwrap();
+
+        assert_eq!(
+            cache.lookup("bn128", 4, &pins).unwrap(),
+            Some(vec![1, 2, 3])
+        );
+        assert!(cache.lookup("bn128", 5, &pins).unwrap().is_none());
+        assert!(cache.lookup("bls12_377", 4, &pins).unwrap().is_none());
+        assert_eq!(cache.list().unwrap(), vec![entry]);
+
+        fs::remove_dir_all(cache.root()).unwrap();
+    }
+
+    #[test]
+    fn tampered_entry_is_rejected() {
+        let cache = cache("tampered_entry_is_rejected");
+        let pins = pins("bn128", 4, &[1, 2, 3]);
+
+        cache.insert("bn128", 4, &[1, 2, 3]).unwrap();
+        fs::write(cache.entry_path("bn128", 4), &[1, 2, 4]).unwrap();
+
+        assert!(matches!(
+            cache.lookup("bn128", 4, &pins),
+            Err(SrsError::HashMismatch { .. })
+        ));
+
+        fs::remove_dir_all(cache.root()).unwrap();
+    }
+
+    #[test]
+    fn unpinned_entry_is_rejected() {
+        let cache = cache("unpinned_entry_is_rejected");
+
+        cache.insert("bn128", 4, &[1, 2, 3]).unwrap();
+
+        assert!(matches!(
+            cache.lookup("bn128", 4, &SrsPins::default()),
+            Err(SrsError::MissingPin(_))
+        ));
+
+        fs::remove_dir_all(cache.root()).unwrap();
+    }
+
+    #[test]
+    fn import_with_wrong_hash_is_rejected() {
+        let cache = cache("import_with_wrong_hash_is_rejected");
+        let source = cache.root().join("source");
+
+        fs::create_dir_all(cache.root()).unwrap();
+        fs::write(&source, &[1, 2, 3]).unwrap();
+
+        let wrong = sha256(&[1, 2]);
+
+        assert!(matches!(
+            cache.import("bn128", 4, source.to_str().unwrap(), &wrong),
+            Err(SrsError::HashMismatch { .. })
+        ));
+        assert!(!cache.entry_path("bn128", 4).exists());
+        assert!(!cache.partial_path("bn128", 4).exists());
+
+        fs::remove_dir_all(cache.root()).unwrap();
+    }
+
+    #[test]
+    fn resume_import() {
+        let cache = cache("resume_import");
+        let source = cache.root().join("source");
+        let content: Vec<u8> = (0..=255).collect();
+
+        fs::create_dir_all(cache.root().join("bn128")).unwrap();
+        fs::write(&source, &content).unwrap();
+
+        // a previous import stopped after the first 100 bytes
+        fs::write(cache.partial_path("bn128", 8), &content[..100]).unwrap();
+
+        let hash = sha256(&content);
+        let url = format!("file://{}", source.display());
+
+        cache.import("bn128", 8, &url, &hash).unwrap();
+
+        assert_eq!(
+            cache
+                .lookup("bn128", 8, &pins("bn128", 8, &content))
+                .unwrap(),
+            Some(content)
+        );
+        assert!(!cache.partial_path("bn128", 8).exists());
+
+        fs::remove_dir_all(cache.root()).unwrap();
+    }
+
+    #[test]
+    fn remote_urls_are_not_supported() {
+        assert!(matches!(
+            SrsCache::source_path("https://example.com/srs"),
+            Err(SrsError::InvalidUrl(_))
+        ));
+    }
+
+    #[test]
+    fn parse_pins() {
+        let pins =
+            SrsPins::parse("# powers of tau\nbn128 20 ABCD\n\nbls12_377 2^8 ef01\n").unwrap();
+
+        assert_eq!(pins.get("bn128", 20), Some("abcd"));
+        assert_eq!(pins.get("bls12_377", 8), Some("ef01"));
+        assert_eq!(pins.get("bn128", 8), None);
+
+        assert!(matches!(
+            SrsPins::parse("bn128 20"),
+            Err(SrsError::InvalidPin(_))
+        ));
+        assert!(matches!(
+            SrsPins::parse("bn128 big abcd"),
+            Err(SrsError::InvalidPin(_))
+        ));
+    }
+}
@@ -0,0 +1,210 @@
+//! Module containing the `StreamingProver` interface, for proving backends which receive the statements of a program
+//! one at a time rather than a complete `Prog`
+//!
+//! `BufferedProver` is a reference implementation on top of any `Backend`: it checks each constraint against the
+//! witness as it is received, so that an invalid witness is reported at the faulty statement, and proves the buffered
+//! program when finalized.
+
+use crate::flat_absy::{FlatParameter, FlatVariable};
+use crate::ir::{Prog, Statement, Witness};
+use crate::proof_system::{Backend, Proof, Scheme};
+use std::fmt;
+use std::marker::PhantomData;
+use zokrates_field::Field;
+
+#[derive(Debug, PartialEq)]
+pub enum ProverError {
+    /// a statement reads a variable which is not assigned in the witness
+    MissingAssignment(FlatVariable),
+    /// a constraint does not hold for the witness
+    UnsatisfiedConstraint(String),
+}
+
+impl fmt::Display for ProverError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProverError::MissingAssignment(v) => {
+                write!(f, "Variable {} is not assigned in the witness", v)
+            }
+            ProverError::UnsatisfiedConstraint(s) => {
+                write!(f, "Constraint `{}` is not satisfied by the witness", s)
+            }
+        }
+    }
+}
+
+/// A prover receiving the statements of a program in order
+pub trait StreamingProver<T: Field, S: Scheme<T>> {
+    /// Receives the next statement of the program
+    fn feed_statement(&mut self, s: &Statement<T>) -> Result<(), ProverError>;
+
+    /// Proves the program made of the statements received so far
+    fn finalize(self) -> Result<Proof<S::ProofPoints>, ProverError>;
+}
+
+/// A `StreamingProver` buffering the statements it receives, and proving them with the backend `B` when finalized
+pub struct BufferedProver<T, S, B> {
+    arguments: Vec<FlatParameter>,
+    returns: Vec<FlatVariable>,
+    statements: Vec<Statement<T>>,
+    witness: Witness<T>,
+    proving_key: Vec<u8>,
+    _scheme: PhantomData<S>,
+    _backend: PhantomData<B>,
+}
+
+impl<T: Field, S: Scheme<T>, B: Backend<T, S>> BufferedProver<T, S, B> {
+    /// Creates a prover for a program with `arguments` and `returns`, whose statements are then fed one by one
+    pub fn new(
+        arguments: Vec<FlatParameter>,
+        returns: Vec<FlatVariable>,
+        witness: Witness<T>,
+        proving_key: Vec<u8>,
+    ) -> Self {
+        BufferedProver {
+            arguments,
+            returns,
+            statements: vec![],
+            witness,
+            proving_key,
+            _scheme: PhantomData,
+            _backend: PhantomData,
+        }
+    }
+}
+
+impl<T: Field, S: Scheme<T>, B: Backend<T, S>> StreamingProver<T, S> for BufferedProver<T, S, B> {
+    fn feed_statement(&mut self, s: &Statement<T>) -> Result<(), ProverError> {
+        // directives only compute the witness, which is given
+        if let Statement::Constraint(quad, lin, _) = s {
+            if let Some((v, _)) = quad
+                .left
+                .0
+                .iter()
+                .chain(quad.right.0.iter())
+                .chain(lin.0.iter())
+                .find(|(v, _)| !self.witness.0.contains_key(v))
+            {
+                return Err(ProverError::MissingAssignment(*v));
+            }
+
+            // all variables are assigned, so evaluation succeeds
+            let left = quad.evaluate(&self.witness.0).unwrap();
+            let right = lin.evaluate(&self.witness.0).unwrap();
+
+            if left != right {
+                return Err(ProverError::UnsatisfiedConstraint(s.to_string()));
+            }
+        }
+
+        self.statements.push(s.clone());
+
+        Ok(())
+    }
+
+    fn finalize(self) -> Result<Proof<S::ProofPoints>, ProverError> {
+        let program = Prog {
+            arguments: self.arguments,
+            returns: self.returns,
+            statements: self.statements,
+        };
+
+        Ok(B::generate_proof(&program, self.witness, self.proving_key))
+    }
+}
+
+#[cfg(all(test, feature = "bellman"))]
+mod tests {
+    use super::*;
+    use crate::ir::{Interpreter, QuadComb};
+    use crate::proof_system::bellman::Bellman;
+    use crate::proof_system::groth16::G16;
+    use crate::proof_system::NonUniversalBackend;
+    use zokrates_field::Bn128Field;
+
+    // def main(private _0) -> (1):
+    //     _1 == _0 * _0
+    //     ~out_0 == _1
+    //     return ~out_0
+    fn program() -> Prog<Bn128Field> {
+        Prog {
+            arguments: vec![FlatParameter::private(FlatVariable::new(0))],
+            returns: vec![FlatVariable::public(0)],
+            statements: vec![
+                Statement::definition(
+                    FlatVariable::new(1),
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::new(0).into(),
+                    ),
+                ),
+                Statement::definition(FlatVariable::public(0), FlatVariable::new(1)),
+            ],
+        }
+    }
+
+    fn buffered_prover(
+        program: &Prog<Bn128Field>,
+        witness: Witness<Bn128Field>,
+        proving_key: Vec<u8>,
+    ) -> BufferedProver<Bn128Field, G16, Bellman> {
+        BufferedProver::new(
+            program.arguments.clone(),
+            program.returns.clone(),
+            witness,
+            proving_key,
+        )
+    }
+
+    #[test]
+    fn prove_streamed_statements() {
+        let program = program();
+
+        let keypair = <Bellman as NonUniversalBackend<Bn128Field, G16>>::setup(&program);
+        let witness = Interpreter::default()
+            .execute(&program, &[Bn128Field::from(3)])
+            .unwrap();
+
+        let mut prover = buffered_prover(&program, witness, keypair.pk);
+
+        for s in &program.statements {
+            prover.feed_statement(s).unwrap();
+        }
+
+        let proof = prover.finalize().unwrap();
+
+        assert!(<Bellman as Backend<Bn128Field, G16>>::verify(
+            keypair.vk, proof
+        ));
+    }
+
+    #[test]
+    fn reject_invalid_witness() {
+        let program = program();
+
+        let mut witness = Interpreter::default()
+            .execute(&program, &[Bn128Field::from(3)])
+            .unwrap();
+
+        // claim that the square of 3 is 8
+        witness.0.insert(FlatVariable::new(1), Bn128Field::from(8));
+
+        let mut prover = buffered_prover(&program, witness.clone(), vec![]);
+
+        assert_eq!(
+            prover.feed_statement(&program.statements[0]),
+            Err(ProverError::UnsatisfiedConstraint(
+                program.statements[0].to_string()
+            ))
+        );
+
+        witness.0.remove(&FlatVariable::public(0));
+
+        let mut prover = buffered_prover(&program, witness, vec![]);
+
+        assert_eq!(
+            prover.feed_statement(&program.statements[1]),
+            Err(ProverError::MissingAssignment(FlatVariable::public(0)))
+        );
+    }
+}
@@ -4,8 +4,11 @@
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
 //! @date 2017
 
+use crate::absy::pattern::{pattern_to_string, DecisionTree};
 use crate::absy::Identifier;
 use crate::absy::*;
+use crate::compile::Warning;
+use crate::typed_absy::phantom::{phantom_type, PHANTOM};
 use crate::typed_absy::types::GGenericsAssignment;
 use crate::typed_absy::*;
 use crate::typed_absy::{DeclarationParameter, DeclarationVariable, Variable};
@@ -58,6 +61,16 @@ impl ErrorInner {
 type TypeMap<'ast> = HashMap<OwnedModuleId, HashMap<UserTypeId, DeclarationType<'ast>>>;
 type ConstantMap<'ast> =
     HashMap<OwnedModuleId, HashMap<ConstantIdentifier<'ast>, DeclarationType<'ast>>>;
+type ImplementationMap<'ast> = HashMap<StructLocation, Implementations<'ast>>;
+
+/// The functions declared in the `impl` blocks of a struct
+#[derive(Debug, Default, Clone)]
+struct Implementations<'ast> {
+    /// The implementations of each operator trait
+    operators: HashMap<Identifier<'ast>, HashSet<DeclarationFunctionKey<'ast>>>,
+    /// The methods of each name
+    methods: HashMap<Identifier<'ast>, HashSet<DeclarationFunctionKey<'ast>>>,
+}
 
 /// The global state of the program during semantic checks
 #[derive(Debug)]
@@ -70,6 +83,12 @@ struct State<'ast, T> {
     types: TypeMap<'ast>,
     // The user-defined constants
     constants: ConstantMap<'ast>,
+    /// The deprecated functions, under each key they can be called with
+    deprecations: HashMap<DeclarationFunctionKey<'ast>, Deprecation<'ast>>,
+    /// The functions of `impl` blocks, by the canonical location of their struct
+    implementations: ImplementationMap<'ast>,
+    /// The warnings emitted so far
+    warnings: Vec<Warning>,
 }
 
 /// A symbol for a given name: either a type or a group of functions. Not both!
@@ -145,10 +164,16 @@ impl<'ast, T: Field> State<'ast, T> {
             typed_modules: HashMap::new(),
             types: HashMap::new(),
             constants: HashMap::new(),
+            deprecations: HashMap::new(),
+            implementations: HashMap::new(),
+            warnings: vec![],
         }
     }
 }
 
+/// The operators which can be implemented for structs, as in `impl Add for Foo`
+const OPERATOR_TRAITS: [&str; 4] = ["Add", "Sub", "Mul", "Div"];
+
 /// A function query in the current module.
 #[derive(Debug)]
 struct FunctionQuery<'ast, T> {
@@ -226,19 +251,23 @@ impl<'ast, T: Field> FunctionQuery<'ast, T> {
 
     /// match a `FunctionKey` against this `FunctionQuery`
     fn match_func(&self, func: &DeclarationFunctionKey<'ast>) -> bool {
-        self.id == func.id
-            && self.generics_count.map(|count| count == func.signature.generics.len()).unwrap_or(true) // we do not look at the values here, this will be checked when inlining anyway
-            && self.inputs.len() == func.signature.inputs.len()
+        self.id == func.id && self.match_signature(&func.signature)
+    }
+
+    /// match a signature against the generics, inputs and outputs of this `FunctionQuery`
+    fn match_signature(&self, signature: &DeclarationSignature<'ast>) -> bool {
+        self.generics_count.map(|count| count == signature.generics.len()).unwrap_or(true) // we do not look at the values here, this will be checked when inlining anyway
+            && self.inputs.len() == signature.inputs.len()
             && self
                 .inputs
                 .iter()
-                .zip(func.signature.inputs.iter())
+                .zip(signature.inputs.iter())
                 .all(|(input_ty, sig_ty)| input_ty.can_be_specialized_to(&sig_ty))
-            && self.outputs.len() == func.signature.outputs.len()
+            && self.outputs.len() == signature.outputs.len()
             && self
                 .outputs
                 .iter()
-                .zip(func.signature.outputs.iter())
+                .zip(signature.outputs.iter())
                 .all(|(output_ty, sig_ty)| {
                     output_ty
                         .as_ref()
@@ -292,7 +321,12 @@ pub struct Checker<'ast, T> {
     return_types: Option<Vec<DeclarationType<'ast>>>,
     scope: HashSet<ScopedVariable<'ast, T>>,
     functions: HashSet<DeclarationFunctionKey<'ast>>,
+    deprecations: HashMap<DeclarationFunctionKey<'ast>, Deprecation<'ast>>,
+    implementations: ImplementationMap<'ast>,
+    allow_deprecated: bool,
+    warnings: Vec<Warning>,
     level: usize,
+    match_count: usize,
 }
 
 impl<'ast, T: Field> Checker<'ast, T> {
@@ -301,7 +335,12 @@ impl<'ast, T: Field> Checker<'ast, T> {
             return_types: None,
             scope: HashSet::new(),
             functions: HashSet::new(),
+            deprecations: HashMap::new(),
+            implementations: HashMap::new(),
+            allow_deprecated: false,
+            warnings: vec![],
             level: 0,
+            match_count: 0,
         }
     }
 
@@ -310,14 +349,16 @@ impl<'ast, T: Field> Checker<'ast, T> {
     /// # Arguments
     ///
     /// * `prog` - The `Program` to be checked
-    pub fn check(prog: Program<'ast>) -> Result<TypedProgram<'ast, T>, Vec<Error>> {
+    ///
+    /// Returns the typed program along with the warnings emitted while checking it
+    pub fn check(prog: Program<'ast>) -> Result<(TypedProgram<'ast, T>, Vec<Warning>), Vec<Error>> {
         Checker::new().check_program(prog)
     }
 
     fn check_program(
         &mut self,
         program: Program<'ast>,
-    ) -> Result<TypedProgram<'ast, T>, Vec<Error>> {
+    ) -> Result<(TypedProgram<'ast, T>, Vec<Warning>), Vec<Error>> {
         let mut state = State::new(program.modules);
 
         let mut errors = vec![];
@@ -343,10 +384,13 @@ impl<'ast, T: Field> Checker<'ast, T> {
             },
         )?;
 
-        Ok(TypedProgram {
-            main: program.main,
-            modules: state.typed_modules,
-        })
+        Ok((
+            TypedProgram {
+                main: program.main,
+                modules: state.typed_modules,
+            },
+            state.warnings,
+        ))
     }
 
     fn check_constant_definition(
@@ -594,8 +638,36 @@ impl<'ast, T: Field> Checker<'ast, T> {
                 }
             }
             Symbol::Here(SymbolDefinition::Function(f)) => {
-                match self.check_function(f, module_id, state) {
-                    Ok(funct) => {
+                let deprecated = f.value.deprecated.clone();
+                let implementation = f.value.implementation;
+
+                match self.check_function(f, module_id, state).and_then(|funct| {
+                    implementation
+                        .map(|i| {
+                            Self::check_implementation(&i, &funct.signature, module_id, state)
+                                .map(|location| (i, location))
+                        })
+                        .transpose()
+                        .map(|implementation| (funct, implementation))
+                        .map_err(|message| {
+                            vec![ErrorInner {
+                                pos: Some(pos),
+                                message,
+                            }]
+                        })
+                }) {
+                    // operators only resolve to the functions of `impl` blocks, so their names are reserved
+                    Ok((_, None)) if OPERATOR_TRAITS.contains(&declaration.id) => errors.push(
+                        ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                                "`{0}` cannot be declared as a function, implement it for a struct with `impl {0} for`",
+                                declaration.id
+                            ),
+                        }
+                        .in_file(module_id),
+                    ),
+                    Ok((funct, implementation)) => {
                         match symbol_unifier
                             .insert_function(declaration.id, funct.signature.clone())
                         {
@@ -612,13 +684,39 @@ impl<'ast, T: Field> Checker<'ast, T> {
                             true => {}
                         };
 
-                        self.functions.insert(
-                            DeclarationFunctionKey::with_location(
-                                module_id.to_path_buf(),
-                                declaration.id,
-                            )
-                            .signature(funct.signature.clone()),
-                        );
+                        let key = DeclarationFunctionKey::with_location(
+                            module_id.to_path_buf(),
+                            declaration.id,
+                        )
+                        .signature(funct.signature.clone());
+
+                        if let Some(deprecation) = deprecated {
+                            self.deprecations.insert(key.clone(), deprecation.clone());
+                            state.deprecations.insert(key.clone(), deprecation);
+                        }
+
+                        match implementation {
+                            // the functions of `impl` blocks are not callable by name, they are only looked up for their struct
+                            Some((implementation, location)) => {
+                                for implementations in
+                                    vec![&mut self.implementations, &mut state.implementations]
+                                {
+                                    let implementations =
+                                        implementations.entry(location.clone()).or_default();
+
+                                    let (functions, id) = match implementation.operator {
+                                        Some(operator) => (&mut implementations.operators, operator),
+                                        None => (&mut implementations.methods, implementation.name),
+                                    };
+
+                                    functions.entry(id).or_default().insert(key.clone());
+                                }
+                            }
+                            None => {
+                                self.functions.insert(key);
+                            }
+                        }
+
                         functions.insert(
                             DeclarationFunctionKey::with_location(
                                 module_id.to_path_buf(),
@@ -639,6 +737,9 @@ impl<'ast, T: Field> Checker<'ast, T> {
 
                 match Checker::new().check_module(&import.module_id, state) {
                     Ok(()) => {
+                        // the implementations of the imported module apply to its structs wherever they are used
+                        self.implementations = state.implementations.clone();
+
                         // find candidates in the checked module
                         let function_candidates: Vec<_> = state
                             .typed_modules
@@ -764,6 +865,12 @@ impl<'ast, T: Field> Checker<'ast, T> {
 
                                     let local_key = candidate.clone().id(declaration.id).module(module_id.to_path_buf());
 
+                                    // the function stays deprecated under its new name, including for the modules importing it from here
+                                    if let Some(deprecation) = state.deprecations.get(&candidate).cloned() {
+                                        self.deprecations.insert(local_key.clone(), deprecation.clone());
+                                        state.deprecations.insert(local_key.clone(), deprecation);
+                                    }
+
                                     self.functions.insert(local_key.clone());
                                     functions.insert(
                                         local_key,
@@ -857,6 +964,8 @@ impl<'ast, T: Field> Checker<'ast, T> {
             }
         };
 
+        state.warnings.append(&mut self.warnings);
+
         // insert into typed_modules if we checked anything
         if let Some(typed_module) = to_insert {
             // there should be no checked module at that key just yet, if there is we have a colision or we checked something twice
@@ -898,6 +1007,48 @@ impl<'ast, T: Field> Checker<'ast, T> {
         }
     }
 
+    /// Checks that a function of signature `signature` fits the `impl` block it is declared in, returning the canonical
+    /// location of the struct of the block
+    fn check_implementation(
+        implementation: &Implementation<'ast>,
+        signature: &DeclarationSignature<'ast>,
+        module_id: &ModuleId,
+        state: &State<'ast, T>,
+    ) -> Result<StructLocation, String> {
+        let location = match state
+            .types
+            .get(module_id)
+            .and_then(|types| types.get(implementation.ty))
+        {
+            Some(DeclarationType::Struct(ty)) => ty.canonical_location.clone(),
+            _ => return Err(format!("Undefined type {}", implementation.ty)),
+        };
+
+        let is_struct = |ty: &DeclarationType<'ast>| matches!(ty, DeclarationType::Struct(ty) if ty.canonical_location == location);
+
+        match implementation.operator {
+            // both operands of the operator have to be of the type of the struct
+            Some(operator) => match signature.inputs.len() == 2
+                && signature.inputs.iter().all(is_struct)
+                && signature.outputs.len() == 1
+            {
+                true => Ok(location),
+                false => Err(format!(
+                    "Implementation of `{0}` for `{1}` must take two `{1}` arguments and return a single value",
+                    operator, implementation.ty
+                )),
+            },
+            // the receiver of a method call is its first argument, so it has to be of the type of the struct
+            None => match signature.inputs.first().map(is_struct).unwrap_or(false) {
+                true => Ok(location),
+                false => Err(format!(
+                    "Method `{0}` of `{1}` must take a `{1}` as its first argument",
+                    implementation.name, implementation.ty
+                )),
+            },
+        }
+    }
+
     fn check_function(
         &mut self,
         funct_node: FunctionNode<'ast>,
@@ -942,6 +1093,16 @@ impl<'ast, T: Field> Checker<'ast, T> {
 
                     let arg = arg.value;
 
+                    if arg.deferred && arg.private {
+                        errors.push(ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                                "Deferred parameter `{}` must be public",
+                                arg.id.value.id
+                            ),
+                        });
+                    }
+
                     let decl_v =
                         DeclarationVariable::with_id_and_type(arg.id.value.id, decl_ty.clone());
 
@@ -959,6 +1120,7 @@ impl<'ast, T: Field> Checker<'ast, T> {
                     arguments_checked.push(DeclarationParameter {
                         id: decl_v,
                         private: arg.private,
+                        deferred: arg.deferred,
                     });
                 }
 
@@ -967,7 +1129,7 @@ impl<'ast, T: Field> Checker<'ast, T> {
                 for stat in funct.statements.into_iter() {
                     let pos = Some(stat.pos());
 
-                    if let Statement::Return(..) = stat.value {
+                    if stat.value.is_return() {
                         if found_return {
                             errors.push(ErrorInner {
                                 pos,
@@ -1186,6 +1348,9 @@ impl<'ast, T: Field> Checker<'ast, T> {
                     size,
                 )))
             }
+            UnresolvedType::User(id, generics) if Self::is_phantom_type(&id, module_id, types) => {
+                Self::check_phantom_tag(generics, pos, module_id, types).map(Type::Struct)
+            }
             UnresolvedType::User(id, generics) => {
                 let declaration_type =
                     types
@@ -1357,6 +1522,12 @@ impl<'ast, T: Field> Checker<'ast, T> {
                     checked_size,
                 )))
             }
+            UnresolvedType::User(id, generics)
+                if Self::is_phantom_type(&id, module_id, &state.types) =>
+            {
+                Self::check_phantom_tag(generics, pos, module_id, &state.types)
+                    .map(DeclarationType::Struct)
+            }
             UnresolvedType::User(id, generics) => {
                 let declared_ty = state
                     .types
@@ -1651,9 +1822,11 @@ impl<'ast, T: Field> Checker<'ast, T> {
             }
             Statement::Definition(assignee, expr) => {
                 // we create multidef when rhs is a function call to benefit from inference
-                // check rhs is not a function call here
-                if let Expression::FunctionCall(..) = expr.value {
-                    panic!("Parser should not generate Definition where the right hand side is a FunctionCall")
+                // check rhs is not a function call here, unless it is the `lookup` or `phantom` intrinsic
+                if let Expression::FunctionCall(fun_id, ..) = expr.value {
+                    if !self.is_lookup_intrinsic(fun_id) && !self.is_phantom_intrinsic(fun_id) {
+                        panic!("Parser should not generate Definition where the right hand side is a FunctionCall")
+                    }
                 }
 
                 // check the expression to be assigned
@@ -1700,13 +1873,36 @@ impl<'ast, T: Field> Checker<'ast, T> {
                 .map(|rhs| TypedStatement::Definition(var, rhs))
                 .map_err(|e| vec![e])
             }
-            Statement::Assertion(e) => {
+            Statement::Assertion(e, error) => {
+                let e = self
+                    .check_expression(e, module_id, types)
+                    .map_err(|e| vec![e])?;
+
+                match e {
+                    TypedExpression::Boolean(e) => Ok(TypedStatement::Assertion(e, error)),
+                    e => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected {} to be of type bool, found {}",
+                            e,
+                            e.get_type(),
+                        ),
+                    }),
+                }
+                .map_err(|e| vec![e])
+            }
+            Statement::StaticAssertion(e, condition_text) => {
+                let source = StaticAssertionSource {
+                    condition_text: condition_text.to_string(),
+                    span: pos,
+                };
+
                 let e = self
                     .check_expression(e, module_id, types)
                     .map_err(|e| vec![e])?;
 
                 match e {
-                    TypedExpression::Boolean(e) => Ok(TypedStatement::Assertion(e)),
+                    TypedExpression::Boolean(e) => Ok(TypedStatement::StaticAssertion(e, source)),
                     e => Err(ErrorInner {
                         pos: Some(pos),
                         message: format!(
@@ -1727,10 +1923,45 @@ impl<'ast, T: Field> Checker<'ast, T> {
 
                 res
             }
-            Statement::MultipleDefinition(assignees, rhs) => {
+            Statement::AllowDeprecated(box statement) => {
+                let allow_deprecated = std::mem::replace(&mut self.allow_deprecated, true);
+
+                let res = self.check_statement(statement, module_id, types);
+
+                self.allow_deprecated = allow_deprecated;
+
+                res
+            }
+            Statement::MultipleDefinition(mut assignees, rhs) => {
+                // `lookup` and `phantom` are not functions, their single result is checked as a definition
+                if let Expression::FunctionCall(fun_id, ..) = rhs.value {
+                    if assignees.len() == 1
+                        && (self.is_lookup_intrinsic(fun_id) || self.is_phantom_intrinsic(fun_id))
+                    {
+                        return self.check_statement(
+                            Node::new(
+                                pos.0,
+                                pos.1,
+                                Statement::Definition(assignees.pop().unwrap(), rhs),
+                            ),
+                            module_id,
+                            types,
+                        );
+                    }
+                }
+
+                let call_pos = rhs.pos();
+
                 match rhs.value {
                     // Right side has to be a function call
-                    Expression::FunctionCall(fun_id, generics, arguments) => {
+                    call @ Expression::FunctionCall(..) | call @ Expression::MethodCall(..) => {
+                        // the receiver of a method call is its first argument
+                        let (fun_id, generics, arguments, is_method) = match call {
+                            Expression::FunctionCall(fun_id, generics, arguments) => (fun_id, generics, arguments, false),
+                            Expression::MethodCall(box receiver, method, generics, arguments) => (method, generics, std::iter::once(receiver).chain(arguments).collect(), true),
+                            _ => unreachable!(),
+                        };
+
                         // check the generic arguments, if any
                         let generics_checked: Option<Vec<Option<UExpression<'ast, T>>>> = generics
                             .map(|generics|
@@ -1778,7 +2009,10 @@ impl<'ast, T: Field> Checker<'ast, T> {
 
                         let query = FunctionQuery::new(&fun_id, &generics_checked, &arguments_types, &assignee_types);
 
-                        let functions = self.find_functions(&query);
+                        let functions = match is_method {
+                            true => self.find_methods(&query),
+                            false => self.find_functions(&query),
+                        };
 
                         match functions.len() {
                     		// the function has to be defined
@@ -1787,6 +2021,8 @@ impl<'ast, T: Field> Checker<'ast, T> {
                                 let mut functions = functions;
                                 let f = functions.pop().unwrap();
 
+                                self.check_deprecation(&f, call_pos);
+
                                 let arguments_checked = arguments_checked.into_iter().zip(f.signature.inputs.iter()).map(|(a, t)| TypedExpression::align_to_type(a, t)).collect::<Result<Vec<_>, _>>().map_err(|e| vec![ErrorInner {
                                     pos: Some(pos),
                                     message: format!("Expected function call argument to be of type {}, found {} of type {}", e.1, e.0, e.0.get_type())
@@ -1986,6 +2222,12 @@ impl<'ast, T: Field> Checker<'ast, T> {
                 let e1_checked = self.check_expression(e1, module_id, types)?;
                 let e2_checked = self.check_expression(e2, module_id, types)?;
 
+                if let Some(e) =
+                    self.check_operator_overload("Add", &e1_checked, &e2_checked, module_id, pos)?
+                {
+                    return Ok(e);
+                }
+
                 use self::TypedExpression::*;
 
                 let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
@@ -2021,6 +2263,12 @@ impl<'ast, T: Field> Checker<'ast, T> {
                 let e1_checked = self.check_expression(e1, module_id, types)?;
                 let e2_checked = self.check_expression(e2, module_id, types)?;
 
+                if let Some(e) =
+                    self.check_operator_overload("Sub", &e1_checked, &e2_checked, module_id, pos)?
+                {
+                    return Ok(e);
+                }
+
                 use self::TypedExpression::*;
 
                 let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
@@ -2052,6 +2300,12 @@ impl<'ast, T: Field> Checker<'ast, T> {
                 let e1_checked = self.check_expression(e1, module_id, types)?;
                 let e2_checked = self.check_expression(e2, module_id, types)?;
 
+                if let Some(e) =
+                    self.check_operator_overload("Mul", &e1_checked, &e2_checked, module_id, pos)?
+                {
+                    return Ok(e);
+                }
+
                 use self::TypedExpression::*;
 
                 let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
@@ -2087,6 +2341,12 @@ impl<'ast, T: Field> Checker<'ast, T> {
                 let e1_checked = self.check_expression(e1, module_id, types)?;
                 let e2_checked = self.check_expression(e2, module_id, types)?;
 
+                if let Some(e) =
+                    self.check_operator_overload("Div", &e1_checked, &e2_checked, module_id, pos)?
+                {
+                    return Ok(e);
+                }
+
                 use self::TypedExpression::*;
 
                 let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
@@ -2264,6 +2524,115 @@ impl<'ast, T: Field> Checker<'ast, T> {
                     }),
                 }
             }
+            Expression::Match(expressions, arms) => {
+                let elements = expressions
+                    .into_iter()
+                    .map(|e| match self.check_expression(e, module_id, types)? {
+                        TypedExpression::Boolean(e) => Ok(e),
+                        e => Err(ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                                "Expected {} in `match` expression to be a boolean, found {}",
+                                e,
+                                e.get_type()
+                            ),
+                        }),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if let Some((pattern, e)) = arms.iter().find(|(p, _)| p.len() != elements.len()) {
+                    return Err(ErrorInner {
+                        pos: Some(e.pos()),
+                        message: format!(
+                            "Expected pattern {} in `match` expression to have {} elements, found {}",
+                            pattern_to_string(pattern),
+                            elements.len(),
+                            pattern.len()
+                        ),
+                    });
+                }
+
+                let patterns: Vec<_> = arms.iter().map(|(p, _)| p.clone()).collect();
+
+                let tree = DecisionTree::build(&patterns, elements.len()).map_err(|missing| {
+                    ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Non-exhaustive patterns in `match` expression: {} not covered",
+                            pattern_to_string(&missing.into_iter().map(Some).collect::<Vec<_>>())
+                        ),
+                    }
+                })?;
+
+                let reached = tree.arms();
+
+                if let Some((pattern, e)) = arms
+                    .iter()
+                    .enumerate()
+                    .find(|(i, _)| !reached.contains(i))
+                    .map(|(_, arm)| arm)
+                {
+                    return Err(ErrorInner {
+                        pos: Some(e.pos()),
+                        message: format!(
+                            "Unreachable pattern {} in `match` expression",
+                            pattern_to_string(pattern)
+                        ),
+                    });
+                }
+
+                let arms = arms
+                    .into_iter()
+                    .map(|(_, e)| self.check_expression(e, module_id, types))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                // align all arms to the type they have in common, if any
+                let aligned = arms.iter().skip(1).try_fold(arms[0].clone(), |aligned, e| {
+                    TypedExpression::align_without_integers(aligned, e.clone()).map(|(a, _)| a)
+                });
+
+                let arms = aligned
+                    .and_then(|aligned| {
+                        arms.into_iter()
+                            .map(|e| {
+                                TypedExpression::align_without_integers(aligned.clone(), e)
+                                    .map(|(_, e)| e)
+                            })
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .map_err(|(e1, e2)| ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Arms of `match` expression should have the same type, found {}, {}",
+                            e1.get_type(),
+                            e2.get_type()
+                        ),
+                    })?;
+
+                Ok(match arms[0].get_type() {
+                    Type::FieldElement => self
+                        .lower_match::<FieldElementExpression<'ast, T>>(&tree, elements, arms)
+                        .into(),
+                    Type::Boolean => self
+                        .lower_match::<BooleanExpression<'ast, T>>(&tree, elements, arms)
+                        .into(),
+                    Type::Uint(..) => self
+                        .lower_match::<UExpression<'ast, T>>(&tree, elements, arms)
+                        .into(),
+                    Type::Array(..) => self
+                        .lower_match::<ArrayExpression<'ast, T>>(&tree, elements, arms)
+                        .into(),
+                    Type::Struct(..) => self
+                        .lower_match::<StructExpression<'ast, T>>(&tree, elements, arms)
+                        .into(),
+                    // integer literals have no block, and are not worth binding
+                    Type::Int => {
+                        let arms: Vec<IntExpression<'ast, T>> =
+                            arms.into_iter().map(IntExpression::from).collect();
+                        tree.lower(&elements, &arms).into()
+                    }
+                })
+            }
             Expression::FieldConstant(n) => Ok(FieldElementExpression::Number(
                 T::try_from(n).map_err(|_| ErrorInner {
                     pos: Some(pos),
@@ -2279,7 +2648,31 @@ impl<'ast, T: Field> Checker<'ast, T> {
             Expression::U16Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(16).into()),
             Expression::U32Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(32).into()),
             Expression::U64Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(64).into()),
-            Expression::FunctionCall(fun_id, generics, arguments) => {
+            Expression::FunctionCall(fun_id, generics, arguments)
+                if self.is_phantom_intrinsic(fun_id) =>
+            {
+                self.check_phantom(generics, arguments, pos, module_id, types)
+            }
+            Expression::FunctionCall(fun_id, generics, arguments)
+                if self.is_lookup_intrinsic(fun_id) =>
+            {
+                self.check_lookup(generics, arguments, pos, module_id, types)
+            }
+            call @ Expression::FunctionCall(..) | call @ Expression::MethodCall(..) => {
+                // the receiver of a method call is its first argument
+                let (fun_id, generics, arguments, is_method) = match call {
+                    Expression::FunctionCall(fun_id, generics, arguments) => {
+                        (fun_id, generics, arguments, false)
+                    }
+                    Expression::MethodCall(box receiver, method, generics, arguments) => (
+                        method,
+                        generics,
+                        std::iter::once(receiver).chain(arguments).collect(),
+                        true,
+                    ),
+                    _ => unreachable!(),
+                };
+
                 // check the generic arguments, if any
                 let generics_checked: Option<Vec<Option<UExpression<'ast, T>>>> = generics
                     .map(|generics| {
@@ -2324,80 +2717,26 @@ impl<'ast, T: Field> Checker<'ast, T> {
                 let query =
                     FunctionQuery::new(&fun_id, &generics_checked, &arguments_types, &[None]);
 
-                let functions = self.find_functions(&query);
+                let functions = match is_method {
+                    true => self.find_methods(&query),
+                    false => self.find_functions(&query),
+                };
 
                 match functions.len() {
                     // the function has to be defined
                     1 => {
                         let mut functions = functions;
-
                         let f = functions.pop().unwrap();
 
-                        let signature = f.signature;
-
-                        let arguments_checked = arguments_checked.into_iter().zip(signature.inputs.iter()).map(|(a, t)| TypedExpression::align_to_type(a, &t)).collect::<Result<Vec<_>, _>>().map_err(|e| ErrorInner {
-                           pos: Some(pos),
-                           message: format!("Expected function call argument to be of type {}, found {}", e.1, e.0)
-                        })?;
-
-                        let generics_checked = generics_checked.unwrap_or_else(|| vec![None; signature.generics.len()]);
+                        self.check_deprecation(&f, pos);
 
-                        let mut output_types = signature.get_output_types(
-                            generics_checked.clone(),
-                            arguments_checked.iter().map(|a| a.get_type()).collect()
-                        ).map_err(|e| ErrorInner {
-                            pos: Some(pos),
-                            message: format!(
-                                "Failed to infer value for generic parameter `{}`, try providing an explicit value",
-                                e,
-                            ),
-                        })?;
-
-                        let function_key = DeclarationFunctionKey {
-                            module: module_id.to_path_buf(),
-                            id: f.id,
-                            signature: signature.clone(),
-                        };
-
-                        // the return count has to be 1
-                        match output_types.len() {
-                            1 => match output_types.pop().unwrap() {
-                                Type::Int => unreachable!(),
-                                Type::FieldElement => Ok(FieldElementExpression::function_call(
-                                    function_key,
-                                    generics_checked,
-                                    arguments_checked,
-                                ).into()),
-                                Type::Boolean => Ok(BooleanExpression::function_call(
-                                    function_key,
-                                    generics_checked,
-                                    arguments_checked,
-                                ).into()),
-                                Type::Uint(bitwidth) => Ok(UExpression::function_call(
-                                    function_key,
-                                    generics_checked,
-                                    arguments_checked,
-                                ).annotate(bitwidth).into()),
-                                Type::Struct(struct_ty) => Ok(StructExpression::function_call(
-                                    function_key,
-                                    generics_checked,
-                                    arguments_checked,
-                                ).annotate(struct_ty).into()),
-                                Type::Array(array_ty) => Ok(ArrayExpression::function_call(
-                                    function_key,
-                                    generics_checked,
-                                    arguments_checked,
-                                ).annotate(*array_ty.ty, array_ty.size).into()),
-                            },
-                            n => Err(ErrorInner {
-                                pos: Some(pos),
-
-                                message: format!(
-                                    "{} returns {} values but is called outside of a definition",
-                                    f.id, n
-                                ),
-                            }),
-                        }
+                        Self::function_call_expression(
+                            f,
+                            generics_checked,
+                            arguments_checked,
+                            module_id,
+                            pos,
+                        )
                     }
                     0 => Err(ErrorInner {
                         pos: Some(pos),
@@ -3267,10 +3606,456 @@ impl<'ast, T: Field> Checker<'ast, T> {
         })
     }
 
+    // builds the call to `f`, the function found for a call with `generics_checked` and `arguments_checked`
+    fn function_call_expression(
+        f: DeclarationFunctionKey<'ast>,
+        generics_checked: Option<Vec<Option<UExpression<'ast, T>>>>,
+        arguments_checked: Vec<TypedExpression<'ast, T>>,
+        module_id: &ModuleId,
+        pos: (Position, Position),
+    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+        let signature = f.signature;
+
+        let arguments_checked = arguments_checked
+            .into_iter()
+            .zip(signature.inputs.iter())
+            .map(|(a, t)| TypedExpression::align_to_type(a, &t))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Expected function call argument to be of type {}, found {}",
+                    e.1, e.0
+                ),
+            })?;
+
+        let generics_checked =
+            generics_checked.unwrap_or_else(|| vec![None; signature.generics.len()]);
+
+        let mut output_types = signature
+            .get_output_types(
+                generics_checked.clone(),
+                arguments_checked.iter().map(|a| a.get_type()).collect(),
+            )
+            .map_err(|e| ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                "Failed to infer value for generic parameter `{}`, try providing an explicit value",
+                e,
+            ),
+            })?;
+
+        let function_key = DeclarationFunctionKey {
+            module: module_id.to_path_buf(),
+            id: f.id,
+            signature: signature.clone(),
+        };
+
+        // the return count has to be 1
+        match output_types.len() {
+            1 => match output_types.pop().unwrap() {
+                Type::Int => unreachable!(),
+                Type::FieldElement => Ok(FieldElementExpression::function_call(
+                    function_key,
+                    generics_checked,
+                    arguments_checked,
+                )
+                .into()),
+                Type::Boolean => Ok(BooleanExpression::function_call(
+                    function_key,
+                    generics_checked,
+                    arguments_checked,
+                )
+                .into()),
+                Type::Uint(bitwidth) => Ok(UExpression::function_call(
+                    function_key,
+                    generics_checked,
+                    arguments_checked,
+                )
+                .annotate(bitwidth)
+                .into()),
+                Type::Struct(struct_ty) => Ok(StructExpression::function_call(
+                    function_key,
+                    generics_checked,
+                    arguments_checked,
+                )
+                .annotate(struct_ty)
+                .into()),
+                Type::Array(array_ty) => Ok(ArrayExpression::function_call(
+                    function_key,
+                    generics_checked,
+                    arguments_checked,
+                )
+                .annotate(*array_ty.ty, array_ty.size)
+                .into()),
+            },
+            n => Err(ErrorInner {
+                pos: Some(pos),
+
+                message: format!(
+                    "{} returns {} values but is called outside of a definition",
+                    f.id, n
+                ),
+            }),
+        }
+    }
+
+    // resolves `e1 op e2` to the implementation of `operator` for the types of the operands, if there is one.
+    // implementations are looked up among those of the struct operand, and take two operands of that struct
+    fn check_operator_overload(
+        &mut self,
+        operator: Identifier<'ast>,
+        e1: &TypedExpression<'ast, T>,
+        e2: &TypedExpression<'ast, T>,
+        module_id: &ModuleId,
+        pos: (Position, Position),
+    ) -> Result<Option<TypedExpression<'ast, T>>, ErrorInner> {
+        let location = match (e1, e2) {
+            (TypedExpression::Struct(s), _) | (_, TypedExpression::Struct(s)) => {
+                &s.ty().canonical_location
+            }
+            _ => return Ok(None),
+        };
+
+        let arguments_types = vec![e1.get_type(), e2.get_type()];
+        let query = FunctionQuery::new(operator, &None, &arguments_types, &[None]);
+
+        let mut functions: Vec<_> = self
+            .implementations
+            .get(location)
+            .and_then(|implementations| implementations.operators.get(operator))
+            .into_iter()
+            .flatten()
+            .filter(|key| query.match_signature(&key.signature))
+            .cloned()
+            .collect();
+
+        match functions.len() {
+            0 => Ok(None),
+            1 => {
+                let f = functions.pop().unwrap();
+
+                self.check_deprecation(&f, pos);
+
+                Self::function_call_expression(
+                    f,
+                    None,
+                    vec![e1.clone(), e2.clone()],
+                    module_id,
+                    pos,
+                )
+                .map(Some)
+            }
+            n => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Ambiguous overload of `{}` for {}, {}: {} implementations were found",
+                    operator, arguments_types[0], arguments_types[1], n
+                ),
+            }),
+        }
+    }
+
+    /// Returns `true` if `id` refers to the `lookup` intrinsic, which is shadowed by any function named `lookup` in scope
+    fn is_lookup_intrinsic(&self, id: &str) -> bool {
+        id == "lookup" && !self.functions.iter().any(|f| f.id == "lookup")
+    }
+
+    /// Checks `lookup(table, index)`, an element of a constant table selected with a tree of selections on the bits of
+    /// the index rather than a comparison with each index of the table
+    fn check_lookup(
+        &mut self,
+        generics: Option<Vec<Option<ExpressionNode<'ast>>>>,
+        arguments: Vec<ExpressionNode<'ast>>,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+        types: &TypeMap<'ast>,
+    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+        if generics.is_some() {
+            return Err(ErrorInner {
+                pos: Some(pos),
+                message: "`lookup` does not take generic arguments".to_string(),
+            });
+        }
+
+        if arguments.len() != 2 {
+            return Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "`lookup` expects 2 arguments, a constant table and an index, found {}",
+                    arguments.len()
+                ),
+            });
+        }
+
+        let mut arguments = arguments.into_iter();
+        let table = arguments.next().unwrap();
+        let index = arguments.next().unwrap();
+
+        let table_pos = table.pos();
+
+        // the table must be a constant so that its elements are known when the lookup is lowered
+        match table.value {
+            Expression::Identifier(name) => match self.get_scope(&name) {
+                Some(v) if v.is_constant() => Ok(()),
+                Some(_) => Err(ErrorInner {
+                    pos: Some(table_pos),
+                    message: format!(
+                        "`lookup` can only be applied to constant arrays, `{}` is not a constant",
+                        name
+                    ),
+                }),
+                None => Err(ErrorInner {
+                    pos: Some(table_pos),
+                    message: format!("Identifier \"{}\" is undefined", name),
+                }),
+            },
+            ref e => Err(ErrorInner {
+                pos: Some(table_pos),
+                message: format!(
+                    "`lookup` can only be applied to constant arrays, found {}",
+                    e
+                ),
+            }),
+        }?;
+
+        let table = match self.check_expression(table, module_id, types)? {
+            TypedExpression::Array(table) => Ok(table),
+            e => Err(ErrorInner {
+                pos: Some(table_pos),
+                message: format!(
+                    "`lookup` can only be applied to constant arrays, found {} of type {}",
+                    e,
+                    e.get_type()
+                ),
+            }),
+        }?;
+
+        // any index is out of range of an empty table, whose selection tree would have no root
+        if let UExpressionInner::Value(0) = table.size().as_inner() {
+            return Err(ErrorInner {
+                pos: Some(table_pos),
+                message: "`lookup` cannot be applied to an empty array".to_string(),
+            });
+        }
+
+        let index = self.check_expression(index, module_id, types)?;
+        let index =
+            UExpression::try_from_typed(index, &UBitwidth::B32).map_err(|e| ErrorInner {
+                pos: Some(pos),
+                message: format!("Expected index to be of type u32, found {}", e),
+            })?;
+
+        match table.inner_type().clone() {
+            Type::FieldElement => {
+                Ok(FieldElementExpression::Select(SelectExpression::lookup(table, index)).into())
+            }
+            Type::Boolean => {
+                Ok(BooleanExpression::Select(SelectExpression::lookup(table, index)).into())
+            }
+            Type::Uint(bitwidth) => Ok(UExpressionInner::Select(SelectExpression::lookup(
+                table, index,
+            ))
+            .annotate(bitwidth)
+            .into()),
+            ty => Err(ErrorInner {
+                pos: Some(table_pos),
+                message: format!(
+                    "`lookup` can only be applied to arrays of field elements, booleans or unsigned integers, found elements of type {}",
+                    ty
+                ),
+            }),
+        }
+    }
+
+    /// Returns `true` if `id` refers to the `phantom` type, which is shadowed by any type named `phantom` in scope
+    fn is_phantom_type(id: &str, module_id: &ModuleId, types: &TypeMap<'ast>) -> bool {
+        id == PHANTOM && !types.get(module_id).unwrap().contains_key(PHANTOM)
+    }
+
+    /// Returns `true` if `id` refers to the `phantom` intrinsic, which is shadowed by any function named `phantom` in
+    /// scope
+    fn is_phantom_intrinsic(&self, id: &str) -> bool {
+        id == PHANTOM && !self.functions.iter().any(|f| f.id == PHANTOM)
+    }
+
+    /// Checks the generic arguments of `phantom<Tag>`, which must be a single struct in scope, and returns the phantom
+    /// type they define
+    fn check_phantom_tag<S>(
+        generics: Option<Vec<Option<ExpressionNode<'ast>>>>,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+        types: &TypeMap<'ast>,
+    ) -> Result<GStructType<S>, ErrorInner> {
+        let generics = generics.unwrap_or_default();
+
+        let tag = match generics.as_slice() {
+            [Some(tag)] => match &tag.value {
+                Expression::Identifier(name) => Ok(*name),
+                e => Err(ErrorInner {
+                    pos: Some(tag.pos()),
+                    message: format!("Expected the tag of `phantom` to be a type, found {}", e),
+                }),
+            },
+            [None] => Err(ErrorInner {
+                pos: Some(pos),
+                message: "Expected the tag of `phantom` to be a type, found `_`".to_string(),
+            }),
+            generics => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Expected 1 generic argument on type phantom, but got {}",
+                    generics.len()
+                ),
+            }),
+        }?;
+
+        match types.get(module_id).unwrap().get(tag) {
+            Some(DeclarationType::Struct(tag)) => Ok(phantom_type(tag)),
+            _ => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Expected the tag of `phantom` to be a struct in scope, found `{}`",
+                    tag
+                ),
+            }),
+        }
+    }
+
+    /// Lowers `tree` over the `elements` and the `arms` of a `match` expression, binding the elements and the arms
+    /// selected by several leaves to variables, so that each of them is computed once
+    fn lower_match<E>(
+        &mut self,
+        tree: &DecisionTree,
+        elements: Vec<BooleanExpression<'ast, T>>,
+        arms: Vec<TypedExpression<'ast, T>>,
+    ) -> E
+    where
+        E: Block<'ast, T> + IfElse<'ast, T> + From<TypedExpression<'ast, T>> + Clone,
+    {
+        let mut statements = vec![];
+
+        let elements: Vec<_> = elements
+            .into_iter()
+            .map(|e| match e {
+                e @ BooleanExpression::Identifier(_) | e @ BooleanExpression::Value(_) => e,
+                e => self.bind_match_value(e.into(), &mut statements).into(),
+            })
+            .collect();
+
+        let leaves = tree.leaves();
+
+        let arms: Vec<E> = arms
+            .into_iter()
+            .enumerate()
+            .map(|(i, e)| {
+                match leaves.iter().filter(|arm| **arm == i).count() > 1
+                    && !e.get_type().contains_integers()
+                {
+                    true => self.bind_match_value(e, &mut statements).into(),
+                    false => e.into(),
+                }
+            })
+            .collect();
+
+        E::block(statements, tree.lower(&elements, &arms))
+    }
+
+    fn bind_match_value(
+        &mut self,
+        e: TypedExpression<'ast, T>,
+        statements: &mut Vec<TypedStatement<'ast, T>>,
+    ) -> TypedExpression<'ast, T> {
+        let variable =
+            Variable::with_id_and_type(CoreIdentifier::Match(self.match_count), e.get_type());
+        self.match_count += 1;
+
+        statements.push(TypedStatement::Definition(
+            TypedAssignee::Identifier(variable.clone()),
+            e,
+        ));
+
+        variable.into()
+    }
+
+    /// Checks `phantom::<Tag>(value)`, the field element `value` tagged with the type `Tag`
+    fn check_phantom(
+        &mut self,
+        generics: Option<Vec<Option<ExpressionNode<'ast>>>>,
+        arguments: Vec<ExpressionNode<'ast>>,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+        types: &TypeMap<'ast>,
+    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+        let ty: StructType<'ast, T> = Self::check_phantom_tag(generics, pos, module_id, types)?;
+
+        if arguments.len() != 1 {
+            return Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "`phantom` expects 1 argument, a field element, found {}",
+                    arguments.len()
+                ),
+            });
+        }
+
+        let value = arguments.into_iter().next().unwrap();
+        let value = self.check_expression(value, module_id, types)?;
+        let value = FieldElementExpression::try_from_typed(value).map_err(|e| ErrorInner {
+            pos: Some(pos),
+            message: format!(
+                "Expected the value of {} to be of type field, found {} of type {}",
+                ty,
+                e,
+                e.get_type()
+            ),
+        })?;
+
+        Ok(StructExpressionInner::Value(vec![value.into()])
+            .annotate(ty)
+            .into())
+    }
+
     fn find_functions(&self, query: &FunctionQuery<'ast, T>) -> Vec<DeclarationFunctionKey<'ast>> {
         query.match_funcs(&self.functions)
     }
 
+    /// Returns the methods matching `query` among those of the struct of its first argument, the receiver of the call
+    fn find_methods(&self, query: &FunctionQuery<'ast, T>) -> Vec<DeclarationFunctionKey<'ast>> {
+        match query.inputs.first() {
+            Some(Type::Struct(ty)) => self
+                .implementations
+                .get(&ty.canonical_location)
+                .and_then(|implementations| implementations.methods.get(query.id))
+                .into_iter()
+                .flatten()
+                .filter(|key| query.match_signature(&key.signature))
+                .cloned()
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Warn about a call to `key` at `span` if it is deprecated, unless the call is in a statement allowing it
+    fn check_deprecation(
+        &mut self,
+        key: &DeclarationFunctionKey<'ast>,
+        span: (Position, Position),
+    ) {
+        if self.allow_deprecated {
+            return;
+        }
+
+        if let Some(deprecation) = self.deprecations.get(key) {
+            self.warnings.push(Warning::DeprecatedUsage {
+                item: key.id.to_string(),
+                span,
+                since: deprecation.since.map(String::from),
+                note: deprecation.note.map(String::from),
+            });
+        }
+    }
+
     fn enter_scope(&mut self) {
         self.level += 1;
     }
@@ -3287,6 +4072,7 @@ impl<'ast, T: Field> Checker<'ast, T> {
 mod tests {
     use super::*;
     use crate::absy;
+    use crate::flat_absy::RuntimeError;
     use crate::typed_absy;
     use lazy_static::lazy_static;
     use zokrates_field::Bn128Field;
@@ -3397,6 +4183,8 @@ mod tests {
             arguments,
             statements,
             signature,
+            deprecated: None,
+            implementation: None,
         }
         .mock()
     }
@@ -3414,6 +4202,7 @@ mod tests {
         let arguments = vec![absy::Parameter {
             id: absy::Variable::new("a", UnresolvedType::FieldElement.mock()).mock(),
             private: true,
+            deferred: false,
         }
         .mock()];
 
@@ -3424,6 +4213,8 @@ mod tests {
             arguments,
             statements,
             signature,
+            deprecated: None,
+            implementation: None,
         }
         .mock()
     }
@@ -4164,6 +4955,8 @@ mod tests {
             arguments: foo_args,
             statements: foo_statements,
             signature: UnresolvedSignature::new(),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4182,6 +4975,8 @@ mod tests {
             signature: UnresolvedSignature::new()
                 .inputs(vec![])
                 .outputs(vec![UnresolvedType::FieldElement.mock()]),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4250,6 +5045,8 @@ mod tests {
             arguments: foo_args,
             statements: foo_statements,
             signature: UnresolvedSignature::new(),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4276,6 +5073,8 @@ mod tests {
             arguments: bar_args,
             statements: bar_statements,
             signature: UnresolvedSignature::new(),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4294,6 +5093,8 @@ mod tests {
             signature: UnresolvedSignature::new()
                 .inputs(vec![])
                 .outputs(vec![UnresolvedType::FieldElement.mock()]),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4352,6 +5153,8 @@ mod tests {
             signature: UnresolvedSignature::new()
                 .inputs(vec![])
                 .outputs(vec![UnresolvedType::FieldElement.mock()]),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4430,6 +5233,8 @@ mod tests {
             arguments: vec![],
             statements: foo_statements,
             signature: UnresolvedSignature::new(),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4490,6 +5295,8 @@ mod tests {
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature::new(),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4523,6 +5330,7 @@ mod tests {
                     box Expression::FunctionCall("foo", None, vec![]).mock(),
                 )
                 .mock(),
+                RuntimeError::Source,
             )
             .mock(),
             Statement::Return(
@@ -4549,6 +5357,8 @@ mod tests {
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature::new(),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4595,6 +5405,8 @@ mod tests {
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature::new(),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4638,6 +5450,7 @@ mod tests {
             arguments: vec![crate::absy::Parameter {
                 id: absy::Variable::new("x", UnresolvedType::FieldElement.mock()).mock(),
                 private: false,
+                deferred: false,
             }
             .mock()],
             statements: foo_statements,
@@ -4647,6 +5460,8 @@ mod tests {
                     UnresolvedType::FieldElement.mock(),
                     UnresolvedType::FieldElement.mock(),
                 ]),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4683,6 +5498,8 @@ mod tests {
             signature: UnresolvedSignature::new()
                 .inputs(vec![])
                 .outputs(vec![UnresolvedType::FieldElement.mock()]),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4744,6 +5561,8 @@ mod tests {
                 UnresolvedType::FieldElement.mock(),
                 UnresolvedType::FieldElement.mock(),
             ]),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4769,6 +5588,8 @@ mod tests {
             arguments: vec![],
             statements: main_statements,
             signature: UnresolvedSignature::new().inputs(vec![]).outputs(vec![]),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4836,6 +5657,8 @@ mod tests {
             signature: UnresolvedSignature::new()
                 .inputs(vec![])
                 .outputs(vec![UnresolvedType::FieldElement.mock()]),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4884,6 +5707,8 @@ mod tests {
             arguments: vec![],
             statements: main_statements,
             signature: UnresolvedSignature::new().inputs(vec![]).outputs(vec![]),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4923,6 +5748,7 @@ mod tests {
                     box Expression::FunctionCall("foo", None, vec![]).mock(),
                 )
                 .mock(),
+                RuntimeError::Source,
             )
             .mock(),
             Statement::Return(
@@ -4938,6 +5764,8 @@ mod tests {
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature::new(),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -4979,6 +5807,8 @@ mod tests {
                 UnresolvedType::FieldElement.mock(),
                 UnresolvedType::FieldElement.mock(),
             ]),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -5079,6 +5909,8 @@ mod tests {
             signature: UnresolvedSignature::new()
                 .inputs(vec![])
                 .outputs(vec![UnresolvedType::FieldElement.mock()]),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -5155,6 +5987,7 @@ mod tests {
         let main1_arguments = vec![crate::absy::Parameter {
             id: absy::Variable::new("a", UnresolvedType::FieldElement.mock()).mock(),
             private: false,
+            deferred: false,
         }
         .mock()];
 
@@ -5174,6 +6007,8 @@ mod tests {
             signature: UnresolvedSignature::new()
                 .inputs(vec![UnresolvedType::FieldElement.mock()])
                 .outputs(vec![UnresolvedType::FieldElement.mock()]),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -5183,6 +6018,8 @@ mod tests {
             signature: UnresolvedSignature::new()
                 .inputs(vec![])
                 .outputs(vec![UnresolvedType::FieldElement.mock()]),
+            deprecated: None,
+            implementation: None,
         }
         .mock();
 
@@ -11,6 +11,10 @@ pub enum Solver {
     ShaAndXorAndXorAnd,
     ShaCh,
     EuclideanDiv,
+    /// checks that its two inputs are equal, failing with a message built from the condition of an `assert_eq!`
+    AssertEq(String),
+    /// checks that its two inputs differ, failing with a message built from the condition of an `assert_ne!`
+    AssertNe(String),
     #[cfg(feature = "bellman")]
     Sha256Round,
     #[cfg(feature = "ark")]
@@ -34,6 +38,7 @@ impl Solver {
             Solver::ShaAndXorAndXorAnd => (3, 1),
             Solver::ShaCh => (3, 1),
             Solver::EuclideanDiv => (2, 2),
+            Solver::AssertEq(..) | Solver::AssertNe(..) => (2, 0),
             #[cfg(feature = "bellman")]
             Solver::Sha256Round => (768, 26935),
             #[cfg(feature = "ark")]
@@ -42,8 +47,256 @@ impl Solver {
     }
 }
 
+impl Solver {
+    /// Whether the witness computation for this solver runs in time independent of its inputs.
+    /// Solvers relying on field inversion or big integer division are not constant-time.
+    pub fn is_constant_time(&self) -> bool {
+        match self {
+            Solver::ConditionEq => false,
+            Solver::Bits(..) => true,
+            Solver::Div => false,
+            Solver::Xor => true,
+            Solver::Or => true,
+            Solver::ShaAndXorAndXorAnd => true,
+            Solver::ShaCh => true,
+            Solver::EuclideanDiv => false,
+            Solver::AssertEq(..) | Solver::AssertNe(..) => true,
+            #[cfg(feature = "bellman")]
+            Solver::Sha256Round => true,
+            #[cfg(feature = "ark")]
+            Solver::SnarkVerifyBls12377(..) => false,
+        }
+    }
+}
+
+impl Solver {
+    /// Whether the outputs of this solver are pinned by golden vectors in `golden_vectors.json`, checked on every
+    /// platform the tests run on. New solvers must come with vectors.
+    /// The solvers generating the witness of an embedded gadget are not pinned, as their witness is checked against
+    /// the constraints of the gadget in `zokrates_embed`.
+    pub fn is_golden_vector_tested(&self) -> bool {
+        match self {
+            Solver::ConditionEq => true,
+            Solver::Bits(..) => true,
+            Solver::Div => true,
+            Solver::Xor => true,
+            Solver::Or => true,
+            Solver::ShaAndXorAndXorAnd => true,
+            Solver::ShaCh => true,
+            Solver::EuclideanDiv => true,
+            Solver::AssertEq(..) | Solver::AssertNe(..) => true,
+            #[cfg(feature = "bellman")]
+            Solver::Sha256Round => false,
+            #[cfg(feature = "ark")]
+            Solver::SnarkVerifyBls12377(..) => false,
+        }
+    }
+}
+
 impl Solver {
     pub fn bits(width: usize) -> Self {
         Solver::Bits(width)
     }
 }
+
+// Declares the registry of solvers as one example for each pattern. The patterns are matched exhaustively in
+// `Solver::example`, so a variant added to `Solver` does not compile until it gets an example in the registry.
+macro_rules! registry {
+    ($($(#[$attr:meta])* $pattern:pat => $example:expr,)*) => {
+        impl Solver {
+            /// One solver of each kind, used to check properties which must hold for all solvers
+            pub fn registry() -> Vec<Solver> {
+                vec![$($(#[$attr])* $example,)*]
+            }
+
+            /// The example of the kind of this solver in the registry
+            pub fn example(&self) -> Solver {
+                match self {
+                    $($(#[$attr])* $pattern => $example,)*
+                }
+            }
+        }
+    };
+}
+
+registry! {
+    Solver::ConditionEq => Solver::ConditionEq,
+    Solver::Bits(..) => Solver::Bits(8),
+    Solver::Div => Solver::Div,
+    Solver::Xor => Solver::Xor,
+    Solver::Or => Solver::Or,
+    Solver::ShaAndXorAndXorAnd => Solver::ShaAndXorAndXorAnd,
+    Solver::ShaCh => Solver::ShaCh,
+    Solver::EuclideanDiv => Solver::EuclideanDiv,
+    Solver::AssertEq(..) => Solver::AssertEq(String::from("a == b")),
+    Solver::AssertNe(..) => Solver::AssertNe(String::from("a != b")),
+    #[cfg(feature = "bellman")]
+    Solver::Sha256Round => Solver::Sha256Round,
+    #[cfg(feature = "ark")]
+    Solver::SnarkVerifyBls12377(..) => Solver::SnarkVerifyBls12377(1),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::Interpreter;
+    use serde_json::Value;
+    use zokrates_field::{Bn128Field, Field};
+
+    #[derive(Clone, Copy)]
+    enum ByteOrder {
+        Little,
+        Big,
+    }
+
+    // serialize `v` on `Field::get_required_bits()` bits in the given byte order, as a host of that byte order would
+    fn encode<T: Field>(v: &T, order: ByteOrder) -> Vec<u8> {
+        let mut bytes = v.to_byte_vector();
+        bytes.resize((T::get_required_bits() + 7) / 8, 0);
+        if let ByteOrder::Big = order {
+            bytes.reverse();
+        }
+        bytes
+    }
+
+    fn decode<T: Field>(mut bytes: Vec<u8>, order: ByteOrder) -> T {
+        if let ByteOrder::Big = order {
+            bytes.reverse();
+        }
+        T::from_byte_vector(bytes)
+    }
+
+    struct GoldenVector {
+        solver: Solver,
+        inputs: Vec<Bn128Field>,
+        outputs: Vec<Bn128Field>,
+    }
+
+    fn golden_vectors() -> Vec<GoldenVector> {
+        let values = |v: &Value| -> Vec<Bn128Field> {
+            v.as_array()
+                .unwrap()
+                .iter()
+                .map(|v| Bn128Field::try_from_dec_str(v.as_str().unwrap()).unwrap())
+                .collect()
+        };
+
+        let vectors: Vec<Value> =
+            serde_json::from_str(include_str!("golden_vectors.json")).unwrap();
+
+        vectors
+            .iter()
+            .map(|v| GoldenVector {
+                solver: serde_json::from_value(v["solver"].clone()).unwrap(),
+                inputs: values(&v["inputs"]),
+                outputs: values(&v["outputs"]),
+            })
+            .collect()
+    }
+
+    // `value`, given as big endian hexadecimal, as bytes in the given byte order
+    fn pinned_bytes(value: &str, order: ByteOrder) -> Vec<u8> {
+        let mut bytes = hex::decode(format!("{:0>64}", value)).unwrap();
+        if let ByteOrder::Little = order {
+            bytes.reverse();
+        }
+        bytes
+    }
+
+    #[test]
+    fn golden_vectors() {
+        for v in golden_vectors() {
+            assert_eq!(
+                Interpreter::execute_solver(&v.solver, &v.inputs).unwrap(),
+                v.outputs,
+                "{} on {:?}",
+                v.solver,
+                v.inputs
+            );
+        }
+    }
+
+    #[test]
+    fn golden_vectors_across_byte_orders() {
+        // inputs and outputs of golden vectors, pinned as bytes rather than derived from the decimal vectors
+        let minus_one = "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+        let half = "183227397098d014dc2822db40c0ac2e9419f4243cdcb848a1f0fac9f8000001";
+
+        let vectors = vec![
+            (Solver::Div, vec!["1", "2"], vec![half]),
+            (Solver::ConditionEq, vec!["2"], vec!["1", half]),
+            (Solver::ConditionEq, vec![minus_one], vec!["1", minus_one]),
+            (
+                Solver::EuclideanDiv,
+                vec![minus_one, "100000000000000000000000000000000"],
+                vec![
+                    "30644e72e131a029b85045b68181585d",
+                    "2833e84879b9709143e1f593f0000000",
+                ],
+            ),
+        ];
+
+        for order in &[ByteOrder::Little, ByteOrder::Big] {
+            for (solver, inputs, outputs) in &vectors {
+                let inputs: Vec<Bn128Field> = inputs
+                    .iter()
+                    .map(|i| decode(pinned_bytes(i, *order), *order))
+                    .collect();
+
+                let outputs: Vec<_> = outputs.iter().map(|o| pinned_bytes(o, *order)).collect();
+
+                assert_eq!(
+                    Interpreter::execute_solver(solver, &inputs)
+                        .unwrap()
+                        .iter()
+                        .map(|o| encode(o, *order))
+                        .collect::<Vec<_>>(),
+                    outputs,
+                    "{} on {:?}",
+                    solver,
+                    inputs
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn registry_is_exhaustive() {
+        // each example stands for its own kind
+        for solver in Solver::registry() {
+            assert_eq!(solver.example(), solver);
+        }
+    }
+
+    #[test]
+    fn registry_is_golden_vector_tested() {
+        let vectors = golden_vectors();
+
+        // the embedded gadgets are the only solvers allowed without golden vectors
+        let gadgets: Vec<Solver> = vec![
+            #[cfg(feature = "bellman")]
+            Solver::Sha256Round,
+            #[cfg(feature = "ark")]
+            Solver::SnarkVerifyBls12377(1),
+        ];
+
+        // solvers of the same kind only differ by their parameters, as in `Bits(8)`
+        let kind = |s: &Solver| s.to_string().split('(').next().unwrap().to_string();
+
+        for solver in Solver::registry() {
+            assert!(
+                solver.is_golden_vector_tested() || gadgets.contains(&solver),
+                "{} is not golden vector tested",
+                solver
+            );
+
+            if solver.is_golden_vector_tested() {
+                assert!(
+                    vectors.iter().any(|v| kind(&v.solver) == kind(&solver)),
+                    "no golden vectors for {}",
+                    solver
+                );
+            }
+        }
+    }
+}
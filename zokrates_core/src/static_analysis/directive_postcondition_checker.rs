@@ -0,0 +1,407 @@
+//! Module containing the `DirectivePostconditionChecker`, checking that the outputs of directives are constrained
+//!
+//! Directives only compute the witness, so the program must enforce the relation between their inputs and outputs.
+//! For `(q, r) = EuclideanDiv(a, b)`, the statements following the directive must contain:
+//! - a constraint involving both `q` and `r`, enforcing `a == q * b + r`
+//! - a decomposition of `r`, enforcing `0 <= r`
+//! - a decomposition of `r - b + 2**w` in `w` bits, enforcing `r < b`
+//!
+//! A decomposition in `w` bits is a linear constraint equating an expression to `bit_0 + 2 * bit_1 + ... + 2**(w-1) *
+//! bit_(w-1)`, where each bit is constrained by `bit * bit == bit`. The first check is structural, only the variables
+//! of the constraints are inspected.
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::{CanonicalLinComb, Directive, LinComb, Prog, QuadComb, Statement};
+use crate::solvers::Solver;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use zokrates_field::Field;
+
+#[derive(Debug, PartialEq)]
+pub struct Error {
+    directive: String,
+    postcondition: &'static str,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Directive `{}` is not followed by constraints enforcing `{}`",
+            self.directive, self.postcondition
+        )
+    }
+}
+
+pub struct DirectivePostconditionChecker;
+
+impl DirectivePostconditionChecker {
+    pub fn check<T: Field>(p: &Prog<T>) -> Result<(), Error> {
+        // bits and decompositions are found once, then looked up for each directive by statement index
+        let bits = boolean_variables(&p.statements);
+        let decompositions = Decompositions::new(&p.statements, &bits);
+
+        for (index, s) in p.statements.iter().enumerate() {
+            if let Statement::Directive(d) = s {
+                if let Solver::EuclideanDiv = d.solver {
+                    check_euclidean_div(d, index, &p.statements, &decompositions)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// checks that the statements following `(q, r) = EuclideanDiv(a, b)`, found at `index`, enforce its postconditions
+fn check_euclidean_div<T: Field>(
+    d: &Directive<T>,
+    index: usize,
+    statements: &[Statement<T>],
+    decompositions: &Decompositions<T>,
+) -> Result<(), Error> {
+    let (q, r) = (d.outputs[0], d.outputs[1]);
+
+    let error = |postcondition| Error {
+        directive: d.to_string(),
+        postcondition,
+    };
+
+    let euclidean = statements[index + 1..].iter().any(|s| match s {
+        Statement::Constraint(quad, lin, _) => {
+            let variables: Vec<_> = quad_variables(quad)
+                .chain(lin.0.iter().map(|(v, _)| v))
+                .collect();
+            variables.contains(&&q) && variables.contains(&&r)
+        }
+        Statement::Directive(..) => false,
+    });
+
+    if !euclidean {
+        return Err(error("a == q * b + r"));
+    }
+
+    let r = LinComb::from(r);
+
+    if !decompositions.follows(index, &r.clone().into_canonical(), None) {
+        return Err(error("0 <= r"));
+    }
+
+    let b = d.inputs[1]
+        .clone()
+        .try_linear()
+        .map_err(|_| error("r < b"))?;
+
+    if !decompositions.widths.iter().any(|width| {
+        let e = r.clone() - b.clone() + LinComb::from(power_of_two::<T>(*width));
+        decompositions.follows(index, &e.into_canonical(), Some(*width))
+    }) {
+        return Err(error("r < b"));
+    }
+
+    Ok(())
+}
+
+// a decomposition in `width` bits found at statement `index`. Each of its bits is constrained to be a bit by a statement
+// after the statements before `bound`
+struct Decomposition {
+    index: usize,
+    width: usize,
+    bound: usize,
+}
+
+// the expressions decomposed in bits by a program
+struct Decompositions<T> {
+    by_expression: HashMap<CanonicalLinComb<T>, Vec<Decomposition>>,
+    widths: BTreeSet<usize>,
+}
+
+impl<T: Field> Decompositions<T> {
+    fn new(statements: &[Statement<T>], bits: &HashMap<FlatVariable, usize>) -> Self {
+        let mut decompositions = Decompositions {
+            by_expression: HashMap::new(),
+            widths: BTreeSet::new(),
+        };
+
+        for (index, s) in statements.iter().enumerate() {
+            if let Statement::Constraint(quad, lin, _) = s {
+                if let Some((e, decomposed)) = quad
+                    .clone()
+                    .try_linear()
+                    .ok()
+                    .and_then(|quad| decomposition(quad - lin.clone(), bits))
+                {
+                    let decomposition = Decomposition {
+                        index,
+                        width: decomposed.len(),
+                        bound: decomposed.iter().map(|b| bits[b]).min().unwrap_or(index),
+                    };
+
+                    decompositions.widths.insert(decomposition.width);
+                    decompositions
+                        .by_expression
+                        .entry(e)
+                        .or_default()
+                        .push(decomposition);
+                }
+            }
+        }
+
+        decompositions
+    }
+
+    // whether `e` is decomposed, in `width` bits if specified, by the statements following the statement at `index`,
+    // which also constrain its bits
+    fn follows(&self, index: usize, e: &CanonicalLinComb<T>, width: Option<usize>) -> bool {
+        self.by_expression.get(e).map_or(false, |decompositions| {
+            decompositions
+                .iter()
+                .any(|d| d.index > index && d.bound > index && width.map_or(true, |w| d.width == w))
+        })
+    }
+}
+
+// the variables constrained to be bits by `v * v == v`, along with the index of the last such constraint
+fn boolean_variables<T: Field>(statements: &[Statement<T>]) -> HashMap<FlatVariable, usize> {
+    statements
+        .iter()
+        .enumerate()
+        .filter_map(|(index, s)| match s {
+            Statement::Constraint(quad, lin, _) => boolean_variable(quad, lin).map(|v| (v, index)),
+            Statement::Directive(..) => None,
+        })
+        .collect()
+}
+
+// `v` if the constraint is `v * v == v`, which enforces that `v` is a bit
+fn boolean_variable<T: Field>(quad: &QuadComb<T>, lin: &LinComb<T>) -> Option<FlatVariable> {
+    let bit = lin.clone().into_canonical();
+
+    match bit.0.iter().next() {
+        Some((v, c))
+            if bit.0.len() == 1
+                && *c == T::one()
+                && *v != FlatVariable::one()
+                && quad.left.clone().into_canonical() == bit
+                && quad.right.clone().into_canonical() == bit =>
+        {
+            Some(*v)
+        }
+        _ => None,
+    }
+}
+
+// `(e, bits)` if the constraint `zero == 0` is `e == bit_0 + 2 * bit_1 + ... + 2**(w-1) * bit_(w-1)` up to its sign
+fn decomposition<T: Field>(
+    zero: LinComb<T>,
+    bits: &HashMap<FlatVariable, usize>,
+) -> Option<(CanonicalLinComb<T>, Vec<FlatVariable>)> {
+    let zero = zero.into_canonical();
+
+    let (decomposed, coefficients): (Vec<_>, Vec<_>) = zero
+        .0
+        .iter()
+        .filter(|(v, _)| bits.contains_key(v))
+        .map(|(v, c)| (*v, c.clone()))
+        .unzip();
+
+    // the coefficient of the lowest bit gives the sign of the sum of the bits
+    let sign = coefficients
+        .iter()
+        .find(|c| **c == T::one() || **c == T::zero() - T::one())?
+        .clone();
+
+    let width = coefficients.len();
+
+    let mut expected: Vec<_> = (0..width).map(power_of_two::<T>).collect();
+    let mut coefficients: Vec<_> = coefficients.into_iter().map(|c| c * sign.clone()).collect();
+    expected.sort();
+    coefficients.sort();
+
+    if coefficients != expected {
+        return None;
+    }
+
+    // `sign * zero` is the sum of the bits minus the decomposed expression
+    let e = zero
+        .0
+        .into_iter()
+        .filter(|(v, _)| !bits.contains_key(v))
+        .map(|(v, c)| (v, T::zero() - c * sign.clone()))
+        .collect();
+
+    Some((CanonicalLinComb(e), decomposed))
+}
+
+fn power_of_two<T: Field>(exponent: usize) -> T {
+    (0..exponent).fold(T::one(), |acc, _| acc.clone() + acc)
+}
+
+fn quad_variables<T>(quad: &QuadComb<T>) -> impl Iterator<Item = &FlatVariable> {
+    quad.left
+        .0
+        .iter()
+        .chain(quad.right.0.iter())
+        .map(|(v, _)| v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatParameter;
+    use zokrates_field::Bn128Field;
+
+    // def main(_0, _1) -> (2):
+    //     # _2, _3 = EuclideanDiv(_0, _1)
+    //     # _4, _5 = Bits(2)(_3)
+    //     _4 * _4 == _4
+    //     _5 * _5 == _5
+    //     _4 + 2 * _5 == _3
+    //     # _6, _7 = Bits(2)(_3 - _1 + 4)
+    //     _6 * _6 == _6
+    //     _7 * _7 == _7
+    //     _6 + 2 * _7 == _3 - _1 + 4
+    //     _2 * _1 == _0 - _3
+    //     ~out_0 == _2
+    //     ~out_1 == _3
+    //     return ~out_0, ~out_1
+    fn statements() -> Vec<Statement<Bn128Field>> {
+        let a = FlatVariable::new(0);
+        let b = FlatVariable::new(1);
+        let q = FlatVariable::new(2);
+        let r = FlatVariable::new(3);
+
+        let lt = LinComb::from(r) - LinComb::from(b) + LinComb::summand(4, FlatVariable::one());
+
+        let decomposition = |e: LinComb<Bn128Field>, bits: [FlatVariable; 2]| {
+            vec![
+                Statement::Directive(Directive {
+                    inputs: vec![e.clone().into()],
+                    outputs: bits.to_vec(),
+                    solver: Solver::Bits(2),
+                }),
+                Statement::constraint(
+                    QuadComb::from_linear_combinations(bits[0].into(), bits[0].into()),
+                    bits[0],
+                ),
+                Statement::constraint(
+                    QuadComb::from_linear_combinations(bits[1].into(), bits[1].into()),
+                    bits[1],
+                ),
+                Statement::constraint(LinComb::from(bits[0]) + LinComb::summand(2, bits[1]), e),
+            ]
+        };
+
+        std::iter::once(Statement::Directive(Directive {
+            inputs: vec![a.into(), b.into()],
+            outputs: vec![q, r],
+            solver: Solver::EuclideanDiv,
+        }))
+        .chain(decomposition(
+            r.into(),
+            [FlatVariable::new(4), FlatVariable::new(5)],
+        ))
+        .chain(decomposition(
+            lt,
+            [FlatVariable::new(6), FlatVariable::new(7)],
+        ))
+        .chain(vec![
+            Statement::constraint(
+                QuadComb::from_linear_combinations(q.into(), b.into()),
+                LinComb::from(a) - LinComb::from(r),
+            ),
+            Statement::definition(FlatVariable::public(0), q),
+            Statement::definition(FlatVariable::public(1), r),
+        ])
+        .collect()
+    }
+
+    fn program(statements: Vec<Statement<Bn128Field>>) -> Prog<Bn128Field> {
+        Prog {
+            arguments: vec![
+                FlatParameter::private(FlatVariable::new(0)),
+                FlatParameter::private(FlatVariable::new(1)),
+            ],
+            statements,
+            returns: vec![FlatVariable::public(0), FlatVariable::public(1)],
+        }
+    }
+
+    fn check(statements: Vec<Statement<Bn128Field>>) -> Result<(), &'static str> {
+        DirectivePostconditionChecker::check(&program(statements)).map_err(|e| e.postcondition)
+    }
+
+    #[test]
+    fn constrained_division() {
+        assert_eq!(check(statements()), Ok(()));
+    }
+
+    #[test]
+    fn missing_euclidean_constraint() {
+        let mut statements = statements();
+        let directive = statements[0].to_string();
+        statements.remove(9);
+
+        assert_eq!(
+            DirectivePostconditionChecker::check(&program(statements)),
+            Err(Error {
+                directive,
+                postcondition: "a == q * b + r"
+            })
+        );
+    }
+
+    #[test]
+    fn missing_range_check() {
+        let mut without_lower_bound = statements();
+        without_lower_bound.remove(4);
+
+        assert_eq!(check(without_lower_bound), Err("0 <= r"));
+
+        let mut without_upper_bound = statements();
+        without_upper_bound.remove(8);
+
+        assert_eq!(check(without_upper_bound), Err("r < b"));
+    }
+
+    #[test]
+    fn unconstrained_bit() {
+        // the directive alone does not make its output a bit
+        let mut statements = statements();
+        statements.remove(7);
+
+        assert_eq!(check(statements), Err("r < b"));
+    }
+
+    #[test]
+    fn decomposition_of_another_expression() {
+        let mut statements = statements();
+        let r = FlatVariable::new(3);
+        let b = FlatVariable::new(1);
+
+        // `r - b + 8` does not fit in two bits, so its decomposition does not bound `r`
+        statements[8] = Statement::constraint(
+            LinComb::from(FlatVariable::new(6)) + LinComb::summand(2, FlatVariable::new(7)),
+            LinComb::from(r) - LinComb::from(b) + LinComb::summand(8, FlatVariable::one()),
+        );
+
+        assert_eq!(check(statements), Err("r < b"));
+    }
+
+    #[test]
+    fn constraints_before_directive() {
+        // moving the directive after its postconditions leaves its outputs unconstrained
+        let mut statements = statements();
+        let directive = statements.remove(0);
+        let directive_string = directive.to_string();
+        statements.insert(9, directive);
+
+        assert_eq!(
+            DirectivePostconditionChecker::check(&program(statements)),
+            Err(Error {
+                directive: directive_string,
+                postcondition: "a == q * b + r"
+            })
+        );
+    }
+}
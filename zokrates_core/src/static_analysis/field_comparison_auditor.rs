@@ -0,0 +1,294 @@
+//! Module containing the `FieldComparisonAuditor`, listing the assertions which compare field elements
+//!
+//! Field comparisons are expensive, as both operands are decomposed into bits before the comparison itself. When
+//! both operands are known to be small, comparing uints instead saves these decompositions. Bounds are inferred by
+//! a forward pass over the definitions, starting from constants.
+//!
+//! Zir statements do not carry source positions, so assertions are identified by their index among the statements
+//! of `main`, nested statements being numbered in order, and by their expression.
+
+use crate::zir::types::UBitwidth;
+use crate::zir::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use zokrates_field::Field;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct FieldComparisonReport {
+    /// the index of the assertion in the statements of `main`
+    pub statement: usize,
+    /// the asserted expression
+    pub assertion: String,
+    /// the field comparison performed by the assertion
+    pub comparison: String,
+    /// the narrowest uint type holding both operands, if they are bounded
+    pub suggested_bitwidth: Option<UBitwidth>,
+    /// the estimated number of constraints saved by comparing uints of the suggested type instead
+    pub estimated_savings: usize,
+}
+
+pub struct FieldComparisonAuditor<'ast> {
+    // the known upper bounds of field variables
+    bounds: HashMap<Identifier<'ast>, u128>,
+    index: usize,
+}
+
+impl<'ast> FieldComparisonAuditor<'ast> {
+    pub fn audit<T: Field>(prog: &ZirProgram<'ast, T>) -> Vec<FieldComparisonReport> {
+        let mut auditor = FieldComparisonAuditor {
+            bounds: HashMap::new(),
+            index: 0,
+        };
+
+        let mut reports = vec![];
+        auditor.audit_statements(&prog.main.statements, &mut reports);
+        reports
+    }
+
+    fn audit_statements<T: Field>(
+        &mut self,
+        statements: &[ZirStatement<'ast, T>],
+        reports: &mut Vec<FieldComparisonReport>,
+    ) {
+        for s in statements {
+            let index = self.index;
+            self.index += 1;
+
+            match s {
+                ZirStatement::Definition(v, ZirExpression::FieldElement(e)) => {
+                    if let Some(bound) = self.bound(e) {
+                        self.bounds.insert(v.id.clone(), bound);
+                    }
+                }
+                ZirStatement::IfElse(_, consequence, alternative) => {
+                    self.audit_statements(consequence, reports);
+                    self.audit_statements(alternative, reports);
+                }
+                ZirStatement::Assertion(e, _) => {
+                    let mut comparisons = vec![];
+                    collect_comparisons(e, &mut comparisons);
+
+                    reports.extend(comparisons.into_iter().map(|(comparison, left, right)| {
+                        self.report(index, e, comparison, left, right)
+                    }));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn report<T: Field>(
+        &self,
+        statement: usize,
+        assertion: &BooleanExpression<'ast, T>,
+        comparison: &BooleanExpression<'ast, T>,
+        left: &FieldElementExpression<'ast, T>,
+        right: &FieldElementExpression<'ast, T>,
+    ) -> FieldComparisonReport {
+        let suggested_bitwidth = match (self.bound(left), self.bound(right)) {
+            (Some(l), Some(r)) => bitwidth(l.max(r)),
+            _ => None,
+        };
+
+        // comparing to a constant only decomposes the other operand, which a uint comparison does too
+        let is_dynamic = !matches!(left, FieldElementExpression::Number(_))
+            && !matches!(right, FieldElementExpression::Number(_));
+
+        // a dynamic field comparison decomposes both operands into `bits - 2` bits and checks the sums
+        let estimated_savings = match (suggested_bitwidth, is_dynamic) {
+            (Some(_), true) => 2 * (T::get_required_bits() - 1),
+            _ => 0,
+        };
+
+        FieldComparisonReport {
+            statement,
+            assertion: assertion.to_string(),
+            comparison: comparison.to_string(),
+            suggested_bitwidth,
+            estimated_savings,
+        }
+    }
+
+    // an upper bound of the value of `e`, if any is known
+    fn bound<T: Field>(&self, e: &FieldElementExpression<'ast, T>) -> Option<u128> {
+        match e {
+            FieldElementExpression::Number(n) => n.to_dec_string().parse().ok(),
+            FieldElementExpression::Identifier(id) => self.bounds.get(id).cloned(),
+            FieldElementExpression::Add(box left, box right) => {
+                self.bound(left)?.checked_add(self.bound(right)?)
+            }
+            FieldElementExpression::Mult(box left, box right) => {
+                self.bound(left)?.checked_mul(self.bound(right)?)
+            }
+            FieldElementExpression::Pow(box base, box exponent) => match exponent.as_inner() {
+                UExpressionInner::Value(e) => {
+                    self.bound(base)?.checked_pow(u32::try_from(*e).ok()?)
+                }
+                _ => None,
+            },
+            FieldElementExpression::IfElse(_, box consequence, box alternative) => {
+                Some(self.bound(consequence)?.max(self.bound(alternative)?))
+            }
+            FieldElementExpression::Select(values, _)
+            | FieldElementExpression::Lookup(values, _) => values
+                .iter()
+                .map(|v| self.bound(v))
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .max(),
+            // subtraction and division can wrap around the field
+            FieldElementExpression::Sub(..) | FieldElementExpression::Div(..) => None,
+        }
+    }
+}
+
+type Comparison<'a, 'ast, T> = (
+    &'a BooleanExpression<'ast, T>,
+    &'a FieldElementExpression<'ast, T>,
+    &'a FieldElementExpression<'ast, T>,
+);
+
+// the field comparisons found in the boolean structure of `e`
+fn collect_comparisons<'a, 'ast, T: Field>(
+    e: &'a BooleanExpression<'ast, T>,
+    comparisons: &mut Vec<Comparison<'a, 'ast, T>>,
+) {
+    match e {
+        BooleanExpression::FieldLt(box left, box right)
+        | BooleanExpression::FieldLe(box left, box right)
+        | BooleanExpression::FieldGe(box left, box right)
+        | BooleanExpression::FieldGt(box left, box right) => comparisons.push((e, left, right)),
+        BooleanExpression::Or(box left, box right)
+        | BooleanExpression::And(box left, box right)
+        | BooleanExpression::BoolEq(box left, box right) => {
+            collect_comparisons(left, comparisons);
+            collect_comparisons(right, comparisons);
+        }
+        BooleanExpression::Not(box e) => collect_comparisons(e, comparisons),
+        BooleanExpression::IfElse(box condition, box consequence, box alternative) => {
+            collect_comparisons(condition, comparisons);
+            collect_comparisons(consequence, comparisons);
+            collect_comparisons(alternative, comparisons);
+        }
+        BooleanExpression::Select(values, _) | BooleanExpression::Lookup(values, _) => {
+            for v in values {
+                collect_comparisons(v, comparisons);
+            }
+        }
+        _ => {}
+    }
+}
+
+// the narrowest uint bitwidth which can hold `bound`
+fn bitwidth(bound: u128) -> Option<UBitwidth> {
+    [
+        UBitwidth::B8,
+        UBitwidth::B16,
+        UBitwidth::B32,
+        UBitwidth::B64,
+    ]
+    .iter()
+    .find(|b| bound < 1 << b.to_usize())
+    .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::RuntimeError;
+    use crate::zir::types::{Signature, Type};
+    use zokrates_field::Bn128Field;
+
+    fn prog(statements: Vec<ZirStatement<Bn128Field>>) -> ZirProgram<Bn128Field> {
+        ZirProgram {
+            main: ZirFunction {
+                arguments: vec![Parameter::private(Variable::field_element("a"))],
+                statements,
+                signature: Signature {
+                    inputs: vec![Type::FieldElement],
+                    outputs: vec![],
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn bounded() {
+        // field x = if a == 1 then 200 else 3 fi
+        // field y = x * 2
+        // assert(x < y)
+
+        let prog = prog(vec![
+            ZirStatement::Definition(
+                Variable::field_element("x"),
+                FieldElementExpression::IfElse(
+                    box BooleanExpression::FieldEq(
+                        box FieldElementExpression::Identifier("a".into()),
+                        box FieldElementExpression::Number(Bn128Field::from(1)),
+                    ),
+                    box FieldElementExpression::Number(Bn128Field::from(200)),
+                    box FieldElementExpression::Number(Bn128Field::from(3)),
+                )
+                .into(),
+            ),
+            ZirStatement::Definition(
+                Variable::field_element("y"),
+                FieldElementExpression::Mult(
+                    box FieldElementExpression::Identifier("x".into()),
+                    box FieldElementExpression::Number(Bn128Field::from(2)),
+                )
+                .into(),
+            ),
+            ZirStatement::Assertion(
+                BooleanExpression::FieldLt(
+                    box FieldElementExpression::Identifier("x".into()),
+                    box FieldElementExpression::Identifier("y".into()),
+                ),
+                RuntimeError::Source,
+            ),
+        ]);
+
+        assert_eq!(
+            FieldComparisonAuditor::audit(&prog),
+            vec![FieldComparisonReport {
+                statement: 2,
+                assertion: "x < y".into(),
+                comparison: "x < y".into(),
+                suggested_bitwidth: Some(UBitwidth::B16),
+                estimated_savings: 2 * (Bn128Field::get_required_bits() - 1),
+            }]
+        );
+    }
+
+    #[test]
+    fn unbounded() {
+        // assert(a >= 42 && !(a - 1 <= 3))
+
+        let a = || FieldElementExpression::Identifier("a".into());
+
+        let prog = prog(vec![ZirStatement::Assertion(
+            BooleanExpression::And(
+                box BooleanExpression::FieldGe(
+                    box a(),
+                    box FieldElementExpression::Number(Bn128Field::from(42)),
+                ),
+                box BooleanExpression::Not(box BooleanExpression::FieldLe(
+                    box FieldElementExpression::Sub(
+                        box a(),
+                        box FieldElementExpression::Number(Bn128Field::from(1)),
+                    ),
+                    box FieldElementExpression::Number(Bn128Field::from(3)),
+                )),
+            ),
+            RuntimeError::Source,
+        )]);
+
+        let reports = FieldComparisonAuditor::audit(&prog);
+
+        assert_eq!(reports.len(), 2);
+        assert!(reports
+            .iter()
+            .all(|r| r.suggested_bitwidth.is_none() && r.estimated_savings == 0));
+    }
+}
@@ -1,11 +1,12 @@
-//! Module containing constant propagation for the flat AST
+//! Module containing constant propagation for the flat AST, and the inlining of variables defined by a linear
+//! expression and used once
 //!
 //! @file propagation.rs
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
 //! @date 2018
 
 use crate::flat_absy::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use zokrates_field::Field;
 
 pub trait Propagate<T: Field> {
@@ -109,6 +110,189 @@ impl<T: Field> FlatProg<T> {
     }
 }
 
+/// Inlines the variables which are defined once by a linear expression and read once, by substituting the expression
+/// at the place the variable is read and removing the definition, like a single-use `let` binding.
+///
+/// Linear expressions are kept in the form expected by the conversion to IR: a constant times a substituted
+/// expression is distributed over it. Variables read by an optimizer barrier are not inlined, so that barriers are
+/// kept as written.
+///
+/// The pass overlaps with the `RedefinitionOptimizer` on the ir program, so it only runs when
+/// `CompileConfig::inline_single_assignments` is set.
+pub struct SingleAssignmentInliner<T: Field> {
+    // the variables to inline
+    inlined: HashSet<FlatVariable>,
+    // the expressions of the variables to inline whose definition was visited but not their use
+    substitutions: HashMap<FlatVariable, FlatExpression<T>>,
+}
+
+impl<T: Field> SingleAssignmentInliner<T> {
+    pub fn inline(p: FlatProg<T>) -> FlatProg<T> {
+        let statements = p.main.statements;
+
+        let mut definitions: HashMap<FlatVariable, usize> = HashMap::new();
+        let mut uses: HashMap<FlatVariable, usize> = HashMap::new();
+        let mut barrier_uses: HashMap<FlatVariable, usize> = HashMap::new();
+
+        for s in &statements {
+            match s {
+                FlatStatement::Return(list) => list
+                    .expressions
+                    .iter()
+                    .for_each(|e| count_uses(e, &mut uses)),
+                FlatStatement::Condition(e1, e2, error) => {
+                    count_uses(e1, &mut uses);
+                    count_uses(e2, &mut uses);
+                    if error.is_barrier() {
+                        count_uses(e1, &mut barrier_uses);
+                        count_uses(e2, &mut barrier_uses);
+                    }
+                }
+                FlatStatement::Definition(v, e) => {
+                    *definitions.entry(*v).or_default() += 1;
+                    count_uses(e, &mut uses);
+                }
+                FlatStatement::Directive(d) => {
+                    d.outputs
+                        .iter()
+                        .for_each(|v| *definitions.entry(*v).or_default() += 1);
+                    d.inputs.iter().for_each(|e| count_uses(e, &mut uses));
+                }
+            }
+        }
+
+        let inlined = statements
+            .iter()
+            .filter_map(|s| match s {
+                FlatStatement::Definition(v, e)
+                    if e.is_linear()
+                        && definitions[v] == 1
+                        && uses.get(v) == Some(&1)
+                        && !barrier_uses.contains_key(v) =>
+                {
+                    Some(*v)
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut inliner = SingleAssignmentInliner {
+            inlined,
+            substitutions: HashMap::new(),
+        };
+
+        let statements = statements
+            .into_iter()
+            .filter_map(|s| inliner.inline_statement(s))
+            .collect();
+
+        assert!(inliner.substitutions.is_empty());
+
+        FlatProg {
+            main: FlatFunction {
+                statements,
+                ..p.main
+            },
+        }
+    }
+
+    fn inline_statement(&mut self, s: FlatStatement<T>) -> Option<FlatStatement<T>> {
+        match s {
+            FlatStatement::Return(list) => Some(FlatStatement::Return(FlatExpressionList {
+                expressions: list
+                    .expressions
+                    .into_iter()
+                    .map(|e| self.inline_expression(e))
+                    .collect(),
+            })),
+            FlatStatement::Condition(e1, e2, error) => Some(FlatStatement::Condition(
+                self.inline_expression(e1),
+                self.inline_expression(e2),
+                error,
+            )),
+            FlatStatement::Definition(v, e) => {
+                let e = self.inline_expression(e);
+                match self.inlined.contains(&v) {
+                    true => {
+                        self.substitutions.insert(v, e);
+                        None
+                    }
+                    false => Some(FlatStatement::Definition(v, e)),
+                }
+            }
+            FlatStatement::Directive(d) => Some(FlatStatement::Directive(FlatDirective {
+                inputs: d
+                    .inputs
+                    .into_iter()
+                    .map(|e| self.inline_expression(e))
+                    .collect(),
+                ..d
+            })),
+        }
+    }
+
+    fn inline_expression(&mut self, e: FlatExpression<T>) -> FlatExpression<T> {
+        match e {
+            FlatExpression::Number(n) => FlatExpression::Number(n),
+            FlatExpression::Identifier(v) => self
+                .substitutions
+                .remove(&v)
+                .unwrap_or(FlatExpression::Identifier(v)),
+            FlatExpression::Add(box e1, box e2) => FlatExpression::Add(
+                box self.inline_expression(e1),
+                box self.inline_expression(e2),
+            ),
+            FlatExpression::Sub(box e1, box e2) => FlatExpression::Sub(
+                box self.inline_expression(e1),
+                box self.inline_expression(e2),
+            ),
+            FlatExpression::Mult(box e1, box e2) => {
+                match (self.inline_expression(e1), self.inline_expression(e2)) {
+                    (FlatExpression::Number(n), e) | (e, FlatExpression::Number(n))
+                        if !matches!(e, FlatExpression::Identifier(_)) =>
+                    {
+                        scale(e, n)
+                    }
+                    (e1, e2) => FlatExpression::Mult(box e1, box e2),
+                }
+            }
+        }
+    }
+}
+
+fn count_uses<T: Field>(e: &FlatExpression<T>, uses: &mut HashMap<FlatVariable, usize>) {
+    match e {
+        FlatExpression::Number(_) => {}
+        FlatExpression::Identifier(v) => *uses.entry(*v).or_default() += 1,
+        FlatExpression::Add(box e1, box e2)
+        | FlatExpression::Sub(box e1, box e2)
+        | FlatExpression::Mult(box e1, box e2) => {
+            count_uses(e1, uses);
+            count_uses(e2, uses);
+        }
+    }
+}
+
+// multiplies the linear expression `e` by `n`, as a sum of numbers and products of a number and a variable
+fn scale<T: Field>(e: FlatExpression<T>, n: T) -> FlatExpression<T> {
+    match e {
+        FlatExpression::Number(m) => FlatExpression::Number(n * m),
+        FlatExpression::Identifier(v) => FlatExpression::Mult(
+            box FlatExpression::Number(n),
+            box FlatExpression::Identifier(v),
+        ),
+        FlatExpression::Add(box e1, box e2) => {
+            FlatExpression::Add(box scale(e1, n.clone()), box scale(e2, n))
+        }
+        FlatExpression::Sub(box e1, box e2) => {
+            FlatExpression::Sub(box scale(e1, n.clone()), box scale(e2, n))
+        }
+        FlatExpression::Mult(box FlatExpression::Number(m), box e)
+        | FlatExpression::Mult(box e, box FlatExpression::Number(m)) => scale(e, n * m),
+        e => unreachable!("expected a linear expression, found {}", e),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,4 +346,147 @@ mod tests {
             }
         }
     }
+
+    mod single_assignment {
+        use super::*;
+        use crate::ir::{self, Interpreter};
+
+        fn prog(statements: Vec<FlatStatement<Bn128Field>>) -> FlatProg<Bn128Field> {
+            FlatProg {
+                main: FlatFunction {
+                    arguments: vec![FlatParameter::private(FlatVariable::new(0))],
+                    statements,
+                },
+            }
+        }
+
+        fn execute(p: FlatProg<Bn128Field>, input: u32) -> Vec<Bn128Field> {
+            Interpreter::default()
+                .execute(&ir::Prog::from(p), &[Bn128Field::from(input)])
+                .unwrap()
+                .return_values()
+        }
+
+        #[test]
+        fn inline_chain() {
+            // _1 = _0 + 1
+            // _2 = 3 * _1
+            // _3 = _2 * _0
+            // return _3
+
+            let p = prog(vec![
+                FlatStatement::Definition(
+                    FlatVariable::new(1),
+                    FlatExpression::Add(
+                        box FlatExpression::Identifier(FlatVariable::new(0)),
+                        box FlatExpression::Number(Bn128Field::from(1)),
+                    ),
+                ),
+                FlatStatement::Definition(
+                    FlatVariable::new(2),
+                    FlatExpression::Mult(
+                        box FlatExpression::Number(Bn128Field::from(3)),
+                        box FlatExpression::Identifier(FlatVariable::new(1)),
+                    ),
+                ),
+                FlatStatement::Definition(
+                    FlatVariable::new(3),
+                    FlatExpression::Mult(
+                        box FlatExpression::Identifier(FlatVariable::new(2)),
+                        box FlatExpression::Identifier(FlatVariable::new(0)),
+                    ),
+                ),
+                FlatStatement::Return(FlatExpressionList {
+                    expressions: vec![FlatExpression::Identifier(FlatVariable::new(3))],
+                }),
+            ]);
+
+            // _3 = (3 * _0 + 3) * _0
+            // return _3
+
+            let expected = prog(vec![
+                FlatStatement::Definition(
+                    FlatVariable::new(3),
+                    FlatExpression::Mult(
+                        box FlatExpression::Add(
+                            box FlatExpression::Mult(
+                                box FlatExpression::Number(Bn128Field::from(3)),
+                                box FlatExpression::Identifier(FlatVariable::new(0)),
+                            ),
+                            box FlatExpression::Number(Bn128Field::from(3)),
+                        ),
+                        box FlatExpression::Identifier(FlatVariable::new(0)),
+                    ),
+                ),
+                FlatStatement::Return(FlatExpressionList {
+                    expressions: vec![FlatExpression::Identifier(FlatVariable::new(3))],
+                }),
+            ]);
+
+            let inlined = SingleAssignmentInliner::inline(p.clone());
+
+            assert_eq!(inlined, expected);
+            assert_eq!(execute(inlined, 5), execute(p, 5));
+        }
+
+        #[test]
+        fn keep_multiple_uses() {
+            // _1 = _0 + 1
+            // _1 == _1 * _1
+            // return _0
+
+            let p = prog(vec![
+                FlatStatement::Definition(
+                    FlatVariable::new(1),
+                    FlatExpression::Add(
+                        box FlatExpression::Identifier(FlatVariable::new(0)),
+                        box FlatExpression::Number(Bn128Field::from(1)),
+                    ),
+                ),
+                FlatStatement::Condition(
+                    FlatExpression::Identifier(FlatVariable::new(1)),
+                    FlatExpression::Mult(
+                        box FlatExpression::Identifier(FlatVariable::new(1)),
+                        box FlatExpression::Identifier(FlatVariable::new(1)),
+                    ),
+                    RuntimeError::Source,
+                ),
+                FlatStatement::Return(FlatExpressionList {
+                    expressions: vec![FlatExpression::Identifier(FlatVariable::new(0))],
+                }),
+            ]);
+
+            assert_eq!(SingleAssignmentInliner::inline(p.clone()), p);
+        }
+
+        #[test]
+        fn keep_barriers() {
+            // _1 = _0 + 1
+            // #[optimizer(barrier)] _1 == _0 * _0
+            // return _0
+
+            let p = prog(vec![
+                FlatStatement::Definition(
+                    FlatVariable::new(1),
+                    FlatExpression::Add(
+                        box FlatExpression::Identifier(FlatVariable::new(0)),
+                        box FlatExpression::Number(Bn128Field::from(1)),
+                    ),
+                ),
+                FlatStatement::Condition(
+                    FlatExpression::Identifier(FlatVariable::new(1)),
+                    FlatExpression::Mult(
+                        box FlatExpression::Identifier(FlatVariable::new(0)),
+                        box FlatExpression::Identifier(FlatVariable::new(0)),
+                    ),
+                    RuntimeError::SourceBarrier,
+                ),
+                FlatStatement::Return(FlatExpressionList {
+                    expressions: vec![FlatExpression::Identifier(FlatVariable::new(0))],
+                }),
+            ]);
+
+            assert_eq!(SingleAssignmentInliner::inline(p.clone()), p);
+        }
+    }
 }
@@ -379,11 +379,14 @@ fn fold_statement<'ast, T: Field>(
         typed_absy::TypedStatement::Declaration(..) => {
             unreachable!()
         }
-        typed_absy::TypedStatement::Assertion(e) => {
+        typed_absy::TypedStatement::Assertion(e, error) => {
             let e = f.fold_boolean_expression(statements_buffer, e);
-            vec![zir::ZirStatement::Assertion(e)]
+            vec![zir::ZirStatement::Assertion(e, error)]
         }
         typed_absy::TypedStatement::For(..) => unreachable!(),
+        typed_absy::TypedStatement::StaticAssertion(..) => {
+            unreachable!("static assertions should have been checked")
+        }
         typed_absy::TypedStatement::MultipleDefinition(variables, elist) => {
             vec![zir::ZirStatement::MultipleDefinition(
                 variables
@@ -574,6 +577,7 @@ fn fold_select_expression<'ast, T: Field, E>(
         .unwrap()
         .get_primitive_count();
 
+    let lookup = select.lookup;
     let array = f.fold_array_expression(statements_buffer, *select.array);
     let index = f.fold_uint_expression(statements_buffer, *select.index);
 
@@ -603,37 +607,49 @@ fn fold_select_expression<'ast, T: Field, E>(
                 let ty = a[0].get_type();
 
                 match ty {
-                    zir::Type::Boolean => zir::BooleanExpression::Select(
-                        a.into_iter()
+                    zir::Type::Boolean => {
+                        let a = a
+                            .into_iter()
                             .map(|e| match e {
                                 zir::ZirExpression::Boolean(e) => e.clone(),
                                 _ => unreachable!(),
                             })
-                            .collect(),
-                        box index.clone(),
-                    )
-                    .into(),
-                    zir::Type::FieldElement => zir::FieldElementExpression::Select(
-                        a.into_iter()
+                            .collect();
+                        match lookup {
+                            true => zir::BooleanExpression::Lookup(a, box index.clone()),
+                            false => zir::BooleanExpression::Select(a, box index.clone()),
+                        }
+                        .into()
+                    }
+                    zir::Type::FieldElement => {
+                        let a = a
+                            .into_iter()
                             .map(|e| match e {
                                 zir::ZirExpression::FieldElement(e) => e.clone(),
                                 _ => unreachable!(),
                             })
-                            .collect(),
-                        box index.clone(),
-                    )
-                    .into(),
-                    zir::Type::Uint(bitwidth) => zir::UExpressionInner::Select(
-                        a.into_iter()
+                            .collect();
+                        match lookup {
+                            true => zir::FieldElementExpression::Lookup(a, box index.clone()),
+                            false => zir::FieldElementExpression::Select(a, box index.clone()),
+                        }
+                        .into()
+                    }
+                    zir::Type::Uint(bitwidth) => {
+                        let a = a
+                            .into_iter()
                             .map(|e| match e {
                                 zir::ZirExpression::Uint(e) => e.clone(),
                                 _ => unreachable!(),
                             })
-                            .collect(),
-                        box index.clone(),
-                    )
-                    .annotate(bitwidth)
-                    .into(),
+                            .collect();
+                        match lookup {
+                            true => zir::UExpressionInner::Lookup(a, box index.clone()),
+                            false => zir::UExpressionInner::Select(a, box index.clone()),
+                        }
+                        .annotate(bitwidth)
+                        .into()
+                    }
                 }
             })
             .collect(),
@@ -0,0 +1,410 @@
+//! Module containing the `InputGenerator`, which derives edge-case inputs from the predicates of a program
+//!
+//! The generator runs on the zir program, which is the reduced and propagated program with complex types
+//! flattened away: its arguments map one to one to the flattened inputs of the compiled program.
+//!
+//! Each comparison between an affine function of a single argument and a constant yields candidates sitting
+//! on, just below and just above the boundary, all other arguments being set to zero.
+//! Comparisons which do not have this shape are skipped and reported.
+//!
+//! The suite is generated at compile time, as the predicates are lost when lowering to ir, and written next to the
+//! compiled program so that `gen-inputs` can encode it against the ABI.
+
+use crate::zir::folder::*;
+use crate::zir::types::UBitwidth;
+use crate::zir::*;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use zokrates_field::Field;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct InputCandidate<T> {
+    /// the predicate this candidate targets
+    pub predicate: String,
+    /// the flattened arguments of the program
+    pub values: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InputSuite<T> {
+    pub candidates: Vec<InputCandidate<T>>,
+    /// the predicates for which no boundary could be found
+    pub skipped: Vec<String>,
+}
+
+impl<T> Default for InputSuite<T> {
+    fn default() -> Self {
+        InputSuite {
+            candidates: vec![],
+            skipped: vec![],
+        }
+    }
+}
+
+pub struct InputGenerator<'ast, T> {
+    // the index of each argument in the flattened inputs
+    arguments: HashMap<Identifier<'ast>, usize>,
+    suite: InputSuite<T>,
+}
+
+impl<'ast, T: Field> InputGenerator<'ast, T> {
+    pub fn generate(p: ZirProgram<'ast, T>) -> InputSuite<T> {
+        let arguments = p
+            .main
+            .arguments
+            .iter()
+            .enumerate()
+            .map(|(index, a)| (a.id.id.clone(), index))
+            .collect();
+
+        let mut generator = InputGenerator {
+            arguments,
+            suite: InputSuite::default(),
+        };
+
+        generator.fold_program(p);
+        generator.suite
+    }
+
+    // a field expression of the shape `a * x + b` for an argument `x` and constants `a` and `b`
+    fn field_affine(&self, e: &FieldElementExpression<'ast, T>) -> Option<(usize, T, T)> {
+        match e {
+            FieldElementExpression::Identifier(id) => self
+                .arguments
+                .get(id)
+                .map(|index| (*index, T::one(), T::zero())),
+            FieldElementExpression::Add(e1, e2) => match (e1.as_ref(), e2.as_ref()) {
+                (e, FieldElementExpression::Number(k)) | (FieldElementExpression::Number(k), e) => {
+                    self.field_affine(e).map(|(i, a, b)| (i, a, b + k))
+                }
+                _ => None,
+            },
+            FieldElementExpression::Sub(e1, e2) => match e2.as_ref() {
+                FieldElementExpression::Number(k) => {
+                    self.field_affine(e1).map(|(i, a, b)| (i, a, b - k))
+                }
+                _ => None,
+            },
+            FieldElementExpression::Mult(e1, e2) => match (e1.as_ref(), e2.as_ref()) {
+                (e, FieldElementExpression::Number(k)) | (FieldElementExpression::Number(k), e) => {
+                    self.field_affine(e).map(|(i, a, b)| (i, a * k, b * k))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // a uint expression of the shape `a * x + b` for an argument `x` and constants `a` and `b`
+    fn uint_affine(&self, e: &UExpression<'ast, T>) -> Option<(usize, T, T)> {
+        match e.as_inner() {
+            UExpressionInner::Identifier(id) => self
+                .arguments
+                .get(id)
+                .map(|index| (*index, T::one(), T::zero())),
+            UExpressionInner::Add(e1, e2) => match (e1.as_inner(), e2.as_inner()) {
+                (_, UExpressionInner::Value(k)) => self
+                    .uint_affine(e1)
+                    .map(|(i, a, b)| (i, a, b + T::from(*k))),
+                (UExpressionInner::Value(k), _) => self
+                    .uint_affine(e2)
+                    .map(|(i, a, b)| (i, a, b + T::from(*k))),
+                _ => None,
+            },
+            UExpressionInner::Sub(e1, e2) => match e2.as_inner() {
+                UExpressionInner::Value(k) => self
+                    .uint_affine(e1)
+                    .map(|(i, a, b)| (i, a, b - T::from(*k))),
+                _ => None,
+            },
+            UExpressionInner::Mult(e1, e2) => match (e1.as_inner(), e2.as_inner()) {
+                (_, UExpressionInner::Value(k)) => self
+                    .uint_affine(e1)
+                    .map(|(i, a, b)| (i, a * T::from(*k), b * T::from(*k))),
+                (UExpressionInner::Value(k), _) => self
+                    .uint_affine(e2)
+                    .map(|(i, a, b)| (i, a * T::from(*k), b * T::from(*k))),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // solve `a * x + b == c` over the field
+    fn solve_field((index, a, b): (usize, T, T), c: T) -> Option<(usize, T)> {
+        if a == T::zero() {
+            return None;
+        }
+
+        Some((index, (c - b) / a))
+    }
+
+    // solve `a * x + b == c` over the integers, as uint arithmetic does not wrap around the field modulus
+    fn solve_uint((index, a, b): (usize, T, T), c: T) -> Option<(usize, T)> {
+        let n = (c - b).to_biguint();
+        let d = a.to_biguint();
+
+        if d == BigUint::from(0u32) || n.clone() % d.clone() != BigUint::from(0u32) {
+            return None;
+        }
+
+        T::try_from(n / d).ok().map(|x| (index, x))
+    }
+
+    fn field_boundary(
+        &self,
+        left: &FieldElementExpression<'ast, T>,
+        right: &FieldElementExpression<'ast, T>,
+    ) -> Option<(usize, T)> {
+        match (left, right) {
+            (e, FieldElementExpression::Number(c)) | (FieldElementExpression::Number(c), e) => self
+                .field_affine(e)
+                .and_then(|affine| Self::solve_field(affine, c.clone())),
+            _ => None,
+        }
+    }
+
+    fn uint_boundary(
+        &self,
+        left: &UExpression<'ast, T>,
+        right: &UExpression<'ast, T>,
+    ) -> Option<(usize, T)> {
+        match (left.as_inner(), right.as_inner()) {
+            (_, UExpressionInner::Value(c)) => self
+                .uint_affine(left)
+                .and_then(|affine| Self::solve_uint(affine, T::from(*c))),
+            (UExpressionInner::Value(c), _) => self
+                .uint_affine(right)
+                .and_then(|affine| Self::solve_uint(affine, T::from(*c))),
+            _ => None,
+        }
+    }
+
+    fn field_predicate(
+        &mut self,
+        predicate: String,
+        left: &FieldElementExpression<'ast, T>,
+        right: &FieldElementExpression<'ast, T>,
+    ) {
+        let candidates = self
+            .field_boundary(left, right)
+            .map(|(index, v)| (index, vec![v.clone() - T::one(), v.clone(), v + T::one()]));
+
+        self.add_candidates(predicate, candidates);
+    }
+
+    fn uint_predicate(
+        &mut self,
+        predicate: String,
+        left: &UExpression<'ast, T>,
+        right: &UExpression<'ast, T>,
+    ) {
+        let bitwidth: UBitwidth = left.bitwidth();
+        let max = T::from(2).pow(bitwidth.to_usize()) - T::one();
+
+        let candidates = self
+            .uint_boundary(left, right)
+            // the boundary must fit in the bitwidth, and so do its neighbours
+            .filter(|(_, v)| *v <= max)
+            .map(|(index, v)| {
+                let mut values = vec![];
+                if v > T::zero() {
+                    values.push(v.clone() - T::one());
+                }
+                values.push(v.clone());
+                if v < max {
+                    values.push(v + T::one());
+                }
+                (index, values)
+            });
+
+        self.add_candidates(predicate, candidates);
+    }
+
+    fn add_candidates(&mut self, predicate: String, candidates: Option<(usize, Vec<T>)>) {
+        match candidates {
+            Some((index, values)) => {
+                for v in values {
+                    let mut row = vec![T::zero(); self.arguments.len()];
+                    row[index] = v;
+
+                    let candidate = InputCandidate {
+                        predicate: predicate.clone(),
+                        values: row,
+                    };
+
+                    if !self.suite.candidates.contains(&candidate) {
+                        self.suite.candidates.push(candidate);
+                    }
+                }
+            }
+            _ => {
+                if !self.suite.skipped.contains(&predicate) {
+                    self.suite.skipped.push(predicate);
+                }
+            }
+        }
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for InputGenerator<'ast, T> {
+    fn fold_boolean_expression(
+        &mut self,
+        e: BooleanExpression<'ast, T>,
+    ) -> BooleanExpression<'ast, T> {
+        match &e {
+            BooleanExpression::FieldLt(left, right)
+            | BooleanExpression::FieldLe(left, right)
+            | BooleanExpression::FieldGe(left, right)
+            | BooleanExpression::FieldGt(left, right)
+            | BooleanExpression::FieldEq(left, right) => {
+                self.field_predicate(e.to_string(), left, right)
+            }
+            BooleanExpression::UintLt(left, right)
+            | BooleanExpression::UintLe(left, right)
+            | BooleanExpression::UintGe(left, right)
+            | BooleanExpression::UintGt(left, right)
+            | BooleanExpression::UintEq(left, right) => {
+                self.uint_predicate(e.to_string(), left, right)
+            }
+            _ => {}
+        };
+
+        fold_boolean_expression(self, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compile::{compile, CompilationArtifacts, CompileConfig};
+    use crate::ir::Interpreter;
+    use std::io;
+    use zokrates_common::Resolver;
+    use zokrates_field::Bn128Field;
+
+    fn compile_with_boundaries(source: &str) -> CompilationArtifacts<Bn128Field> {
+        compile(
+            source.to_string(),
+            "main".into(),
+            None::<&dyn Resolver<io::Error>>,
+            &CompileConfig::default().input_boundaries(true),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn boundaries() {
+        let artifacts = compile_with_boundaries(
+            r#"
+            def main(field x, field y) -> field:
+                assert(x < 100)
+                return if y == 0 then 1 else 2 fi
+        "#,
+        );
+
+        let suite = artifacts.input_suite().unwrap();
+
+        let values: Vec<Vec<Bn128Field>> =
+            suite.candidates.iter().map(|c| c.values.clone()).collect();
+
+        assert_eq!(
+            values,
+            vec![
+                vec![Bn128Field::from(99), Bn128Field::from(0)],
+                vec![Bn128Field::from(100), Bn128Field::from(0)],
+                vec![Bn128Field::from(101), Bn128Field::from(0)],
+                vec![Bn128Field::from(0), Bn128Field::from(-1)],
+                vec![Bn128Field::from(0), Bn128Field::from(0)],
+                vec![Bn128Field::from(0), Bn128Field::from(1)],
+            ]
+        );
+        assert!(suite.skipped.is_empty());
+
+        // running the program on the candidates exercises both outcomes of the assertion
+        let interpreter = Interpreter::default();
+
+        let results: Vec<bool> = values
+            .iter()
+            .map(|v| interpreter.execute(artifacts.prog(), v).is_ok())
+            .collect();
+
+        assert_eq!(results, vec![true, false, false, true, true, true]);
+    }
+
+    #[test]
+    fn field_boundaries_use_the_inverse() {
+        let artifacts = compile_with_boundaries(
+            r#"
+            def main(field x) -> bool:
+                return 2 * x == 3
+        "#,
+        );
+
+        let suite = artifacts.input_suite().unwrap();
+
+        // `3 / 2` has no integer solution, but has one in the field
+        let boundary = Bn128Field::from(3) / Bn128Field::from(2);
+
+        let values: Vec<Vec<Bn128Field>> =
+            suite.candidates.iter().map(|c| c.values.clone()).collect();
+
+        assert_eq!(
+            values,
+            vec![
+                vec![boundary.clone() - Bn128Field::from(1)],
+                vec![boundary.clone()],
+                vec![boundary + Bn128Field::from(1)],
+            ]
+        );
+        assert!(suite.skipped.is_empty());
+    }
+
+    #[test]
+    fn uint_boundaries_stay_in_range() {
+        let artifacts = compile_with_boundaries(
+            r#"
+            def main(u8 x) -> bool:
+                return x + 1 > 0
+        "#,
+        );
+
+        let suite = artifacts.input_suite().unwrap();
+
+        // the boundary `x == -1` does not fit in a u8
+        assert!(suite.candidates.is_empty());
+        assert_eq!(suite.skipped.len(), 1);
+    }
+
+    #[test]
+    fn uint_boundaries_are_integers() {
+        let artifacts = compile_with_boundaries(
+            r#"
+            def main(u8 x) -> bool:
+                return 2 * x == 3
+        "#,
+        );
+
+        let suite = artifacts.input_suite().unwrap();
+
+        // uint arithmetic does not wrap around the field modulus, so `3 / 2` is not a boundary
+        assert!(suite.candidates.is_empty());
+        assert_eq!(suite.skipped.len(), 1);
+    }
+
+    #[test]
+    fn non_affine_predicates_are_skipped() {
+        let artifacts = compile_with_boundaries(
+            r#"
+            def main(field x) -> bool:
+                return x * x == 4
+        "#,
+        );
+
+        let suite = artifacts.input_suite().unwrap();
+
+        assert!(suite.candidates.is_empty());
+        assert_eq!(suite.skipped.len(), 1);
+    }
+}
@@ -0,0 +1,100 @@
+//! Module containing the `LookupReport`, listing the lookups in constant tables which are left with a dynamic index
+//! after propagation, along with their cost
+//!
+//! Lookups with a constant index are folded to the element of the table and cost nothing, so they do not appear in
+//! the report. The cost of the others is given by `lookup_cost` and counted before optimization.
+
+use crate::flatten::lookup_cost;
+use crate::zir::folder::{self, Folder};
+use crate::zir::types::UBitwidth;
+use crate::zir::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use zokrates_field::Field;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct LookupReportEntry {
+    /// the number of elements of the table
+    pub size: usize,
+    /// the number of constraints of the lookup
+    pub cost: usize,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LookupReport {
+    pub entries: Vec<LookupReportEntry>,
+}
+
+impl LookupReport {
+    pub fn generate<T: Field>(prog: &ZirProgram<T>) -> Self {
+        let mut report = LookupReport::default();
+        report.fold_function(prog.main.clone());
+        report
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The number of constraints of all lookups
+    pub fn cost(&self) -> usize {
+        self.entries.iter().map(|e| e.cost).sum()
+    }
+
+    fn push(&mut self, size: usize) {
+        self.entries.push(LookupReportEntry {
+            size,
+            cost: lookup_cost(size),
+        });
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for LookupReport {
+    fn fold_field_expression(
+        &mut self,
+        e: FieldElementExpression<'ast, T>,
+    ) -> FieldElementExpression<'ast, T> {
+        if let FieldElementExpression::Lookup(ref a, _) = e {
+            self.push(a.len());
+        }
+        folder::fold_field_expression(self, e)
+    }
+
+    fn fold_boolean_expression(
+        &mut self,
+        e: BooleanExpression<'ast, T>,
+    ) -> BooleanExpression<'ast, T> {
+        if let BooleanExpression::Lookup(ref a, _) = e {
+            self.push(a.len());
+        }
+        folder::fold_boolean_expression(self, e)
+    }
+
+    fn fold_uint_expression_inner(
+        &mut self,
+        bitwidth: UBitwidth,
+        e: UExpressionInner<'ast, T>,
+    ) -> UExpressionInner<'ast, T> {
+        if let UExpressionInner::Lookup(ref a, _) = e {
+            self.push(a.len());
+        }
+        folder::fold_uint_expression_inner(self, bitwidth, e)
+    }
+}
+
+impl fmt::Display for LookupReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.entries
+                .iter()
+                .map(|e| format!(
+                    "lookup in a table of {} elements: {} constraints",
+                    e.size, e.cost
+                ))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    }
+}
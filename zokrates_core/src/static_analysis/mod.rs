@@ -7,25 +7,47 @@
 mod branch_isolator;
 mod constant_argument_checker;
 mod constant_inliner;
+mod directive_postcondition_checker;
+mod field_comparison_auditor;
 mod flat_propagation;
 mod flatten_complex_types;
+mod input_generator;
+mod lookup_report;
 mod propagation;
 mod reducer;
+mod static_assertion_checker;
+mod timing_report;
 mod uint_optimizer;
+mod unchecked_coercion_checker;
 mod unconstrained_vars;
+mod unreachable_code;
 mod variable_write_remover;
+mod wire_equality;
+mod zero_size_checker;
 mod zir_propagation;
 
 use self::branch_isolator::Isolator;
 use self::constant_argument_checker::ConstantArgumentChecker;
+use self::directive_postcondition_checker::DirectivePostconditionChecker;
+pub use self::field_comparison_auditor::{FieldComparisonAuditor, FieldComparisonReport};
+pub use self::flat_propagation::SingleAssignmentInliner;
 use self::flatten_complex_types::Flattener;
+pub use self::input_generator::{InputCandidate, InputGenerator, InputSuite};
+pub use self::lookup_report::{LookupReport, LookupReportEntry};
 use self::propagation::Propagator;
 use self::reducer::reduce_program;
+use self::static_assertion_checker::StaticAssertionChecker;
+pub use self::timing_report::{TimingReport, TimingReportEntry};
 use self::uint_optimizer::UintOptimizer;
+pub use self::unchecked_coercion_checker::UncheckedCoercionChecker;
 use self::unconstrained_vars::UnconstrainedVariableDetector;
+pub use self::unreachable_code::UnreachableCodeDetector;
 use self::variable_write_remover::VariableWriteRemover;
+use self::wire_equality::WireEqualityMerger;
+use self::zero_size_checker::ZeroSizeChecker;
 use crate::compile::CompileConfig;
 use crate::ir::Prog;
+use crate::optimizer::{TautologyOptimizer, ZirDuplicateOptimizer};
 use crate::static_analysis::constant_inliner::ConstantInliner;
 use crate::static_analysis::zir_propagation::ZirPropagator;
 use crate::typed_absy::{abi::Abi, TypedProgram};
@@ -48,6 +70,9 @@ pub enum Error {
     NonConstantArgument(self::constant_argument_checker::Error),
     ConstantInliner(self::constant_inliner::Error),
     UnconstrainedVariable(self::unconstrained_vars::Error),
+    ZeroSize(self::zero_size_checker::Error),
+    StaticAssertion(self::static_assertion_checker::Error),
+    DirectivePostcondition(self::directive_postcondition_checker::Error),
 }
 
 impl From<constant_inliner::Error> for Error {
@@ -86,6 +111,24 @@ impl From<unconstrained_vars::Error> for Error {
     }
 }
 
+impl From<zero_size_checker::Error> for Error {
+    fn from(e: zero_size_checker::Error) -> Self {
+        Error::ZeroSize(e)
+    }
+}
+
+impl From<static_assertion_checker::Error> for Error {
+    fn from(e: static_assertion_checker::Error) -> Self {
+        Error::StaticAssertion(e)
+    }
+}
+
+impl From<directive_postcondition_checker::Error> for Error {
+    fn from(e: directive_postcondition_checker::Error) -> Self {
+        Error::DirectivePostcondition(e)
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -95,6 +138,9 @@ impl fmt::Display for Error {
             Error::NonConstantArgument(e) => write!(f, "{}", e),
             Error::ConstantInliner(e) => write!(f, "{}", e),
             Error::UnconstrainedVariable(e) => write!(f, "{}", e),
+            Error::ZeroSize(e) => write!(f, "{}", e),
+            Error::StaticAssertion(e) => write!(f, "{}", e),
+            Error::DirectivePostcondition(e) => write!(f, "{}", e),
         }
     }
 }
@@ -119,9 +165,14 @@ impl<'ast, T: Field> TypedProgram<'ast, T> {
 
         // reduce the program to a single function
         log::debug!("Static analyser: Reduce program");
+        let zero_size_checker = ZeroSizeChecker::new(&r);
         let r = reduce_program(r).map_err(Error::from)?;
         log::trace!("\n{}", r);
 
+        // detect arrays of size zero passed to functions
+        log::debug!("Static analyser: Detect arrays of size zero");
+        zero_size_checker.check(&r).map_err(Error::from)?;
+
         // generate abi
         log::debug!("Static analyser: Generate abi");
         let abi = r.abi();
@@ -131,6 +182,11 @@ impl<'ast, T: Field> TypedProgram<'ast, T> {
         let r = Propagator::propagate(r).map_err(Error::from)?;
         log::trace!("\n{}", r);
 
+        // evaluate static assertions, now that generics are known
+        log::debug!("Static analyser: Check static assertions");
+        let r = StaticAssertionChecker::check(r).map_err(Error::from)?;
+        log::trace!("\n{}", r);
+
         // remove assignment to variable index
         log::debug!("Static analyser: Remove variable index");
         let r = VariableWriteRemover::apply(r);
@@ -151,6 +207,11 @@ impl<'ast, T: Field> TypedProgram<'ast, T> {
         let zir = ZirPropagator::propagate(zir).map_err(Error::from)?;
         log::trace!("\n{}", zir);
 
+        // remove duplicate statements in zir
+        log::debug!("Static analyser: Remove duplicate statements in zir");
+        let zir = ZirDuplicateOptimizer::optimize(zir);
+        log::trace!("\n{}", zir);
+
         // optimize uint expressions
         log::debug!("Static analyser: Optimize uints");
         let zir = UintOptimizer::optimize(zir);
@@ -164,8 +225,17 @@ impl<T: Field> Analyse for Prog<T> {
     type Error = Error;
 
     fn analyse(self) -> Result<Self, Self::Error> {
+        log::debug!("Static analyser: Merge wire equalities");
+        let r = WireEqualityMerger::merge(self);
+
+        log::debug!("Static analyser: Remove tautologies");
+        let r = TautologyOptimizer::optimize(r);
+
+        log::debug!("Static analyser: Check directive postconditions");
+        DirectivePostconditionChecker::check(&r).map_err(Error::from)?;
+
         log::debug!("Static analyser: Detect unconstrained zir");
-        UnconstrainedVariableDetector::detect(&self).map_err(Error::from)?;
-        Ok(self)
+        UnconstrainedVariableDetector::detect(&r).map_err(Error::from)?;
+        Ok(r)
     }
 }
@@ -3,6 +3,10 @@
 //! Constant propagation on the SSA program. The constants map can be passed by the caller to allow for many passes to use
 //! the same constants.
 //!
+//! Expressions are folded bottom-up: as soon as a sub-expression reduces to a constant, its parents are folded with that
+//! constant before the next statement is visited. For example, once the reducer inlined `outer(inner(5))`, the return
+//! value of `inner` is substituted into the arguments of `outer`, and the whole call reduces in a single pass.
+//!
 //! @file propagation.rs
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
 //! @date 2018
@@ -24,6 +28,7 @@ pub enum Error {
     AssertionFailed(String),
     OutOfBounds(u128, u128),
     NonConstantExponent(String),
+    ValueTooLarge(String, u32),
 }
 
 impl fmt::Display for Error {
@@ -41,6 +46,11 @@ impl fmt::Display for Error {
                 "Non-constant exponent `{}` detected during static analysis",
                 s
             ),
+            Error::ValueTooLarge(s, bit_width) => write!(
+                f,
+                "Value `{}` does not fit in {} bits, found during static analysis",
+                s, bit_width
+            ),
         }
     }
 }
@@ -435,8 +445,8 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                                         match FieldElementExpression::try_from(arguments[0].clone())
                                             .unwrap()
                                         {
-                                            FieldElementExpression::Number(num) => {
-                                                let mut num = num;
+                                            FieldElementExpression::Number(value) => {
+                                                let mut num = value.clone();
                                                 let mut res = vec![];
 
                                                 for i in (0..bit_width as usize).rev() {
@@ -447,7 +457,15 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                                                         res.push(false);
                                                     }
                                                 }
-                                                assert_eq!(num, T::zero());
+
+                                                // the decomposition is checked at runtime, so a constant which
+                                                // does not fit would fail in the same way
+                                                if num != T::zero() {
+                                                    return Err(Error::ValueTooLarge(
+                                                        value.to_string(),
+                                                        bit_width,
+                                                    ));
+                                                }
 
                                                 Some(
                                                     ArrayExpressionInner::Value(
@@ -610,7 +628,7 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
 
                 Ok(statements)
             }
-            TypedStatement::Assertion(e) => {
+            TypedStatement::Assertion(e, error) => {
                 let e_str = e.to_string();
                 let expr = self.fold_boolean_expression(e)?;
                 match expr {
@@ -618,7 +636,7 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                         "Assertion failed on expression `{}`",
                         e_str
                     ))),
-                    _ => Ok(vec![TypedStatement::Assertion(expr)]),
+                    _ => Ok(vec![TypedStatement::Assertion(expr, error)]),
                 }
             }
             s @ TypedStatement::PushCallLog(..) => Ok(vec![s]),
@@ -980,6 +998,7 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
         _: &E::Ty,
         e: SelectExpression<'ast, T, E>,
     ) -> Result<SelectOrExpression<'ast, T, E>, Self::Error> {
+        let lookup = e.lookup;
         let array = self.fold_array_expression(*e.array)?;
         let index = self.fold_uint_expression(*e.index)?;
 
@@ -1032,13 +1051,14 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                         )),
                     }
                 }
-                (a, i) => Ok(SelectOrExpression::Select(SelectExpression::new(
+                (a, i) => Ok(SelectOrExpression::Select(SelectExpression::with_lookup(
                     a.annotate(inner_type, size as u32),
                     i.annotate(UBitwidth::B32),
+                    lookup,
                 ))),
             },
-            _ => Ok(SelectOrExpression::Select(SelectExpression::new(
-                array, index,
+            _ => Ok(SelectOrExpression::Select(SelectExpression::with_lookup(
+                array, index, lookup,
             ))),
         }
     }
@@ -1429,6 +1449,39 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn neg() {
+                let mut constants = Constants::new();
+                constants.insert(
+                    "x".into(),
+                    FieldElementExpression::Number(Bn128Field::from(5)).into(),
+                );
+
+                // -x
+                let e =
+                    FieldElementExpression::Neg(box FieldElementExpression::Identifier("x".into()));
+
+                assert_eq!(
+                    Propagator::with_constants(&mut constants).fold_field_expression(e),
+                    Ok(FieldElementExpression::Number(
+                        Bn128Field::from(0) - Bn128Field::from(5)
+                    ))
+                );
+
+                // -x + 7
+                let e = FieldElementExpression::Add(
+                    box FieldElementExpression::Neg(box FieldElementExpression::Identifier(
+                        "x".into(),
+                    )),
+                    box FieldElementExpression::Number(Bn128Field::from(7)),
+                );
+
+                assert_eq!(
+                    Propagator::with_constants(&mut constants).fold_field_expression(e),
+                    Ok(FieldElementExpression::Number(Bn128Field::from(2)))
+                );
+            }
+
             #[test]
             fn if_else_true() {
                 let e = FieldElementExpression::if_else(
@@ -0,0 +1,171 @@
+//! Module containing the `StaticAssertionChecker`, evaluating the `static_assert` statements of a program
+//!
+//! The condition of a static assertion can depend on generic parameters, so it is checked on the reduced program, once
+//! generics are specialized and constants are propagated. Assertions which hold are removed, and the others are
+//! reported with the source of their condition, as they do not reach witness computation.
+
+use crate::parser::Position;
+use crate::typed_absy::result_folder::{fold_statement, ResultFolder};
+use crate::typed_absy::{BooleanExpression, StaticAssertionSource, TypedProgram, TypedStatement};
+use std::fmt;
+use zokrates_field::Field;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    StaticAssertionFailed {
+        condition_text: String,
+        span: (Position, Position),
+    },
+    NonConstantStaticAssertion {
+        condition_text: String,
+        span: (Position, Position),
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::StaticAssertionFailed {
+                condition_text,
+                span,
+            } => write!(
+                f,
+                "Static assertion `{}` failed at {}",
+                condition_text, span.0
+            ),
+            Error::NonConstantStaticAssertion {
+                condition_text,
+                span,
+            } => write!(
+                f,
+                "Static assertion `{}` at {} could not be evaluated at compile time",
+                condition_text, span.0
+            ),
+        }
+    }
+}
+
+pub struct StaticAssertionChecker;
+
+impl StaticAssertionChecker {
+    pub fn check<T: Field>(p: TypedProgram<T>) -> Result<TypedProgram<T>, Error> {
+        StaticAssertionChecker.fold_program(p)
+    }
+}
+
+impl<'ast, T: Field> ResultFolder<'ast, T> for StaticAssertionChecker {
+    type Error = Error;
+
+    fn fold_statement(
+        &mut self,
+        s: TypedStatement<'ast, T>,
+    ) -> Result<Vec<TypedStatement<'ast, T>>, Error> {
+        match s {
+            TypedStatement::StaticAssertion(
+                e,
+                StaticAssertionSource {
+                    condition_text,
+                    span,
+                },
+            ) => match e {
+                BooleanExpression::Value(true) => Ok(vec![]),
+                BooleanExpression::Value(false) => Err(Error::StaticAssertionFailed {
+                    condition_text,
+                    span,
+                }),
+                _ => Err(Error::NonConstantStaticAssertion {
+                    condition_text,
+                    span,
+                }),
+            },
+            s => fold_statement(self, s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::typed_absy::{
+        DeclarationFunctionKey, DeclarationSignature, TypedFunction, TypedFunctionSymbol,
+        TypedModule,
+    };
+    use zokrates_field::Bn128Field;
+
+    fn source() -> StaticAssertionSource {
+        StaticAssertionSource {
+            condition_text: "N > 0".into(),
+            span: (Position::mock(), Position::mock()),
+        }
+    }
+
+    fn program(statements: Vec<TypedStatement<Bn128Field>>) -> TypedProgram<Bn128Field> {
+        TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(DeclarationSignature::new()),
+                        TypedFunctionSymbol::Here(TypedFunction {
+                            arguments: vec![],
+                            statements,
+                            signature: DeclarationSignature::new(),
+                        }),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    #[test]
+    fn remove_holding_assertions() {
+        let p = program(vec![
+            TypedStatement::StaticAssertion(BooleanExpression::Value(true), source()),
+            TypedStatement::Return(vec![]),
+        ]);
+
+        assert_eq!(
+            StaticAssertionChecker::check(p),
+            Ok(program(vec![TypedStatement::Return(vec![])]))
+        );
+    }
+
+    #[test]
+    fn report_failing_assertions() {
+        let p = program(vec![
+            TypedStatement::StaticAssertion(BooleanExpression::Value(false), source()),
+            TypedStatement::Return(vec![]),
+        ]);
+
+        assert_eq!(
+            StaticAssertionChecker::check(p),
+            Err(Error::StaticAssertionFailed {
+                condition_text: "N > 0".into(),
+                span: (Position::mock(), Position::mock()),
+            })
+        );
+    }
+
+    #[test]
+    fn report_non_constant_assertions() {
+        let p = program(vec![
+            TypedStatement::StaticAssertion(BooleanExpression::Identifier("b".into()), source()),
+            TypedStatement::Return(vec![]),
+        ]);
+
+        assert_eq!(
+            StaticAssertionChecker::check(p),
+            Err(Error::NonConstantStaticAssertion {
+                condition_text: "N > 0".into(),
+                span: (Position::mock(), Position::mock()),
+            })
+        );
+    }
+}
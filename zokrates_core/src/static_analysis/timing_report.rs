@@ -0,0 +1,267 @@
+//! Module containing the `TimingReport`, listing the directives whose witness computation is not
+//! constant-time and can be influenced by private inputs, together with these inputs
+//!
+//! The report is derived from a forward information-flow analysis on the IR: each variable is
+//! mapped to the set of private arguments its value depends on.
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::{Prog, QuadComb, Statement};
+use crate::solvers::Solver;
+use crate::typed_absy::abi::Abi;
+use crate::typed_absy::types::ConcreteType;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use zokrates_field::Field;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimingReportEntry {
+    /// the index of the directive in the program statements
+    pub statement: usize,
+    pub solver: Solver,
+    /// the private inputs which can reach the inputs of the directive
+    pub private_inputs: Vec<String>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimingReport {
+    pub entries: Vec<TimingReportEntry>,
+}
+
+impl TimingReport {
+    pub fn generate<T: Field>(prog: &Prog<T>, abi: &Abi) -> Self {
+        let names = argument_names(abi);
+
+        // the private arguments each variable depends on, identified by their index in the arguments
+        let mut sources: HashMap<FlatVariable, BTreeSet<usize>> = HashMap::new();
+        sources.insert(FlatVariable::one(), BTreeSet::new());
+
+        for (index, argument) in prog.arguments.iter().enumerate() {
+            let mut s = BTreeSet::new();
+            if argument.private {
+                s.insert(index);
+            }
+            sources.insert(argument.id, s);
+        }
+
+        let mut entries = vec![];
+
+        for (index, statement) in prog.statements.iter().enumerate() {
+            match statement {
+                Statement::Constraint(quad, lin, _) => {
+                    // like in the interpreter, a constraint defines its right hand side if it is a single unknown variable
+                    if lin.0.len() == 1
+                        && lin.0[0].1 == T::one()
+                        && !sources.contains_key(&lin.0[0].0)
+                    {
+                        let s = reaching(&sources, std::iter::once(quad));
+                        sources.insert(lin.0[0].0, s);
+                    }
+                }
+                Statement::Directive(d) => {
+                    let s = reaching(&sources, d.inputs.iter());
+
+                    // a directive reached by public inputs only does not leak anything through its timing
+                    if !d.solver.is_constant_time() && !s.is_empty() {
+                        entries.push(TimingReportEntry {
+                            statement: index,
+                            solver: d.solver.clone(),
+                            private_inputs: s
+                                .iter()
+                                .map(|i| {
+                                    names
+                                        .get(*i)
+                                        .cloned()
+                                        .unwrap_or_else(|| prog.arguments[*i].id.to_string())
+                                })
+                                .collect(),
+                        });
+                    }
+
+                    for o in d.outputs.iter() {
+                        sources.insert(*o, s.clone());
+                    }
+                }
+            }
+        }
+
+        TimingReport { entries }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+fn reaching<'a, T: 'a, I: Iterator<Item = &'a QuadComb<T>>>(
+    sources: &HashMap<FlatVariable, BTreeSet<usize>>,
+    quads: I,
+) -> BTreeSet<usize> {
+    quads
+        .flat_map(|q| q.left.0.iter().chain(q.right.0.iter()))
+        .filter_map(|(v, _)| sources.get(v))
+        .flat_map(|s| s.iter().cloned())
+        .collect()
+}
+
+// the names of the flattened arguments, in order
+fn argument_names(abi: &Abi) -> Vec<String> {
+    abi.inputs
+        .iter()
+        .flat_map(|input| match input.ty {
+            ConcreteType::FieldElement | ConcreteType::Boolean | ConcreteType::Uint(..) => {
+                vec![input.name.clone()]
+            }
+            _ => (0..input.ty.get_primitive_count())
+                .map(|i| format!("{}[{}]", input.name, i))
+                .collect(),
+        })
+        .collect()
+}
+
+impl fmt::Display for TimingReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.entries
+                .iter()
+                .map(|e| format!(
+                    "#{} {}: {}",
+                    e.statement,
+                    e.solver,
+                    e.private_inputs.join(", ")
+                ))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatParameter;
+    use crate::ir::{Directive, LinComb};
+    use crate::typed_absy::abi::AbiInput;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn private_input_reaches_division() {
+        // def main(private _0, _1) -> (1):
+        //     (1 * _0) * (1 * _0) == 1 * _2
+        //     # _3 = Div(_2, 1 * ~one)
+        //     # _4 = Div(_1, 1 * ~one)
+        //     # _5, _6 = ConditionEq(_1)
+        //     (1 * _3) * (1 * _4) == 1 * ~out_0
+        //     return ~out_0
+
+        let a = FlatParameter::private(FlatVariable::new(0));
+        let b = FlatParameter::public(FlatVariable::new(1));
+        let _2 = FlatVariable::new(2);
+        let _3 = FlatVariable::new(3);
+        let _4 = FlatVariable::new(4);
+        let _5 = FlatVariable::new(5);
+        let _6 = FlatVariable::new(6);
+        let out_0 = FlatVariable::public(0);
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![a, b],
+            statements: vec![
+                Statement::definition(
+                    _2,
+                    QuadComb::from_linear_combinations(a.id.into(), a.id.into()),
+                ),
+                Statement::Directive(Directive {
+                    inputs: vec![_2.into(), LinComb::one().into()],
+                    outputs: vec![_3],
+                    solver: Solver::Div,
+                }),
+                Statement::Directive(Directive {
+                    inputs: vec![b.id.into(), LinComb::one().into()],
+                    outputs: vec![_4],
+                    solver: Solver::Div,
+                }),
+                Statement::Directive(Directive {
+                    inputs: vec![b.id.into()],
+                    outputs: vec![_5, _6],
+                    solver: Solver::ConditionEq,
+                }),
+                Statement::definition(
+                    out_0,
+                    QuadComb::from_linear_combinations(_3.into(), _4.into()),
+                ),
+            ],
+            returns: vec![out_0],
+        };
+
+        let abi = Abi {
+            inputs: vec![
+                AbiInput {
+                    name: "a".into(),
+                    public: false,
+                    deferred: false,
+                    ty: ConcreteType::FieldElement,
+                },
+                AbiInput {
+                    name: "b".into(),
+                    public: true,
+                    deferred: false,
+                    ty: ConcreteType::FieldElement,
+                },
+            ],
+            outputs: vec![ConcreteType::FieldElement],
+        };
+
+        let report = TimingReport::generate(&p, &abi);
+
+        assert_eq!(
+            report,
+            TimingReport {
+                entries: vec![TimingReportEntry {
+                    statement: 1,
+                    solver: Solver::Div,
+                    private_inputs: vec!["a".into()]
+                }]
+            }
+        );
+    }
+
+    #[test]
+    fn constant_time_directives_are_not_reported() {
+        // def main(private _0) -> (1):
+        //     # _1, _2 = Bits(_0)
+        //     (1 * ~one) * (1 * _1 + 1 * _2) == 1 * ~out_0
+        //     return ~out_0
+
+        let a = FlatParameter::private(FlatVariable::new(0));
+        let _1 = FlatVariable::new(1);
+        let _2 = FlatVariable::new(2);
+        let out_0 = FlatVariable::public(0);
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![a],
+            statements: vec![
+                Statement::Directive(Directive {
+                    inputs: vec![a.id.into()],
+                    outputs: vec![_1, _2],
+                    solver: Solver::Bits(2),
+                }),
+                Statement::definition(out_0, LinComb::from(_1) + LinComb::from(_2)),
+            ],
+            returns: vec![out_0],
+        };
+
+        let abi = Abi {
+            inputs: vec![AbiInput {
+                name: "a".into(),
+                public: false,
+                deferred: false,
+                ty: ConcreteType::FieldElement,
+            }],
+            outputs: vec![ConcreteType::FieldElement],
+        };
+
+        assert!(TimingReport::generate(&p, &abi).is_empty());
+    }
+}
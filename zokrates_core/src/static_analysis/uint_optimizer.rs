@@ -69,6 +69,15 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
 
                 FieldElementExpression::Select(a, box force_reduce(i))
             }
+            FieldElementExpression::Lookup(a, box i) => {
+                let a = a
+                    .into_iter()
+                    .map(|e| self.fold_field_expression(e))
+                    .collect();
+                let i = self.fold_uint_expression(i);
+
+                FieldElementExpression::Lookup(a, box force_reduce(i))
+            }
             _ => fold_field_expression(self, e),
         }
     }
@@ -87,6 +96,15 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
 
                 BooleanExpression::Select(a, box force_reduce(i))
             }
+            BooleanExpression::Lookup(a, box i) => {
+                let a = a
+                    .into_iter()
+                    .map(|e| self.fold_boolean_expression(e))
+                    .collect();
+                let i = self.fold_uint_expression(i);
+
+                BooleanExpression::Lookup(a, box force_reduce(i))
+            }
             BooleanExpression::UintEq(box left, box right) => {
                 let left = self.fold_uint_expression(left);
                 let right = self.fold_uint_expression(right);
@@ -182,6 +200,27 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
 
                 UExpression::select(values, index).with_max(max_value)
             }
+            Lookup(values, box index) => {
+                let index = self.fold_uint_expression(index);
+
+                let index = force_reduce(index);
+
+                let values: Vec<_> = values
+                    .into_iter()
+                    .map(|v| force_no_reduce(self.fold_uint_expression(v)))
+                    .collect();
+
+                let max_value = T::try_from(
+                    values
+                        .iter()
+                        .map(|v| v.metadata.as_ref().unwrap().max.to_biguint())
+                        .max()
+                        .unwrap(),
+                )
+                .unwrap();
+
+                UExpression::lookup(values, index).with_max(max_value)
+            }
             Add(box left, box right) => {
                 // reduce the two terms
                 let left = self.fold_uint_expression(left);
@@ -531,7 +570,7 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
                     }
                 }
             }
-            ZirStatement::Assertion(BooleanExpression::UintEq(box left, box right)) => {
+            ZirStatement::Assertion(BooleanExpression::UintEq(box left, box right), error) => {
                 let left = self.fold_uint_expression(left);
                 let right = self.fold_uint_expression(right);
 
@@ -539,9 +578,10 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
                 let left = force_reduce(left);
                 let right = force_reduce(right);
 
-                vec![ZirStatement::Assertion(BooleanExpression::UintEq(
-                    box left, box right,
-                ))]
+                vec![ZirStatement::Assertion(
+                    BooleanExpression::UintEq(box left, box right),
+                    error,
+                )]
             }
             s => fold_statement(self, s),
         }
@@ -799,4 +839,61 @@ mod tests {
             Bn128Field::from(42)
         );
     }
+
+    #[test]
+    fn merge_range_checks() {
+        // def main(u32 a) -> (u32, u32):
+        //     u32 c = a + a
+        //     return c ^ a, c & a
+
+        use crate::compile::CompileConfig;
+        use crate::flat_absy::{FlatDirective, FlatStatement};
+        use crate::flatten::Flattener;
+        use crate::solvers::Solver;
+        use crate::zir::types::{Signature, UBitwidth};
+
+        let a = || UExpressionInner::Identifier("a".into()).annotate(32);
+        let c = || UExpressionInner::Identifier("c".into()).annotate(32);
+
+        let p: ZirProgram<Bn128Field> = ZirProgram {
+            main: ZirFunction {
+                arguments: vec![Parameter::private(Variable::uint("a".into(), 32))],
+                statements: vec![
+                    ZirStatement::Definition(
+                        Variable::uint("c".into(), 32),
+                        UExpression::add(a(), a()).into(),
+                    ),
+                    ZirStatement::Return(vec![
+                        UExpression::xor(c(), a()).into(),
+                        UExpression::and(c(), a()).into(),
+                    ]),
+                ],
+                signature: Signature::new()
+                    .inputs(vec![Type::Uint(UBitwidth::B32)])
+                    .outputs(vec![Type::Uint(UBitwidth::B32), Type::Uint(UBitwidth::B32)]),
+            },
+        };
+
+        let p = UintOptimizer::optimize(p);
+
+        // both operations reduce `c`, which is only decomposed once: range checks are emitted by the flattener, which
+        // keeps the bit decompositions of the variables it already checked
+        let config = CompileConfig::default();
+        let decompositions: Vec<_> = Flattener::flatten(p, &config)
+            .main
+            .statements
+            .into_iter()
+            .filter_map(|s| match s {
+                FlatStatement::Directive(FlatDirective {
+                    inputs,
+                    solver: Solver::Bits(..),
+                    ..
+                }) => Some(inputs),
+                _ => None,
+            })
+            .collect();
+
+        // the decomposition of the argument `a` and the one of `c`
+        assert_eq!(decompositions.len(), 2);
+    }
 }
@@ -0,0 +1,194 @@
+//! Module containing the `UncheckedCoercionChecker`, warning about private field inputs which are returned without
+//! being checked
+//!
+//! Uint and boolean inputs are range checked when they are read, but a private field input can take any value. If
+//! such an input reaches a return value without appearing in any assertion, the prover is free to choose it and the
+//! returned value is malleable. Dependencies are tracked by a forward pass over the definitions of `main`: a variable
+//! depends on the inputs its defining expression depends on.
+
+use crate::compile::Warning;
+use crate::zir::folder::Folder;
+use crate::zir::*;
+use std::collections::{BTreeSet, HashMap};
+use zokrates_field::Field;
+
+pub struct UncheckedCoercionChecker<'ast> {
+    // the private field inputs each variable depends on, by index
+    dependencies: HashMap<Identifier<'ast>, BTreeSet<usize>>,
+    // the inputs appearing in an assertion
+    checked: BTreeSet<usize>,
+    // the inputs appearing in a return value
+    returned: BTreeSet<usize>,
+}
+
+// collects the identifiers read by an expression
+#[derive(Default)]
+struct Names<'ast>(Vec<Identifier<'ast>>);
+
+impl<'ast, T: Field> Folder<'ast, T> for Names<'ast> {
+    fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
+        self.0.push(n.clone());
+        n
+    }
+}
+
+impl<'ast> UncheckedCoercionChecker<'ast> {
+    pub fn check<T: Field>(prog: &ZirProgram<'ast, T>) -> Vec<Warning> {
+        let inputs: Vec<_> = prog
+            .main
+            .arguments
+            .iter()
+            .filter(|p| p.private && p.id._type == Type::FieldElement)
+            .map(|p| p.id.id.clone())
+            .collect();
+
+        let mut checker = UncheckedCoercionChecker {
+            dependencies: inputs
+                .iter()
+                .enumerate()
+                .map(|(index, id)| (id.clone(), vec![index].into_iter().collect()))
+                .collect(),
+            checked: BTreeSet::new(),
+            returned: BTreeSet::new(),
+        };
+
+        checker.check_statements(&prog.main.statements);
+
+        inputs
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| {
+                checker.returned.contains(index) && !checker.checked.contains(index)
+            })
+            .map(|(_, id)| Warning::UncheckedCoercion {
+                parameter: id.to_string(),
+            })
+            .collect()
+    }
+
+    fn check_statements<T: Field>(&mut self, statements: &[ZirStatement<'ast, T>]) {
+        for s in statements {
+            match s {
+                ZirStatement::Definition(a, e) => {
+                    let dependencies = self.expression_dependencies(e.clone());
+                    self.dependencies.insert(a.id.clone(), dependencies);
+                }
+                ZirStatement::MultipleDefinition(
+                    assignees,
+                    ZirExpressionList::EmbedCall(_, _, arguments),
+                ) => {
+                    let dependencies = arguments
+                        .iter()
+                        .flat_map(|e| self.expression_dependencies(e.clone()))
+                        .collect::<BTreeSet<_>>();
+                    for a in assignees {
+                        self.dependencies.insert(a.id.clone(), dependencies.clone());
+                    }
+                }
+                ZirStatement::IfElse(_, consequence, alternative) => {
+                    self.check_statements(consequence);
+                    self.check_statements(alternative);
+                }
+                ZirStatement::Assertion(e, _) => {
+                    let dependencies =
+                        self.expression_dependencies(ZirExpression::Boolean(e.clone()));
+                    self.checked.extend(dependencies);
+                }
+                ZirStatement::Return(expressions) => {
+                    for e in expressions {
+                        let dependencies = self.expression_dependencies(e.clone());
+                        self.returned.extend(dependencies);
+                    }
+                }
+            }
+        }
+    }
+
+    fn expression_dependencies<T: Field>(&self, e: ZirExpression<'ast, T>) -> BTreeSet<usize> {
+        let mut names = Names::default();
+        names.fold_expression(e);
+
+        names
+            .0
+            .iter()
+            .filter_map(|id| self.dependencies.get(id))
+            .flatten()
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::RuntimeError;
+    use crate::zir::types::Signature;
+    use zokrates_field::Bn128Field;
+
+    // def main(private field a, private field b, field c) -> (field, field):
+    //     field d = a * c
+    //     assert(b == 1)   (if `check_b` is set)
+    //     return d, b
+    fn program(check_b: bool) -> ZirProgram<'static, Bn128Field> {
+        let mut statements = vec![ZirStatement::Definition(
+            Variable::field_element("d"),
+            FieldElementExpression::Mult(
+                box FieldElementExpression::Identifier("a".into()),
+                box FieldElementExpression::Identifier("c".into()),
+            )
+            .into(),
+        )];
+
+        if check_b {
+            statements.push(ZirStatement::Assertion(
+                BooleanExpression::FieldEq(
+                    box FieldElementExpression::Identifier("b".into()),
+                    box FieldElementExpression::Number(Bn128Field::from(1)),
+                ),
+                RuntimeError::Source,
+            ));
+        }
+
+        statements.push(ZirStatement::Return(vec![
+            FieldElementExpression::Identifier("d".into()).into(),
+            FieldElementExpression::Identifier("b".into()).into(),
+        ]));
+
+        ZirProgram {
+            main: ZirFunction {
+                arguments: vec![
+                    Parameter::private(Variable::field_element("a")),
+                    Parameter::private(Variable::field_element("b")),
+                    Parameter {
+                        id: Variable::field_element("c"),
+                        private: false,
+                    },
+                ],
+                statements,
+                signature: Signature::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn unchecked_inputs() {
+        assert_eq!(
+            UncheckedCoercionChecker::check(&program(false)),
+            vec![
+                Warning::UncheckedCoercion {
+                    parameter: "a".into()
+                },
+                Warning::UncheckedCoercion {
+                    parameter: "b".into()
+                }
+            ]
+        );
+
+        assert_eq!(
+            UncheckedCoercionChecker::check(&program(true)),
+            vec![Warning::UncheckedCoercion {
+                parameter: "a".into()
+            }]
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Module containing the `UnreachableCodeDetector`, warning about statements which can never be executed
+//!
+//! A statement is unreachable when it follows, in the same block, a statement which always returns or aborts:
+//! - a `return` statement
+//! - an `assert(false)`
+//! - a `for` loop whose body always returns or aborts, and whose constant bounds guarantee at least one iteration
+//!
+//! Conditionals are expressions in ZoKrates, so they cannot contain statements and never terminate a block. The
+//! detection runs on the untyped program, which still has the positions of the statements, and only the first
+//! unreachable statement of each block is reported.
+
+use crate::absy::{
+    Expression, ExpressionNode, Program, Statement, StatementNode, Symbol, SymbolDefinition,
+};
+use crate::compile::Warning;
+use num_bigint::BigUint;
+
+#[derive(Default)]
+pub struct UnreachableCodeDetector {
+    warnings: Vec<Warning>,
+}
+
+impl UnreachableCodeDetector {
+    pub fn detect(p: &Program) -> Vec<Warning> {
+        let mut detector = Self::default();
+
+        // modules are visited in a fixed order so that warnings are deterministic
+        let mut modules: Vec<_> = p.modules.iter().collect();
+        modules.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (_, module) in modules {
+            for declaration in &module.symbols {
+                if let Symbol::Here(SymbolDefinition::Function(f)) = &declaration.value.symbol {
+                    detector.check_statements(&f.value.statements);
+                }
+            }
+        }
+
+        detector.warnings
+    }
+
+    // checks a block, returning whether it always returns or aborts
+    fn check_statements(&mut self, statements: &[StatementNode]) -> bool {
+        let mut terminated = false;
+
+        for s in statements {
+            if terminated {
+                self.warnings.push(Warning::UnreachableCode {
+                    span: (s.start, s.end),
+                });
+                break;
+            }
+
+            terminated = self.check_statement(s);
+        }
+
+        terminated
+    }
+
+    // checks a statement, returning whether it always returns or aborts
+    fn check_statement(&mut self, s: &StatementNode) -> bool {
+        match &s.value {
+            Statement::Return(..) => true,
+            Statement::Assertion(e, _) => matches!(e.value, Expression::BooleanConstant(false)),
+            Statement::For(_, from, to, statements) => {
+                let terminated = self.check_statements(statements);
+
+                let iterates = match (constant(from), constant(to)) {
+                    (Some(from), Some(to)) => from < to,
+                    _ => false,
+                };
+
+                terminated && iterates
+            }
+            Statement::AllowDeprecated(s) => self.check_statement(s),
+            Statement::Declaration(..)
+            | Statement::Definition(..)
+            | Statement::StaticAssertion(..)
+            | Statement::MultipleDefinition(..) => false,
+        }
+    }
+}
+
+// the value of `e` if it is a literal integer
+fn constant(e: &ExpressionNode) -> Option<BigUint> {
+    match &e.value {
+        Expression::IntConstant(v) => Some(v.clone()),
+        Expression::U8Constant(v) => Some((*v).into()),
+        Expression::U16Constant(v) => Some((*v).into()),
+        Expression::U32Constant(v) => Some((*v).into()),
+        Expression::U64Constant(v) => Some((*v).into()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::absy::Module;
+    use crate::parser::Position;
+    use std::path::PathBuf;
+
+    fn detect(source: &str) -> Vec<Warning> {
+        let ast = zokrates_pest_ast::generate_ast(source).unwrap();
+
+        let main = PathBuf::from("main");
+
+        UnreachableCodeDetector::detect(&Program {
+            modules: vec![(main.clone(), Module::from(ast))]
+                .into_iter()
+                .collect(),
+            main,
+        })
+    }
+
+    fn unreachable(start: (usize, usize), end: (usize, usize)) -> Warning {
+        Warning::UnreachableCode {
+            span: (
+                Position {
+                    line: start.0,
+                    col: start.1,
+                },
+                Position {
+                    line: end.0,
+                    col: end.1,
+                },
+            ),
+        }
+    }
+
+    #[test]
+    fn reachable() {
+        let source = r#"
+def main(field a) -> field:
+    assert(a == 1)
+    for u32 i in 0..3 do
+        a = a + 1
+    endfor
+    return a
+"#;
+
+        assert_eq!(detect(source), vec![]);
+    }
+
+    #[test]
+    fn after_return() {
+        let source = r#"
+def main(field a) -> field:
+    return a
+    assert(a == 1)
+    field b = a
+"#;
+
+        // only the first unreachable statement is reported
+        assert_eq!(detect(source), vec![unreachable((4, 5), (4, 19))]);
+    }
+
+    #[test]
+    fn after_abort() {
+        let source = r#"
+def main(field a) -> field:
+    assert(false)
+    return a
+"#;
+
+        assert_eq!(detect(source), vec![unreachable((4, 5), (4, 13))]);
+    }
+
+    #[test]
+    fn in_and_after_loop() {
+        let source = r#"
+def main(field a) -> field:
+    for u32 i in 0..3 do
+        assert(false)
+        a = a + 1
+    endfor
+    return a
+"#;
+
+        assert_eq!(
+            detect(source),
+            vec![unreachable((5, 9), (5, 18)), unreachable((7, 5), (7, 13))]
+        );
+    }
+
+    #[test]
+    fn after_empty_loop() {
+        let source = r#"
+def main(field a) -> field:
+    for u32 i in 3..3 do
+        assert(false)
+    endfor
+    return a
+"#;
+
+        // the loop body is never executed, so the rest of the function is
+        assert_eq!(detect(source), vec![]);
+    }
+}
@@ -4,6 +4,7 @@
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
 //! @date 2018
 
+use crate::flat_absy::RuntimeError;
 use crate::typed_absy::folder::*;
 use crate::typed_absy::types::{MemberId, Type};
 use crate::typed_absy::*;
@@ -49,6 +50,7 @@ impl<'ast> VariableWriteRemover {
                         Access::Select(head) => {
                             statements.insert(TypedStatement::Assertion(
                                 BooleanExpression::UintLt(box head.clone(), box size.into()),
+                                RuntimeError::Source,
                             ));
 
                             ArrayExpressionInner::Value(
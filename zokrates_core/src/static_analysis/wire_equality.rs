@@ -0,0 +1,328 @@
+//! Module containing the `WireEqualityMerger` to remove constraints of the form
+// ```
+// (1 * ~one) * (1 * x) == 1 * y
+// ```
+//
+// Such a constraint, or its symmetric, is a wire equality: it only states that `x` and `y` are equal. The equalities are
+// collected in a union-find structure, each class of equal variables is represented by one of them, and every other
+// variable of the class is replaced by its representative in the whole program, so that the equalities can be removed.
+// Only equalities between variables which already appear in previous statements are merged: an equality defining a new
+// variable is left to the `RedefinitionOptimizer`, and removing it here could leave the other variable unconstrained.
+//
+// The variables which are part of the interface of the program (`~one`, the arguments and the return values), the
+// outputs of directives and the variables of barriers are pinned: they are never replaced, so a class contains at most
+// one of them, which is its representative. An equality between two pinned variables is kept.
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::folder::Folder;
+use crate::ir::*;
+use crate::optimizer::barrier_variables;
+use std::collections::{HashMap, HashSet};
+use zokrates_field::Field;
+
+#[derive(Default)]
+pub struct WireEqualityMerger {
+    /// the parent of each variable merged into another one, representatives being their own parent
+    parent: HashMap<FlatVariable, FlatVariable>,
+    /// variables which must not be replaced
+    pinned: HashSet<FlatVariable>,
+    /// variables which appear in the statements visited so far
+    defined: HashSet<FlatVariable>,
+    count: usize,
+}
+
+impl WireEqualityMerger {
+    pub fn merge<T: Field>(p: Prog<T>) -> Prog<T> {
+        let mut merger = Self::default();
+
+        merger.pinned = barrier_variables(&p);
+        merger.pinned.insert(FlatVariable::one());
+        merger.pinned.extend(p.arguments.iter().map(|a| a.id));
+        merger.defined = merger.pinned.iter().cloned().collect();
+        merger.pinned.extend(p.returns.iter().cloned());
+        for s in &p.statements {
+            if let Statement::Directive(d) = s {
+                merger.pinned.extend(d.outputs.iter().cloned());
+            }
+        }
+
+        // record the equalities, dropping the ones which are merged
+        let statements = p
+            .statements
+            .into_iter()
+            .filter(|s| !merger.absorb(s))
+            .collect();
+
+        log::debug!(
+            "Merged {} wire {}",
+            merger.count,
+            if merger.count == 1 {
+                "equality"
+            } else {
+                "equalities"
+            }
+        );
+
+        // replace the merged variables in the whole program
+        merger.fold_module(Prog { statements, ..p })
+    }
+
+    fn find(&mut self, v: FlatVariable) -> FlatVariable {
+        match self.parent.get(&v).cloned() {
+            Some(parent) if parent != v => {
+                let representative = self.find(parent);
+                self.parent.insert(v, representative);
+                representative
+            }
+            _ => v,
+        }
+    }
+
+    // records `s` if it is a wire equality between defined variables, returning whether it can be removed
+    fn absorb<T: Field>(&mut self, s: &Statement<T>) -> bool {
+        let merged = match wire_equality(s) {
+            Some((x, y)) if self.defined.contains(&x) && self.defined.contains(&y) => {
+                self.union(x, y)
+            }
+            _ => false,
+        };
+
+        self.defined.extend(variables(s));
+
+        merged
+    }
+
+    // merges the classes of `x` and `y`, returning whether the equality of `x` and `y` is implied
+    fn union(&mut self, x: FlatVariable, y: FlatVariable) -> bool {
+        let x = self.find(x);
+        let y = self.find(y);
+
+        let (representative, merged) = match (self.pinned.contains(&x), self.pinned.contains(&y)) {
+            _ if x == y => {
+                self.count += 1;
+                return true;
+            }
+            (true, true) => return false,
+            (true, false) => (x, y),
+            (false, true) => (y, x),
+            (false, false) => (std::cmp::min(x, y), std::cmp::max(x, y)),
+        };
+
+        self.parent.insert(merged, representative);
+        self.count += 1;
+        true
+    }
+}
+
+// the two variables of `s` if it is a wire equality
+fn wire_equality<T: Field>(s: &Statement<T>) -> Option<(FlatVariable, FlatVariable)> {
+    match s {
+        s if s.is_barrier() => None,
+        Statement::Constraint(quad, lin, _) => {
+            let x = single_variable(quad.clone().try_linear().ok()?)?;
+            let y = single_variable(lin.clone())?;
+            Some((x, y))
+        }
+        Statement::Directive(..) => None,
+    }
+}
+
+// the variables which appear in `s`
+fn variables<T: Field>(s: &Statement<T>) -> Vec<FlatVariable> {
+    match s {
+        Statement::Constraint(quad, lin, _) => quad
+            .left
+            .0
+            .iter()
+            .chain(quad.right.0.iter())
+            .chain(lin.0.iter())
+            .map(|(v, _)| *v)
+            .collect(),
+        Statement::Directive(d) => d
+            .inputs
+            .iter()
+            .flat_map(|quad| quad.left.0.iter().chain(quad.right.0.iter()))
+            .map(|(v, _)| *v)
+            .chain(d.outputs.iter().cloned())
+            .collect(),
+    }
+}
+
+// the variable of `lin` if it is of the form `1 * v`
+fn single_variable<T: Field>(lin: LinComb<T>) -> Option<FlatVariable> {
+    match lin
+        .into_canonical()
+        .0
+        .into_iter()
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [(v, coefficient)] if *coefficient == T::one() => Some(*v),
+        _ => None,
+    }
+}
+
+impl<T: Field> Folder<T> for WireEqualityMerger {
+    fn fold_variable(&mut self, v: FlatVariable) -> FlatVariable {
+        self.find(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatParameter;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn merge_equal_variables() {
+        // def main(_0) -> (1):
+        //     _1 == _0 * _0
+        //     _2 == _0 * _0
+        //     _2 == _1
+        //     ~out_0 == _1 * _2
+        //     return ~out_0
+
+        let _0 = FlatParameter::private(FlatVariable::new(0));
+        let _1 = FlatVariable::new(1);
+        let _2 = FlatVariable::new(2);
+        let out = FlatVariable::public(0);
+
+        let square =
+            || QuadComb::from_linear_combinations(LinComb::from(_0.id), LinComb::from(_0.id));
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![_0],
+            statements: vec![
+                Statement::definition(_1, square()),
+                Statement::definition(_2, square()),
+                Statement::constraint(_1, _2),
+                Statement::definition(
+                    out,
+                    QuadComb::from_linear_combinations(LinComb::from(_1), LinComb::from(_2)),
+                ),
+            ],
+            returns: vec![out],
+        };
+
+        // `_2` is replaced by `_1`, so that its definition becomes a check
+        let expected = Prog {
+            arguments: vec![_0],
+            statements: vec![
+                Statement::definition(_1, square()),
+                Statement::definition(_1, square()),
+                Statement::definition(
+                    out,
+                    QuadComb::from_linear_combinations(LinComb::from(_1), LinComb::from(_1)),
+                ),
+            ],
+            returns: vec![out],
+        };
+
+        let merged = WireEqualityMerger::merge(p.clone());
+
+        assert_eq!(merged, expected);
+
+        let interpreter = Interpreter::default();
+        let inputs = vec![Bn128Field::from(3)];
+
+        assert_eq!(
+            interpreter
+                .execute(&merged, &inputs)
+                .unwrap()
+                .return_values(),
+            interpreter.execute(&p, &inputs).unwrap().return_values()
+        );
+    }
+
+    #[test]
+    fn keep_pinned_equalities() {
+        // def main(_0, _1) -> (1):
+        //     _1 == _0
+        //     ~out_0 == _0
+        //     return ~out_0
+
+        let _0 = FlatParameter::private(FlatVariable::new(0));
+        let _1 = FlatParameter::private(FlatVariable::new(1));
+        let out = FlatVariable::public(0);
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![_0, _1],
+            statements: vec![
+                Statement::constraint(_0.id, _1.id),
+                Statement::definition(out, _0.id),
+            ],
+            returns: vec![out],
+        };
+
+        // all variables are part of the interface
+        assert_eq!(WireEqualityMerger::merge(p.clone()), p);
+    }
+
+    #[test]
+    fn merge_into_pinned_variable() {
+        // def main(_0) -> (1):
+        //     _1 == _0 * _0
+        //     ~out_0 == _0 * _0
+        //     ~out_0 == _1
+        //     ~out_0 == _1
+        //     return ~out_0
+
+        let _0 = FlatParameter::private(FlatVariable::new(0));
+        let _1 = FlatVariable::new(1);
+        let out = FlatVariable::public(0);
+
+        let square =
+            || QuadComb::from_linear_combinations(LinComb::from(_0.id), LinComb::from(_0.id));
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![_0],
+            statements: vec![
+                Statement::definition(_1, square()),
+                Statement::definition(out, square()),
+                Statement::definition(out, _1),
+                Statement::definition(out, _1),
+            ],
+            returns: vec![out],
+        };
+
+        // `_1` is replaced by the output, and the repeated equality is dropped
+        let expected = Prog {
+            arguments: vec![_0],
+            statements: vec![
+                Statement::definition(out, square()),
+                Statement::definition(out, square()),
+            ],
+            returns: vec![out],
+        };
+
+        assert_eq!(WireEqualityMerger::merge(p), expected);
+    }
+
+    #[test]
+    fn keep_definitions() {
+        // def main(_0) -> (1):
+        //     _1 == _0
+        //     ~out_0 == _1 * _1
+        //     return ~out_0
+
+        let _0 = FlatParameter::private(FlatVariable::new(0));
+        let _1 = FlatVariable::new(1);
+        let out = FlatVariable::public(0);
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![_0],
+            statements: vec![
+                Statement::definition(_1, _0.id),
+                Statement::definition(
+                    out,
+                    QuadComb::from_linear_combinations(LinComb::from(_1), LinComb::from(_1)),
+                ),
+            ],
+            returns: vec![out],
+        };
+
+        // `_1` is defined by the equality, which is left to the redefinition optimizer
+        assert_eq!(WireEqualityMerger::merge(p.clone()), p);
+    }
+}
@@ -0,0 +1,152 @@
+//! Module containing the `ZeroSizeChecker`, rejecting programs in which a function receives an array of size zero
+//!
+//! Array sizes are only known once generics are specialized, so the check runs on the reduced program. The parameters
+//! of each function are collected from the program before reduction, and every call log left by the reducer gives
+//! the function called along with the values of its generics, from which the sizes of its parameters follow. The
+//! typed program does not keep source positions, so errors locate the call by the chain of calls leading to it.
+
+use crate::typed_absy::types::{
+    ConcreteGenericsAssignment, DeclarationConstant, DeclarationFunctionKey, DeclarationType,
+};
+use crate::typed_absy::{DeclarationParameter, TypedFunctionSymbol, TypedProgram, TypedStatement};
+use std::collections::HashMap;
+use std::fmt;
+use zokrates_field::Field;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    ZeroSizeArray {
+        function: String,
+        param: String,
+        /// the calls leading to the function, starting from `main`
+        calls: Vec<String>,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::ZeroSizeArray {
+                function,
+                param,
+                calls,
+            } => {
+                write!(
+                    f,
+                    "Parameter `{}` of function `{}` is an array of size 0",
+                    param, function
+                )?;
+                if !calls.is_empty() {
+                    write!(f, ", in call {}", calls.join(" -> "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+pub struct ZeroSizeChecker<'ast> {
+    parameters: HashMap<DeclarationFunctionKey<'ast>, Vec<DeclarationParameter<'ast>>>,
+}
+
+// whether `ty` contains an array of size zero, given the values of the generics
+fn has_zero_size(ty: &DeclarationType, generics: &ConcreteGenericsAssignment) -> bool {
+    match ty {
+        DeclarationType::Array(array_type) => {
+            let size = match &array_type.size {
+                DeclarationConstant::Generic(g) => generics.0.get(g).cloned(),
+                DeclarationConstant::Concrete(s) => Some(*s as usize),
+                DeclarationConstant::Constant(_) => None,
+            };
+            size == Some(0) || has_zero_size(&array_type.ty, generics)
+        }
+        // the sizes in struct members refer to the generics of the struct, only concrete ones are checked
+        DeclarationType::Struct(struct_type) => struct_type
+            .members
+            .iter()
+            .any(|m| has_zero_size(&m.ty, &ConcreteGenericsAssignment::default())),
+        _ => false,
+    }
+}
+
+impl<'ast> ZeroSizeChecker<'ast> {
+    /// Collects the parameters of the functions of `program`, which must not be reduced yet
+    pub fn new<T: Field>(program: &TypedProgram<'ast, T>) -> Self {
+        ZeroSizeChecker {
+            parameters: program
+                .modules
+                .values()
+                .flat_map(|m| m.functions.iter())
+                .filter_map(|(key, symbol)| match symbol {
+                    TypedFunctionSymbol::Here(f) => Some((key.clone(), f.arguments.clone())),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Checks the parameters of `main` and of every function called in the reduced `program`
+    pub fn check<T: Field>(&self, program: &TypedProgram<'ast, T>) -> Result<(), Error> {
+        let (key, main) = program.modules[&program.main]
+            .functions
+            .iter()
+            .find(|(key, _)| key.id == "main")
+            .unwrap();
+
+        let main = match main {
+            TypedFunctionSymbol::Here(main) => main,
+            _ => unreachable!(),
+        };
+
+        self.check_call(key, &ConcreteGenericsAssignment::default(), &[])?;
+        self.check_statements(&main.statements, &mut vec![])
+    }
+
+    fn check_statements<T: Field>(
+        &self,
+        statements: &[TypedStatement<'ast, T>],
+        calls: &mut Vec<String>,
+    ) -> Result<(), Error> {
+        for s in statements {
+            match s {
+                TypedStatement::PushCallLog(key, generics) => {
+                    calls.push(format!("{}::<{}>", key.id, generics));
+                    self.check_call(key, generics, calls)?;
+                }
+                TypedStatement::PopCallLog => {
+                    calls.pop();
+                }
+                TypedStatement::For(_, _, _, statements) => {
+                    self.check_statements(statements, calls)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_call(
+        &self,
+        key: &DeclarationFunctionKey<'ast>,
+        generics: &ConcreteGenericsAssignment<'ast>,
+        calls: &[String],
+    ) -> Result<(), Error> {
+        let parameters = match self.parameters.get(key) {
+            Some(parameters) => parameters,
+            None => return Ok(()),
+        };
+
+        match parameters
+            .iter()
+            .find(|p| has_zero_size(&p.id._type, generics))
+        {
+            Some(p) => Err(Error::ZeroSizeArray {
+                function: key.id.to_string(),
+                param: p.id.id.to_string(),
+                calls: calls.to_vec(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,3 +1,12 @@
+//! Module containing the `ZirPropagator`, propagating constants and divisibility information in a ZIR program
+//!
+//! Alongside constants, the propagator tracks for each uint variable a number of low bits known to be zero, that is a
+//! power of two the variable is known to be a multiple of. Uint arithmetic wraps modulo `2**bitwidth`, so a multiple
+//! of an odd constant can stop being one after an overflow, while multiples of powers of two are preserved by every
+//! arithmetic and bitwise operation. When a value is known to be a multiple of a constant divisor `2**n`, dividing it
+//! is lowered to a right shift by `n`, which is free once the value is decomposed into bits, and taking its remainder
+//! yields zero.
+
 use crate::zir::result_folder::fold_statement;
 use crate::zir::result_folder::ResultFolder;
 use crate::zir::types::UBitwidth;
@@ -10,6 +19,7 @@ use std::fmt;
 use zokrates_field::Field;
 
 type Constants<'ast, T> = HashMap<Identifier<'ast>, ZirExpression<'ast, T>>;
+type Divisibility<'ast> = HashMap<Identifier<'ast>, u32>;
 
 #[derive(Debug, PartialEq)]
 pub enum Error {
@@ -35,12 +45,64 @@ impl fmt::Display for Error {
 #[derive(Default)]
 pub struct ZirPropagator<'ast, T> {
     constants: Constants<'ast, T>,
+    // the number of low bits known to be zero for each uint variable
+    divisibility: Divisibility<'ast>,
 }
 
 impl<'ast, T: Field> ZirPropagator<'ast, T> {
     pub fn propagate(p: ZirProgram<T>) -> Result<ZirProgram<T>, Error> {
         ZirPropagator::default().fold_program(p)
     }
+
+    // the number of low bits of the folded expression `e` known to be zero, so that `e` is a multiple of `2**n`
+    fn trailing_zeros(&self, e: &UExpressionInner<'ast, T>, bitwidth: UBitwidth) -> u32 {
+        let zeros = match e {
+            UExpressionInner::Value(v) => v.trailing_zeros(),
+            UExpressionInner::Identifier(id) => self.divisibility.get(id).cloned().unwrap_or(0),
+            UExpressionInner::Select(e, _) | UExpressionInner::Lookup(e, _) => e
+                .iter()
+                .map(|e| self.trailing_zeros(e.as_inner(), bitwidth))
+                .min()
+                .unwrap_or(0),
+            UExpressionInner::Add(box e1, box e2)
+            | UExpressionInner::Sub(box e1, box e2)
+            | UExpressionInner::Xor(box e1, box e2)
+            | UExpressionInner::Or(box e1, box e2) => std::cmp::min(
+                self.trailing_zeros(e1.as_inner(), bitwidth),
+                self.trailing_zeros(e2.as_inner(), bitwidth),
+            ),
+            UExpressionInner::Mult(box e1, box e2) => self
+                .trailing_zeros(e1.as_inner(), bitwidth)
+                .saturating_add(self.trailing_zeros(e2.as_inner(), bitwidth)),
+            UExpressionInner::And(box e1, box e2) => std::cmp::max(
+                self.trailing_zeros(e1.as_inner(), bitwidth),
+                self.trailing_zeros(e2.as_inner(), bitwidth),
+            ),
+            UExpressionInner::LeftShift(box e, by) => self
+                .trailing_zeros(e.as_inner(), bitwidth)
+                .saturating_add(*by),
+            UExpressionInner::RightShift(box e, by) => self
+                .trailing_zeros(e.as_inner(), bitwidth)
+                .saturating_sub(*by),
+            UExpressionInner::IfElse(_, box consequence, box alternative) => std::cmp::min(
+                self.trailing_zeros(consequence.as_inner(), bitwidth),
+                self.trailing_zeros(alternative.as_inner(), bitwidth),
+            ),
+            UExpressionInner::Div(..) | UExpressionInner::Rem(..) | UExpressionInner::Not(..) => 0,
+        };
+
+        std::cmp::min(zeros, bitwidth as u32)
+    }
+
+    // whether the folded expression `e` is known to be a multiple of `divisor`, which is a power of two
+    fn is_multiple_of(
+        &self,
+        e: &UExpressionInner<'ast, T>,
+        divisor: u128,
+        bitwidth: UBitwidth,
+    ) -> bool {
+        divisor.is_power_of_two() && self.trailing_zeros(e, bitwidth) >= divisor.trailing_zeros()
+    }
 }
 
 impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
@@ -51,9 +113,10 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
         s: ZirStatement<'ast, T>,
     ) -> Result<Vec<ZirStatement<'ast, T>>, Self::Error> {
         match s {
-            ZirStatement::Assertion(e) => match self.fold_boolean_expression(e)? {
-                BooleanExpression::Value(true) => Ok(vec![]),
-                e => Ok(vec![ZirStatement::Assertion(e)]),
+            ZirStatement::Assertion(e, error) => match self.fold_boolean_expression(e)? {
+                // barriers are kept even when they are trivially satisfied
+                BooleanExpression::Value(true) if !error.is_barrier() => Ok(vec![]),
+                e => Ok(vec![ZirStatement::Assertion(e, error)]),
             },
             ZirStatement::Definition(a, e) => {
                 let e = self.fold_expression(e)?;
@@ -69,6 +132,19 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                     }
                     _ => {
                         self.constants.remove(&a.id);
+
+                        match &e {
+                            ZirExpression::Uint(e) => {
+                                match self.trailing_zeros(e.as_inner(), e.bitwidth) {
+                                    0 => self.divisibility.remove(&a.id),
+                                    zeros => self.divisibility.insert(a.id.clone(), zeros),
+                                };
+                            }
+                            _ => {
+                                self.divisibility.remove(&a.id);
+                            }
+                        }
+
                         Ok(vec![ZirStatement::Definition(a, e)])
                     }
                 }
@@ -76,6 +152,7 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
             ZirStatement::MultipleDefinition(assignees, list) => {
                 for a in &assignees {
                     self.constants.remove(&a.id);
+                    self.divisibility.remove(&a.id);
                 }
                 Ok(vec![ZirStatement::MultipleDefinition(
                     assignees,
@@ -116,6 +193,24 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                     )),
                 }
             }
+            FieldElementExpression::Lookup(e, box index) => {
+                let index = self.fold_uint_expression(index)?;
+                let e: Vec<FieldElementExpression<'ast, T>> = e
+                    .into_iter()
+                    .map(|e| self.fold_field_expression(e))
+                    .collect::<Result<_, _>>()?;
+
+                match index.into_inner() {
+                    UExpressionInner::Value(v) => e
+                        .get(v as usize)
+                        .cloned()
+                        .ok_or_else(|| Error::OutOfBounds(v, e.len() as u128)),
+                    i => Ok(FieldElementExpression::Lookup(
+                        e,
+                        box i.annotate(UBitwidth::B32),
+                    )),
+                }
+            }
             FieldElementExpression::Add(box e1, box e2) => {
                 match (
                     self.fold_field_expression(e1)?,
@@ -245,6 +340,21 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                     _ => Ok(BooleanExpression::Select(e, box index)),
                 }
             }
+            BooleanExpression::Lookup(e, box index) => {
+                let index = self.fold_uint_expression(index)?;
+                let e: Vec<BooleanExpression<'ast, T>> = e
+                    .into_iter()
+                    .map(|e| self.fold_boolean_expression(e))
+                    .collect::<Result<_, _>>()?;
+
+                match index.as_inner() {
+                    UExpressionInner::Value(v) => e
+                        .get(*v as usize)
+                        .cloned()
+                        .ok_or_else(|| Error::OutOfBounds(*v, e.len() as u128)),
+                    _ => Ok(BooleanExpression::Lookup(e, box index)),
+                }
+            }
             BooleanExpression::FieldLt(box e1, box e2) => {
                 match (
                     self.fold_field_expression(e1)?,
@@ -465,6 +575,22 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                     i => Ok(UExpressionInner::Select(e, box i.annotate(UBitwidth::B32))),
                 }
             }
+            UExpressionInner::Lookup(e, box index) => {
+                let index = self.fold_uint_expression(index)?;
+                let e: Vec<UExpression<'ast, T>> = e
+                    .into_iter()
+                    .map(|e| self.fold_uint_expression(e))
+                    .collect::<Result<_, _>>()?;
+
+                match index.into_inner() {
+                    UExpressionInner::Value(v) => e
+                        .get(v as usize)
+                        .cloned()
+                        .ok_or_else(|| Error::OutOfBounds(v, e.len() as u128))
+                        .map(|e| e.into_inner()),
+                    i => Ok(UExpressionInner::Lookup(e, box i.annotate(UBitwidth::B32))),
+                }
+            }
             UExpressionInner::Add(box e1, box e2) => {
                 let e1 = self.fold_uint_expression(e1)?;
                 let e2 = self.fold_uint_expression(e2)?;
@@ -525,6 +651,9 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                     (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => Ok(
                         UExpressionInner::Value((n1 / n2) % 2_u128.pow(bitwidth.to_usize() as u32)),
                     ),
+                    (e, UExpressionInner::Value(n)) if self.is_multiple_of(&e, n, bitwidth) => Ok(
+                        UExpressionInner::RightShift(box e.annotate(bitwidth), n.trailing_zeros()),
+                    ),
                     (e1, e2) => Ok(UExpressionInner::Div(
                         box e1.annotate(bitwidth),
                         box e2.annotate(bitwidth),
@@ -539,6 +668,9 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                     (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => Ok(
                         UExpressionInner::Value((n1 % n2) % 2_u128.pow(bitwidth.to_usize() as u32)),
                     ),
+                    (e, UExpressionInner::Value(n)) if self.is_multiple_of(&e, n, bitwidth) => {
+                        Ok(UExpressionInner::Value(0))
+                    }
                     (e1, e2) => Ok(UExpressionInner::Rem(
                         box e1.annotate(bitwidth),
                         box e2.annotate(bitwidth),
@@ -654,21 +786,62 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::flat_absy::RuntimeError;
+    use crate::zir::Variable;
     use zokrates_field::Bn128Field;
 
     #[test]
     fn propagation() {
         // assert([x, 1] == [y, 1])
-        let statements = vec![ZirStatement::Assertion(BooleanExpression::And(
-            box BooleanExpression::FieldEq(
-                box FieldElementExpression::Identifier("x".into()),
-                box FieldElementExpression::Identifier("y".into()),
-            ),
-            box BooleanExpression::FieldEq(
-                box FieldElementExpression::Number(Bn128Field::from(1)),
-                box FieldElementExpression::Number(Bn128Field::from(1)),
+        let statements = vec![ZirStatement::Assertion(
+            BooleanExpression::And(
+                box BooleanExpression::FieldEq(
+                    box FieldElementExpression::Identifier("x".into()),
+                    box FieldElementExpression::Identifier("y".into()),
+                ),
+                box BooleanExpression::FieldEq(
+                    box FieldElementExpression::Number(Bn128Field::from(1)),
+                    box FieldElementExpression::Number(Bn128Field::from(1)),
+                ),
             ),
-        ))];
+            RuntimeError::Source,
+        )];
+
+        let mut propagator = ZirPropagator::default();
+        let statements: Vec<ZirStatement<_>> = statements
+            .into_iter()
+            .map(|s| propagator.fold_statement(s))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect();
+
+        assert_eq!(
+            statements,
+            vec![ZirStatement::Assertion(
+                BooleanExpression::FieldEq(
+                    box FieldElementExpression::Identifier("x".into()),
+                    box FieldElementExpression::Identifier("y".into()),
+                ),
+                RuntimeError::Source
+            )]
+        );
+    }
+
+    #[test]
+    fn keep_barrier() {
+        // #[optimizer(barrier)] assert(1 == 1)
+        // assert(1 == 1)
+        let assertion = BooleanExpression::FieldEq(
+            box FieldElementExpression::Number(Bn128Field::from(1)),
+            box FieldElementExpression::Number(Bn128Field::from(1)),
+        );
+
+        let statements = vec![
+            ZirStatement::Assertion(assertion.clone(), RuntimeError::SourceBarrier),
+            ZirStatement::Assertion(assertion, RuntimeError::Source),
+        ];
 
         let mut propagator = ZirPropagator::default();
         let statements: Vec<ZirStatement<_>> = statements
@@ -682,10 +855,10 @@ mod tests {
 
         assert_eq!(
             statements,
-            vec![ZirStatement::Assertion(BooleanExpression::FieldEq(
-                box FieldElementExpression::Identifier("x".into()),
-                box FieldElementExpression::Identifier("y".into()),
-            ))]
+            vec![ZirStatement::Assertion(
+                BooleanExpression::Value(true),
+                RuntimeError::SourceBarrier
+            )]
         );
     }
 
@@ -1458,6 +1631,118 @@ mod tests {
             );
         }
 
+        #[test]
+        fn divisibility() {
+            let mut propagator = ZirPropagator::<Bn128Field>::default();
+
+            let identifier =
+                |id: &'static str| UExpressionInner::Identifier(id.into()).annotate(UBitwidth::B32);
+
+            // u32 b = (a << 2) + 8 * c
+            // u32 d = b / 4
+            // u32 e = b % 4
+            // u32 f = b / 8
+            let statements = vec![
+                ZirStatement::Definition(
+                    Variable::uint("b".into(), UBitwidth::B32),
+                    UExpressionInner::Add(
+                        box UExpressionInner::LeftShift(box identifier("a"), 2)
+                            .annotate(UBitwidth::B32),
+                        box UExpressionInner::Mult(
+                            box UExpressionInner::Value(8).annotate(UBitwidth::B32),
+                            box identifier("c"),
+                        )
+                        .annotate(UBitwidth::B32),
+                    )
+                    .annotate(UBitwidth::B32)
+                    .into(),
+                ),
+                ZirStatement::Definition(
+                    Variable::uint("d".into(), UBitwidth::B32),
+                    UExpressionInner::Div(
+                        box identifier("b"),
+                        box UExpressionInner::Value(4).annotate(UBitwidth::B32),
+                    )
+                    .annotate(UBitwidth::B32)
+                    .into(),
+                ),
+                ZirStatement::Definition(
+                    Variable::uint("e".into(), UBitwidth::B32),
+                    UExpressionInner::Rem(
+                        box identifier("b"),
+                        box UExpressionInner::Value(4).annotate(UBitwidth::B32),
+                    )
+                    .annotate(UBitwidth::B32)
+                    .into(),
+                ),
+                ZirStatement::Definition(
+                    Variable::uint("f".into(), UBitwidth::B32),
+                    UExpressionInner::Div(
+                        box identifier("b"),
+                        box UExpressionInner::Value(8).annotate(UBitwidth::B32),
+                    )
+                    .annotate(UBitwidth::B32)
+                    .into(),
+                ),
+            ];
+
+            let statements: Vec<ZirStatement<_>> = statements
+                .into_iter()
+                .map(|s| propagator.fold_statement(s))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+                .into_iter()
+                .flatten()
+                .collect();
+
+            // `b` is a multiple of 4 but not of 8, and `e` is a constant
+            assert_eq!(statements.len(), 3);
+            assert_eq!(
+                statements[1],
+                ZirStatement::Definition(
+                    Variable::uint("d".into(), UBitwidth::B32),
+                    UExpressionInner::RightShift(box identifier("b"), 2)
+                        .annotate(UBitwidth::B32)
+                        .into(),
+                )
+            );
+            assert_eq!(
+                statements[2],
+                ZirStatement::Definition(
+                    Variable::uint("f".into(), UBitwidth::B32),
+                    UExpressionInner::Div(
+                        box identifier("b"),
+                        box UExpressionInner::Value(8).annotate(UBitwidth::B32),
+                    )
+                    .annotate(UBitwidth::B32)
+                    .into(),
+                )
+            );
+
+            // an overflow preserves multiples of powers of two only
+            assert_eq!(
+                propagator.fold_uint_expression_inner(
+                    UBitwidth::B32,
+                    UExpressionInner::Rem(
+                        box UExpressionInner::Mult(
+                            box UExpressionInner::Value(3).annotate(UBitwidth::B32),
+                            box identifier("a"),
+                        )
+                        .annotate(UBitwidth::B32),
+                        box UExpressionInner::Value(3).annotate(UBitwidth::B32),
+                    )
+                ),
+                Ok(UExpressionInner::Rem(
+                    box UExpressionInner::Mult(
+                        box UExpressionInner::Value(3).annotate(UBitwidth::B32),
+                        box identifier("a"),
+                    )
+                    .annotate(UBitwidth::B32),
+                    box UExpressionInner::Value(3).annotate(UBitwidth::B32),
+                ))
+            );
+        }
+
         #[test]
         fn xor() {
             let mut propagator = ZirPropagator::<Bn128Field>::default();
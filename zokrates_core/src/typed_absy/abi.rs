@@ -1,17 +1,21 @@
 use crate::typed_absy::types::{ConcreteSignature, ConcreteType};
 use serde::{Deserialize, Serialize};
+use std::ops::Range;
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct AbiInput {
     pub name: String,
     pub public: bool,
+    /// whether the value of this input is only known when proving, see `Interpreter::execute_deferred`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deferred: bool,
     #[serde(flatten)]
     pub ty: ConcreteType,
 }
 
 pub type AbiOutput = ConcreteType;
 
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct Abi {
     pub inputs: Vec<AbiInput>,
     pub outputs: Vec<AbiOutput>,
@@ -25,6 +29,23 @@ impl Abi {
             outputs: self.outputs.clone(),
         }
     }
+
+    /// Returns the range of the flattened program arguments holding the input at `index`
+    pub fn argument_range(&self, index: usize) -> Range<usize> {
+        let start = self.inputs[..index]
+            .iter()
+            .map(|i| i.ty.get_primitive_count())
+            .sum();
+        start..start + self.inputs[index].ty.get_primitive_count()
+    }
+
+    /// Returns the indices of the flattened program arguments holding deferred inputs
+    pub fn deferred_arguments(&self) -> Vec<usize> {
+        (0..self.inputs.len())
+            .filter(|i| self.inputs[*i].deferred)
+            .flat_map(|i| self.argument_range(i))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -50,10 +71,12 @@ mod tests {
                     DeclarationParameter {
                         id: DeclarationVariable::field_element("a"),
                         private: true,
+                        deferred: false,
                     },
                     DeclarationParameter {
                         id: DeclarationVariable::boolean("b"),
                         private: false,
+                        deferred: false,
                     },
                 ],
                 statements: vec![],
@@ -84,11 +107,13 @@ mod tests {
                 AbiInput {
                     name: String::from("a"),
                     public: false,
+                    deferred: false,
                     ty: ConcreteType::FieldElement,
                 },
                 AbiInput {
                     name: String::from("b"),
                     public: true,
+                    deferred: false,
                     ty: ConcreteType::Boolean,
                 },
             ],
@@ -131,11 +156,13 @@ mod tests {
                 AbiInput {
                     name: String::from("a"),
                     public: true,
+                    deferred: false,
                     ty: ConcreteType::FieldElement,
                 },
                 AbiInput {
                     name: String::from("b"),
                     public: true,
+                    deferred: false,
                     ty: ConcreteType::FieldElement,
                 },
             ],
@@ -170,6 +197,61 @@ mod tests {
         assert_eq!(de_abi, abi);
     }
 
+    #[test]
+    fn serialize_deferred() {
+        let abi: Abi = Abi {
+            inputs: vec![
+                AbiInput {
+                    name: String::from("a"),
+                    public: false,
+                    deferred: false,
+                    ty: ConcreteType::Array(ConcreteArrayType::new(
+                        ConcreteType::FieldElement,
+                        2usize,
+                    )),
+                },
+                AbiInput {
+                    name: String::from("b"),
+                    public: true,
+                    deferred: true,
+                    ty: ConcreteType::FieldElement,
+                },
+            ],
+            outputs: vec![],
+        };
+
+        let json = serde_json::to_string_pretty(&abi).unwrap();
+        assert_eq!(
+            &json,
+            r#"{
+  "inputs": [
+    {
+      "name": "a",
+      "public": false,
+      "type": "array",
+      "components": {
+        "size": 2,
+        "type": "field"
+      }
+    },
+    {
+      "name": "b",
+      "public": true,
+      "deferred": true,
+      "type": "field"
+    }
+  ],
+  "outputs": []
+}"#
+        );
+
+        let de_abi: Abi = serde_json::from_str(json.as_ref()).unwrap();
+        assert_eq!(de_abi, abi);
+
+        assert_eq!(abi.argument_range(1), 2..3);
+        assert_eq!(abi.deferred_arguments(), vec![2]);
+    }
+
     #[test]
     fn serialize_uints() {
         let abi: Abi = Abi {
@@ -177,16 +259,19 @@ mod tests {
                 AbiInput {
                     name: String::from("a"),
                     public: true,
+                    deferred: false,
                     ty: ConcreteType::Uint(UBitwidth::B8),
                 },
                 AbiInput {
                     name: String::from("b"),
                     public: true,
+                    deferred: false,
                     ty: ConcreteType::Uint(UBitwidth::B16),
                 },
                 AbiInput {
                     name: String::from("c"),
                     public: true,
+                    deferred: false,
                     ty: ConcreteType::Uint(UBitwidth::B32),
                 },
             ],
@@ -228,6 +313,7 @@ mod tests {
             inputs: vec![AbiInput {
                 name: String::from("foo"),
                 public: true,
+                deferred: false,
                 ty: ConcreteType::Struct(ConcreteStructType::new(
                     "".into(),
                     "Bar".into(),
@@ -311,6 +397,7 @@ mod tests {
             inputs: vec![AbiInput {
                 name: String::from("foo"),
                 public: true,
+                deferred: false,
                 ty: ConcreteType::Struct(ConcreteStructType::new(
                     "".into(),
                     "Foo".into(),
@@ -387,6 +474,7 @@ mod tests {
             inputs: vec![AbiInput {
                 name: String::from("a"),
                 public: false,
+                deferred: false,
                 ty: ConcreteType::Array(ConcreteArrayType::new(
                     ConcreteType::Struct(ConcreteStructType::new(
                         "".into(),
@@ -453,6 +541,7 @@ mod tests {
             inputs: vec![AbiInput {
                 name: String::from("a"),
                 public: false,
+                deferred: false,
                 ty: ConcreteType::Array(ConcreteArrayType::new(
                     ConcreteType::Array(ConcreteArrayType::new(ConcreteType::FieldElement, 2usize)),
                     2usize,
@@ -5,6 +5,8 @@ use std::fmt;
 pub enum CoreIdentifier<'ast> {
     Source(&'ast str),
     Call(usize),
+    /// a value bound once by a `match` expression, so that it is not recomputed in each branch
+    Match(usize),
 }
 
 impl<'ast> fmt::Display for CoreIdentifier<'ast> {
@@ -12,6 +14,7 @@ impl<'ast> fmt::Display for CoreIdentifier<'ast> {
         match self {
             CoreIdentifier::Source(s) => write!(f, "{}", s),
             CoreIdentifier::Call(i) => write!(f, "#CALL_RETURN_AT_INDEX_{}", i),
+            CoreIdentifier::Match(i) => write!(f, "#MATCH_{}", i),
         }
     }
 }
@@ -12,6 +12,7 @@ pub mod result_folder;
 
 mod integer;
 mod parameter;
+pub mod phantom;
 pub mod types;
 mod uint;
 pub mod variable;
@@ -34,6 +35,8 @@ pub use crate::typed_absy::integer::IntExpression;
 pub use crate::typed_absy::uint::{bitwidth, UExpression, UExpressionInner, UMetadata};
 
 use crate::embed::FlatEmbed;
+use crate::flat_absy::RuntimeError;
+use crate::parser::Position;
 
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
@@ -116,6 +119,7 @@ impl<'ast, T: Field> TypedProgram<'ast, T> {
                     )
                     .map(|ty| AbiInput {
                         public: !p.private,
+                        deferred: p.deferred,
                         name: p.id.id.to_string(),
                         ty,
                     })
@@ -485,6 +489,13 @@ impl<'ast, T: fmt::Display> fmt::Display for TypedAssignee<'ast, T> {
     }
 }
 
+/// The source of the condition of a `static_assert`, to report it if the condition does not hold
+#[derive(Clone, PartialEq, Debug, Hash, Eq)]
+pub struct StaticAssertionSource {
+    pub condition_text: String,
+    pub span: (Position, Position),
+}
+
 /// A statement in a `TypedFunction`
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, PartialEq, Debug, Hash, Eq)]
@@ -492,7 +503,8 @@ pub enum TypedStatement<'ast, T> {
     Return(Vec<TypedExpression<'ast, T>>),
     Definition(TypedAssignee<'ast, T>, TypedExpression<'ast, T>),
     Declaration(Variable<'ast, T>),
-    Assertion(BooleanExpression<'ast, T>),
+    Assertion(BooleanExpression<'ast, T>, RuntimeError),
+    StaticAssertion(BooleanExpression<'ast, T>, StaticAssertionSource),
     For(
         Variable<'ast, T>,
         UExpression<'ast, T>,
@@ -540,7 +552,13 @@ impl<'ast, T: fmt::Display> fmt::Display for TypedStatement<'ast, T> {
             }
             TypedStatement::Declaration(ref var) => write!(f, "{}", var),
             TypedStatement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
-            TypedStatement::Assertion(ref e) => write!(f, "assert({})", e),
+            TypedStatement::Assertion(ref e, ref error) => {
+                if error.is_barrier() {
+                    write!(f, "#[optimizer(barrier)] ")?;
+                }
+                write!(f, "assert({})", e)
+            }
+            TypedStatement::StaticAssertion(ref e, _) => write!(f, "static_assert({})", e),
             TypedStatement::For(ref var, ref start, ref stop, ref list) => {
                 writeln!(f, "for {} in {}..{} do", var, start, stop)?;
                 for l in list {
@@ -786,14 +804,29 @@ impl<'ast, T: fmt::Display, E> fmt::Display for MemberExpression<'ast, T, E> {
 pub struct SelectExpression<'ast, T, E> {
     pub array: Box<ArrayExpression<'ast, T>>,
     pub index: Box<UExpression<'ast, T>>,
+    /// whether this is a `lookup` in a constant table, lowered to a tree of selections on the bits of the index
+    pub lookup: bool,
     ty: PhantomData<E>,
 }
 
 impl<'ast, T, E> SelectExpression<'ast, T, E> {
     pub fn new(array: ArrayExpression<'ast, T>, index: UExpression<'ast, T>) -> Self {
+        Self::with_lookup(array, index, false)
+    }
+
+    pub fn lookup(array: ArrayExpression<'ast, T>, index: UExpression<'ast, T>) -> Self {
+        Self::with_lookup(array, index, true)
+    }
+
+    pub fn with_lookup(
+        array: ArrayExpression<'ast, T>,
+        index: UExpression<'ast, T>,
+        lookup: bool,
+    ) -> Self {
         SelectExpression {
             array: box array,
             index: box index,
+            lookup,
             ty: PhantomData,
         }
     }
@@ -801,7 +834,10 @@ impl<'ast, T, E> SelectExpression<'ast, T, E> {
 
 impl<'ast, T: fmt::Display, E> fmt::Display for SelectExpression<'ast, T, E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}[{}]", self.array, self.index)
+        match self.lookup {
+            true => write!(f, "lookup({}, {})", self.array, self.index),
+            false => write!(f, "{}[{}]", self.array, self.index),
+        }
     }
 }
 
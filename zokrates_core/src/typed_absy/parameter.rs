@@ -6,6 +6,7 @@ use std::fmt;
 pub struct GParameter<'ast, S> {
     pub id: GVariable<'ast, S>,
     pub private: bool,
+    pub deferred: bool,
 }
 
 #[cfg(test)]
@@ -14,6 +15,7 @@ impl<'ast, S> From<GVariable<'ast, S>> for GParameter<'ast, S> {
         GParameter {
             id: v,
             private: true,
+            deferred: false,
         }
     }
 }
@@ -22,8 +24,16 @@ pub type DeclarationParameter<'ast> = GParameter<'ast, DeclarationConstant<'ast>
 
 impl<'ast, S: fmt::Display + Clone> fmt::Display for GParameter<'ast, S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let deferred = if self.deferred { "#[deferred] " } else { "" };
         let visibility = if self.private { "private " } else { "" };
-        write!(f, "{}{} {}", visibility, self.id.get_type(), self.id.id)
+        write!(
+            f,
+            "{}{}{} {}",
+            deferred,
+            visibility,
+            self.id.get_type(),
+            self.id.id
+        )
     }
 }
 
@@ -0,0 +1,65 @@
+//! Module containing the `phantom<Tag>` type, a field element tagged with a type
+//!
+//! `phantom<Tag>` is represented as a struct with a single `field` member named `value`, located next to the
+//! declaration of `Tag`. Phantom types with different tags are therefore incompatible, while all of them are compiled
+//! to a single field element.
+
+use crate::typed_absy::types::{
+    DeclarationStructType, GStructMember, GStructType, GType, StructLocation,
+};
+
+/// The name of the phantom type, which is shadowed by any type of the same name in scope
+pub const PHANTOM: &str = "phantom";
+
+/// The name of the member holding the tagged field element
+pub const PHANTOM_VALUE: &str = "value";
+
+/// Returns the type `phantom<Tag>` where `Tag` is the struct type `tag`
+pub fn phantom_type<S>(tag: &DeclarationStructType) -> GStructType<S> {
+    GStructType {
+        canonical_location: phantom_location(&tag.canonical_location),
+        location: tag.location.as_ref().map(phantom_location),
+        generics: vec![],
+        members: vec![GStructMember::new(
+            PHANTOM_VALUE.to_string(),
+            GType::FieldElement,
+        )],
+    }
+}
+
+// the name of a declared type cannot contain `<`, so phantom types cannot be confused with declared types
+fn phantom_location(tag: &StructLocation) -> StructLocation {
+    StructLocation {
+        module: tag.module.clone(),
+        name: format!("{}<{}>", PHANTOM, tag.name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::typed_absy::types::ConcreteStructType;
+
+    fn tag(module: &str, name: &str) -> DeclarationStructType<'static> {
+        DeclarationStructType::new(module.into(), name.into(), vec![], vec![])
+    }
+
+    #[test]
+    fn same_tag() {
+        let a: ConcreteStructType = phantom_type(&tag("a", "XCoord"));
+        let b: ConcreteStructType = phantom_type(&tag("a", "XCoord"));
+
+        assert_eq!(a, b);
+        assert_eq!(a.to_string(), "phantom<XCoord>");
+    }
+
+    #[test]
+    fn different_tags() {
+        let x: ConcreteStructType = phantom_type(&tag("a", "XCoord"));
+        let y: ConcreteStructType = phantom_type(&tag("a", "YCoord"));
+        let other: ConcreteStructType = phantom_type(&tag("b", "XCoord"));
+
+        assert_ne!(x, y);
+        assert_ne!(x, other);
+    }
+}
@@ -444,7 +444,12 @@ pub fn fold_statement<'ast, T: Field, F: ResultFolder<'ast, T>>(
             TypedStatement::Definition(f.fold_assignee(a)?, f.fold_expression(e)?)
         }
         TypedStatement::Declaration(v) => TypedStatement::Declaration(f.fold_variable(v)?),
-        TypedStatement::Assertion(e) => TypedStatement::Assertion(f.fold_boolean_expression(e)?),
+        TypedStatement::Assertion(e, error) => {
+            TypedStatement::Assertion(f.fold_boolean_expression(e)?, error)
+        }
+        TypedStatement::StaticAssertion(e, source) => {
+            TypedStatement::StaticAssertion(f.fold_boolean_expression(e)?, source)
+        }
         TypedStatement::For(v, from, to, statements) => TypedStatement::For(
             f.fold_variable(v)?,
             f.fold_uint_expression(from)?,
@@ -706,9 +711,10 @@ pub fn fold_select_expression<
     _: &E::Ty,
     e: SelectExpression<'ast, T, E>,
 ) -> Result<SelectOrExpression<'ast, T, E>, F::Error> {
-    Ok(SelectOrExpression::Select(SelectExpression::new(
+    Ok(SelectOrExpression::Select(SelectExpression::with_lookup(
         f.fold_array_expression(*e.array)?,
         f.fold_uint_expression(*e.index)?,
+        e.lookup,
     )))
 }
 
@@ -735,6 +735,16 @@ impl<S> GType<S> {
     pub fn uint<W: Into<UBitwidth>>(b: W) -> Self {
         GType::Uint(b.into())
     }
+
+    /// Whether values of this type contain integer literals, which cannot be assigned
+    pub fn contains_integers(&self) -> bool {
+        match self {
+            GType::Int => true,
+            GType::Array(array_ty) => array_ty.ty.contains_integers(),
+            GType::Struct(struct_ty) => struct_ty.members.iter().any(|m| m.ty.contains_integers()),
+            _ => false,
+        }
+    }
 }
 
 impl<'ast, T: fmt::Display + PartialEq + fmt::Debug> Type<'ast, T> {
@@ -115,7 +115,9 @@ pub fn fold_statement<'ast, T: Field, F: Folder<'ast, T>>(
                 .flat_map(|e| f.fold_statement(e))
                 .collect(),
         ),
-        ZirStatement::Assertion(e) => ZirStatement::Assertion(f.fold_boolean_expression(e)),
+        ZirStatement::Assertion(e, error) => {
+            ZirStatement::Assertion(f.fold_boolean_expression(e), error)
+        }
         ZirStatement::MultipleDefinition(variables, elist) => ZirStatement::MultipleDefinition(
             variables.into_iter().map(|v| f.fold_variable(v)).collect(),
             f.fold_expression_list(elist),
@@ -137,6 +139,10 @@ pub fn fold_field_expression<'ast, T: Field, F: Folder<'ast, T>>(
             a.into_iter().map(|a| f.fold_field_expression(a)).collect(),
             box f.fold_uint_expression(i),
         ),
+        FieldElementExpression::Lookup(a, box i) => FieldElementExpression::Lookup(
+            a.into_iter().map(|a| f.fold_field_expression(a)).collect(),
+            box f.fold_uint_expression(i),
+        ),
         FieldElementExpression::Add(box e1, box e2) => {
             let e1 = f.fold_field_expression(e1);
             let e2 = f.fold_field_expression(e2);
@@ -184,6 +190,12 @@ pub fn fold_boolean_expression<'ast, T: Field, F: Folder<'ast, T>>(
                 .collect(),
             box f.fold_uint_expression(i),
         ),
+        BooleanExpression::Lookup(a, box i) => BooleanExpression::Lookup(
+            a.into_iter()
+                .map(|a| f.fold_boolean_expression(a))
+                .collect(),
+            box f.fold_uint_expression(i),
+        ),
         BooleanExpression::FieldEq(box e1, box e2) => {
             let e1 = f.fold_field_expression(e1);
             let e2 = f.fold_field_expression(e2);
@@ -284,6 +296,10 @@ pub fn fold_uint_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
             a.into_iter().map(|a| f.fold_uint_expression(a)).collect(),
             box f.fold_uint_expression(i),
         ),
+        UExpressionInner::Lookup(a, box i) => UExpressionInner::Lookup(
+            a.into_iter().map(|a| f.fold_uint_expression(a)).collect(),
+            box f.fold_uint_expression(i),
+        ),
         UExpressionInner::Add(box left, box right) => {
             let left = f.fold_uint_expression(left);
             let right = f.fold_uint_expression(right);
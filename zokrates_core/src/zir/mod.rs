@@ -13,6 +13,7 @@ pub use self::variable::Variable;
 pub use crate::zir::uint::{ShouldReduce, UExpression, UExpressionInner, UMetadata};
 
 use crate::embed::FlatEmbed;
+use crate::flat_absy::RuntimeError;
 use crate::zir::types::Signature;
 use std::convert::TryFrom;
 use std::fmt;
@@ -23,7 +24,7 @@ pub use self::folder::Folder;
 pub use self::identifier::{Identifier, SourceIdentifier};
 
 /// A typed program as a collection of modules, one of them being the main
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct ZirProgram<'ast, T> {
     pub main: ZirFunction<'ast, T>,
 }
@@ -96,7 +97,7 @@ pub enum ZirStatement<'ast, T> {
         Vec<ZirStatement<'ast, T>>,
         Vec<ZirStatement<'ast, T>>,
     ),
-    Assertion(BooleanExpression<'ast, T>),
+    Assertion(BooleanExpression<'ast, T>, RuntimeError),
     MultipleDefinition(Vec<ZirAssignee<'ast>>, ZirExpressionList<'ast, T>),
 }
 
@@ -119,7 +120,9 @@ impl<'ast, T: fmt::Debug> fmt::Debug for ZirStatement<'ast, T> {
             ZirStatement::IfElse(ref condition, ref lhs, ref rhs) => {
                 write!(f, "IfElse({:?}, {:?}, {:?})", condition, lhs, rhs)
             }
-            ZirStatement::Assertion(ref e) => write!(f, "Assertion({:?})", e),
+            ZirStatement::Assertion(ref e, ref error) => {
+                write!(f, "Assertion({:?}, {:?})", e, error)
+            }
             ZirStatement::MultipleDefinition(ref lhs, ref rhs) => {
                 write!(f, "MultipleDefinition({:?}, {:?})", lhs, rhs)
             }
@@ -158,7 +161,12 @@ impl<'ast, T: fmt::Display> fmt::Display for ZirStatement<'ast, T> {
                         .join("\n")
                 )
             }
-            ZirStatement::Assertion(ref e) => write!(f, "assert({})", e),
+            ZirStatement::Assertion(ref e, ref error) => {
+                if error.is_barrier() {
+                    write!(f, "#[optimizer(barrier)] ")?;
+                }
+                write!(f, "assert({})", e)
+            }
             ZirStatement::MultipleDefinition(ref ids, ref rhs) => {
                 for (i, id) in ids.iter().enumerate() {
                     write!(f, "{}", id)?;
@@ -265,6 +273,8 @@ pub enum FieldElementExpression<'ast, T> {
     Number(T),
     Identifier(Identifier<'ast>),
     Select(Vec<Self>, Box<UExpression<'ast, T>>),
+    /// a selection in a constant table, lowered to a tree of selections on the bits of the index
+    Lookup(Vec<Self>, Box<UExpression<'ast, T>>),
     Add(
         Box<FieldElementExpression<'ast, T>>,
         Box<FieldElementExpression<'ast, T>>,
@@ -298,6 +308,8 @@ pub enum BooleanExpression<'ast, T> {
     Value(bool),
     Identifier(Identifier<'ast>),
     Select(Vec<Self>, Box<UExpression<'ast, T>>),
+    /// a selection in a constant table, lowered to a tree of selections on the bits of the index
+    Lookup(Vec<Self>, Box<UExpression<'ast, T>>),
     FieldLt(
         Box<FieldElementExpression<'ast, T>>,
         Box<FieldElementExpression<'ast, T>>,
@@ -423,6 +435,15 @@ impl<'ast, T: fmt::Display> fmt::Display for FieldElementExpression<'ast, T> {
                     .join(", "),
                 i
             ),
+            FieldElementExpression::Lookup(ref a, ref i) => write!(
+                f,
+                "lookup([{}], {})",
+                a.iter()
+                    .map(|a| a.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                i
+            ),
             FieldElementExpression::Add(ref lhs, ref rhs) => write!(f, "({} + {})", lhs, rhs),
             FieldElementExpression::Sub(ref lhs, ref rhs) => write!(f, "({} - {})", lhs, rhs),
             FieldElementExpression::Mult(ref lhs, ref rhs) => write!(f, "({} * {})", lhs, rhs),
@@ -453,6 +474,15 @@ impl<'ast, T: fmt::Display> fmt::Display for UExpression<'ast, T> {
                     .join(", "),
                 i
             ),
+            UExpressionInner::Lookup(ref a, ref i) => write!(
+                f,
+                "lookup([{}], {})",
+                a.iter()
+                    .map(|a| a.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                i
+            ),
             UExpressionInner::Add(ref lhs, ref rhs) => write!(f, "({} + {})", lhs, rhs),
             UExpressionInner::Sub(ref lhs, ref rhs) => write!(f, "({} - {})", lhs, rhs),
             UExpressionInner::Mult(ref lhs, ref rhs) => write!(f, "({} * {})", lhs, rhs),
@@ -487,6 +517,15 @@ impl<'ast, T: fmt::Display> fmt::Display for BooleanExpression<'ast, T> {
                     .join(", "),
                 i
             ),
+            BooleanExpression::Lookup(ref a, ref i) => write!(
+                f,
+                "lookup([{}], {})",
+                a.iter()
+                    .map(|a| a.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                i
+            ),
             BooleanExpression::FieldLt(ref lhs, ref rhs) => write!(f, "{} < {}", lhs, rhs),
             BooleanExpression::FieldLe(ref lhs, ref rhs) => write!(f, "{} <= {}", lhs, rhs),
             BooleanExpression::FieldGe(ref lhs, ref rhs) => write!(f, "{} >= {}", lhs, rhs),
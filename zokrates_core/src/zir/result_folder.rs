@@ -137,7 +137,9 @@ pub fn fold_statement<'ast, T: Field, F: ResultFolder<'ast, T>>(
                 .flatten()
                 .collect(),
         ),
-        ZirStatement::Assertion(e) => ZirStatement::Assertion(f.fold_boolean_expression(e)?),
+        ZirStatement::Assertion(e, error) => {
+            ZirStatement::Assertion(f.fold_boolean_expression(e)?, error)
+        }
         ZirStatement::MultipleDefinition(variables, elist) => ZirStatement::MultipleDefinition(
             variables
                 .into_iter()
@@ -164,6 +166,12 @@ pub fn fold_field_expression<'ast, T: Field, F: ResultFolder<'ast, T>>(
                 .collect::<Result<_, _>>()?,
             box f.fold_uint_expression(i)?,
         ),
+        FieldElementExpression::Lookup(a, box i) => FieldElementExpression::Lookup(
+            a.into_iter()
+                .map(|a| f.fold_field_expression(a))
+                .collect::<Result<_, _>>()?,
+            box f.fold_uint_expression(i)?,
+        ),
         FieldElementExpression::Add(box e1, box e2) => {
             let e1 = f.fold_field_expression(e1)?;
             let e2 = f.fold_field_expression(e2)?;
@@ -211,6 +219,12 @@ pub fn fold_boolean_expression<'ast, T: Field, F: ResultFolder<'ast, T>>(
                 .collect::<Result<_, _>>()?,
             box f.fold_uint_expression(i)?,
         ),
+        BooleanExpression::Lookup(a, box i) => BooleanExpression::Lookup(
+            a.into_iter()
+                .map(|a| f.fold_boolean_expression(a))
+                .collect::<Result<_, _>>()?,
+            box f.fold_uint_expression(i)?,
+        ),
         BooleanExpression::FieldEq(box e1, box e2) => {
             let e1 = f.fold_field_expression(e1)?;
             let e2 = f.fold_field_expression(e2)?;
@@ -313,6 +327,12 @@ pub fn fold_uint_expression_inner<'ast, T: Field, F: ResultFolder<'ast, T>>(
                 .collect::<Result<_, _>>()?,
             box f.fold_uint_expression(i)?,
         ),
+        UExpressionInner::Lookup(a, box i) => UExpressionInner::Lookup(
+            a.into_iter()
+                .map(|a| f.fold_uint_expression(a))
+                .collect::<Result<_, _>>()?,
+            box f.fold_uint_expression(i)?,
+        ),
         UExpressionInner::Add(box left, box right) => {
             let left = f.fold_uint_expression(left)?;
             let right = f.fold_uint_expression(right)?;
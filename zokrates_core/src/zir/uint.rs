@@ -21,6 +21,11 @@ impl<'ast, T: Field> UExpression<'ast, T> {
         UExpressionInner::Select(values, box index).annotate(bitwidth)
     }
 
+    pub fn lookup(values: Vec<Self>, index: Self) -> UExpression<'ast, T> {
+        let bitwidth = values[0].bitwidth;
+        UExpressionInner::Lookup(values, box index).annotate(bitwidth)
+    }
+
     pub fn mult(self, other: Self) -> UExpression<'ast, T> {
         let bitwidth = self.bitwidth;
         assert_eq!(bitwidth, other.bitwidth);
@@ -174,6 +179,7 @@ pub enum UExpressionInner<'ast, T> {
     Value(u128),
     Identifier(Identifier<'ast>),
     Select(Vec<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    Lookup(Vec<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
     Add(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
     Sub(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
     Mult(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
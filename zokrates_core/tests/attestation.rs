@@ -0,0 +1,154 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use zokrates_common::Resolver;
+use zokrates_core::attestation::{sha256, Attestation, AttestationError, BuildRecord, SetupRecord};
+use zokrates_core::compile::{compile, CompilationArtifacts, CompileConfig, OptimizerSchedule};
+use zokrates_field::Bn128Field;
+
+// resolves imports from an in-memory set of modules, located in the `project` directory
+struct MemoryResolver(HashMap<&'static str, String>);
+
+impl Resolver<io::Error> for MemoryResolver {
+    fn resolve(
+        &self,
+        _: PathBuf,
+        import_location: PathBuf,
+    ) -> Result<(String, PathBuf), io::Error> {
+        let location = import_location.display().to_string();
+        self.0
+            .get(location.as_str())
+            .cloned()
+            .map(|source| (source, Path::new("project").join(import_location)))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, location))
+    }
+}
+
+const MAIN: &str = r#"
+import "square"
+
+def main(private field a, field b) -> field:
+    assert(square(a) == b)
+    return a + b
+"#;
+
+const SQUARE: &str = r#"
+def main(field x) -> field:
+    return x * x
+"#;
+
+fn compile_project(
+    main: &str,
+    square: &str,
+    config: &CompileConfig,
+) -> CompilationArtifacts<Bn128Field> {
+    let resolver = MemoryResolver(vec![("square", square.to_string())].into_iter().collect());
+
+    compile(
+        main.to_string(),
+        "project/main".into(),
+        Some(&resolver),
+        config,
+    )
+    .unwrap()
+}
+
+fn records(main: &str, square: &str, config: &CompileConfig) -> (BuildRecord, SetupRecord) {
+    let artifacts = compile_project(main, square, config);
+
+    let build = artifacts.build_record(config, Path::new("project"));
+    // the verification key only matters through its hash, so any content will do
+    let setup = SetupRecord::new("bellman", "g16", artifacts.prog(), b"vk");
+
+    (build, setup)
+}
+
+fn attest(main: &str, square: &str, config: &CompileConfig) -> String {
+    let (build, setup) = records(main, square, config);
+    serde_json::to_string(&Attestation::new(build, setup, b"vk").unwrap()).unwrap()
+}
+
+#[test]
+fn stable() {
+    let config = CompileConfig::default();
+    let (build, _) = records(MAIN, SQUARE, &config);
+
+    assert_eq!(
+        build.sources.keys().cloned().collect::<Vec<_>>(),
+        vec!["main".to_string(), "square".to_string()]
+    );
+    assert_eq!(build.sources["square"], sha256(SQUARE));
+
+    assert_eq!(attest(MAIN, SQUARE, &config), attest(MAIN, SQUARE, &config));
+}
+
+#[test]
+fn relative_to_root() {
+    let config = CompileConfig::default();
+    let artifacts = compile_project(MAIN, SQUARE, &config);
+
+    let sources = |root: &str| {
+        artifacts
+            .build_record(&config, Path::new(root))
+            .sources
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(sources("./project"), vec!["main", "square"]);
+    assert_eq!(sources("project/src"), vec!["../main", "../square"]);
+    assert_eq!(sources("."), vec!["project/main", "project/square"]);
+}
+
+#[test]
+fn ingredients() {
+    let config = CompileConfig::default();
+    let reference = attest(MAIN, SQUARE, &config);
+
+    // a change to an imported module which does not change the program
+    assert_ne!(
+        attest(MAIN, &format!("{}\n// comment", SQUARE), &config),
+        reference
+    );
+
+    // a change to the configuration
+    assert_ne!(
+        attest(
+            MAIN,
+            SQUARE,
+            &config
+                .clone()
+                .optimizer_schedule(Some("redefinition".parse::<OptimizerSchedule>().unwrap()))
+        ),
+        reference
+    );
+
+    // a change to the program
+    assert_ne!(
+        attest(&MAIN.replace("a + b", "a * b"), SQUARE, &config),
+        reference
+    );
+}
+
+#[test]
+fn mismatch() {
+    let config = CompileConfig::default();
+
+    let (build, setup) = records(MAIN, SQUARE, &config);
+    assert!(matches!(
+        Attestation::new(build, setup, b"another vk"),
+        Err(AttestationError::VerificationKeyMismatch { .. })
+    ));
+
+    let (build, _) = records(MAIN, SQUARE, &config);
+    let (_, setup) = records(&MAIN.replace("a + b", "a * b"), SQUARE, &config);
+    assert!(matches!(
+        Attestation::new(build, setup, b"vk"),
+        Err(AttestationError::ProgramMismatch { .. })
+    ));
+}
@@ -0,0 +1,210 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::{compile_with, execute};
+use std::io;
+use std::process::Command;
+use zokrates_common::Resolver;
+use zokrates_core::compile::{compile_to_cairo, CompileConfig};
+use zokrates_field::{Bn128Field, Field};
+
+// the Cairo program emitted for `source`
+fn emit(source: &str, config: &CompileConfig) -> String {
+    let mut cairo = vec![];
+    compile_to_cairo::<Bn128Field, io::Error>(
+        source.to_string(),
+        "./path/to/file".into(),
+        None::<&dyn Resolver<io::Error>>,
+        config,
+        &mut cairo,
+    )
+    .unwrap();
+
+    String::from_utf8(cairo).unwrap()
+}
+
+// runs `main` in the Cairo program emitted for `source` on `inputs` with `cairo-run`, returning its outputs, which
+// are of the Cairo type `outputs`
+fn run_cairo(source: &str, config: &CompileConfig, outputs: &str, inputs: &[u32]) -> Vec<String> {
+    // `cairo-run` runs a `main` function without arguments, which we provide to call the emitted one on `inputs`
+    let cairo = emit(source, config).replace("fn main(", "fn zokrates_main(");
+    let arguments = inputs
+        .iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let cairo = format!(
+        "{}\n{}\nfn main() -> Array<felt252> {{\n    zokrates_output(zokrates_main({}))\n}}\n",
+        cairo,
+        output_function(outputs),
+        arguments
+    );
+
+    let path = std::env::temp_dir().join(format!("zokrates_cairo_{}.cairo", std::process::id()));
+    std::fs::write(&path, cairo).unwrap();
+
+    let output = Command::new(std::env::var("CAIRO_RUN").unwrap_or_else(|_| "cairo-run".into()))
+        .arg("--single-file")
+        .arg(&path)
+        .output()
+        .expect("cairo-run should be in the PATH, or its location in CAIRO_RUN");
+    std::fs::remove_file(&path).unwrap();
+
+    // the outputs are printed as `Run completed successfully, returning [1, 2]`
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let list = stdout
+        .split("returning [")
+        .nth(1)
+        .and_then(|s| s.split(']').next())
+        .unwrap_or_else(|| panic!("unexpected cairo-run output: {}", stdout));
+
+    list.split(',')
+        .map(|o| o.trim().to_string())
+        .filter(|o| !o.is_empty())
+        .collect()
+}
+
+// runs the compiled `source` on `inputs` with the interpreter
+fn run_zokrates(source: &str, config: &CompileConfig, inputs: &[u32]) -> Vec<String> {
+    execute(compile_with(source, config).unwrap().prog(), inputs)
+        .unwrap()
+        .iter()
+        .map(|v| v.to_dec_string())
+        .collect()
+}
+
+// a Cairo function collecting values of type `outputs` in an array, so that `cairo-run` prints them in a single list
+fn output_function(outputs: &str) -> String {
+    let count = outputs.split(", ").count();
+    let names: Vec<_> = (0..count).map(|i| format!("o{}", i)).collect();
+
+    let destructure = match count {
+        1 => "o0 = o".to_string(),
+        _ => format!("({}) = o", names.join(", ")),
+    };
+
+    let appends: String = names
+        .iter()
+        .map(|name| format!("    a.append({}.into());\n", name))
+        .collect();
+
+    format!(
+        "use core::array::ArrayTrait;\n\nfn zokrates_output(o: {}) -> Array<felt252> {{\n    let {};\n    let mut a = ArrayTrait::new();\n{}    a\n}}\n",
+        outputs, destructure, appends
+    )
+}
+
+fn assert_same_outputs(
+    source: &str,
+    config: &CompileConfig,
+    cairo_outputs: &str,
+    inputs: &[&[u32]],
+) {
+    for inputs in inputs {
+        assert_eq!(
+            run_cairo(source, config, cairo_outputs, inputs),
+            run_zokrates(source, config, inputs),
+            "on {:?}",
+            inputs
+        );
+    }
+}
+
+#[test]
+#[ignore]
+fn field_arithmetic() {
+    let source = r#"
+def main(field a, field b) -> field:
+    field c = a * a + b
+    assert(c != 0)
+    return c / 2 - b
+"#;
+
+    assert_same_outputs(
+        source,
+        &CompileConfig::default(),
+        "felt252",
+        &[&[3, 5], &[4, 2], &[1000, 6]],
+    );
+}
+
+#[test]
+#[ignore]
+fn uint_wrapping() {
+    let source = r#"
+def main(u8 a, u8 b) -> (u8, u8, u8, u8):
+    return a + b, a - b, a * b, !a ^ (b << 3)
+"#;
+
+    assert_same_outputs(
+        source,
+        &CompileConfig::default(),
+        "(u8, u8, u8, u8)",
+        &[&[200, 100], &[3, 5], &[255, 255], &[0, 0]],
+    );
+}
+
+#[test]
+#[ignore]
+fn conditionals() {
+    let source = r#"
+def main(u32 a, field b) -> (u32, field):
+    u32 c = if a > 10 then a / 3 else a % 3 fi
+    field d = if b == 4 then b * b else b + 1 fi
+    return c, d
+"#;
+
+    assert_same_outputs(
+        source,
+        &CompileConfig::default(),
+        "(u32, felt252)",
+        &[&[11, 4], &[10, 3], &[0, 0]],
+    );
+}
+
+const ISOLATED: &str = r#"
+def inverse(field x) -> field:
+    assert(x != 0)
+    return 1 / x
+
+def main(field x) -> field:
+    return if x == 0 then 0 else inverse(x) fi
+"#;
+
+#[test]
+fn isolated_branches_are_guarded() {
+    let config = CompileConfig::default().isolate_branches(true);
+
+    // the assertion of `inverse` is only checked when its branch is taken
+    let cairo = emit(ISOLATED, &config);
+    let guard = cairo
+        .lines()
+        .find(|l| l.contains("let condition_1: bool = "))
+        .expect("the branch not taken when `x` is zero should be guarded");
+    assert_eq!(guard.trim(), "let condition_1: bool = !condition;");
+
+    let assertions: Vec<_> = cairo
+        .lines()
+        .filter(|l| l.trim_start().starts_with("assert("))
+        .collect();
+    assert!(!assertions.is_empty());
+    assert!(assertions.iter().all(|l| l.contains("(if condition_1 {")));
+
+    // without isolation, both branches are executed and `x == 0` fails
+    let cairo = emit(ISOLATED, &CompileConfig::default());
+    assert!(!cairo.contains("condition_1"));
+}
+
+#[test]
+#[ignore]
+fn isolated_branches() {
+    assert_same_outputs(
+        ISOLATED,
+        &CompileConfig::default().isolate_branches(true),
+        "felt252",
+        &[&[0], &[1], &[5]],
+    );
+}
@@ -0,0 +1,65 @@
+//! Helpers shared by the integration tests, which compile self-contained sources for the bn128 field
+//!
+//! Each test file is its own crate and only uses some of the helpers.
+#![allow(dead_code)]
+
+use std::io;
+use zokrates_common::Resolver;
+use zokrates_core::compile::{
+    check, compile, CompilationArtifacts, CompileConfig, CompileErrorInner, CompileErrors,
+};
+use zokrates_core::ir::{Interpreter, Prog};
+use zokrates_field::Bn128Field;
+
+/// Compiles `source` with `config`
+pub fn compile_with(
+    source: &str,
+    config: &CompileConfig,
+) -> Result<CompilationArtifacts<Bn128Field>, CompileErrors> {
+    compile(
+        source.to_string(),
+        "./path/to/file".into(),
+        None::<&dyn Resolver<io::Error>>,
+        config,
+    )
+}
+
+/// Compiles `source` with the default configuration, panicking if compilation fails
+pub fn compile_source(source: &str) -> CompilationArtifacts<Bn128Field> {
+    compile_with(source, &CompileConfig::default()).unwrap()
+}
+
+/// The message of the first error raised when compiling `source`, panicking if compilation succeeds
+pub fn error(source: &str) -> String {
+    compile_with(source, &CompileConfig::default())
+        .err()
+        .unwrap()
+        .0[0]
+        .value()
+        .to_string()
+}
+
+/// Type checks `source`, returning the message of the first semantic error if any
+pub fn check_source(source: &str) -> Result<(), String> {
+    check::<Bn128Field, io::Error>(
+        source.to_string(),
+        "main".into(),
+        None::<&dyn Resolver<io::Error>>,
+        &CompileConfig::default(),
+    )
+    .map_err(|e| match e.0[0].value() {
+        CompileErrorInner::SemanticError(e) => e.message().to_string(),
+        e => panic!("expected a semantic error, found {}", e),
+    })
+}
+
+/// Executes `prog` on `inputs`, returning its return values or the message of the error if the witness cannot be
+/// computed
+pub fn execute(prog: &Prog<Bn128Field>, inputs: &[u32]) -> Result<Vec<Bn128Field>, String> {
+    let inputs: Vec<_> = inputs.iter().map(|i| Bn128Field::from(*i)).collect();
+
+    Interpreter::default()
+        .execute(prog, &inputs)
+        .map(|witness| witness.return_values())
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,91 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use zokrates_core::compile::{ComparisonStrategy, CompileConfig};
+use zokrates_core::ir::{Interpreter, Prog};
+use zokrates_field::Bn128Field;
+
+fn compile_with(source: &str, strategy: Option<ComparisonStrategy>) -> Prog<Bn128Field> {
+    common::compile_with(
+        source,
+        &CompileConfig::default().comparison_strategy(strategy),
+    )
+    .unwrap()
+    .prog()
+    .clone()
+}
+
+fn lt(prog: &Prog<Bn128Field>, a: u32, b: u32) -> bool {
+    let witness = Interpreter::default()
+        .execute(prog, &[Bn128Field::from(a), Bn128Field::from(b)])
+        .unwrap();
+
+    witness.return_values() == vec![Bn128Field::from(1)]
+}
+
+const UINT_LT: &str = r#"
+def main(u8 a, u8 b) -> bool:
+    return a < b
+"#;
+
+#[test]
+fn difference_is_cheaper_on_uints() {
+    let default = compile_with(UINT_LT, None);
+    let bit_compare = compile_with(UINT_LT, Some(ComparisonStrategy::BitCompare));
+
+    assert!(default.constraint_count() < bit_compare.constraint_count());
+}
+
+#[test]
+fn difference_is_not_used_on_fields() {
+    let source = r#"
+def main(field a, field b) -> bool:
+    return a < b
+"#;
+
+    assert_eq!(
+        compile_with(source, Some(ComparisonStrategy::Difference)).constraint_count(),
+        compile_with(source, Some(ComparisonStrategy::BitCompare)).constraint_count()
+    );
+}
+
+#[test]
+fn strategies_agree() {
+    let difference = compile_with(UINT_LT, Some(ComparisonStrategy::Difference));
+    let bit_compare = compile_with(UINT_LT, Some(ComparisonStrategy::BitCompare));
+
+    // the difference encoding is checked on all inputs
+    for a in 0..256 {
+        for b in 0..256 {
+            assert_eq!(lt(&difference, a, b), a < b, "{} < {}", a, b);
+        }
+    }
+
+    // the bit-compare encoding decomposes over the full field width, so only a sample is checked
+    for a in (0..256).step_by(17) {
+        for b in (0..256).step_by(15) {
+            assert_eq!(lt(&bit_compare, a, b), lt(&difference, a, b));
+        }
+    }
+}
+
+#[test]
+fn wider_operands() {
+    // the sum is not reduced before the comparison, yet both strategies must agree with the reduced value
+    let source = r#"
+def main(u8 a, u8 b) -> bool:
+    return a + b < b
+"#;
+
+    let difference = compile_with(source, Some(ComparisonStrategy::Difference));
+    let bit_compare = compile_with(source, Some(ComparisonStrategy::BitCompare));
+
+    for (a, b) in [(0, 0), (1, 2), (200, 100), (255, 255), (100, 200)].iter() {
+        let expected = (a + b) % 256 < *b;
+        assert_eq!(lt(&difference, *a, *b), expected);
+        assert_eq!(lt(&bit_compare, *a, *b), expected);
+    }
+}
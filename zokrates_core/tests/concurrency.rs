@@ -0,0 +1,186 @@
+#[macro_use]
+extern crate lazy_static;
+extern crate serde_json;
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::compile_source;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::mem;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use zokrates_core::ir::{Interpreter, Prog, Statement};
+use zokrates_field::Bn128Field;
+
+// counts the allocations of `WATCHED` bytes
+struct Counter;
+
+static WATCHED: AtomicUsize = AtomicUsize::new(0);
+static HITS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.size() == WATCHED.load(Ordering::SeqCst) {
+            HITS.fetch_add(1, Ordering::SeqCst);
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counter = Counter;
+
+lazy_static! {
+    // the tests run one at a time, so that each of them only counts its own allocations
+    static ref SERIAL: Mutex<()> = Mutex::new(());
+}
+
+// returns the result of `f`, and the number of copies of the statements of `program` made by `f`
+fn copies<R>(program: &Prog<Bn128Field>, f: impl FnOnce() -> R) -> (R, usize) {
+    WATCHED.store(
+        program.statements.len() * mem::size_of::<Statement<Bn128Field>>(),
+        Ordering::SeqCst,
+    );
+    HITS.store(0, Ordering::SeqCst);
+    let res = f();
+    WATCHED.store(0, Ordering::SeqCst);
+    (res, HITS.load(Ordering::SeqCst))
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+fn program() -> Prog<Bn128Field> {
+    let source = r#"
+def main(private field a, u32 b) -> (field, u32):
+    field acc = a
+    u32 bits = b
+    for u32 i in 0..16 do
+        acc = acc * acc + a
+        bits = (bits >> 1) ^ (bits << 3) + i
+    endfor
+    return acc, bits
+"#;
+
+    compile_source(source).prog().clone()
+}
+
+fn inputs(i: u32) -> Vec<Bn128Field> {
+    vec![
+        Bn128Field::from(i),
+        Bn128Field::from(i.wrapping_mul(2654435761)),
+    ]
+}
+
+#[test]
+fn shared_program_witnesses() {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+
+    assert_send_sync::<Prog<Bn128Field>>();
+    assert_send_sync::<Interpreter>();
+
+    let program = Arc::new(program());
+
+    let sequential: Vec<_> = (0..64)
+        .map(|i| {
+            Interpreter::default()
+                .execute(&program, &inputs(i))
+                .unwrap()
+        })
+        .collect();
+
+    let (results, cloned) = copies(&program, || {
+        let handles: Vec<_> = (0..16)
+            .map(|thread| {
+                let program = Arc::clone(&program);
+                thread::spawn(move || {
+                    let interpreter = Interpreter::default();
+                    (0..64)
+                        .filter(|i| i % 16 == thread)
+                        .map(|i| (i, interpreter.execute(&program, &inputs(i)).unwrap()))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    // the threads only borrowed the program, while cloning it copies its statements once
+    assert_eq!(cloned, 0);
+    assert_eq!(copies(&program, || Prog::clone(&program)).1, 1);
+
+    let mut count = 0;
+
+    for witnesses in results {
+        for (i, witness) in witnesses {
+            assert_eq!(witness, sequential[i as usize]);
+            count += 1;
+        }
+    }
+
+    assert_eq!(count, 64);
+}
+
+#[cfg(feature = "bellman")]
+#[test]
+fn shared_program_proofs() {
+    use zokrates_core::proof_system::bellman::Bellman;
+    use zokrates_core::proof_system::groth16::G16;
+    use zokrates_core::proof_system::{Backend, NonUniversalBackend};
+
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+
+    let program = Arc::new(program());
+
+    let ((vk, proofs), cloned) = copies(&program, || {
+        let keypair = <Bellman as NonUniversalBackend<Bn128Field, G16>>::setup(&program);
+        let pk = Arc::new(keypair.pk);
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let program = Arc::clone(&program);
+                let pk = Arc::clone(&pk);
+                thread::spawn(move || {
+                    let witness = Interpreter::default()
+                        .execute(&program, &inputs(i))
+                        .unwrap();
+                    <Bellman as Backend<Bn128Field, G16>>::generate_proof(
+                        &program,
+                        witness,
+                        pk.to_vec(),
+                    )
+                })
+            })
+            .collect();
+
+        let proofs: Vec<_> = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+
+        (keypair.vk, proofs)
+    });
+
+    // neither the setup nor the provers copied the program
+    assert_eq!(cloned, 0);
+
+    let vk = serde_json::to_value(&vk).unwrap();
+
+    for proof in proofs {
+        assert!(<Bellman as Backend<Bn128Field, G16>>::verify(
+            serde_json::from_value(vk.clone()).unwrap(),
+            proof
+        ));
+    }
+}
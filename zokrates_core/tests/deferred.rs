@@ -0,0 +1,76 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::{compile_source, error};
+use zokrates_core::ir::Interpreter;
+use zokrates_field::Bn128Field;
+
+#[test]
+fn finalize_matches_full_execution() {
+    let source = r#"
+def main(private field preimage, #[deferred] field block_hash) -> field:
+    assert(preimage * preimage == 9)
+    assert(block_hash != 0)
+    field mixed = preimage * block_hash
+    return mixed + preimage
+"#;
+
+    let res = compile_source(source);
+
+    assert!(res.abi().inputs[1].deferred);
+
+    let deferred = res.abi().deferred_arguments();
+    assert_eq!(deferred, vec![1]);
+
+    let interpreter = Interpreter::default();
+
+    // the placeholder does not satisfy `block_hash != 0`, which is only checked when finalizing
+    let pending = interpreter
+        .execute_deferred(
+            res.prog(),
+            &[Bn128Field::from(3), Bn128Field::from(0)],
+            &deferred,
+        )
+        .unwrap();
+
+    for block_hash in [42, 1337].iter().cloned() {
+        let finalized = interpreter
+            .finalize(
+                res.prog(),
+                pending.clone(),
+                &[(1, Bn128Field::from(block_hash))],
+            )
+            .unwrap();
+
+        let expected = interpreter
+            .execute(
+                res.prog(),
+                &[Bn128Field::from(3), Bn128Field::from(block_hash)],
+            )
+            .unwrap();
+
+        assert_eq!(finalized, expected);
+        assert_eq!(
+            finalized.return_values(),
+            vec![Bn128Field::from(3 * block_hash + 3)]
+        );
+    }
+
+    // constraints depending on the deferred input are checked when finalizing
+    assert!(interpreter
+        .finalize(res.prog(), pending, &[(1, Bn128Field::from(0))])
+        .is_err());
+}
+
+#[test]
+fn deferred_private_parameter() {
+    let source = r#"
+def main(#[deferred] private field block_hash) -> field:
+    return block_hash
+"#;
+
+    assert!(error(source).contains("must be public"));
+}
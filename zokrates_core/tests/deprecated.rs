@@ -0,0 +1,140 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+use std::io;
+use std::path::PathBuf;
+use zokrates_common::Resolver;
+use zokrates_core::compile::{compile, CompilationArtifacts, CompileConfig};
+use zokrates_field::Bn128Field;
+
+const HASH: &str = r#"
+#[deprecated(since = "0.8.0", note = "Use new_hash instead")]
+def old_hash(field x) -> field:
+    return x * x
+
+def new_hash(field x) -> field:
+    return x * x * x
+"#;
+
+// resolves the `hash` module to `HASH`
+struct HashResolver;
+
+impl<E> Resolver<E> for HashResolver {
+    fn resolve(&self, _: PathBuf, import_location: PathBuf) -> Result<(String, PathBuf), E> {
+        assert_eq!(import_location.display().to_string(), "hash");
+        Ok((HASH.into(), "hash".into()))
+    }
+}
+
+// the warnings emitted when compiling `source`, as displayed to the user
+fn warnings(source: &str) -> Vec<String> {
+    let res: CompilationArtifacts<Bn128Field> = compile(
+        source.to_string(),
+        "main".into(),
+        Some(&HashResolver as &dyn Resolver<io::Error>),
+        &CompileConfig::default(),
+    )
+    .unwrap();
+
+    res.warnings().iter().map(|w| w.to_string()).collect()
+}
+
+fn old_hash_usage(position: &str) -> String {
+    format!(
+        "Use of deprecated function `old_hash` at {} (deprecated since 0.8.0): Use new_hash instead",
+        position
+    )
+}
+
+#[test]
+fn each_call_site() {
+    let source = format!(
+        r#"{}
+def main(field x) -> field:
+    field a = old_hash(x)
+    assert(new_hash(a) == old_hash(x + 1))
+    return a
+"#,
+        HASH
+    );
+
+    assert_eq!(
+        warnings(&source),
+        vec![old_hash_usage("10:15"), old_hash_usage("11:27")]
+    );
+}
+
+#[test]
+fn allowed() {
+    let source = format!(
+        r#"{}
+def main(field x) -> field:
+    #[allow(deprecated)]
+    field a = old_hash(x)
+    #[allow(deprecated)] assert(a == old_hash(x))
+    #[allow(deprecated)]
+    return old_hash(a)
+"#,
+        HASH
+    );
+
+    assert!(warnings(&source).is_empty());
+}
+
+#[test]
+fn allowed_on_one_statement_only() {
+    let source = format!(
+        r#"{}
+def main(field x) -> field:
+    #[allow(deprecated)]
+    field a = old_hash(x)
+    return old_hash(a)
+"#,
+        HASH
+    );
+
+    assert_eq!(warnings(&source), vec![old_hash_usage("12:12")]);
+}
+
+#[test]
+fn without_arguments() {
+    let source = r#"
+#[deprecated]
+def foo() -> field:
+    return 1
+
+def main() -> field:
+    return foo()
+"#;
+
+    assert_eq!(
+        warnings(source),
+        vec!["Use of deprecated function `foo` at 7:12".to_string()]
+    );
+}
+
+#[test]
+fn imported() {
+    let source = r#"
+from "hash" import old_hash, new_hash
+
+def main(field x) -> field:
+    return new_hash(old_hash(x))
+"#;
+
+    assert_eq!(warnings(source), vec![old_hash_usage("5:21")]);
+}
+
+#[test]
+fn declaration_only() {
+    // importing from a module declaring a deprecated function is not a usage of it
+    let source = r#"
+from "hash" import new_hash
+
+def main(field x) -> field:
+    return new_hash(x)
+"#;
+
+    assert!(warnings(source).is_empty());
+}
@@ -0,0 +1,69 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::{compile_source, execute};
+
+#[test]
+fn assert_eq() {
+    let source = r#"
+def main(private field a, private field b):
+    assert_eq!(a * a, b + 1)
+    return
+"#;
+
+    assert_eq!(
+        execute(compile_source(source).prog(), &[3, 8]).map(|_| ()),
+        Ok(())
+    );
+    assert_eq!(
+        execute(compile_source(source).prog(), &[3, 9]).map(|_| ()),
+        Err("assertion failed: a * a == b + 1 (left: 9, right: 10)".to_string())
+    );
+}
+
+#[test]
+fn assert_ne() {
+    let source = r#"
+def main(private u32 a, private u32 b):
+    assert_ne!(a, b)
+    return
+"#;
+
+    assert_eq!(
+        execute(compile_source(source).prog(), &[3, 4]).map(|_| ()),
+        Ok(())
+    );
+    assert_eq!(
+        execute(compile_source(source).prog(), &[3, 3]).map(|_| ()),
+        Err("assertion failed: a != b (left: 3, right: 3)".to_string())
+    );
+}
+
+#[test]
+fn same_constraints_as_assert() {
+    let with_macro = compile_source(
+        r#"
+def main(private field a, private field b):
+    assert_eq!(a * a, b)
+    assert_ne!(a, b)
+    return
+"#,
+    );
+
+    let with_assert = compile_source(
+        r#"
+def main(private field a, private field b):
+    assert(a * a == b)
+    assert(a != b)
+    return
+"#,
+    );
+
+    assert_eq!(
+        with_macro.prog().constraint_count(),
+        with_assert.prog().constraint_count()
+    );
+}
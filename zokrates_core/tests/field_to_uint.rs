@@ -0,0 +1,77 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::{compile_source, compile_with};
+use zokrates_core::compile::CompileConfig;
+use zokrates_core::ir::Interpreter;
+use zokrates_field::{Bn128Field, Field};
+
+// casts `value` to a u32 at runtime, returning `None` if the execution fails
+fn execute(value: &Bn128Field) -> Option<Bn128Field> {
+    let source = r#"
+from "EMBED" import unpack, u32_from_bits
+
+def main(field a) -> u32:
+    bool[32] bits = unpack(a)
+    return u32_from_bits(bits)
+"#;
+
+    let res = compile_source(source);
+
+    Interpreter::default()
+        .execute(res.prog(), &[value.clone()])
+        .ok()
+        .map(|witness| witness.return_values()[0].clone())
+}
+
+// casts `value` to a u32 during compilation, returning `None` if the compilation fails
+fn fold(value: &Bn128Field) -> Option<Bn128Field> {
+    let source = format!(
+        r#"
+from "EMBED" import unpack, u32_from_bits
+
+def main() -> u32:
+    bool[32] bits = unpack({})
+    return u32_from_bits(bits)
+"#,
+        value
+    );
+
+    match compile_with(&source, &CompileConfig::default()) {
+        Ok(res) => Some(
+            Interpreter::default()
+                .execute(res.prog(), &[])
+                .unwrap()
+                .return_values()[0]
+                .clone(),
+        ),
+        Err(e) => {
+            assert!(e
+                .0
+                .iter()
+                .any(|e| e.value().to_string().contains("does not fit in 32 bits")));
+            None
+        }
+    }
+}
+
+#[test]
+fn folding_matches_execution() {
+    let values = vec![
+        Bn128Field::from(0),
+        Bn128Field::from(42),
+        Bn128Field::from(u32::MAX),
+        Bn128Field::from(u32::MAX as u128 + 1),
+        Bn128Field::max_value(),
+    ];
+
+    for value in values {
+        assert_eq!(fold(&value), execute(&value), "casting {}", value);
+    }
+
+    assert_eq!(fold(&Bn128Field::from(42)), Some(Bn128Field::from(42)));
+    assert_eq!(fold(&Bn128Field::max_value()), None);
+}
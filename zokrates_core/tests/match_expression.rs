@@ -0,0 +1,140 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::{compile_source, error};
+use zokrates_core::ir::Interpreter;
+use zokrates_field::Bn128Field;
+
+#[test]
+fn select_first_matching_arm() {
+    let source = r#"
+def main(bool a, bool b, bool c) -> field:
+    return match (a, b, c) {
+        (true, true, _) => 1,
+        (false, _, true) => 2,
+        _ => 3
+    }
+"#;
+
+    let res = compile_source(source);
+    let interpreter = Interpreter::default();
+
+    for i in 0..8u32 {
+        let (a, b, c) = (i & 4 != 0, i & 2 != 0, i & 1 != 0);
+
+        let expected = match (a, b, c) {
+            (true, true, _) => 1,
+            (false, _, true) => 2,
+            _ => 3,
+        };
+
+        let inputs: Vec<Bn128Field> = vec![a, b, c]
+            .into_iter()
+            .map(|v| Bn128Field::from(v as u32))
+            .collect();
+
+        assert_eq!(
+            interpreter
+                .execute(res.prog(), &inputs)
+                .unwrap()
+                .return_values(),
+            vec![Bn128Field::from(expected)]
+        );
+    }
+}
+
+#[test]
+fn non_exhaustive() {
+    let source = r#"
+def main(bool a, bool b) -> field:
+    return match (a, b) {
+        (true, _) => 1,
+        (false, true) => 2
+    }
+"#;
+
+    assert!(error(source).contains("(false, false) not covered"));
+}
+
+#[test]
+fn unreachable_arm() {
+    let source = r#"
+def main(bool a, bool b) -> field:
+    return match (a, b) {
+        (true, _) => 1,
+        _ => 2,
+        (false, false) => 3
+    }
+"#;
+
+    assert!(error(source).contains("Unreachable pattern (false, false)"));
+}
+
+#[test]
+fn wrong_pattern_width() {
+    let source = r#"
+def main(bool a, bool b) -> field:
+    return match (a, b) {
+        (true, _, _) => 1,
+        _ => 2
+    }
+"#;
+
+    assert!(error(source).contains("to have 2 elements, found 3"));
+}
+
+#[test]
+fn non_boolean_element() {
+    let source = r#"
+def main(bool a, field b) -> field:
+    return match (a, b) {
+        (true, true) => 1,
+        _ => 2
+    }
+"#;
+
+    assert!(error(source).contains("to be a boolean, found field"));
+}
+
+#[test]
+fn shared_arm_is_computed_once() {
+    // the default arm is selected by two leaves of the decision tree
+    let source = |default: &str| {
+        format!(
+            r#"
+def main(bool a, bool b, bool c, field x, field y) -> field:
+    return match (a, b, c) {{
+        (true, true, _) => 1,
+        (false, _, true) => 2,
+        _ => {}
+    }}
+"#,
+            default
+        )
+    };
+
+    let product = compile_source(&source("x * y"));
+    let sum = compile_source(&source("x + y"));
+
+    assert_eq!(
+        product.prog().constraint_count(),
+        sum.prog().constraint_count() + 1
+    );
+}
+
+#[test]
+fn arms_of_different_types() {
+    let source = r#"
+def main(bool a, bool b) -> field:
+    return match (a, b) {
+        (true, _) => 1,
+        _ => false
+    }
+"#;
+
+    assert!(error(source)
+        .contains("Arms of `match` expression should have the same type, found {integer}, bool"));
+}
@@ -0,0 +1,244 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::{check_source, compile_source, execute};
+use zokrates_field::Bn128Field;
+
+#[test]
+fn call_methods() {
+    let source = r#"
+struct Point {
+    field x
+    field y
+}
+
+impl Point:
+    def norm(Point self) -> field:
+        return self.x * self.x + self.y * self.y
+
+    def scale(Point self, field k) -> Point:
+        return Point { x: k * self.x, y: k * self.y }
+endimpl
+
+def main(Point p) -> field:
+    return p.scale(2).norm()
+"#;
+
+    // (2 * 1)^2 + (2 * 3)^2
+    assert_eq!(
+        execute(compile_source(source).prog(), &[1, 3]).unwrap(),
+        vec![Bn128Field::from(40)]
+    );
+}
+
+#[test]
+fn methods_of_different_structs() {
+    let source = r#"
+struct Foo {
+    field a
+}
+
+struct Bar {
+    field b
+    field c
+}
+
+impl Foo:
+    def get(Foo self) -> field:
+        return self.a
+endimpl
+
+impl Bar:
+    def get(Bar self) -> field:
+        return self.b + self.c
+endimpl
+
+def main(Foo foo, Bar bar) -> (field, field):
+    return foo.get(), bar.get()
+"#;
+
+    // the method is resolved by the type of the receiver
+    assert_eq!(
+        execute(compile_source(source).prog(), &[1, 2, 3]).unwrap(),
+        vec![Bn128Field::from(1), Bn128Field::from(5)]
+    );
+}
+
+#[test]
+fn member_receiver() {
+    let source = r#"
+struct Inner {
+    field a
+}
+
+struct Outer {
+    Inner inner
+}
+
+impl Inner:
+    def double(Inner self) -> field:
+        return 2 * self.a
+endimpl
+
+def main(Outer o) -> field:
+    return o.inner.double()
+"#;
+
+    assert_eq!(
+        execute(compile_source(source).prog(), &[21]).unwrap(),
+        vec![Bn128Field::from(42)]
+    );
+}
+
+#[test]
+fn misplaced_receiver() {
+    let source = r#"
+struct Foo {
+    field a
+}
+
+impl Foo:
+    def get(field a, Foo self) -> field:
+        return self.a
+endimpl
+
+def main():
+    return
+"#;
+
+    assert_eq!(
+        check_source(source),
+        Err("Method `get` of `Foo` must take a `Foo` as its first argument".into())
+    );
+}
+
+#[test]
+fn undefined_method() {
+    let source = r#"
+struct Foo {
+    field a
+}
+
+def main(Foo foo) -> field:
+    return foo.get()
+"#;
+
+    assert!(check_source(source).is_err());
+}
+
+#[test]
+fn several_return_values() {
+    let source = r#"
+struct Point {
+    field x
+    field y
+}
+
+impl Point:
+    def coordinates(Point self) -> (field, field):
+        return self.x, self.y
+endimpl
+
+def main(Point p) -> field:
+    field x, field y = p.coordinates()
+    return x - y
+"#;
+
+    assert_eq!(
+        execute(compile_source(source).prog(), &[5, 3]).unwrap(),
+        vec![Bn128Field::from(2)]
+    );
+}
+
+#[test]
+fn method_and_function_of_the_same_signature() {
+    let source = r#"
+struct Point {
+    field x
+    field y
+}
+
+impl Point:
+    def norm(Point self) -> field:
+        return self.x * self.x + self.y * self.y
+endimpl
+
+def norm(Point p) -> field:
+    return p.x + p.y
+
+def main(Point p) -> (field, field):
+    return p.norm(), norm(p)
+"#;
+
+    // methods do not collide with free functions
+    assert_eq!(
+        execute(compile_source(source).prog(), &[1, 3]).unwrap(),
+        vec![Bn128Field::from(10), Bn128Field::from(4)]
+    );
+}
+
+#[test]
+fn method_not_callable_by_name() {
+    let source = r#"
+struct Foo {
+    field a
+}
+
+impl Foo:
+    def get(Foo self) -> field:
+        return self.a
+endimpl
+
+def main(Foo foo) -> field:
+    return get(foo)
+"#;
+
+    assert!(check_source(source)
+        .unwrap_err()
+        .starts_with("Function definition for function get with signature"));
+}
+
+#[test]
+fn function_not_callable_as_method() {
+    let source = r#"
+struct Foo {
+    field a
+}
+
+def get(Foo self) -> field:
+    return self.a
+
+def main(Foo foo) -> field:
+    return foo.get()
+"#;
+
+    assert!(check_source(source)
+        .unwrap_err()
+        .starts_with("Function definition for function get with signature"));
+}
+
+#[test]
+fn method_named_after_operator() {
+    let source = r#"
+struct Foo {
+    field a
+}
+
+impl Foo:
+    def Add(Foo self, Foo other) -> Foo:
+        return self
+endimpl
+
+def main(Foo a, Foo b) -> Foo:
+    return a + b
+"#;
+
+    // a method is never an implementation of an operator
+    assert_eq!(
+        check_source(source),
+        Err("Cannot apply `+` to Foo, Foo".into())
+    );
+}
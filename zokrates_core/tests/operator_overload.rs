@@ -0,0 +1,229 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::{check_source, compile_source};
+use zokrates_core::ir::Interpreter;
+use zokrates_field::Bn128Field;
+
+#[test]
+fn resolve_implementation() {
+    let source = r#"
+struct Fp2 {
+    field c0
+    field c1
+}
+
+impl Add for Fp2:
+    def add(Fp2 a, Fp2 b) -> Fp2:
+        return Fp2 { c0: a.c0 + b.c0, c1: a.c1 + b.c1 }
+endimpl
+
+impl Mul for Fp2:
+    def mul(Fp2 a, Fp2 b) -> Fp2:
+        return Fp2 { c0: a.c0 * b.c0 - a.c1 * b.c1, c1: a.c0 * b.c1 + a.c1 * b.c0 }
+endimpl
+
+def main(Fp2 a, Fp2 b) -> Fp2:
+    return a * b + a
+"#;
+
+    let res = compile_source(source);
+
+    let inputs: Vec<Bn128Field> = vec![1, 2, 3, 4].into_iter().map(Bn128Field::from).collect();
+
+    // (1 + 2i) * (3 + 4i) + (1 + 2i) = -5 + 10i + 1 + 2i
+    assert_eq!(
+        Interpreter::default()
+            .execute(res.prog(), &inputs)
+            .unwrap()
+            .return_values(),
+        vec![Bn128Field::from(-4), Bn128Field::from(12)]
+    );
+}
+
+#[test]
+fn ambiguous_implementation() {
+    let source = r#"
+struct Foo<N> {
+    field[N] a
+}
+
+impl Add for Foo:
+    def add<N>(Foo<N> a, Foo<2> b) -> Foo<2>:
+        return b
+endimpl
+
+impl Add for Foo:
+    def add<N>(Foo<2> a, Foo<N> b) -> Foo<2>:
+        return a
+endimpl
+
+def main(Foo<2> a, Foo<2> b) -> Foo<2>:
+    return a + b
+"#;
+
+    assert_eq!(
+        check_source(source),
+        Err("Ambiguous overload of `Add` for Foo<2>, Foo<2>: 2 implementations were found".into())
+    );
+}
+
+#[test]
+fn invalid_implementation() {
+    let source = r#"
+struct Foo {
+    field a
+}
+
+impl Sub for Foo:
+    def sub(Foo a) -> Foo:
+        return a
+endimpl
+
+def main():
+    return
+"#;
+
+    assert_eq!(
+        check_source(source),
+        Err(
+            "Implementation of `Sub` for `Foo` must take two `Foo` arguments and return a single value"
+                .into()
+        )
+    );
+}
+
+#[test]
+fn implementation_for_another_struct() {
+    let source = r#"
+struct Foo {
+    field a
+}
+
+struct Bar {
+    field a
+}
+
+impl Add for Foo:
+    def add(Bar a, Bar b) -> Bar:
+        return a
+endimpl
+
+def main():
+    return
+"#;
+
+    assert_eq!(
+        check_source(source),
+        Err(
+            "Implementation of `Add` for `Foo` must take two `Foo` arguments and return a single value"
+                .into()
+        )
+    );
+}
+
+#[test]
+fn implementation_for_undefined_struct() {
+    let source = r#"
+impl Add for Foo:
+    def add(field a, field b) -> field:
+        return a
+endimpl
+
+def main():
+    return
+"#;
+
+    assert_eq!(check_source(source), Err("Undefined type Foo".into()));
+}
+
+#[test]
+fn implementation_not_callable() {
+    let source = r#"
+struct Foo {
+    field a
+}
+
+impl Add for Foo:
+    def add(Foo a, Foo b) -> Foo:
+        return a
+endimpl
+
+def main(Foo a, Foo b) -> Foo:
+    return Add(a, b)
+"#;
+
+    assert!(check_source(source)
+        .unwrap_err()
+        .starts_with("Function definition for function Add with signature"));
+}
+
+#[test]
+fn function_named_after_operator() {
+    let source = r#"
+struct Foo {
+    field a
+}
+
+def Add(Foo a, Foo b) -> Foo:
+    return a
+
+def main():
+    return
+"#;
+
+    assert_eq!(
+        check_source(source),
+        Err(
+            "`Add` cannot be declared as a function, implement it for a struct with `impl Add for`"
+                .into()
+        )
+    );
+}
+
+#[test]
+fn operand_of_another_type() {
+    let source = r#"
+struct Foo {
+    field a
+}
+
+impl Add for Foo:
+    def add(Foo a, Foo b) -> Foo:
+        return a
+endimpl
+
+def main(Foo a, field b) -> Foo:
+    return a + b
+"#;
+
+    assert_eq!(
+        check_source(source),
+        Err("Cannot apply `+` to Foo, field".into())
+    );
+}
+
+#[test]
+fn missing_implementation() {
+    let source = r#"
+struct Foo {
+    field a
+}
+
+impl Add for Foo:
+    def add(Foo a, Foo b) -> Foo:
+        return a
+endimpl
+
+def main(Foo a, Foo b) -> Foo:
+    return a / b
+"#;
+
+    assert_eq!(
+        check_source(source),
+        Err("Cannot apply `/` to Foo, Foo".into())
+    );
+}
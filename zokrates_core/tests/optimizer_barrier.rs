@@ -0,0 +1,64 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::compile_source;
+use zokrates_core::ir::Interpreter;
+use zokrates_field::Bn128Field;
+
+#[test]
+fn barrier_survives_optimization() {
+    let annotated = compile_source(
+        r#"
+def main(private field a, private field b) -> field:
+    assert(a * a == b)
+    #[optimizer(barrier)]
+    assert(a * a == b)
+    assert(a * a == b)
+    return b
+"#,
+    );
+
+    let plain = compile_source(
+        r#"
+def main(private field a, private field b) -> field:
+    assert(a * a == b)
+    assert(a * a == b)
+    assert(a * a == b)
+    return b
+"#,
+    );
+
+    // the unannotated duplicates are removed, the barrier is kept
+    assert_eq!(plain.prog().barrier_count(), 0);
+    assert_eq!(annotated.prog().barrier_count(), 1);
+    assert_eq!(
+        annotated.prog().constraint_count(),
+        plain.prog().constraint_count() + 1
+    );
+    assert!(annotated
+        .prog()
+        .to_string()
+        .contains("#[optimizer(barrier)]"));
+
+    // the witness is unchanged
+    let interpreter = Interpreter::default();
+
+    for inputs in &[[3, 9], [3, 10]] {
+        let inputs: Vec<_> = inputs.iter().map(|i| Bn128Field::from(*i)).collect();
+
+        let annotated = interpreter
+            .execute(annotated.prog(), &inputs)
+            .map(|w| w.return_values());
+        let plain = interpreter
+            .execute(plain.prog(), &inputs)
+            .map(|w| w.return_values());
+
+        assert_eq!(annotated.is_ok(), plain.is_ok());
+        if let (Ok(annotated), Ok(plain)) = (annotated, plain) {
+            assert_eq!(annotated, plain);
+        }
+    }
+}
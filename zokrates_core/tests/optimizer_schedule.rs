@@ -0,0 +1,63 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::compile_with;
+use zokrates_core::compile::CompileConfig;
+
+const FIXTURES: &[&str] = &[
+    r#"
+def main(private field a, field b) -> field:
+    field c = a * a
+    assert(c == b)
+    assert(a * a == b)
+    return c + a
+"#,
+    r#"
+def main(private u32 a, private u32 b) -> u32:
+    u32 c = (a ^ b) + (a & b)
+    return if a < b then c else c >> 3 fi
+"#,
+    r#"
+def main(private field[4] a, field x) -> field:
+    field res = 0
+    for u32 i in 0..4 do
+        res = res + (if a[i] == x then 1 else 0 fi)
+    endfor
+    return res
+"#,
+    r#"
+def main(private bool a, private bool b, field x) -> bool:
+    return (a && b) || !(x < 42)
+"#,
+];
+
+#[test]
+fn tuned_never_worse_than_default() {
+    for source in FIXTURES {
+        let default = compile_with(source, &CompileConfig::default()).unwrap();
+        let tuned = compile_with(source, &CompileConfig::default().optimize_tuned(true)).unwrap();
+
+        assert!(default.optimizer_schedule().is_none());
+        assert!(tuned.optimizer_schedule().is_some());
+        assert!(tuned.prog().constraint_count() <= default.prog().constraint_count());
+    }
+}
+
+#[test]
+fn replay_is_deterministic() {
+    for source in FIXTURES {
+        let tuned = compile_with(source, &CompileConfig::default().optimize_tuned(true)).unwrap();
+        let schedule = tuned.optimizer_schedule().cloned();
+
+        let config = CompileConfig::default().optimizer_schedule(schedule.clone());
+        let first = compile_with(source, &config).unwrap();
+        let second = compile_with(source, &config).unwrap();
+
+        assert_eq!(first.optimizer_schedule().cloned(), schedule);
+        assert_eq!(first.prog(), tuned.prog());
+        assert_eq!(first.prog(), second.prog());
+    }
+}
@@ -0,0 +1,134 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::{check_source, compile_source};
+use zokrates_core::ir::Interpreter;
+use zokrates_field::Bn128Field;
+
+#[test]
+fn tagged_field_elements() {
+    let tagged = compile_source(
+        r#"
+struct XCoord {}
+struct YCoord {}
+
+def scale(phantom<XCoord> x, field k) -> phantom<XCoord>:
+    return phantom::<XCoord>(x.value * k)
+
+def main(phantom<XCoord> x, phantom<YCoord> y) -> field:
+    phantom<XCoord> z = scale(x, 2)
+    return z.value * y.value
+"#,
+    );
+
+    let untagged = compile_source(
+        r#"
+def main(field x, field y) -> field:
+    return x * 2 * y
+"#,
+    );
+
+    // phantom types are compiled to plain field elements
+    assert_eq!(
+        tagged.prog().constraint_count(),
+        untagged.prog().constraint_count()
+    );
+
+    let inputs = [3, 5]
+        .iter()
+        .map(|i| Bn128Field::from(*i))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        Interpreter::default()
+            .execute(tagged.prog(), &inputs)
+            .unwrap()
+            .return_values(),
+        vec![Bn128Field::from(30)]
+    );
+}
+
+#[test]
+fn different_tags() {
+    let res = check_source(
+        r#"
+struct XCoord {}
+struct YCoord {}
+
+def main(phantom<YCoord> y) -> field:
+    phantom<XCoord> x = y
+    return x.value
+"#,
+    );
+
+    assert!(res
+        .unwrap_err()
+        .contains("of type `phantom<YCoord>` cannot be assigned"));
+}
+
+#[test]
+fn no_implicit_conversion() {
+    let res = check_source(
+        r#"
+struct XCoord {}
+
+def main(field a) -> field:
+    phantom<XCoord> x = a
+    return x.value
+"#,
+    );
+
+    assert!(res
+        .unwrap_err()
+        .contains("of type `field` cannot be assigned"));
+
+    let res = check_source(
+        r#"
+struct XCoord {}
+
+def main(bool a) -> field:
+    phantom<XCoord> x = phantom::<XCoord>(a)
+    return x.value
+"#,
+    );
+
+    assert_eq!(
+        res,
+        Err(
+            "Expected the value of phantom<XCoord> to be of type field, found a of type bool"
+                .into()
+        )
+    );
+}
+
+#[test]
+fn undefined_tag() {
+    let res = check_source(
+        r#"
+def main(phantom<XCoord> x) -> field:
+    return x.value
+"#,
+    );
+
+    assert_eq!(
+        res,
+        Err("Expected the tag of `phantom` to be a struct in scope, found `XCoord`".into())
+    );
+
+    let res = check_source(
+        r#"
+const field XCoord = 1
+
+def main(phantom<XCoord> x) -> field:
+    return x.value
+"#,
+    );
+
+    assert_eq!(
+        res,
+        Err("Expected the tag of `phantom` to be a struct in scope, found `XCoord`".into())
+    );
+}
@@ -0,0 +1,64 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::compile_source;
+use zokrates_core::ir::Interpreter;
+use zokrates_field::Bn128Field;
+
+#[test]
+fn nested_calls_with_constant_results() {
+    let source = r#"
+def inner(field x) -> field:
+    return x - 2
+
+def outer(field x) -> field:
+    field y = x * x
+    assert(y == 9)
+    return y + x
+
+def main() -> field:
+    return outer(inner(5))
+"#;
+
+    let res = compile_source(source);
+
+    // `inner(5)` reduces to `3`, which makes `outer(3)` reduce to `12`: only the output binding is left
+    assert_eq!(res.prog().constraint_count(), 1);
+
+    let interpreter = Interpreter::default();
+
+    assert_eq!(
+        interpreter
+            .execute(res.prog(), &[])
+            .unwrap()
+            .return_values(),
+        vec![Bn128Field::from(12)]
+    );
+}
+
+#[test]
+fn repeated_struct_field_reads() {
+    // struct members are flattened to plain variables before zir, so reading the same member twice is free
+    let with_struct = r#"
+struct Foo {
+    field x
+    u32 y
+}
+
+def main(Foo foo) -> field:
+    return foo.x * foo.x + (if foo.y + foo.y == foo.y then foo.x else 1 fi)
+"#;
+
+    let without_struct = r#"
+def main(field x, u32 y) -> field:
+    return x * x + (if y + y == y then x else 1 fi)
+"#;
+
+    assert_eq!(
+        compile_source(with_struct).prog().constraint_count(),
+        compile_source(without_struct).prog().constraint_count()
+    );
+}
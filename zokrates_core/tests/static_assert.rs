@@ -0,0 +1,91 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::{compile_source, error};
+
+#[test]
+fn generic_assertion_holds() {
+    let with_assertion = r#"
+def first<N>(field[N] a) -> field:
+    static_assert(N > 0)
+    return a[0]
+
+def main(field[2] a) -> field:
+    return first(a) + first([1, 2, 3])
+"#;
+
+    let without_assertion = r#"
+def first<N>(field[N] a) -> field:
+    return a[0]
+
+def main(field[2] a) -> field:
+    return first(a) + first([1, 2, 3])
+"#;
+
+    // the assertion does not reach the compiled program
+    assert_eq!(
+        compile_source(with_assertion).prog().constraint_count(),
+        compile_source(without_assertion).prog().constraint_count()
+    );
+}
+
+#[test]
+fn generic_assertion_fails() {
+    let source = r#"
+def first<N>(field[N] a) -> field:
+    static_assert(N > 2)
+    return a[0]
+
+def main(field[2] a) -> field:
+    return first([1, 2, 3]) + first(a)
+"#;
+
+    assert_eq!(error(source), "\n\tStatic assertion `N > 2` failed at 3:5");
+}
+
+#[test]
+fn constant_assertion() {
+    let source = r#"
+const u32 SIZE = 4
+
+def main() -> field:
+    static_assert(SIZE % 2 == 1)
+    return 1
+"#;
+
+    assert_eq!(
+        error(source),
+        "\n\tStatic assertion `SIZE % 2 == 1` failed at 5:5"
+    );
+}
+
+#[test]
+fn non_constant_assertion() {
+    let source = r#"
+def main(field a) -> field:
+    static_assert(a == 1)
+    return a
+"#;
+
+    assert_eq!(
+        error(source),
+        "\n\tStatic assertion `a == 1` at 3:5 could not be evaluated at compile time"
+    );
+}
+
+#[test]
+fn non_boolean_assertion() {
+    let source = r#"
+def foo<N>(field[N] a) -> field:
+    static_assert(N)
+    return 1
+
+def main(field[2] a) -> field:
+    return foo(a)
+"#;
+
+    assert!(error(source).ends_with("Expected N to be of type bool, found u32"));
+}
@@ -0,0 +1,209 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::check_source;
+
+fn binary(left: &str, op: &str, right: &str, result: &str) -> String {
+    format!(
+        r#"
+def main({} a, {} b) -> {}:
+    return a {} b
+"#,
+        left, right, result, op
+    )
+}
+
+fn unary(ty: &str, op: &str, result: &str) -> String {
+    format!(
+        r#"
+def main({} a) -> {}:
+    return {}a
+"#,
+        ty, result, op
+    )
+}
+
+#[test]
+fn arithmetic() {
+    for op in &["+", "-", "*", "/"] {
+        assert_eq!(check_source(&binary("field", op, "field", "field")), Ok(()));
+        assert_eq!(check_source(&binary("u32", op, "u32", "u32")), Ok(()));
+
+        assert_eq!(
+            check_source(&binary("field", op, "bool", "field")),
+            Err(format!("Cannot apply `{}` to field, bool", op))
+        );
+        assert_eq!(
+            check_source(&binary("u8", op, "u16", "u8")),
+            Err(format!("Cannot apply `{}` to u8, u16", op))
+        );
+        assert_eq!(
+            check_source(&binary("field", op, "u32", "field")),
+            Err(format!("Cannot apply `{}` to field, u32", op))
+        );
+        assert_eq!(
+            check_source(&binary("bool", op, "bool", "bool")),
+            Err(format!("Cannot apply `{}` to bool, bool", op))
+        );
+    }
+}
+
+#[test]
+fn comparison() {
+    for op in &["<", "<="] {
+        assert_eq!(check_source(&binary("field", op, "field", "bool")), Ok(()));
+        assert_eq!(check_source(&binary("u64", op, "u64", "bool")), Ok(()));
+
+        assert_eq!(
+            check_source(&binary("field", op, "bool", "bool")),
+            Err("Cannot compare a of type field to b of type bool".into())
+        );
+        assert_eq!(
+            check_source(&binary("u32", op, "u8", "bool")),
+            Err("Cannot compare a of type u32 to b of type u8".into())
+        );
+        assert_eq!(
+            check_source(&binary("bool", op, "bool", "bool")),
+            Err("Cannot compare a of type bool to b of type bool".into())
+        );
+    }
+}
+
+#[test]
+fn boolean() {
+    assert_eq!(check_source(&binary("bool", "&&", "bool", "bool")), Ok(()));
+    assert_eq!(check_source(&binary("bool", "||", "bool", "bool")), Ok(()));
+
+    assert_eq!(
+        check_source(&binary("bool", "&&", "field", "bool")),
+        Err("Cannot apply boolean operators to bool and field".into())
+    );
+    assert_eq!(
+        check_source(&binary("field", "&&", "field", "bool")),
+        Err("Cannot apply boolean operators to field and field".into())
+    );
+    assert_eq!(
+        check_source(&binary("bool", "||", "u32", "bool")),
+        Err("Cannot apply `||` to bool, u32".into())
+    );
+    assert_eq!(
+        check_source(&binary("field", "||", "field", "bool")),
+        Err("Cannot apply `||` to field, field".into())
+    );
+}
+
+#[test]
+fn not() {
+    assert_eq!(check_source(&unary("bool", "!", "bool")), Ok(()));
+    assert_eq!(check_source(&unary("u8", "!", "u8")), Ok(()));
+
+    assert_eq!(
+        check_source(&unary("field", "!", "field")),
+        Err("Cannot negate field".into())
+    );
+}
+
+#[test]
+fn array_indexing() {
+    assert_eq!(
+        check_source(
+            r#"
+def main(field[3] a, u32 i) -> field:
+    return a[i]
+"#
+        ),
+        Ok(())
+    );
+
+    assert_eq!(
+        check_source(
+            r#"
+def main(bool[3] a) -> bool:
+    return a[1]
+"#
+        ),
+        Ok(())
+    );
+
+    assert_eq!(
+        check_source(
+            r#"
+def main(field[3] a, bool i) -> field:
+    return a[i]
+"#
+        ),
+        Err("Expected index to be of type u32, found i".into())
+    );
+
+    assert_eq!(
+        check_source(
+            r#"
+def main(field a) -> field:
+    return a[0]
+"#
+        ),
+        Err("Cannot access element as index 0 of type u32 on expression a of type field".into())
+    );
+
+    assert!(check_source(
+        r#"
+def main(field[3] a) -> bool:
+    return a[0]
+"#
+    )
+    .is_err());
+}
+
+#[test]
+fn struct_member_access() {
+    let declaration = r#"
+struct Foo {
+    field a
+    bool b
+}
+"#;
+
+    assert_eq!(
+        check_source(&format!(
+            r#"{}
+def main(Foo foo) -> bool:
+    return foo.b
+"#,
+            declaration
+        )),
+        Ok(())
+    );
+
+    assert_eq!(
+        check_source(&format!(
+            r#"{}
+def main(Foo foo) -> field:
+    return foo.c
+"#,
+            declaration
+        )),
+        Err("Foo {a: field, b: bool} doesn't have member c".into())
+    );
+
+    assert_eq!(
+        check_source(
+            r#"
+def main(field foo) -> field:
+    return foo.a
+"#
+        ),
+        Err("Cannot access member a on expression of type field".into())
+    );
+
+    assert!(check_source(&format!(
+        r#"{}
+def main(Foo foo) -> field:
+    return foo.b
+"#,
+        declaration
+    ))
+    .is_err());
+}
@@ -0,0 +1,41 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::compile_source;
+use zokrates_core::compile::Warning;
+
+fn warnings(source: &str) -> Vec<Warning> {
+    compile_source(source).warnings().to_vec()
+}
+
+#[test]
+fn returned_unchecked() {
+    let source = r#"
+def main(private field a, private field b, private u32 c) -> (field, u32):
+    assert(b * b == 4)
+    field d = a + b
+    return d, c
+"#;
+
+    assert_eq!(
+        warnings(source),
+        vec![Warning::UncheckedCoercion {
+            parameter: "a".into()
+        }]
+    );
+}
+
+#[test]
+fn checked() {
+    let source = r#"
+def main(private field a) -> field:
+    field b = a * a
+    assert(b == 9)
+    return a
+"#;
+
+    assert_eq!(warnings(source), vec![]);
+}
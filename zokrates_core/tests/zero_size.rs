@@ -0,0 +1,77 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+mod common;
+
+use common::{compile_with, error};
+use zokrates_core::compile::CompileConfig;
+
+#[test]
+fn generic_of_size_zero() {
+    let source = r#"
+def sum<N>(field[N] a) -> field:
+    field res = 0
+    for u32 i in 0..N do
+        res = res + a[i]
+    endfor
+    return res
+
+def foo<M>(field[M] b) -> field:
+    return sum(b)
+
+def main(field[2] a) -> field:
+    return foo(a[1..1])
+"#;
+
+    assert_eq!(
+        error(source),
+        "\n\tParameter `b` of function `foo` is an array of size 0, in call foo::<M: 0>"
+    );
+}
+
+#[test]
+fn explicit_generic_of_size_zero() {
+    let source = r#"
+def first<N>(bool[N] a) -> field:
+    return 42
+
+def main(bool[2] a) -> field:
+    return first::<0>(a[2..])
+"#;
+
+    assert_eq!(
+        error(source),
+        "\n\tParameter `a` of function `first` is an array of size 0, in call first::<N: 0>"
+    );
+}
+
+#[test]
+fn main_parameter_of_size_zero() {
+    let source = r#"
+def main(field[0] a) -> field:
+    return 1
+"#;
+
+    assert_eq!(
+        error(source),
+        "\n\tParameter `a` of function `main` is an array of size 0"
+    );
+}
+
+#[test]
+fn non_zero_sizes() {
+    let source = r#"
+def sum<N>(field[N] a) -> field:
+    field res = 0
+    for u32 i in 0..N do
+        res = res + a[i]
+    endfor
+    return res
+
+def main(field[2] a) -> field:
+    return sum(a) + sum([1])
+"#;
+
+    assert!(compile_with(source, &CompileConfig::default()).is_ok());
+}
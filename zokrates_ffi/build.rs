@@ -0,0 +1,20 @@
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=ZOKRATES_FFI_UPDATE_HEADER");
+
+    let bindings = cbindgen::generate(&crate_dir).expect("Unable to generate the C header");
+
+    bindings.write_to_file(out_dir.join("zokrates_ffi.h"));
+
+    // the checked-in header is only updated on request, so that building never modifies the source tree
+    if env::var_os("ZOKRATES_FFI_UPDATE_HEADER").is_some() {
+        bindings.write_to_file(crate_dir.join("include").join("zokrates_ffi.h"));
+    }
+}
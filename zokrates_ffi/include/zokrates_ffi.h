@@ -0,0 +1,93 @@
+#ifndef ZOKRATES_FFI_H
+#define ZOKRATES_FFI_H
+
+/* Generated by cbindgen from zokrates_ffi/src/lib.rs, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The call succeeded
+ */
+#define ZOKRATES_OK 0
+
+/**
+ * A pointer argument was null or a string was not valid UTF-8
+ */
+#define ZOKRATES_ERR_ARGUMENT 1
+
+/**
+ * The program could not be deserialized
+ */
+#define ZOKRATES_ERR_PROGRAM 2
+
+/**
+ * The abi could not be deserialized
+ */
+#define ZOKRATES_ERR_ABI 3
+
+/**
+ * The inputs do not match the abi
+ */
+#define ZOKRATES_ERR_INPUTS 4
+
+/**
+ * The program could not be executed on the inputs
+ */
+#define ZOKRATES_ERR_EXECUTION 5
+
+/**
+ * An unexpected internal error occurred
+ */
+#define ZOKRATES_ERR_PANIC -1
+
+/**
+ * A byte buffer allocated by this library
+ */
+typedef struct ZokratesBuffer {
+  uint8_t *data;
+  uintptr_t len;
+} ZokratesBuffer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Computes the witness of a compiled program.
+ *
+ * `program` points to `program_len` bytes of a program serialized by `zokrates compile`, `abi_json` and
+ * `inputs_json` are NUL-terminated UTF-8 strings holding the abi of the program and a JSON array of its arguments.
+ *
+ * Returns `ZOKRATES_OK` and sets `out_witness` to a JSON object with the `witness` and the `output` of the
+ * program, or returns an error code and sets `out_err` to a JSON object with the `code`, `kind` and `message`
+ * of the error.
+ *
+ * # Safety
+ *
+ * `program` must be valid for reads of `program_len` bytes, `abi_json` and `inputs_json` must be null or point to
+ * NUL-terminated strings, and `out_witness` and `out_err` must be null or valid for writes.
+ */
+int32_t zokrates_compute_witness(const uint8_t *program,
+                                 uintptr_t program_len,
+                                 const char *abi_json,
+                                 const char *inputs_json,
+                                 struct ZokratesBuffer *out_witness,
+                                 struct ZokratesBuffer *out_err);
+
+/**
+ * Releases a buffer returned by this library. Empty buffers are ignored.
+ *
+ * # Safety
+ *
+ * `buffer` must have been returned by this library and not have been freed yet.
+ */
+void zokrates_free_buffer(struct ZokratesBuffer buffer);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* ZOKRATES_FFI_H */
@@ -0,0 +1,379 @@
+//! C ABI to compute witnesses of compiled ZoKrates programs
+//!
+//! All functions are safe to call from C: panics are caught at every entry point and reported as errors.
+//!
+//! # Ownership
+//!
+//! * Input pointers are borrowed for the duration of the call and never freed by this library.
+//! * Every `ZokratesBuffer` written to an out parameter is owned by the caller, who must release it with
+//!   `zokrates_free_buffer` exactly once. Buffers are NUL-terminated UTF-8, the terminator is not counted in `len`.
+//! * On success only `out_witness` is set, on failure only `out_err` is set. The other one is left empty.
+
+use serde_json::json;
+use std::any::Any;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use zokrates_abi::{parse_strict, Decode, Encode, Inputs, Values};
+use zokrates_core::ir::{self, ProgEnum};
+use zokrates_core::typed_absy::abi::Abi;
+use zokrates_field::Field;
+
+/// The call succeeded
+pub const ZOKRATES_OK: i32 = 0;
+/// A pointer argument was null or a string was not valid UTF-8
+pub const ZOKRATES_ERR_ARGUMENT: i32 = 1;
+/// The program could not be deserialized
+pub const ZOKRATES_ERR_PROGRAM: i32 = 2;
+/// The abi could not be deserialized
+pub const ZOKRATES_ERR_ABI: i32 = 3;
+/// The inputs do not match the abi
+pub const ZOKRATES_ERR_INPUTS: i32 = 4;
+/// The program could not be executed on the inputs
+pub const ZOKRATES_ERR_EXECUTION: i32 = 5;
+/// An unexpected internal error occurred
+pub const ZOKRATES_ERR_PANIC: i32 = -1;
+
+/// A byte buffer allocated by this library
+#[repr(C)]
+pub struct ZokratesBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl ZokratesBuffer {
+    fn empty() -> Self {
+        ZokratesBuffer {
+            data: ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    fn from_string(s: String) -> Self {
+        let mut bytes = s.into_bytes();
+        let len = bytes.len();
+        bytes.push(0);
+
+        ZokratesBuffer {
+            data: Box::into_raw(bytes.into_boxed_slice()) as *mut u8,
+            len,
+        }
+    }
+}
+
+struct Diagnostic {
+    code: i32,
+    message: String,
+}
+
+impl Diagnostic {
+    fn new<S: Into<String>>(code: i32, message: S) -> Self {
+        Diagnostic {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self.code {
+            ZOKRATES_ERR_ARGUMENT => "argument",
+            ZOKRATES_ERR_PROGRAM => "program",
+            ZOKRATES_ERR_ABI => "abi",
+            ZOKRATES_ERR_INPUTS => "inputs",
+            ZOKRATES_ERR_EXECUTION => "execution",
+            _ => "panic",
+        }
+    }
+
+    fn to_json(&self) -> String {
+        json!({
+            "code": self.code,
+            "kind": self.kind(),
+            "message": self.message,
+        })
+        .to_string()
+    }
+
+    fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "Unknown panic".to_string(),
+            },
+        };
+
+        Diagnostic::new(ZOKRATES_ERR_PANIC, message)
+    }
+}
+
+unsafe fn read_str<'a>(s: *const c_char, name: &str) -> Result<&'a str, Diagnostic> {
+    if s.is_null() {
+        return Err(Diagnostic::new(
+            ZOKRATES_ERR_ARGUMENT,
+            format!("`{}` is null", name),
+        ));
+    }
+
+    CStr::from_ptr(s).to_str().map_err(|e| {
+        Diagnostic::new(
+            ZOKRATES_ERR_ARGUMENT,
+            format!("`{}` is not valid UTF-8: {}", name, e),
+        )
+    })
+}
+
+fn compute_witness<T: Field>(
+    program: ir::Prog<T>,
+    abi: &Abi,
+    inputs: &str,
+) -> Result<String, Diagnostic> {
+    let signature = abi.signature();
+
+    let inputs = parse_strict(inputs, signature.inputs)
+        .map(Inputs::Abi)
+        .map_err(|why| Diagnostic::new(ZOKRATES_ERR_INPUTS, why.to_string()))?;
+
+    let interpreter = ir::Interpreter::default();
+
+    let witness = interpreter
+        .execute(&program, &inputs.encode())
+        .map_err(|e| Diagnostic::new(ZOKRATES_ERR_EXECUTION, format!("Execution failed: {}", e)))?;
+
+    let output = Values::decode(witness.return_values(), signature.outputs).into_serde_json();
+
+    Ok(json!({
+        "witness": witness.to_string(),
+        "output": output,
+    })
+    .to_string())
+}
+
+unsafe fn compute_witness_raw(
+    program: *const u8,
+    program_len: usize,
+    abi_json: *const c_char,
+    inputs_json: *const c_char,
+) -> Result<String, Diagnostic> {
+    if program.is_null() {
+        return Err(Diagnostic::new(ZOKRATES_ERR_ARGUMENT, "`program` is null"));
+    }
+
+    let program = slice::from_raw_parts(program, program_len);
+    let abi = read_str(abi_json, "abi_json")?;
+    let inputs = read_str(inputs_json, "inputs_json")?;
+
+    let program =
+        ProgEnum::deserialize(program).map_err(|e| Diagnostic::new(ZOKRATES_ERR_PROGRAM, e))?;
+    let abi: Abi = serde_json::from_str(abi).map_err(|e| {
+        Diagnostic::new(
+            ZOKRATES_ERR_ABI,
+            format!("Could not deserialize abi: {}", e),
+        )
+    })?;
+
+    match program {
+        ProgEnum::Bn128Program(p) => compute_witness(p, &abi, inputs),
+        ProgEnum::Bls12_381Program(p) => compute_witness(p, &abi, inputs),
+        ProgEnum::Bls12_377Program(p) => compute_witness(p, &abi, inputs),
+        ProgEnum::Bw6_761Program(p) => compute_witness(p, &abi, inputs),
+    }
+}
+
+/// Computes the witness of a compiled program.
+///
+/// `program` points to `program_len` bytes of a program serialized by `zokrates compile`, `abi_json` and
+/// `inputs_json` are NUL-terminated UTF-8 strings holding the abi of the program and a JSON array of its arguments.
+///
+/// Returns `ZOKRATES_OK` and sets `out_witness` to a JSON object with the `witness` and the `output` of the
+/// program, or returns an error code and sets `out_err` to a JSON object with the `code`, `kind` and `message`
+/// of the error.
+///
+/// # Safety
+///
+/// `program` must be valid for reads of `program_len` bytes, `abi_json` and `inputs_json` must be null or point to
+/// NUL-terminated strings, and `out_witness` and `out_err` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn zokrates_compute_witness(
+    program: *const u8,
+    program_len: usize,
+    abi_json: *const c_char,
+    inputs_json: *const c_char,
+    out_witness: *mut ZokratesBuffer,
+    out_err: *mut ZokratesBuffer,
+) -> i32 {
+    if out_witness.is_null() || out_err.is_null() {
+        return ZOKRATES_ERR_ARGUMENT;
+    }
+
+    out_witness.write(ZokratesBuffer::empty());
+    out_err.write(ZokratesBuffer::empty());
+
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+        compute_witness_raw(program, program_len, abi_json, inputs_json)
+    }))
+    .unwrap_or_else(|payload| Err(Diagnostic::from_panic(payload)));
+
+    match res {
+        Ok(witness) => {
+            out_witness.write(ZokratesBuffer::from_string(witness));
+            ZOKRATES_OK
+        }
+        Err(diagnostic) => {
+            // building the diagnostic only allocates, but never unwind into C
+            let error = panic::catch_unwind(AssertUnwindSafe(|| {
+                ZokratesBuffer::from_string(diagnostic.to_json())
+            }))
+            .unwrap_or_else(|_| ZokratesBuffer::empty());
+            out_err.write(error);
+            diagnostic.code
+        }
+    }
+}
+
+/// Releases a buffer returned by this library. Empty buffers are ignored.
+///
+/// # Safety
+///
+/// `buffer` must have been returned by this library and not have been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn zokrates_free_buffer(buffer: ZokratesBuffer) {
+    if buffer.data.is_null() {
+        return;
+    }
+
+    let _ = panic::catch_unwind(|| {
+        drop(Box::from_raw(slice::from_raw_parts_mut(
+            buffer.data,
+            buffer.len + 1,
+        )))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+    use std::io;
+    use std::path::PathBuf;
+    use zokrates_common::Resolver;
+    use zokrates_core::compile::{compile, CompilationArtifacts, CompileConfig};
+    use zokrates_field::Bn128Field;
+
+    fn artifacts() -> (Vec<u8>, CString) {
+        let artifacts: CompilationArtifacts<Bn128Field> = compile(
+            "def main(private field a, field b) -> field:\n    assert(a * a == b)\n    return a + b\n"
+                .to_string(),
+            PathBuf::from("main.zok"),
+            None::<&dyn Resolver<io::Error>>,
+            &CompileConfig::default(),
+        )
+        .unwrap();
+
+        let mut program = vec![];
+        artifacts.prog().serialize(&mut program);
+        let abi = CString::new(serde_json::to_string(artifacts.abi()).unwrap()).unwrap();
+
+        (program, abi)
+    }
+
+    unsafe fn call(program: &[u8], abi: &CString, inputs: &str) -> (i32, String, String) {
+        let inputs = CString::new(inputs).unwrap();
+        let mut witness = ZokratesBuffer::empty();
+        let mut err = ZokratesBuffer::empty();
+
+        let code = zokrates_compute_witness(
+            program.as_ptr(),
+            program.len(),
+            abi.as_ptr(),
+            inputs.as_ptr(),
+            &mut witness,
+            &mut err,
+        );
+
+        let read = |b: &ZokratesBuffer| {
+            if b.data.is_null() {
+                String::new()
+            } else {
+                String::from_utf8(slice::from_raw_parts(b.data, b.len).to_vec()).unwrap()
+            }
+        };
+        let res = (code, read(&witness), read(&err));
+
+        zokrates_free_buffer(witness);
+        zokrates_free_buffer(err);
+
+        res
+    }
+
+    #[test]
+    fn success() {
+        let (program, abi) = artifacts();
+
+        let (code, witness, err) = unsafe { call(&program, &abi, r#"["3", "9"]"#) };
+
+        assert_eq!(code, ZOKRATES_OK);
+        assert_eq!(err, "");
+
+        let witness: serde_json::Value = serde_json::from_str(&witness).unwrap();
+        assert_eq!(witness["output"], json!(["12"]));
+        assert!(witness["witness"].as_str().unwrap().contains("~out_0 12"));
+    }
+
+    #[test]
+    fn errors() {
+        let (program, abi) = artifacts();
+
+        for (program, inputs, expected) in vec![
+            (&program[..], r#"["3", "10"]"#, ZOKRATES_ERR_EXECUTION),
+            (&program[..], r#"["3"]"#, ZOKRATES_ERR_INPUTS),
+            (&program[..], "not json", ZOKRATES_ERR_INPUTS),
+            (&program[..4], r#"["3", "9"]"#, ZOKRATES_ERR_PROGRAM),
+            // a truncated program body
+            (&program[..16], r#"["3", "9"]"#, ZOKRATES_ERR_PROGRAM),
+        ] {
+            let (code, witness, err) = unsafe { call(program, &abi, inputs) };
+
+            assert_eq!(code, expected);
+            assert_eq!(witness, "");
+
+            let err: serde_json::Value = serde_json::from_str(&err).unwrap();
+            assert_eq!(err["code"], json!(expected));
+        }
+    }
+
+    #[test]
+    fn null_arguments() {
+        let (program, _) = artifacts();
+        let mut witness = ZokratesBuffer::empty();
+        let mut err = ZokratesBuffer::empty();
+
+        let code = unsafe {
+            zokrates_compute_witness(
+                program.as_ptr(),
+                program.len(),
+                ptr::null(),
+                ptr::null(),
+                &mut witness,
+                &mut err,
+            )
+        };
+        assert_eq!(code, ZOKRATES_ERR_ARGUMENT);
+        assert!(witness.data.is_null());
+        unsafe { zokrates_free_buffer(err) };
+
+        let code = unsafe {
+            zokrates_compute_witness(
+                program.as_ptr(),
+                program.len(),
+                ptr::null(),
+                ptr::null(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        };
+        assert_eq!(code, ZOKRATES_ERR_ARGUMENT);
+    }
+}
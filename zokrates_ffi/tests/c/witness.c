@@ -0,0 +1,66 @@
+/*
+ * Computes a witness through the C ABI.
+ *
+ * usage: witness <program> <abi> <inputs>
+ *
+ * Prints the result on stdout and exits with the code returned by `zokrates_compute_witness`.
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "zokrates_ffi.h"
+
+static char *read_file(const char *path, size_t *len)
+{
+    FILE *f = fopen(path, "rb");
+    if (f == NULL)
+        return NULL;
+
+    fseek(f, 0, SEEK_END);
+    long size = ftell(f);
+    fseek(f, 0, SEEK_SET);
+
+    char *data = malloc(size + 1);
+    *len = fread(data, 1, size, f);
+    data[*len] = '\0';
+    fclose(f);
+
+    return data;
+}
+
+int main(int argc, char **argv)
+{
+    if (argc != 4)
+    {
+        fprintf(stderr, "usage: %s <program> <abi> <inputs>\n", argv[0]);
+        return 100;
+    }
+
+    size_t program_len, abi_len, inputs_len;
+    char *program = read_file(argv[1], &program_len);
+    char *abi = read_file(argv[2], &abi_len);
+    char *inputs = read_file(argv[3], &inputs_len);
+    if (program == NULL || abi == NULL || inputs == NULL)
+    {
+        fprintf(stderr, "could not read arguments\n");
+        return 101;
+    }
+
+    ZokratesBuffer witness, err;
+    int32_t code = zokrates_compute_witness((const uint8_t *)program, program_len, abi, inputs, &witness, &err);
+
+    if (code == ZOKRATES_OK)
+        printf("%s", (const char *)witness.data);
+    else
+        printf("%s", (const char *)err.data);
+
+    zokrates_free_buffer(witness);
+    zokrates_free_buffer(err);
+
+    free(program);
+    free(abi);
+    free(inputs);
+
+    return code;
+}
@@ -0,0 +1,110 @@
+//! Compiles `tests/c/witness.c` against the shared library and runs it
+
+#![cfg(unix)]
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use zokrates_common::Resolver;
+use zokrates_core::compile::{compile, CompilationArtifacts, CompileConfig};
+use zokrates_field::Bn128Field;
+
+// the shared library is built next to the `deps` folder containing this test binary
+fn target_dir() -> PathBuf {
+    env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+fn build_c_program(out: &Path) {
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(crate_dir.join("tests").join("c").join("witness.c"))
+        .arg("-I")
+        .arg(env!("OUT_DIR"))
+        .arg("-L")
+        .arg(target_dir())
+        .arg("-lzokrates_ffi")
+        .arg("-o")
+        .arg(out)
+        .status()
+        .expect("Could not run the C compiler");
+
+    assert!(status.success());
+}
+
+fn run(binary: &Path, args: &[&Path]) -> (i32, serde_json::Value) {
+    let output = Command::new(binary)
+        .args(args)
+        .env("LD_LIBRARY_PATH", target_dir())
+        .env("DYLD_LIBRARY_PATH", target_dir())
+        .output()
+        .unwrap();
+
+    (
+        output.status.code().unwrap(),
+        serde_json::from_slice(&output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn checked_in_header_is_up_to_date() {
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    let generated = fs::read_to_string(Path::new(env!("OUT_DIR")).join("zokrates_ffi.h")).unwrap();
+    let checked_in = fs::read_to_string(crate_dir.join("include").join("zokrates_ffi.h")).unwrap();
+
+    assert!(
+        generated == checked_in,
+        "include/zokrates_ffi.h is outdated, build with ZOKRATES_FFI_UPDATE_HEADER=1 to update it"
+    );
+}
+
+#[test]
+fn compute_witness_from_c() {
+    let dir = env::temp_dir().join(format!("zokrates_ffi_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let artifacts: CompilationArtifacts<Bn128Field> = compile(
+        "def main(private field a, field b) -> field:\n    assert(a * a == b)\n    return a + b\n"
+            .to_string(),
+        PathBuf::from("main.zok"),
+        None::<&dyn Resolver<io::Error>>,
+        &CompileConfig::default(),
+    )
+    .unwrap();
+
+    let program = dir.join("out");
+    let mut buffer = vec![];
+    artifacts.prog().serialize(&mut buffer);
+    fs::write(&program, buffer).unwrap();
+
+    let abi = dir.join("abi.json");
+    fs::write(&abi, serde_json::to_string(artifacts.abi()).unwrap()).unwrap();
+
+    let binary = dir.join("witness");
+    build_c_program(&binary);
+
+    let valid = dir.join("valid.json");
+    fs::write(&valid, r#"["3", "9"]"#).unwrap();
+
+    let (code, result) = run(&binary, &[&program, &abi, &valid]);
+    assert_eq!(code, zokrates_ffi::ZOKRATES_OK);
+    assert_eq!(result["output"], serde_json::json!(["12"]));
+
+    let invalid = dir.join("invalid.json");
+    fs::write(&invalid, r#"["3", "10"]"#).unwrap();
+
+    let (code, result) = run(&binary, &[&program, &abi, &invalid]);
+    assert_eq!(code, zokrates_ffi::ZOKRATES_ERR_EXECUTION);
+    assert_eq!(result["kind"], "execution");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
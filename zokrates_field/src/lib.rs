@@ -150,6 +150,8 @@ pub trait Field:
     fn to_biguint(&self) -> BigUint;
 }
 
+// Field arithmetic is implemented on top of `num-bigint`, in portable Rust: it does not depend on platform intrinsics,
+// which are only used by the proving backends
 #[macro_use]
 mod prime_field {
     macro_rules! prime_field {
@@ -616,3 +618,56 @@ pub use bls12_377::FieldPrime as Bls12_377Field;
 pub use bls12_381::FieldPrime as Bls12_381Field;
 pub use bn128::FieldPrime as Bn128Field;
 pub use bw6_761::FieldPrime as Bw6_761Field;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    // a pseudo-random element spanning the whole field
+    fn random<T: Field, R: Rng>(rng: &mut R) -> T {
+        T::from(rng.gen::<u64>() as u128 * rng.gen::<u64>() as u128)
+            * T::from(rng.gen::<u64>() as u128 * rng.gen::<u64>() as u128)
+            + T::from(rng.gen::<u32>())
+    }
+
+    fn field_axioms<T: Field>() {
+        let mut rng = thread_rng();
+
+        for _ in 0..100 {
+            let a: T = random(&mut rng);
+            let b: T = random(&mut rng);
+            let c: T = random(&mut rng);
+
+            assert_eq!(a.clone() + &b, b.clone() + &a);
+            assert_eq!(a.clone() * &b, b.clone() * &a);
+            assert_eq!((a.clone() + &b) + &c, a.clone() + (b.clone() + &c));
+            assert_eq!((a.clone() * &b) * &c, a.clone() * (b.clone() * &c));
+            assert_eq!(
+                a.clone() * (b.clone() + &c),
+                a.clone() * &b + a.clone() * &c
+            );
+
+            assert_eq!(a.clone() + T::zero(), a);
+            assert_eq!(a.clone() * T::one(), a);
+            assert_eq!(T::zero() - &a + &a, T::zero());
+            assert_eq!(a.clone() - &b + &b, a);
+
+            if !a.is_zero() {
+                assert_eq!(a.clone() * a.inverse_mul().unwrap(), T::one());
+                assert_eq!(b.clone() * &a / &a, b);
+            }
+
+            assert!(a <= T::max_value());
+            assert_eq!(T::try_from_dec_str(&a.to_dec_string()).unwrap(), a);
+        }
+    }
+
+    #[test]
+    fn axioms() {
+        field_axioms::<Bn128Field>();
+        field_axioms::<Bls12_381Field>();
+        field_axioms::<Bls12_377Field>();
+        field_axioms::<Bw6_761Field>();
+    }
+}
@@ -37,7 +37,7 @@ ace.define("ace/mode/zokrates_highlight_rules",["require","exports","module","ac
     var ZoKratesHighlightRules = function () {
 
         var keywords = (
-            "assert|as|bool|byte|const|def|do|else|endfor|export|false|field|for|if|then|fi|import|from|in|private|public|return|struct|true|u8|u16|u32|u64"
+            "assert|as|bool|byte|const|def|do|else|endfor|endimpl|export|false|field|for|if|then|fi|impl|import|from|in|match|private|public|return|struct|true|u8|u16|u32|u64"
         );
 
         var keywordMapper = this.createKeywordMapper({
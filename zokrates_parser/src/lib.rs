@@ -332,5 +332,17 @@ mod tests {
             let parse = ZoKratesParser::parse(Rule::iteration_statement, input);
             assert!(parse.is_ok());
         }
+
+        #[test]
+        fn parse_attributes() {
+            let input = "#[optimizer(barrier)] #[allow(deprecated)] assert(a == b)";
+            assert!(ZoKratesParser::parse(Rule::expression_statement, input).is_ok());
+
+            let input = "#[allow(deprecated)]\n assert_eq!(a, b)";
+            assert!(ZoKratesParser::parse(Rule::equality_assertion_statement, input).is_ok());
+
+            let input = "#[deprecated(note = \"use g\", since = \"0.8.0\")]\ndef f() -> field:\n return 1\n";
+            assert!(ZoKratesParser::parse(Rule::function_definition, input).is_ok());
+        }
     }
 }
@@ -8,17 +8,20 @@ use zokrates_parser::Rule;
 extern crate lazy_static;
 
 pub use ast::{
-    Access, Arguments, ArrayAccess, ArrayInitializerExpression, ArrayType, AssertionStatement,
-    Assignee, AssigneeAccess, BasicOrStructType, BasicType, BinaryExpression, BinaryOperator,
-    CallAccess, ConstantDefinition, ConstantGenericValue, DecimalLiteralExpression, DecimalNumber,
-    DecimalSuffix, DefinitionStatement, ExplicitGenerics, Expression, FieldType, File,
-    FromExpression, FunctionDefinition, HexLiteralExpression, HexNumberExpression,
-    IdentifierExpression, ImportDirective, ImportSource, ImportSymbol, InlineArrayExpression,
-    InlineStructExpression, InlineStructMember, IterationStatement, LiteralExpression, Parameter,
+    Access, Arguments, ArrayAccess, ArrayInitializerExpression, ArrayType, AssertEqMacro,
+    AssertNeMacro, AssertionStatement, Assignee, AssigneeAccess, Attribute, AttributeArgument,
+    AttributeString, BasicOrStructType, BasicType, BinaryExpression, BinaryOperator, CallAccess,
+    ConstantDefinition, ConstantGenericValue, DecimalLiteralExpression, DecimalNumber,
+    DecimalSuffix, DeferredAttribute, DefinitionStatement, EqualityAssertionMacro,
+    EqualityAssertionStatement, ExplicitGenerics, Expression, FieldType, File, FromExpression,
+    FunctionDefinition, HexLiteralExpression, HexNumberExpression, IdentifierExpression,
+    ImplDefinition, ImportDirective, ImportSource, ImportSymbol, InlineArrayExpression,
+    InlineStructExpression, InlineStructMember, IterationStatement, LiteralExpression, MatchArm,
+    MatchExpression, MatchPattern, MethodImplDefinition, OperatorTrait, Parameter, PatternElement,
     PostfixExpression, Range, RangeOrExpression, ReturnStatement, Span, Spread, SpreadOrExpression,
-    Statement, StructDefinition, StructField, SymbolDeclaration, TernaryExpression, ToExpression,
-    Type, TypedIdentifier, TypedIdentifierOrAssignee, UnaryExpression, UnaryOperator, Underscore,
-    Visibility,
+    Statement, StaticAssertionStatement, StructDefinition, StructField, SymbolDeclaration,
+    TernaryExpression, ToExpression, TuplePattern, Type, TypedIdentifier,
+    TypedIdentifierOrAssignee, UnaryExpression, UnaryOperator, Underscore, Visibility,
 };
 
 mod ast {
@@ -140,6 +143,8 @@ mod ast {
         Import(ImportDirective<'ast>),
         Constant(ConstantDefinition<'ast>),
         Struct(StructDefinition<'ast>),
+        Impl(ImplDefinition<'ast>),
+        MethodImpl(MethodImplDefinition<'ast>),
         Function(FunctionDefinition<'ast>),
     }
 
@@ -165,6 +170,7 @@ mod ast {
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::function_definition))]
     pub struct FunctionDefinition<'ast> {
+        pub attributes: Vec<Attribute<'ast>>,
         pub id: IdentifierExpression<'ast>,
         pub generics: Vec<IdentifierExpression<'ast>>,
         pub parameters: Vec<Parameter<'ast>>,
@@ -174,6 +180,32 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::impl_definition))]
+    pub struct ImplDefinition<'ast> {
+        pub operator: OperatorTrait<'ast>,
+        pub ty: StructType<'ast>,
+        pub function: FunctionDefinition<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::method_impl_definition))]
+    pub struct MethodImplDefinition<'ast> {
+        pub ty: StructType<'ast>,
+        pub functions: Vec<FunctionDefinition<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::operator_trait))]
+    pub struct OperatorTrait<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::const_definition))]
     pub struct ConstantDefinition<'ast> {
@@ -316,6 +348,7 @@ mod ast {
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::parameter))]
     pub struct Parameter<'ast> {
+        pub deferred: Option<DeferredAttribute<'ast>>,
         pub visibility: Option<Visibility>,
         pub ty: Type<'ast>,
         pub id: IdentifierExpression<'ast>,
@@ -323,6 +356,40 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::deferred_attribute))]
+    pub struct DeferredAttribute<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::attribute))]
+    pub struct Attribute<'ast> {
+        pub id: IdentifierExpression<'ast>,
+        pub arguments: Vec<AttributeArgument<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::attribute_argument))]
+    pub struct AttributeArgument<'ast> {
+        pub id: IdentifierExpression<'ast>,
+        pub value: Option<AttributeString<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::attribute_string))]
+    pub struct AttributeString<'ast> {
+        #[pest_ast(outer(with(span_into_str)))]
+        pub value: String,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::vis))]
     pub enum Visibility {
@@ -345,12 +412,28 @@ mod ast {
         Return(ReturnStatement<'ast>),
         Definition(DefinitionStatement<'ast>),
         Assertion(AssertionStatement<'ast>),
+        StaticAssertion(StaticAssertionStatement<'ast>),
+        EqualityAssertion(EqualityAssertionStatement<'ast>),
         Iteration(IterationStatement<'ast>),
     }
 
+    impl<'ast> Statement<'ast> {
+        pub fn attributes(&self) -> &[Attribute<'ast>] {
+            match self {
+                Statement::Return(s) => &s.attributes,
+                Statement::Definition(s) => &s.attributes,
+                Statement::Assertion(s) => &s.attributes,
+                Statement::StaticAssertion(s) => &s.attributes,
+                Statement::EqualityAssertion(s) => &s.attributes,
+                Statement::Iteration(s) => &s.attributes,
+            }
+        }
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::definition_statement))]
     pub struct DefinitionStatement<'ast> {
+        pub attributes: Vec<Attribute<'ast>>,
         pub lhs: Vec<TypedIdentifierOrAssignee<'ast>>,
         pub expression: Expression<'ast>,
         #[pest_ast(outer())]
@@ -360,14 +443,51 @@ mod ast {
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::expression_statement))]
     pub struct AssertionStatement<'ast> {
+        pub attributes: Vec<Attribute<'ast>>,
         pub expression: Expression<'ast>,
         #[pest_ast(outer())]
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::static_assertion_statement))]
+    pub struct StaticAssertionStatement<'ast> {
+        pub attributes: Vec<Attribute<'ast>>,
+        pub expression: Expression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::equality_assertion_statement))]
+    pub struct EqualityAssertionStatement<'ast> {
+        pub attributes: Vec<Attribute<'ast>>,
+        pub kind: EqualityAssertionMacro,
+        pub left: Expression<'ast>,
+        pub right: Expression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::equality_assertion_macro))]
+    pub enum EqualityAssertionMacro {
+        Eq(AssertEqMacro),
+        Ne(AssertNeMacro),
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::assert_eq_macro))]
+    pub struct AssertEqMacro {}
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::assert_ne_macro))]
+    pub struct AssertNeMacro {}
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::iteration_statement))]
     pub struct IterationStatement<'ast> {
+        pub attributes: Vec<Attribute<'ast>>,
         pub ty: Type<'ast>,
         pub index: IdentifierExpression<'ast>,
         pub from: Expression<'ast>,
@@ -380,6 +500,7 @@ mod ast {
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::return_statement))]
     pub struct ReturnStatement<'ast> {
+        pub attributes: Vec<Attribute<'ast>>,
         pub expressions: Vec<Expression<'ast>>,
         #[pest_ast(outer())]
         pub span: Span<'ast>,
@@ -411,6 +532,7 @@ mod ast {
     #[derive(Debug, PartialEq, Clone)]
     pub enum Expression<'ast> {
         Ternary(TernaryExpression<'ast>),
+        Match(MatchExpression<'ast>),
         Binary(BinaryExpression<'ast>),
         Unary(UnaryExpression<'ast>),
         Postfix(PostfixExpression<'ast>),
@@ -427,6 +549,7 @@ mod ast {
         Expression(Expression<'ast>),
         InlineStruct(InlineStructExpression<'ast>),
         Ternary(TernaryExpression<'ast>),
+        Match(MatchExpression<'ast>),
         Postfix(PostfixExpression<'ast>),
         Primary(PrimaryExpression<'ast>),
         InlineArray(InlineArrayExpression<'ast>),
@@ -512,6 +635,7 @@ mod ast {
             match t {
                 Term::Expression(e) => e,
                 Term::Ternary(e) => Expression::Ternary(e),
+                Term::Match(e) => Expression::Match(e),
                 Term::Postfix(e) => Expression::Postfix(e),
                 Term::Primary(e) => e.into(),
                 Term::InlineArray(e) => Expression::InlineArray(e),
@@ -749,6 +873,46 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::match_expression))]
+    pub struct MatchExpression<'ast> {
+        pub expressions: Vec<Expression<'ast>>,
+        pub arms: Vec<MatchArm<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::match_arm))]
+    pub struct MatchArm<'ast> {
+        pub pattern: MatchPattern<'ast>,
+        pub expression: Expression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::match_pattern))]
+    pub enum MatchPattern<'ast> {
+        Tuple(TuplePattern<'ast>),
+        Wildcard(Underscore<'ast>),
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::tuple_pattern))]
+    pub struct TuplePattern<'ast> {
+        pub elements: Vec<PatternElement<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::pattern_element))]
+    pub enum PatternElement<'ast> {
+        Literal(BooleanLiteralExpression<'ast>),
+        Wildcard(Underscore<'ast>),
+    }
+
     impl<'ast> Expression<'ast> {
         pub fn ternary(
             first: Box<Expression<'ast>>,
@@ -784,6 +948,7 @@ mod ast {
                 Expression::Identifier(i) => &i.span,
                 Expression::Literal(c) => &c.span(),
                 Expression::Ternary(t) => &t.span,
+                Expression::Match(m) => &m.span,
                 Expression::Postfix(p) => &p.span,
                 Expression::InlineArray(a) => &a.span,
                 Expression::InlineStruct(s) => &s.span,
@@ -1084,6 +1249,7 @@ mod tests {
                         span: Span::new(&source, 0, 29).unwrap()
                     })),
                     SymbolDeclaration::Function(FunctionDefinition {
+                        attributes: vec![],
                         generics: vec![],
                         id: IdentifierExpression {
                             value: String::from("main"),
@@ -1094,6 +1260,7 @@ mod tests {
                             span: Span::new(&source, 44, 49).unwrap()
                         }))],
                         statements: vec![Statement::Return(ReturnStatement {
+                            attributes: vec![],
                             expressions: vec![Expression::add(
                                 Expression::Literal(LiteralExpression::DecimalLiteral(
                                     DecimalLiteralExpression {
@@ -1145,6 +1312,7 @@ mod tests {
                         span: Span::new(&source, 0, 29).unwrap()
                     })),
                     SymbolDeclaration::Function(FunctionDefinition {
+                        attributes: vec![],
                         generics: vec![],
                         id: IdentifierExpression {
                             value: String::from("main"),
@@ -1155,6 +1323,7 @@ mod tests {
                             span: Span::new(&source, 44, 49).unwrap()
                         }))],
                         statements: vec![Statement::Return(ReturnStatement {
+                            attributes: vec![],
                             expressions: vec![Expression::add(
                                 Expression::Literal(LiteralExpression::DecimalLiteral(
                                     DecimalLiteralExpression {
@@ -1230,6 +1399,7 @@ mod tests {
                         span: Span::new(&source, 0, 29).unwrap()
                     })),
                     SymbolDeclaration::Function(FunctionDefinition {
+                        attributes: vec![],
                         generics: vec![],
                         id: IdentifierExpression {
                             value: String::from("main"),
@@ -1240,6 +1410,7 @@ mod tests {
                             span: Span::new(&source, 44, 49).unwrap()
                         }))],
                         statements: vec![Statement::Return(ReturnStatement {
+                            attributes: vec![],
                             expressions: vec![Expression::if_else(
                                 Expression::Literal(LiteralExpression::DecimalLiteral(
                                     DecimalLiteralExpression {
@@ -1290,6 +1461,7 @@ mod tests {
             Ok(File {
                 pragma: None,
                 declarations: vec![SymbolDeclaration::Function(FunctionDefinition {
+                    attributes: vec![],
                     generics: vec![],
                     id: IdentifierExpression {
                         value: String::from("main"),
@@ -1300,6 +1472,7 @@ mod tests {
                         span: Span::new(&source, 15, 20).unwrap()
                     }))],
                     statements: vec![Statement::Return(ReturnStatement {
+                        attributes: vec![],
                         expressions: vec![Expression::Literal(LiteralExpression::DecimalLiteral(
                             DecimalLiteralExpression {
                                 suffix: None,
@@ -1328,6 +1501,7 @@ mod tests {
             Ok(File {
                 pragma: None,
                 declarations: vec![SymbolDeclaration::Function(FunctionDefinition {
+                    attributes: vec![],
                     generics: vec![],
                     id: IdentifierExpression {
                         value: String::from("main"),
@@ -1338,6 +1512,7 @@ mod tests {
                         span: Span::new(&source, 15, 20).unwrap()
                     }))],
                     statements: vec![Statement::Definition(DefinitionStatement {
+                        attributes: vec![],
                         lhs: vec![
                             TypedIdentifierOrAssignee::TypedIdentifier(TypedIdentifier {
                                 ty: Type::Basic(BasicType::Field(FieldType {